@@ -35,6 +35,27 @@
 //! queue flavour, then you need to pass as many timers as you start executors.
 //! In other cases, you can pass a single timer.
 //!
+//! ## Time driver sources
+//!
+//! The time driver can be backed by the alarms of the SYSTIMER peripheral
+//! (`esp_hal::timer::systimer::Alarm`), or by the timers of the TIMG
+//! peripherals (`esp_hal::timer::timg::Timer`). On chips that have a
+//! SYSTIMER, using its alarms keeps the TIMG timers free for
+//! other uses, for example as a watchdog or to drive a software PWM.
+//!
+//! Embassy time is always counted in microseconds, but the two sources differ
+//! in their hardware resolution:
+//!
+//! - The SYSTIMER counts at a fixed 16 MHz (80 MHz on the ESP32-S2), derived from the crystal, so
+//!   it is not affected by changes of the CPU or APB clock frequency.
+//! - The TIMG timers count at half of their source clock: the APB clock, which gives 40 MHz on
+//!   most chips, or the 48 MHz PLL clock on the ESP32-H2. Their frequency follows changes of the
+//!   APB clock.
+//!
+//! Alarms are scheduled in whole microseconds, which both sources can
+//! represent exactly, so the choice doesn't change the timing of embassy
+//! timers. The ESP32 has no SYSTIMER, so a TIMG timer must be used there.
+//!
 //! ## Additional Configuration
 //!
 //! You can configure the behaviour of the embassy runtime by using the
@@ -176,6 +197,9 @@ impl_array!(4);
 /// you need to pass as many timers as you start executors. In other cases,
 /// you can pass a single timer.
 ///
+/// See [Time driver sources](crate#time-driver-sources) for the differences
+/// between the SYSTIMER and the TIMG timers.
+///
 /// # Examples
 ///
 /// ```rust, no_run
@@ -3,7 +3,9 @@
 SECTIONS {
   .rtc_fast.text : {
    . = ALIGN(4);
+   _rtc_fast_text_start = ABSOLUTE(.);
    *(.rtc_fast.literal .rtc_fast.text .rtc_fast.literal.* .rtc_fast.text.*)
+   _rtc_fast_text_end = ABSOLUTE(.);
    . = ALIGN(4);
   } > RTC_FAST_RWTEXT AT > RODATA
   
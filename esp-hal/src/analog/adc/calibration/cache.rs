@@ -0,0 +1,133 @@
+use portable_atomic::{AtomicBool, AtomicU32, Ordering};
+
+use super::CalValue;
+use crate::{
+    analog::adc::Attenuation,
+    rtc_cntl::{SocResetReason, reset_reason},
+    system::Cpu,
+};
+
+/// The entry holds a value read from eFuse.
+const CACHED: u32 = 1 << 31;
+
+/// The eFuse contains the value, it is stored in the lower 16 bits.
+const PRESENT: u32 = 1 << 16;
+
+const UNITS: usize = 2;
+const ATTENUATIONS: usize = 4;
+
+#[procmacros::ram(rtc_fast, persistent)]
+static ENTRIES: [[[AtomicU32; CalValue::COUNT]; ATTENUATIONS]; UNITS] =
+    [const { [const { [const { AtomicU32::new(0) }; CalValue::COUNT] }; ATTENUATIONS] }; UNITS];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Set once the entries have been checked since the last reset.
+static CHECKED: AtomicBool = AtomicBool::new(false);
+
+static EFUSE_READS: AtomicU32 = AtomicU32::new(0);
+
+#[procmacros::doc_replace]
+/// Caches the ADC calibration values read from eFuse in RTC FAST memory.
+///
+/// Reading eFuse is slow, while the calibration values never change. When the
+/// cache is enabled, creating a calibration scheme (for example by
+/// [`AdcConfig::enable_pin_with_cal`](crate::analog::adc::AdcConfig::enable_pin_with_cal))
+/// reads each value from eFuse only once. As RTC FAST memory is retained in
+/// deep sleep, re-initializing the ADC after waking up does not access eFuse
+/// at all, which shortens the time until the first calibrated sample.
+///
+/// The cache is cleared on every boot that is not a wakeup from deep sleep,
+/// since RTC FAST memory is not initialized after a power-on reset.
+///
+/// ## Example
+///
+/// ```rust, no_run
+/// # {before_snippet}
+/// use esp_hal::{
+///     analog::adc::{AdcCalCache, AdcCalCurve, AdcConfig, Attenuation},
+///     peripherals::ADC1,
+/// };
+/// # let analog_pin = peripherals.GPIO2;
+///
+/// // Enable the cache before creating the calibration schemes.
+/// AdcCalCache::enable();
+///
+/// let mut adc1_config = AdcConfig::new();
+/// let pin =
+///     adc1_config.enable_pin_with_cal::<_, AdcCalCurve<ADC1>>(analog_pin, Attenuation::_11dB);
+/// # {after_snippet}
+/// ```
+#[instability::unstable]
+pub struct AdcCalCache;
+
+impl AdcCalCache {
+    /// Enables the cache.
+    ///
+    /// The cache needs to be enabled after every reset, including wakeups from
+    /// deep sleep.
+    pub fn enable() {
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+
+    /// Disables and clears the cache.
+    pub fn disable() {
+        ENABLED.store(false, Ordering::Relaxed);
+        clear();
+    }
+
+    /// Returns the number of calibration values read from eFuse since the
+    /// last reset.
+    ///
+    /// Values served from the cache are not counted.
+    pub fn efuse_reads() -> u32 {
+        EFUSE_READS.load(Ordering::Relaxed)
+    }
+}
+
+fn clear() {
+    for entry in ENTRIES.iter().flatten().flatten() {
+        entry.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Returns the calibration value of the given ADC unit, calling `read` to
+/// read it from eFuse if it is not cached.
+pub(super) fn read_cached(
+    unit: u8,
+    atten: Attenuation,
+    value: CalValue,
+    read: impl FnOnce() -> Option<u16>,
+) -> Option<u16> {
+    let read = move || {
+        EFUSE_READS.fetch_add(1, Ordering::Relaxed);
+        read()
+    };
+
+    if !ENABLED.load(Ordering::Relaxed) {
+        return read();
+    }
+
+    // RTC memory is not initialized after a power-on reset. The cache is only kept
+    // through deep sleep, where the chip is known not to have lost power.
+    if !CHECKED.swap(true, Ordering::Relaxed)
+        && reset_reason(Cpu::ProCpu) != Some(SocResetReason::CoreDeepSleep)
+    {
+        clear();
+    }
+
+    let entry = &ENTRIES[unit as usize - 1][atten as usize][value as usize];
+    let cached = entry.load(Ordering::Relaxed);
+    if cached & CACHED != 0 {
+        return (cached & PRESENT != 0).then_some(cached as u16);
+    }
+
+    let result = read();
+    let encoded = match result {
+        Some(value) => CACHED | PRESENT | value as u32,
+        None => CACHED,
+    };
+    entry.store(encoded, Ordering::Relaxed);
+
+    result
+}
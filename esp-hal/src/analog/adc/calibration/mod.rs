@@ -1,6 +1,8 @@
 #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
 pub use self::basic::AdcCalBasic;
 #[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+pub use self::cache::AdcCalCache;
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
 pub use self::curve::{AdcCalCurve, AdcHasCurveCal};
 #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
 pub use self::line::{AdcCalLine, AdcHasLineCal};
@@ -8,6 +10,42 @@ pub use self::line::{AdcCalLine, AdcHasLineCal};
 #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
 mod basic;
 #[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+mod cache;
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
 mod curve;
 #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
 mod line;
+
+/// The calibration values stored in eFuse.
+#[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
+#[derive(Clone, Copy)]
+pub(super) enum CalValue {
+    InitCode,
+    CalMv,
+    CalCode,
+}
+
+#[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
+impl CalValue {
+    #[cfg_attr(esp32c2, expect(dead_code))]
+    const COUNT: usize = 3;
+}
+
+/// Reads a calibration value of the given ADC unit using `read`, or from the
+/// [`AdcCalCache`] if it is enabled.
+#[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
+#[cfg_attr(esp32c2, expect(unused_variables))]
+pub(super) fn read_efuse(
+    unit: u8,
+    atten: super::Attenuation,
+    value: CalValue,
+    read: impl FnOnce() -> Option<u16>,
+) -> Option<u16> {
+    cfg_if::cfg_if! {
+        if #[cfg(esp32c2)] {
+            read()
+        } else {
+            cache::read_cached(unit, atten, value, read)
+        }
+    }
+}
@@ -52,6 +52,8 @@
 //! ## Implementation State
 //!
 //!  - [ADC calibration is not implemented for all targets].
+//!  - Interrupt-driven queued conversions are only available on RISC-V
+//!    devices.
 //!
 //! [ADC calibration is not implemented for all targets]: https://github.com/esp-rs/esp-hal/issues/326
 use core::marker::PhantomData;
@@ -13,12 +13,12 @@ use core::{
     task::{Context, Poll},
 };
 
-// We only have to count on devices that have multiple ADCs sharing the same interrupt
-#[cfg(all(adc_adc1, adc_adc2))]
-use portable_atomic::{AtomicU32, Ordering};
+use portable_atomic::{AtomicBool, AtomicPtr, AtomicU8, AtomicU16, AtomicU32, AtomicUsize, Ordering};
 use procmacros::handler;
 
 pub use self::calibration::*;
+#[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2))]
+use self::calibration::{CalValue, read_efuse};
 use super::{AdcCalSource, AdcConfig, Attenuation};
 #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2))]
 use crate::efuse::Efuse;
@@ -285,6 +285,23 @@ pub struct Adc<'d, ADCI, Dm: crate::DriverMode> {
     _phantom: PhantomData<(Dm, &'d mut ())>,
 }
 
+/// Sets up and starts a single conversion.
+fn start_conversion<ADCI: RegisterAccess>(channel: u8, attenuation: u8, cal_code: u16) {
+    // Set ADC unit calibration according used scheme for pin
+    ADCI::set_init_code(cal_code);
+
+    ADCI::config_onetime_sample(channel, attenuation);
+    ADCI::start_onetime_sample();
+
+    // see https://github.com/espressif/esp-idf/blob/b4268c874a4cf8fcf7c0c4153cffb76ad2ddda4e/components/hal/adc_oneshot_hal.c#L105-L107
+    // the delay might be a bit generous but longer delay seem to not cause problems
+    #[cfg(esp32c6)]
+    {
+        crate::rom::ets_delay_us(40);
+        ADCI::start_onetime_sample();
+    }
+}
+
 impl<'d, ADCI> Adc<'d, ADCI, Blocking>
 where
     ADCI: RegisterAccess + 'd,
@@ -311,7 +328,11 @@ where
     }
 
     /// Reconfigures the ADC driver to operate in asynchronous mode.
-    pub fn into_async(mut self) -> Adc<'d, ADCI, Async> {
+    pub fn into_async(mut self) -> Adc<'d, ADCI, Async>
+    where
+        ADCI: Instance,
+    {
+        ADCI::queue().enabled.store(false, Ordering::Release);
         acquire_async_adc();
         self.set_interrupt_handler(adc_interrupt_handler);
 
@@ -357,21 +378,9 @@ where
             // If no conversions are in progress, start a new one for given channel
             self.active_channel = Some(PIN::CHANNEL);
 
-            // Set ADC unit calibration according used scheme for pin
-            ADCI::set_init_code(pin.cal_scheme.adc_cal());
-
             let channel = self.active_channel.unwrap();
             let attenuation = self.attenuations[channel as usize].unwrap() as u8;
-            ADCI::config_onetime_sample(channel, attenuation);
-            ADCI::start_onetime_sample();
-
-            // see https://github.com/espressif/esp-idf/blob/b4268c874a4cf8fcf7c0c4153cffb76ad2ddda4e/components/hal/adc_oneshot_hal.c#L105-L107
-            // the delay might be a bit generous but longer delay seem to not cause problems
-            #[cfg(esp32c6)]
-            {
-                crate::rom::ets_delay_us(40);
-                ADCI::start_onetime_sample();
-            }
+            start_conversion::<ADCI>(channel, attenuation, pin.cal_scheme.adc_cal());
         }
 
         // Wait for ADC to finish conversion
@@ -402,6 +411,156 @@ where
 
         Ok(converted_value)
     }
+
+    /// Start an interrupt-driven conversion on the specified pin
+    ///
+    /// Unlike [`Self::read_oneshot`], this method does not wait for the
+    /// conversion to finish. The result is pushed into a software queue by
+    /// the ADC interrupt handler, and can be retrieved with
+    /// [`Self::read_queued`]. The queue holds up to [`QUEUE_CAPACITY`]
+    /// conversions, further results are dropped until the queue is drained.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] if a previously started conversion
+    /// has not finished yet.
+    ///
+    /// The first call binds the ADC interrupt handler, replacing any handler
+    /// that was set using [`InterruptConfigurable::set_interrupt_handler`].
+    /// Conversions of different pins should not be mixed in the queue, as
+    /// [`Self::read_queued`] applies the calibration scheme of the pin it is
+    /// given.
+    pub fn start_queued<PIN, CS>(
+        &mut self,
+        pin: &mut super::AdcPin<PIN, ADCI, CS>,
+    ) -> nb::Result<(), ()>
+    where
+        ADCI: Instance,
+        PIN: super::AdcChannel,
+        CS: super::AdcCalScheme<ADCI>,
+    {
+        let channel = PIN::CHANNEL;
+        if self.attenuations[channel as usize].is_none() {
+            panic!("Channel {} is not configured reading!", channel);
+        }
+
+        let queue = ADCI::queue();
+        if self.active_channel.is_some() || queue.busy.load(Ordering::Acquire) {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        if !queue.enabled.swap(true, Ordering::AcqRel) {
+            self.set_interrupt_handler(adc_interrupt_handler);
+            ADCI::reset();
+        }
+
+        queue.channel.store(channel, Ordering::Relaxed);
+        queue.busy.store(true, Ordering::Release);
+        ADCI::listen();
+
+        let attenuation = self.attenuations[channel as usize].unwrap() as u8;
+        start_conversion::<ADCI>(channel, attenuation, pin.cal_scheme.adc_cal());
+
+        Ok(())
+    }
+
+    /// Take the oldest conversion result from the queue
+    ///
+    /// The result is postprocessed according to the calibration scheme of the
+    /// given pin, which should be the pin the conversion was started for in
+    /// [`Self::start_queued`].
+    ///
+    /// Returns [`nb::Error::WouldBlock`] if the queue is empty. If conversions
+    /// were dropped because the queue was full, this is reported once as
+    /// [`QueueOverflowed`] before the remaining results are returned.
+    pub fn read_queued<PIN, CS>(
+        &mut self,
+        pin: &mut super::AdcPin<PIN, ADCI, CS>,
+    ) -> nb::Result<u16, QueueOverflowed>
+    where
+        ADCI: Instance,
+        PIN: super::AdcChannel,
+        CS: super::AdcCalScheme<ADCI>,
+    {
+        let queue = ADCI::queue();
+
+        let dropped = queue.dropped.swap(0, Ordering::AcqRel);
+        if dropped != 0 {
+            return Err(nb::Error::Other(QueueOverflowed { dropped }));
+        }
+
+        match queue.pop() {
+            Some(value) => Ok(pin.cal_scheme.adc_val(value)),
+            None => Err(nb::Error::WouldBlock),
+        }
+    }
+
+    #[procmacros::doc_replace]
+    /// Call `handler` whenever a conversion of `pin` falls outside of the
+    /// window `low..=high`
+    ///
+    /// The window is checked against the raw conversion result, before the
+    /// calibration scheme of the pin is applied, for every conversion started
+    /// with [`Self::start_queued`]. `handler` is called from the ADC
+    /// interrupt handler, at the priority of the ADC interrupt, after the
+    /// result has been queued, so it can be read with [`Self::read_queued`].
+    /// It should return quickly, as it delays the next conversion result.
+    ///
+    /// The SAR ADC of these chips has hardware threshold monitors, but they
+    /// only observe conversions of the digital controller's continuous (DMA)
+    /// mode, which this driver does not implement.
+    ///
+    /// Only one monitor can be active per ADC instance, calling this again
+    /// replaces the previous one.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// # use esp_hal::analog::adc::{Adc, AdcConfig, Attenuation};
+    /// fn out_of_window() {
+    ///     // e.g. signal an alarm
+    /// }
+    ///
+    /// let mut config = AdcConfig::new();
+    /// let mut pin = config.enable_pin(peripherals.GPIO2, Attenuation::_11dB);
+    /// let mut adc = Adc::new(peripherals.ADC1, config);
+    ///
+    /// adc.enable_monitor(&pin, 1000, 3000, out_of_window);
+    /// loop {
+    ///     nb::block!(adc.start_queued(&mut pin))?;
+    ///     while adc.read_queued(&mut pin).is_ok() {}
+    /// }
+    /// # {after_snippet}
+    /// ```
+    pub fn enable_monitor<PIN, CS>(
+        &mut self,
+        _pin: &super::AdcPin<PIN, ADCI, CS>,
+        low: u16,
+        high: u16,
+        handler: fn(),
+    ) where
+        ADCI: Instance,
+        PIN: super::AdcChannel,
+        CS: super::AdcCalScheme<ADCI>,
+    {
+        let queue = ADCI::queue();
+
+        // Make sure a half-updated window is never used.
+        queue.monitor.store(core::ptr::null_mut(), Ordering::Release);
+        queue.monitor_channel.store(PIN::CHANNEL, Ordering::Relaxed);
+        queue.monitor_low.store(low, Ordering::Relaxed);
+        queue.monitor_high.store(high, Ordering::Relaxed);
+        queue.monitor.store(handler as *mut (), Ordering::Release);
+    }
+
+    /// Stop calling the handler registered with [`Self::enable_monitor`]
+    pub fn disable_monitor(&mut self)
+    where
+        ADCI: Instance,
+    {
+        ADCI::queue()
+            .monitor
+            .store(core::ptr::null_mut(), Ordering::Release);
+    }
 }
 
 impl<ADCI> crate::private::Sealed for Adc<'_, ADCI, Blocking> {}
@@ -422,30 +581,42 @@ impl<ADCI> InterruptConfigurable for Adc<'_, ADCI, Blocking> {
 #[cfg(adc_adc1)]
 impl super::AdcCalEfuse for crate::peripherals::ADC1<'_> {
     fn init_code(atten: Attenuation) -> Option<u16> {
-        Efuse::rtc_calib_init_code(1, atten)
+        read_efuse(1, atten, CalValue::InitCode, || {
+            Efuse::rtc_calib_init_code(1, atten)
+        })
     }
 
     fn cal_mv(atten: Attenuation) -> u16 {
-        Efuse::rtc_calib_cal_mv(1, atten)
+        unwrap!(read_efuse(1, atten, CalValue::CalMv, || {
+            Some(Efuse::rtc_calib_cal_mv(1, atten))
+        }))
     }
 
     fn cal_code(atten: Attenuation) -> Option<u16> {
-        Efuse::rtc_calib_cal_code(1, atten)
+        read_efuse(1, atten, CalValue::CalCode, || {
+            Efuse::rtc_calib_cal_code(1, atten)
+        })
     }
 }
 
 #[cfg(adc_adc2)]
 impl super::AdcCalEfuse for crate::peripherals::ADC2<'_> {
     fn init_code(atten: Attenuation) -> Option<u16> {
-        Efuse::rtc_calib_init_code(2, atten)
+        read_efuse(2, atten, CalValue::InitCode, || {
+            Efuse::rtc_calib_init_code(2, atten)
+        })
     }
 
     fn cal_mv(atten: Attenuation) -> u16 {
-        Efuse::rtc_calib_cal_mv(2, atten)
+        unwrap!(read_efuse(2, atten, CalValue::CalMv, || {
+            Some(Efuse::rtc_calib_cal_mv(2, atten))
+        }))
     }
 
     fn cal_code(atten: Attenuation) -> Option<u16> {
-        Efuse::rtc_calib_cal_code(2, atten)
+        read_efuse(2, atten, CalValue::CalCode, || {
+            Efuse::rtc_calib_cal_code(2, atten)
+        })
     }
 }
 
@@ -475,6 +646,11 @@ where
     /// This method takes an [AdcPin](super::AdcPin) reference, as it is
     /// expected that the ADC will be able to sample whatever channel
     /// underlies the pin.
+    ///
+    /// The task is woken by the ADC interrupt when the conversion is done,
+    /// so other tasks can run in the meantime. The result is processed by the
+    /// pin's calibration scheme, like in the blocking driver. Reads of
+    /// different pins can follow each other directly.
     pub async fn read_oneshot<PIN, CS>(&mut self, pin: &mut super::AdcPin<PIN, ADCI, CS>) -> u16
     where
         ADCI: Instance,
@@ -486,12 +662,8 @@ where
             panic!("Channel {} is not configured reading!", channel);
         }
 
-        // Set ADC unit calibration according used scheme for pin
-        ADCI::set_init_code(pin.cal_scheme.adc_cal());
-
         let attenuation = self.attenuations[channel as usize].unwrap() as u8;
-        ADCI::config_onetime_sample(channel, attenuation);
-        ADCI::start_onetime_sample();
+        start_conversion::<ADCI>(channel, attenuation, pin.cal_scheme.adc_cal());
 
         // Wait for ADC to finish conversion and get value
         let adc_ready_future = AdcFuture::new(self);
@@ -515,6 +687,112 @@ where
     }
 }
 
+/// The number of conversion results the queue of [`Adc::start_queued`] can
+/// hold.
+pub const QUEUE_CAPACITY: usize = 32;
+
+/// Conversion results were dropped because the queue was full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct QueueOverflowed {
+    dropped: u32,
+}
+
+impl QueueOverflowed {
+    /// The number of conversion results that were dropped.
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+}
+
+impl core::error::Error for QueueOverflowed {}
+
+impl core::fmt::Display for QueueOverflowed {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} ADC conversions were dropped", self.dropped)
+    }
+}
+
+/// Single-producer, single-consumer queue of conversion results, filled by the
+/// ADC interrupt handler.
+#[doc(hidden)]
+pub struct ConversionQueue {
+    buffer: [AtomicU16; QUEUE_CAPACITY],
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    dropped: AtomicU32,
+    enabled: AtomicBool,
+    busy: AtomicBool,
+    channel: AtomicU8,
+    monitor: AtomicPtr<()>,
+    monitor_channel: AtomicU8,
+    monitor_low: AtomicU16,
+    monitor_high: AtomicU16,
+}
+
+impl ConversionQueue {
+    const fn new() -> Self {
+        Self {
+            buffer: [const { AtomicU16::new(0) }; QUEUE_CAPACITY],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            dropped: AtomicU32::new(0),
+            enabled: AtomicBool::new(false),
+            busy: AtomicBool::new(false),
+            channel: AtomicU8::new(0),
+            monitor: AtomicPtr::new(core::ptr::null_mut()),
+            monitor_channel: AtomicU8::new(0),
+            monitor_low: AtomicU16::new(0),
+            monitor_high: AtomicU16::new(0),
+        }
+    }
+
+    // Only called from the interrupt handler.
+    fn push(&self, value: u16) {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        if head.wrapping_sub(tail) == QUEUE_CAPACITY {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.buffer[head % QUEUE_CAPACITY].store(value, Ordering::Relaxed);
+            self.head.store(head.wrapping_add(1), Ordering::Release);
+        }
+    }
+
+    // Only called by the driver.
+    fn pop(&self) -> Option<u16> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        let value = self.buffer[tail % QUEUE_CAPACITY].load(Ordering::Relaxed);
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+
+        Some(value)
+    }
+
+    // Only called from the interrupt handler.
+    fn check_monitor(&self, value: u16) {
+        let handler = self.monitor.load(Ordering::Acquire);
+        if handler.is_null()
+            || self.channel.load(Ordering::Relaxed) != self.monitor_channel.load(Ordering::Relaxed)
+        {
+            return;
+        }
+
+        let low = self.monitor_low.load(Ordering::Relaxed);
+        let high = self.monitor_high.load(Ordering::Relaxed);
+        if !(low..=high).contains(&value) {
+            // Only `fn()` pointers are stored in `monitor`.
+            unsafe { core::mem::transmute::<*mut (), fn()>(handler)() };
+        }
+    }
+}
+
 #[cfg(all(adc_adc1, adc_adc2))]
 static ASYNC_ADC_COUNT: AtomicU32 = AtomicU32::new(0);
 
@@ -549,7 +827,20 @@ pub(crate) fn adc_interrupt_handler() {
     }
 }
 
-fn handle_async<ADCI: Instance>(_instance: ADCI) {
+fn handle_async<ADCI: Instance + RegisterAccess>(_instance: ADCI) {
+    let queue = ADCI::queue();
+    if queue.enabled.load(Ordering::Acquire) {
+        if ADCI::is_done() {
+            let value = ADCI::read_data();
+            queue.push(value);
+            ADCI::reset();
+            queue.busy.store(false, Ordering::Release);
+            queue.check_monitor(value);
+        }
+        ADCI::unlisten();
+        return;
+    }
+
     ADCI::waker().wake();
     ADCI::unlisten();
 }
@@ -567,6 +858,10 @@ pub trait Instance: crate::private::Sealed {
 
     /// Obtain the waker for the ADC interrupt
     fn waker() -> &'static AtomicWaker;
+
+    /// Obtain the conversion result queue
+    #[doc(hidden)]
+    fn queue() -> &'static ConversionQueue;
 }
 
 #[cfg(adc_adc1)]
@@ -594,6 +889,12 @@ impl Instance for crate::peripherals::ADC1<'_> {
 
         &WAKER
     }
+
+    fn queue() -> &'static ConversionQueue {
+        static QUEUE: ConversionQueue = ConversionQueue::new();
+
+        &QUEUE
+    }
 }
 
 #[cfg(adc_adc2)]
@@ -621,6 +922,12 @@ impl Instance for crate::peripherals::ADC2<'_> {
 
         &WAKER
     }
+
+    fn queue() -> &'static ConversionQueue {
+        static QUEUE: ConversionQueue = ConversionQueue::new();
+
+        &QUEUE
+    }
 }
 
 #[must_use = "futures do nothing unless you `.await` or poll them"]
@@ -2,6 +2,8 @@ use core::marker::PhantomData;
 
 #[cfg(esp32s3)]
 pub use self::calibration::*;
+#[cfg(esp32s3)]
+use self::calibration::{CalValue, read_efuse};
 use super::{AdcCalScheme, AdcCalSource, AdcChannel, AdcConfig, AdcPin, Attenuation};
 #[cfg(esp32s3)]
 use crate::efuse::Efuse;
@@ -492,29 +494,41 @@ where
 #[cfg(esp32s3)]
 impl super::AdcCalEfuse for crate::peripherals::ADC1<'_> {
     fn init_code(atten: Attenuation) -> Option<u16> {
-        Efuse::rtc_calib_init_code(1, atten)
+        read_efuse(1, atten, CalValue::InitCode, || {
+            Efuse::rtc_calib_init_code(1, atten)
+        })
     }
 
     fn cal_mv(atten: Attenuation) -> u16 {
-        Efuse::rtc_calib_cal_mv(1, atten)
+        unwrap!(read_efuse(1, atten, CalValue::CalMv, || {
+            Some(Efuse::rtc_calib_cal_mv(1, atten))
+        }))
     }
 
     fn cal_code(atten: Attenuation) -> Option<u16> {
-        Efuse::rtc_calib_cal_code(1, atten)
+        read_efuse(1, atten, CalValue::CalCode, || {
+            Efuse::rtc_calib_cal_code(1, atten)
+        })
     }
 }
 
 #[cfg(esp32s3)]
 impl super::AdcCalEfuse for crate::peripherals::ADC2<'_> {
     fn init_code(atten: Attenuation) -> Option<u16> {
-        Efuse::rtc_calib_init_code(2, atten)
+        read_efuse(2, atten, CalValue::InitCode, || {
+            Efuse::rtc_calib_init_code(2, atten)
+        })
     }
 
     fn cal_mv(atten: Attenuation) -> u16 {
-        Efuse::rtc_calib_cal_mv(2, atten)
+        unwrap!(read_efuse(2, atten, CalValue::CalMv, || {
+            Some(Efuse::rtc_calib_cal_mv(2, atten))
+        }))
     }
 
     fn cal_code(atten: Attenuation) -> Option<u16> {
-        Efuse::rtc_calib_cal_code(2, atten)
+        read_efuse(2, atten, CalValue::CalCode, || {
+            Efuse::rtc_calib_cal_code(2, atten)
+        })
     }
 }
@@ -42,8 +42,31 @@
 //! }
 //! # }
 //! ```
+//!
+//! ### Generate a cosine wave
+//! The DAC has a cosine wave generator, which outputs a wave without any CPU
+//! involvement. The frequency is shared by both channels, the amplitude,
+//! phase and offset can be configured per channel.
+//! ```rust, no_run
+//! # {before_snippet}
+//! # use esp_hal::analog::dac::{CosineConfig, CosineScale, Dac};
+//! # {dac1_pin}
+//! let mut dac1 = Dac::new(peripherals.DAC1, dac1_pin);
+//!
+//! dac1.enable_cosine(
+//!     &CosineConfig::default()
+//!         .with_frequency(Rate::from_khz(1))
+//!         .with_scale(CosineScale::Half),
+//! )?;
+//! # {after_snippet}
+//! ```
 
-use crate::gpio::AnalogPin;
+use crate::{
+    clock::Clocks,
+    gpio::AnalogPin,
+    peripherals::{LPWR, SENS},
+    time::Rate,
+};
 
 // Only specific pins can be used with each DAC peripheral, and of course
 // these pins are different depending on which chip you are using; for this
@@ -79,7 +102,7 @@ where
         pin.set_analog(crate::private::Internal);
 
         #[cfg(esp32s2)]
-        crate::peripherals::SENS::regs()
+        SENS::regs()
             .sar_dac_ctrl1()
             .modify(|_, w| w.dac_clkgate_en().set_bit());
 
@@ -95,12 +118,141 @@ where
     ///
     /// For each DAC channel, the output analog voltage can be calculated as
     /// follows: DACn_OUT = VDD3P3_RTC * PDACn_DAC/256
+    ///
+    /// This stops the cosine wave generator on this channel.
     pub fn write(&mut self, value: u8) {
         T::set_pad_source();
         T::write_byte(value);
     }
+
+    /// Outputs a cosine wave generated by the hardware.
+    ///
+    /// The wave is centered on half the supply voltage, shifted by the
+    /// configured offset, and swings by up to half the supply voltage on each
+    /// side. A full-scale wave with an offset gets clipped.
+    ///
+    /// The generator is clocked from RC_FAST_CLK, so the frequency is only as
+    /// accurate as the frequency [`Clocks::rc_fast_clock`] reports, and the
+    /// step between two frequencies is about 130 Hz. The output gets
+    /// noticeably distorted above a hundred kHz or so.
+    ///
+    /// The frequency is shared by both DAC channels, so this also changes
+    /// the frequency of the other channel if it outputs a cosine wave.
+    ///
+    /// Call [`Self::write`] to stop the generator.
+    pub fn enable_cosine(&mut self, config: &CosineConfig) -> Result<(), CosineError> {
+        let rc_fast = Clocks::rc_fast_clock().as_hz() as u64;
+        let step = config.frequency.as_hz() as u64 * 65536 / rc_fast;
+        if !(1..=u16::MAX as u64).contains(&step) {
+            return Err(CosineError::UnsupportedFrequency);
+        }
+
+        // The generator runs from the digital gate of RC_FAST_CLK.
+        LPWR::regs()
+            .clk_conf()
+            .modify(|_, w| w.dig_clk8m_en().set_bit());
+
+        let offset = match config.phase {
+            CosinePhase::Phase0 => config.offset,
+            // Inverting the phase also inverts the offset.
+            CosinePhase::Phase180 => config.offset.wrapping_neg(),
+        };
+
+        let sens = SENS::regs();
+        sens.sar_dac_ctrl1().modify(|_, w| unsafe {
+            w.sw_fstep().bits(step as u16);
+            w.sw_tone_en().set_bit()
+        });
+        sens.sar_dac_ctrl2().modify(|_, w| unsafe {
+            w.dac_scale(T::INDEX as u8).bits(config.scale as u8);
+            w.dac_inv(T::INDEX as u8).bits(config.phase as u8);
+            w.dac_dc(T::INDEX as u8).bits(offset as u8);
+            w.dac_cw_en(T::INDEX as u8).set_bit()
+        });
+
+        Ok(())
+    }
+}
+
+/// The amplitude of the cosine wave, relative to the largest wave the DAC can
+/// output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CosineScale {
+    /// The full range of the DAC.
+    #[default]
+    Full    = 0,
+    /// Half of the range.
+    Half    = 1,
+    /// A quarter of the range.
+    Quarter = 2,
+    /// An eighth of the range.
+    Eighth  = 3,
+}
+
+/// The phase of the cosine wave.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CosinePhase {
+    /// No phase shift.
+    #[default]
+    Phase0   = 2,
+    /// Shifted by 180 degrees, which allows outputting a differential signal
+    /// on the two channels.
+    Phase180 = 3,
+}
+
+/// Cosine wave generator configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, procmacros::BuilderLite)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub struct CosineConfig {
+    /// The frequency of the wave.
+    frequency: Rate,
+
+    /// The amplitude of the wave.
+    scale: CosineScale,
+
+    /// The phase of the wave.
+    phase: CosinePhase,
+
+    /// The offset of the center of the wave, in DAC steps.
+    offset: i8,
+}
+
+impl Default for CosineConfig {
+    fn default() -> Self {
+        Self {
+            frequency: Rate::from_khz(1),
+            scale: CosineScale::default(),
+            phase: CosinePhase::default(),
+            offset: 0,
+        }
+    }
+}
+
+/// Cosine wave generator errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum CosineError {
+    /// The frequency is below the frequency step of the generator, or above
+    /// the frequency of RC_FAST_CLK.
+    UnsupportedFrequency,
+}
+
+impl core::fmt::Display for CosineError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CosineError::UnsupportedFrequency => {
+                write!(f, "The frequency is not supported by the generator")
+            }
+        }
+    }
 }
 
+impl core::error::Error for CosineError {}
+
 #[doc(hidden)]
 pub trait Instance: crate::private::Sealed {
     const INDEX: usize;
@@ -114,7 +266,7 @@ pub trait Instance: crate::private::Sealed {
     }
 
     fn set_pad_source() {
-        crate::peripherals::SENS::regs()
+        SENS::regs()
             .sar_dac_ctrl2()
             .modify(|_, w| w.dac_cw_en(Self::INDEX as u8).clear_bit());
     }
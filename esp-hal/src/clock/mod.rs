@@ -47,6 +47,8 @@
 
 use core::{cell::Cell, marker::PhantomData};
 
+use portable_atomic::{AtomicI32, AtomicU32, Ordering};
+
 #[cfg(bt)]
 use crate::peripherals::BT;
 #[cfg(all(feature = "unstable", ieee802154))]
@@ -328,6 +330,30 @@ pub struct Clocks {
 
 static mut ACTIVE_CLOCKS: Option<Clocks> = None;
 
+/// The last measured frequency of RC_FAST_CLK in Hz, or 0 if it hasn't been
+/// measured yet.
+static RC_FAST_FREQUENCY: AtomicU32 = AtomicU32::new(0);
+
+/// The change of the RC_FAST_CLK frequency found by the last measurement, in
+/// parts per million.
+static RC_FAST_DEVIATION_PPM: AtomicI32 = AtomicI32::new(0);
+
+/// RC_FAST_CLK did not tick while it was measured.
+///
+/// This error is returned by [`Clocks::recalibrate_rc`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub struct RcFastNotRunning;
+
+impl core::error::Error for RcFastNotRunning {}
+
+impl core::fmt::Display for RcFastNotRunning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "RC_FAST_CLK did not tick while it was measured")
+    }
+}
+
 impl Clocks {
     pub(crate) fn init(cpu_clock_speed: CpuClock) {
         critical_section::with(|_| {
@@ -352,6 +378,67 @@ impl Clocks {
         unwrap!(Self::try_get())
     }
 
+    /// Returns the frequency of RC_FAST_CLK that drivers assume.
+    ///
+    /// This is the nominal frequency of the oscillator until
+    /// [`Self::recalibrate_rc`] is called, and the measured frequency after
+    /// that.
+    #[instability::unstable]
+    pub fn rc_fast_clock() -> Rate {
+        match RC_FAST_FREQUENCY.load(Ordering::Relaxed) {
+            0 => Rate::from_hz(property!("soc.rc_fast_clk_default")),
+            hz => Rate::from_hz(hz),
+        }
+    }
+
+    /// Measures the frequency of RC_FAST_CLK against the XTAL clock.
+    ///
+    /// The frequency of the internal RC oscillator changes with the
+    /// temperature and the supply voltage, which affects the peripherals
+    /// clocked from it, for example the baud rate of a UART using
+    /// `ClockSource::RcFast`. After this function returns, drivers use the
+    /// measured frequency when they are configured. Drivers that have already
+    /// been configured need to be configured again, for example by calling
+    /// `Uart::apply_config`, to pick up the new frequency.
+    ///
+    /// The measurement takes up to a few tens of milliseconds, during which
+    /// this function blocks.
+    ///
+    /// Returns the difference between the measured frequency and the
+    /// frequency assumed before, in parts per million. See
+    /// [`Self::rc_fast_deviation_ppm`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`RcFastNotRunning`] if the oscillator could not be measured.
+    /// The previously assumed frequency is kept in this case.
+    #[instability::unstable]
+    pub fn recalibrate_rc() -> Result<i32, RcFastNotRunning> {
+        let previous = Self::rc_fast_clock().as_hz() as i64;
+        let measured = RtcClock::measure_rc_fast_frequency()
+            .ok_or(RcFastNotRunning)?
+            .as_hz();
+
+        let deviation_ppm = ((measured as i64 - previous) * 1_000_000 / previous) as i32;
+
+        RC_FAST_FREQUENCY.store(measured, Ordering::Relaxed);
+        RC_FAST_DEVIATION_PPM.store(deviation_ppm, Ordering::Relaxed);
+
+        Ok(deviation_ppm)
+    }
+
+    /// Returns the result of the last [`Self::recalibrate_rc`] call.
+    ///
+    /// This is the difference between the measured RC_FAST_CLK frequency and
+    /// the frequency assumed before, in parts per million, or 0 if the
+    /// oscillator hasn't been measured yet. A large deviation means the
+    /// conditions are changing quickly, and the oscillator should be measured
+    /// more often.
+    #[instability::unstable]
+    pub fn rc_fast_deviation_ppm() -> i32 {
+        RC_FAST_DEVIATION_PPM.load(Ordering::Relaxed)
+    }
+
     /// Returns the xtal frequency.
     ///
     /// This function will run the frequency estimation if called before
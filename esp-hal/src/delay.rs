@@ -56,6 +56,41 @@ impl Delay {
         while start.elapsed() < delay {}
     }
 
+    #[procmacros::doc_replace]
+    /// Delay until the specified instant
+    ///
+    /// Unlike [`Self::delay`], this waits for an absolute point in time, so
+    /// the time spent between calls doesn't accumulate. This makes it
+    /// suitable for running a task periodically without drifting.
+    ///
+    /// If `deadline` is already in the past, this returns immediately. A loop
+    /// that overruns its period will therefore not wait at all until it has
+    /// caught up with the schedule.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::{
+    ///     delay::Delay,
+    ///     time::{Duration, Instant},
+    /// };
+    /// let delay = Delay::new();
+    ///
+    /// let mut next = Instant::now();
+    /// for _ in 0..100 {
+    ///     // Do some periodic work here.
+    ///
+    ///     next += Duration::from_millis(10);
+    ///     delay.delay_until(next);
+    /// }
+    /// # {after_snippet}
+    /// ```
+    #[instability::unstable]
+    pub fn delay_until(&self, deadline: Instant) {
+        while Instant::now() < deadline {}
+    }
+
     /// Delay for the specified number of milliseconds
     pub fn delay_millis(&self, ms: u32) {
         self.delay(Duration::from_millis(ms as u64));
@@ -479,6 +479,33 @@ pub unsafe trait DmaRxBuffer {
     fn from_view(view: Self::View) -> Self::Final;
 }
 
+/// DMA buffers that can report the progress of an in-flight transfer.
+///
+/// The DMA only exposes its progress one descriptor at a time, so the reported
+/// number of bytes advances in steps of up to [CHUNK_SIZE](super::CHUNK_SIZE)
+/// bytes:
+///
+/// - For receive buffers, a descriptor is counted once the DMA has written back its length, i.e.
+///   once its data has landed in memory.
+/// - For transmit buffers, a descriptor is counted once the DMA has moved on to the next one, i.e.
+///   once its data has been pushed into the peripheral's FIFO. The data may still be waiting in the
+///   FIFO at that point.
+///
+/// Drivers expose this through a `transferred_bytes` method on their transfer
+/// types, for example
+/// [SpiDmaTransfer::transferred_bytes](crate::spi::master::SpiDmaTransfer::transferred_bytes).
+#[instability::unstable]
+pub trait DmaBufferProgress {
+    /// Returns the number of bytes the DMA has completed so far.
+    ///
+    /// `current_out_descriptor` is the address of the descriptor the transmit
+    /// channel is currently processing. Receive buffers ignore it.
+    fn completed_bytes(&self, current_out_descriptor: usize) -> usize;
+
+    /// Returns the number of bytes the transfer moves in total.
+    fn total_bytes(&self) -> usize;
+}
+
 /// An in-progress view into [DmaRxBuf]/[DmaTxBuf].
 ///
 /// In the future, this could support peeking into state of the
@@ -633,6 +660,25 @@ impl DmaTxBuf {
     }
 }
 
+impl DmaBufferProgress for DmaTxBuf {
+    fn completed_bytes(&self, current_out_descriptor: usize) -> usize {
+        let mut completed = 0;
+        for desc in self.descriptors.linked_iter() {
+            if core::ptr::from_ref(desc) as usize == current_out_descriptor {
+                return completed;
+            }
+            completed += desc.len();
+        }
+
+        // The channel hasn't picked up any of our descriptors yet.
+        0
+    }
+
+    fn total_bytes(&self) -> usize {
+        self.len()
+    }
+}
+
 unsafe impl DmaTxBuffer for DmaTxBuf {
     type View = BufView<DmaTxBuf>;
     type Final = DmaTxBuf;
@@ -840,6 +886,32 @@ impl DmaRxBuf {
     }
 }
 
+impl DmaBufferProgress for DmaRxBuf {
+    fn completed_bytes(&self, _current_out_descriptor: usize) -> usize {
+        self.number_of_received_bytes()
+    }
+
+    fn total_bytes(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<RX, TX> DmaBufferProgress for (RX, TX)
+where
+    RX: DmaBufferProgress,
+    TX: DmaBufferProgress,
+{
+    fn completed_bytes(&self, current_out_descriptor: usize) -> usize {
+        self.0
+            .completed_bytes(current_out_descriptor)
+            .max(self.1.completed_bytes(current_out_descriptor))
+    }
+
+    fn total_bytes(&self) -> usize {
+        self.0.total_bytes().max(self.1.total_bytes())
+    }
+}
+
 unsafe impl DmaRxBuffer for DmaRxBuf {
     type View = BufView<DmaRxBuf>;
     type Final = DmaRxBuf;
@@ -1625,24 +1697,108 @@ unsafe impl DmaRxBuffer for NoBuffer {
     fn from_view(_view: Self::View) {}
 }
 
-/// Prepares data unsafely to be transmitted via DMA.
+#[procmacros::doc_replace]
+/// A fixed-size set of DMA descriptors.
 ///
-/// `block_size` is the requirement imposed by the peripheral that receives the data. It
-/// ensures that the DMA will not try to copy a partial block, which would cause the RX DMA (that
-/// moves results back into RAM) to never complete.
+/// Each descriptor can point to at most one chunk of data, so the number of
+/// descriptors limits the size of the buffer that can be transferred at once.
+/// Preparing a transfer for a buffer that does not fit into the set fails with
+/// [`DmaError::OutOfDescriptors`], instead of transferring only part of the
+/// buffer.
 ///
-/// The function returns the DMA buffer, and the number of bytes that will be transferred.
+/// ## Example
 ///
-/// # Safety
+/// ```rust,no_run
+/// # {before_snippet}
+/// use esp_hal::dma::DmaDescriptorSet;
 ///
-/// The caller must keep all its descriptors and the buffers they
-/// point to valid while the buffer is being transferred.
-#[cfg_attr(not(aes_dma), expect(unused))]
-pub(crate) unsafe fn prepare_for_tx(
-    descriptors: &mut [DmaDescriptor],
-    mut data: NonNull<[u8]>,
-    block_size: usize,
-) -> Result<(NoBuffer, usize), DmaError> {
+/// let descriptors = DmaDescriptorSet::<3>::new();
+/// assert_eq!(descriptors.len(), 3);
+/// // For a peripheral that processes 64-byte blocks, each descriptor points to
+/// // at most 4096 - 64 bytes.
+/// assert_eq!(DmaDescriptorSet::<3>::max_transfer_size(64), 3 * (4096 - 64));
+/// # {after_snippet}
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub struct DmaDescriptorSet<const N: usize> {
+    descriptors: [DmaDescriptor; N],
+}
+
+impl<const N: usize> DmaDescriptorSet<N> {
+    /// Creates a new set of empty descriptors.
+    pub const fn new() -> Self {
+        const { assert!(N >= 1, "A descriptor set needs at least one descriptor") };
+
+        Self {
+            descriptors: [DmaDescriptor::EMPTY; N],
+        }
+    }
+
+    /// Returns the largest buffer that can be transmitted to a peripheral that
+    /// processes `block_size` sized blocks.
+    ///
+    /// Each descriptor points to at most `4096 - max(alignment, block_size)`
+    /// bytes, where `alignment` is the alignment the DMA requires for the
+    /// buffer. This function assumes the strictest alignment of the default
+    /// [`BurstConfig`], so buffers of the returned size are accepted
+    /// regardless of where they are located.
+    pub const fn max_transfer_size(block_size: usize) -> usize {
+        let alignment = max(BurstConfig::DEFAULT.min_compatible_alignment(), block_size);
+        N * BurstConfig::chunk_size_for_alignment(alignment)
+    }
+
+    /// Prepares the whole of `data` to be transmitted via DMA.
+    ///
+    /// See [`prepare_for_tx`] for the meaning of `block_size`. Unlike
+    /// [`prepare_for_tx`], this function returns
+    /// [`DmaError::OutOfDescriptors`] if `data` does not fit into the set.
+    ///
+    /// # Safety
+    ///
+    /// The caller must keep the set and `data` valid while the buffer is
+    /// being transferred.
+    #[cfg_attr(not(sha_dma), expect(unused))]
+    pub(crate) unsafe fn prepare_for_tx(
+        &mut self,
+        data: NonNull<[u8]>,
+        block_size: usize,
+    ) -> Result<(NoBuffer, usize), DmaError> {
+        let chunk_size = tx_chunk_size(data, block_size)?;
+        if data.len() > N * chunk_size {
+            return Err(DmaError::OutOfDescriptors);
+        }
+
+        unsafe { prepare_for_tx(&mut self.descriptors, data, block_size) }
+    }
+}
+
+impl<const N: usize> Default for DmaDescriptorSet<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for DmaDescriptorSet<N> {
+    type Target = [DmaDescriptor];
+
+    fn deref(&self) -> &Self::Target {
+        &self.descriptors
+    }
+}
+
+impl<const N: usize> DerefMut for DmaDescriptorSet<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.descriptors
+    }
+}
+
+/// Returns the number of bytes a single TX descriptor may point to, when
+/// transferring `data` to a peripheral that processes `block_size` sized
+/// blocks.
+#[cfg_attr(not(any(aes_dma, sha_dma)), expect(unused))]
+fn tx_chunk_size(data: NonNull<[u8]>, block_size: usize) -> Result<usize, DmaError> {
     let alignment =
         BurstConfig::DEFAULT.min_alignment(unsafe { data.as_ref() }, TransferDirection::Out);
 
@@ -1657,7 +1813,28 @@ pub(crate) unsafe fn prepare_for_tx(
     // most N+2 descriptors. While the hardware doesn't require this on the TX DMA side, (the TX DMA
     // can, except on the ESP32, transfer any amount of data), it makes usage MUCH simpler.
     let alignment = alignment.max(block_size);
-    let chunk_size = 4096 - alignment;
+    Ok(4096 - alignment)
+}
+
+/// Prepares data unsafely to be transmitted via DMA.
+///
+/// `block_size` is the requirement imposed by the peripheral that receives the data. It
+/// ensures that the DMA will not try to copy a partial block, which would cause the RX DMA (that
+/// moves results back into RAM) to never complete.
+///
+/// The function returns the DMA buffer, and the number of bytes that will be transferred.
+///
+/// # Safety
+///
+/// The caller must keep all its descriptors and the buffers they
+/// point to valid while the buffer is being transferred.
+#[cfg_attr(not(any(aes_dma, sha_dma)), expect(unused))]
+pub(crate) unsafe fn prepare_for_tx(
+    descriptors: &mut [DmaDescriptor],
+    mut data: NonNull<[u8]>,
+    block_size: usize,
+) -> Result<(NoBuffer, usize), DmaError> {
+    let chunk_size = tx_chunk_size(data, block_size)?;
 
     let data_len = data.len().min(chunk_size * descriptors.len());
 
@@ -228,6 +228,10 @@ impl TxRegisterAccess for AnyGdmaTxChannel<'_> {
             .bits() as _
     }
 
+    fn current_dscr_address(&self) -> usize {
+        self.ch().out_dscr().read().outlink_dscr().bits() as _
+    }
+
     fn async_handler(&self) -> Option<InterruptHandler> {
         match self.channel {
             0 => DMA_CH0::handler_out(),
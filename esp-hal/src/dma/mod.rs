@@ -45,6 +45,28 @@
 //! # {after_snippet}
 //! ```
 //!
+//! ### Channel ownership
+//!
+//! Each DMA channel is a singleton in [`crate::peripherals`], and drivers take
+//! ownership of the channel they are configured with. A channel can therefore
+//! not be used by two drivers at the same time, the compiler rejects this:
+//!
+//! ```rust, compile_fail
+//! # {before_snippet}
+//! # use esp_hal::spi::master::{Config, Spi};
+//! # {dma_channel}
+//! let spi = Spi::new(peripherals.SPI2, Config::default())?.with_dma(dma_channel);
+//!
+//! // error[E0382]: use of moved value: `dma_channel`
+//! let channel_again = dma_channel;
+//! # drop(spi);
+//! # {after_snippet}
+//! ```
+//!
+//! To use a channel with a driver only temporarily, pass
+//! `dma_channel.reborrow()` instead. The channel can be used again once that
+//! driver has been dropped.
+//!
 //! ⚠️ Note: Descriptors should be sized as `(max_transfer_size + CHUNK_SIZE - 1) / CHUNK_SIZE`.
 //! I.e., to transfer buffers of size `1..=CHUNK_SIZE`, you need 1 descriptor.
 //!
@@ -2262,6 +2284,10 @@ where
         self.tx_impl.last_dscr_address()
     }
 
+    pub(crate) fn current_out_dscr_address(&self) -> usize {
+        self.tx_impl.current_dscr_address()
+    }
+
     pub(crate) fn is_done(&self) -> bool {
         self.pending_out_interrupts()
             .contains(DmaTxInterrupt::TotalEof)
@@ -2340,6 +2366,10 @@ pub trait TxRegisterAccess: RegisterAccess {
     /// Outlink descriptor address when EOF occurs of Tx channel.
     fn last_dscr_address(&self) -> usize;
 
+    /// Address of the outlink descriptor the Tx channel is currently
+    /// processing.
+    fn current_dscr_address(&self) -> usize;
+
     fn peripheral_interrupt(&self) -> Option<Interrupt>;
     fn async_handler(&self) -> Option<InterruptHandler>;
 }
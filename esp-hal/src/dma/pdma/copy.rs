@@ -133,6 +133,10 @@ impl TxRegisterAccess for CopyDmaTxChannel<'_> {
             .bits() as usize
     }
 
+    fn current_dscr_address(&self) -> usize {
+        self.regs().outlink_dscr().read().outlink_dscr().bits() as usize
+    }
+
     fn peripheral_interrupt(&self) -> Option<Interrupt> {
         None
     }
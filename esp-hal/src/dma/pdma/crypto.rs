@@ -159,6 +159,10 @@ impl TxRegisterAccess for CryptoDmaTxChannel<'_> {
             .bits() as usize
     }
 
+    fn current_dscr_address(&self) -> usize {
+        self.regs().dma_out_dscr().read().dma_out_dscr().bits() as usize
+    }
+
     fn peripheral_interrupt(&self) -> Option<Interrupt> {
         None
     }
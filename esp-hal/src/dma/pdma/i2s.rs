@@ -145,6 +145,10 @@ impl TxRegisterAccess for AnyI2sDmaTxChannel<'_> {
             .bits() as usize
     }
 
+    fn current_dscr_address(&self) -> usize {
+        self.regs().outlink_dscr().read().outlink_dscr().bits() as usize
+    }
+
     fn peripheral_interrupt(&self) -> Option<Interrupt> {
         Some(self.0.peripheral_interrupt())
     }
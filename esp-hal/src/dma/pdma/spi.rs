@@ -146,6 +146,10 @@ impl TxRegisterAccess for AnySpiDmaTxChannel<'_> {
             .bits() as usize
     }
 
+    fn current_dscr_address(&self) -> usize {
+        self.regs().outlink_dscr().read().dma_outlink_dscr().bits() as usize
+    }
+
     fn peripheral_interrupt(&self) -> Option<Interrupt> {
         None
     }
@@ -5,6 +5,7 @@ pub use fields::*;
 
 /// Representing different types of ESP32 chips.
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ChipType {
     /// Represents the ESP32 D0WDQ6 chip variant.
     Esp32D0wdq6,
@@ -72,10 +73,54 @@ impl super::Efuse {
         }
     }
 
+    /// Returns the major hardware revision
+    pub fn major_chip_version() -> u8 {
+        // Like `efuse_hal_get_major_chip_version` in ESP-IDF: the revision is
+        // encoded in two eFuse bits and in the date register of APB_CTRL.
+        let eco_bit0 = Self::read_bit(CHIP_VER_REV1) as u8;
+        let eco_bit1 = Self::read_bit(CHIP_VER_REV2) as u8;
+        let eco_bit2 = (crate::peripherals::APB_CTRL::regs().date().read().bits() >> 31) as u8;
+
+        match (eco_bit2 << 2) | (eco_bit1 << 1) | eco_bit0 {
+            0b001 => 1,
+            0b011 => 2,
+            0b111 => 3,
+            _ => 0,
+        }
+    }
+
+    /// Returns the minor hardware revision
+    pub fn minor_chip_version() -> u8 {
+        Self::read_field_le(WAFER_VERSION_MINOR)
+    }
+
+    /// Returns the hardware revision
+    ///
+    /// The chip version is calculated using the following
+    /// formula: MAJOR * 100 + MINOR. (if the result is 1, then version is v0.1)
+    pub fn chip_revision() -> u16 {
+        Self::major_chip_version() as u16 * 100 + Self::minor_chip_version() as u16
+    }
+
     /// Get status of SPI boot encryption.
     pub fn flash_encryption() -> bool {
         (Self::read_field_le::<u8>(FLASH_CRYPT_CNT).count_ones() % 2) != 0
     }
+
+    /// Returns whether secure boot V2 is enabled.
+    pub fn secure_boot_enabled() -> bool {
+        Self::read_bit(ABS_DONE_1)
+    }
+
+    /// Returns whether JTAG is permanently disabled.
+    pub fn jtag_disabled() -> bool {
+        Self::read_bit(JTAG_DISABLE)
+    }
+
+    /// Returns whether the ROM's UART download mode is permanently disabled.
+    pub fn download_mode_disabled() -> bool {
+        Self::read_bit(UART_DOWNLOAD_DIS)
+    }
 }
 
 #[derive(Debug, Clone, Copy, strum::FromRepr)]
@@ -3,6 +3,16 @@ use crate::{analog::adc::Attenuation, peripherals::EFUSE};
 mod fields;
 pub use fields::*;
 
+/// Chip package, as encoded in the `PKG_VERSION` eFuse field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Package {
+    /// ESP32-C2 / ESP8684 (QFN24)
+    Esp32c2,
+    /// A package version this driver doesn't know about.
+    Unknown(u8),
+}
+
 impl super::Efuse {
     /// Get status of SPI boot encryption.
     pub fn flash_encryption() -> bool {
@@ -11,6 +21,21 @@ impl super::Efuse {
             .is_multiple_of(2)
     }
 
+    /// Returns whether secure boot V2 is enabled.
+    pub fn secure_boot_enabled() -> bool {
+        Self::read_bit(SECURE_BOOT_EN)
+    }
+
+    /// Returns whether JTAG is permanently disabled.
+    pub fn jtag_disabled() -> bool {
+        Self::read_bit(DIS_PAD_JTAG)
+    }
+
+    /// Returns whether the ROM's UART download mode is permanently disabled.
+    pub fn download_mode_disabled() -> bool {
+        Self::read_bit(DIS_DOWNLOAD_MODE)
+    }
+
     /// Get the multiplier for the timeout value of the RWDT STAGE 0 register.
     pub fn rwdt_multiplier() -> u8 {
         Self::read_field_le::<u8>(WDT_DELAY_SEL)
@@ -107,6 +132,47 @@ impl super::Efuse {
 
         Some(code11)
     }
+
+    /// Get the temperature sensor calibration value, in units of 0.1 °C
+    ///
+    /// The value is the deviation of the factory-measured temperature from the
+    /// sensor's reading, which has to be subtracted from the computed temperature.
+    /// Returns `None` if the chip has no calibration data.
+    ///
+    /// see <https://github.com/espressif/esp-idf/blob/903af13e8/components/efuse/esp32c2/esp_efuse_rtc_calib.c>
+    pub fn rtc_calib_tsens_val() -> Option<i16> {
+        if Self::rtc_calib_version() != 1 {
+            return None;
+        }
+
+        Some(Self::read_tsens_calibration(TEMP_CALIB))
+    }
+
+    /// Returns the major hardware revision
+    pub fn major_chip_version() -> u8 {
+        Self::read_field_le(WAFER_VERSION_MAJOR)
+    }
+
+    /// Returns the minor hardware revision
+    pub fn minor_chip_version() -> u8 {
+        Self::read_field_le(WAFER_VERSION_MINOR)
+    }
+
+    /// Returns the hardware revision
+    ///
+    /// The chip version is calculated using the following
+    /// formula: MAJOR * 100 + MINOR. (if the result is 1, then version is v0.1)
+    pub fn chip_revision() -> u16 {
+        Self::major_chip_version() as u16 * 100 + Self::minor_chip_version() as u16
+    }
+
+    /// Returns the package the chip is mounted in.
+    pub fn package() -> Package {
+        match Self::read_field_le::<u8>(PKG_VERSION) {
+            0 | 1 => Package::Esp32c2,
+            other => Package::Unknown(other),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, strum::FromRepr)]
@@ -1,8 +1,20 @@
-use crate::{analog::adc::Attenuation, peripherals::EFUSE};
+use crate::{analog::adc::Attenuation, efuse::FlashSize, peripherals::EFUSE};
 
 mod fields;
 pub use fields::*;
 
+/// Chip package, as encoded in the `PKG_VERSION` eFuse field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Package {
+    /// ESP32-C6 (QFN40)
+    Esp32c6,
+    /// ESP32-C6FH4 (QFN32)
+    Esp32c6Fh4,
+    /// A package version this driver doesn't know about.
+    Unknown(u8),
+}
+
 impl super::Efuse {
     /// Get status of SPI boot encryption.
     pub fn flash_encryption() -> bool {
@@ -11,6 +23,25 @@ impl super::Efuse {
             .is_multiple_of(2)
     }
 
+    /// Returns whether secure boot V2 is enabled.
+    pub fn secure_boot_enabled() -> bool {
+        Self::read_bit(SECURE_BOOT_EN)
+    }
+
+    /// Returns whether JTAG is permanently disabled, both on the pads and on the
+    /// USB Serial/JTAG peripheral.
+    ///
+    /// JTAG that is only disabled by `SOFT_DIS_JTAG` can be re-enabled by the
+    /// HMAC peripheral, so it is not considered disabled.
+    pub fn jtag_disabled() -> bool {
+        Self::read_bit(DIS_PAD_JTAG) && Self::read_bit(DIS_USB_JTAG)
+    }
+
+    /// Returns whether the ROM's UART download mode is permanently disabled.
+    pub fn download_mode_disabled() -> bool {
+        Self::read_bit(DIS_DOWNLOAD_MODE)
+    }
+
     /// Get the multiplier for the timeout value of the RWDT STAGE 0 register.
     pub fn rwdt_multiplier() -> u8 {
         Self::read_field_le::<u8>(WDT_DELAY_SEL)
@@ -113,6 +144,44 @@ impl super::Efuse {
     pub fn chip_revision() -> u16 {
         Self::major_chip_version() as u16 * 100 + Self::minor_chip_version() as u16
     }
+
+    /// Get the temperature sensor calibration value, in units of 0.1 °C
+    ///
+    /// The value is the deviation of the factory-measured temperature from the
+    /// sensor's reading, which has to be subtracted from the computed temperature.
+    /// Returns `None` if the chip has no calibration data.
+    ///
+    /// see <https://github.com/espressif/esp-idf/blob/903af13e8/components/efuse/esp32c6/esp_efuse_rtc_calib.c>
+    pub fn rtc_calib_tsens_val() -> Option<i16> {
+        if Self::rtc_calib_version() != 1 {
+            return None;
+        }
+
+        Some(Self::read_tsens_calibration(TEMP_CALIB))
+    }
+
+    /// Returns the package the chip is mounted in.
+    pub fn package() -> Package {
+        match Self::read_field_le::<u8>(PKG_VERSION) {
+            0 => Package::Esp32c6,
+            1 => Package::Esp32c6Fh4,
+            other => Package::Unknown(other),
+        }
+    }
+
+    /// Returns the capacity of the flash embedded in the chip package.
+    ///
+    /// Returns `None` for chips without embedded flash, or if the eFuse value
+    /// is not recognized.
+    pub fn flash_size() -> Option<FlashSize> {
+        match Self::read_field_le::<u8>(FLASH_CAP) {
+            1 => Some(FlashSize::_4MB),
+            2 => Some(FlashSize::_2MB),
+            3 => Some(FlashSize::_1MB),
+            4 => Some(FlashSize::_8MB),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, strum::FromRepr)]
@@ -1,8 +1,24 @@
-use crate::{analog::adc::Attenuation, peripherals::EFUSE};
+use crate::{
+    analog::adc::Attenuation,
+    efuse::{FlashSize, PsramSize},
+    peripherals::EFUSE,
+};
 
 mod fields;
 pub use fields::*;
 
+/// Chip package, as encoded in the `PKG_VERSION` eFuse field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Package {
+    /// ESP32-S3 (QFN56)
+    Esp32s3,
+    /// ESP32-S3-PICO-1 (LGA56)
+    Esp32s3Pico1,
+    /// A package version this driver doesn't know about.
+    Unknown(u8),
+}
+
 impl super::Efuse {
     /// Get status of SPI boot encryption.
     pub fn flash_encryption() -> bool {
@@ -11,6 +27,25 @@ impl super::Efuse {
             .is_multiple_of(2)
     }
 
+    /// Returns whether secure boot V2 is enabled.
+    pub fn secure_boot_enabled() -> bool {
+        Self::read_bit(SECURE_BOOT_EN)
+    }
+
+    /// Returns whether JTAG is permanently disabled, both on the pads and on the
+    /// USB Serial/JTAG peripheral.
+    ///
+    /// JTAG that is only disabled by `SOFT_DIS_JTAG` can be re-enabled by the
+    /// HMAC peripheral, so it is not considered disabled.
+    pub fn jtag_disabled() -> bool {
+        Self::read_bit(DIS_PAD_JTAG) && Self::read_bit(DIS_USB_JTAG)
+    }
+
+    /// Returns whether the ROM's UART download mode is permanently disabled.
+    pub fn download_mode_disabled() -> bool {
+        Self::read_bit(DIS_DOWNLOAD_MODE)
+    }
+
     /// Get the multiplier for the timeout value of the RWDT STAGE 0 register.
     pub fn rwdt_multiplier() -> u8 {
         Self::read_field_le::<u8>(WDT_DELAY_SEL)
@@ -140,6 +175,83 @@ impl super::Efuse {
             adc2_vol[atten]
         })
     }
+
+    /// Get the temperature sensor calibration value, in units of 0.1 °C
+    ///
+    /// The value is the deviation of the factory-measured temperature from the
+    /// sensor's reading, which has to be subtracted from the computed temperature.
+    /// Returns `None` if the chip has no calibration data.
+    ///
+    /// see <https://github.com/espressif/esp-idf/blob/903af13e8/components/efuse/esp32s3/esp_efuse_rtc_calib.c>
+    pub fn rtc_calib_tsens_val() -> Option<i16> {
+        if Self::rtc_calib_version() != 1 {
+            return None;
+        }
+
+        Some(Self::read_tsens_calibration(TEMP_CALIB))
+    }
+
+    /// Returns the number of CPUs available on the chip.
+    ///
+    /// The application CPU can be disabled in eFuse, which leaves only the
+    /// protocol CPU.
+    pub fn core_count() -> u32 {
+        if Self::read_bit(DIS_APP_CPU) { 1 } else { 2 }
+    }
+
+    /// Returns the major hardware revision
+    pub fn major_chip_version() -> u8 {
+        Self::read_field_le(WAFER_VERSION_MAJOR)
+    }
+
+    /// Returns the minor hardware revision
+    pub fn minor_chip_version() -> u8 {
+        // The minor revision is split between two fields.
+        (Self::read_field_le::<u8>(WAFER_VERSION_MINOR_HI) << 3)
+            | Self::read_field_le::<u8>(WAFER_VERSION_MINOR_LO)
+    }
+
+    /// Returns the hardware revision
+    ///
+    /// The chip version is calculated using the following
+    /// formula: MAJOR * 100 + MINOR. (if the result is 1, then version is v0.1)
+    pub fn chip_revision() -> u16 {
+        Self::major_chip_version() as u16 * 100 + Self::minor_chip_version() as u16
+    }
+
+    /// Returns the package the chip is mounted in.
+    pub fn package() -> Package {
+        match Self::read_field_le::<u8>(PKG_VERSION) {
+            0 => Package::Esp32s3,
+            1 => Package::Esp32s3Pico1,
+            other => Package::Unknown(other),
+        }
+    }
+
+    /// Returns the capacity of the flash embedded in the chip package.
+    ///
+    /// Returns `None` for chips without embedded flash, or if the eFuse value
+    /// is not recognized.
+    pub fn flash_size() -> Option<FlashSize> {
+        match Self::read_field_le::<u8>(FLASH_CAP) {
+            1 => Some(FlashSize::_8MB),
+            2 => Some(FlashSize::_4MB),
+            _ => None,
+        }
+    }
+
+    /// Returns the capacity of the PSRAM embedded in the chip package.
+    ///
+    /// Returns `None` for chips without embedded PSRAM, or if the eFuse value
+    /// is not recognized.
+    pub fn psram_size() -> Option<PsramSize> {
+        match Self::read_field_le::<u8>(PSRAM_CAP) {
+            1 => Some(PsramSize::_8MB),
+            2 => Some(PsramSize::_2MB),
+            3 => Some(PsramSize::_16MB),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, strum::FromRepr)]
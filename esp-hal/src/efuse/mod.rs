@@ -9,6 +9,8 @@
 //!
 //!   * MAC address
 //!   * Chip revision
+//!   * Chip package and embedded flash/PSRAM capacity
+//!   * Flash encryption, secure boot, JTAG and download mode status
 //!
 //! and more. It is useful for retrieving chip-specific configuration and
 //! identification data during runtime.
@@ -37,8 +39,13 @@
 //! );
 //!
 //! println!("MAC address {:02x?}", Efuse::mac_address());
+//! println!("{:?}", Efuse::chip_info());
 //! println!("Flash Encryption {:?}", Efuse::flash_encryption());
 //!
+//! if !Efuse::security_info().is_locked_down() {
+//!     println!("The device is not locked down");
+//! }
+//!
 //! // Besides the helper methods, various eFuse field constants can also be read with a lower-level API:
 //!
 //! println!(
@@ -47,12 +54,22 @@
 //! );
 //! # {after_snippet}
 //! ```
+//!
+//! ## Writing eFuses
+//!
+//! On the ESP32-C3, ESP32-C6, ESP32-H2 and ESP32-S3, fields of the user data
+//! block (`BLOCK3`) can be programmed with [`Efuse::write_field`], for
+//! example to store provisioning data. Programming eFuses is irreversible, see
+//! the documentation of that function before using it.
 
 use core::{cmp, mem, slice, sync::atomic::Ordering};
 
 use bytemuck::AnyBitPattern;
 use portable_atomic::AtomicU8;
 
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+use crate::peripherals::EFUSE;
+
 #[cfg_attr(esp32, path = "esp32/mod.rs")]
 #[cfg_attr(esp32c2, path = "esp32c2/mod.rs")]
 #[cfg_attr(esp32c3, path = "esp32c3/mod.rs")]
@@ -192,6 +209,21 @@ impl Efuse {
         Self::read_field_le::<u8>(field) != 0
     }
 
+    /// Reads a temperature sensor calibration value.
+    ///
+    /// The value is stored in sign-magnitude form: bits 0 to 7 hold the
+    /// magnitude, and bit 8 is set if the value is negative.
+    #[cfg(not(esp32))]
+    fn read_tsens_calibration(field: EfuseField) -> i16 {
+        let cal_temp: u16 = Self::read_field_le(field);
+        let magnitude = (cal_temp & 0xff) as i16;
+        if cal_temp & (1 << 8) != 0 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
     /// Set the base mac address
     ///
     /// The new value will be returned by `read_mac_address` instead of the one
@@ -229,6 +261,97 @@ impl Efuse {
             Self::read_base_mac_address()
         }
     }
+
+    /// Returns the purpose programmed for key block `key` (`BLOCK_KEY0` to
+    /// `BLOCK_KEY5`).
+    ///
+    /// Returns `None` if `key` is not a valid key block index.
+    #[cfg(not(any(esp32, esp32c2)))]
+    #[instability::unstable]
+    pub fn key_purpose(key: usize) -> Option<KeyPurpose> {
+        let field = match key {
+            0 => KEY_PURPOSE_0,
+            1 => KEY_PURPOSE_1,
+            2 => KEY_PURPOSE_2,
+            3 => KEY_PURPOSE_3,
+            4 => KEY_PURPOSE_4,
+            5 => KEY_PURPOSE_5,
+            _ => return None,
+        };
+
+        Some(KeyPurpose::from_raw(Self::read_field_le(field)))
+    }
+
+    /// Returns a summary of the security-related eFuses.
+    ///
+    /// This reads the same fields as [`Self::flash_encryption`],
+    /// [`Self::secure_boot_enabled`], [`Self::jtag_disabled`] and
+    /// [`Self::download_mode_disabled`], and can be used to check that a
+    /// device has been locked down before running sensitive code.
+    #[instability::unstable]
+    pub fn security_info() -> SecurityInfo {
+        SecurityInfo {
+            flash_encryption: Self::flash_encryption(),
+            secure_boot: Self::secure_boot_enabled(),
+            jtag_disabled: Self::jtag_disabled(),
+            download_mode_disabled: Self::download_mode_disabled(),
+            #[cfg(not(any(esp32, esp32c2)))]
+            key_purposes: core::array::from_fn(|key| unwrap!(Self::key_purpose(key))),
+        }
+    }
+
+    /// Returns the identity and the capabilities of the chip.
+    ///
+    /// This collects the values returned by [`Self::chip_revision`],
+    /// [`Self::read_base_mac_address`] and the package-related functions of
+    /// this module into one struct, which is convenient to log or to report
+    /// to a server.
+    #[instability::unstable]
+    pub fn chip_info() -> ChipInfo {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                let package = Self::chip_type();
+            } else {
+                let package = Self::package();
+            }
+        }
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32, esp32s3))] {
+                let cores = Self::core_count();
+            } else {
+                let cores = 1;
+            }
+        }
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32c3, esp32c6, esp32h2, esp32s2, esp32s3))] {
+                let embedded_flash = Self::flash_size();
+            } else {
+                let embedded_flash = None;
+            }
+        }
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32s2, esp32s3))] {
+                let embedded_psram = Self::psram_size();
+            } else {
+                let embedded_psram = None;
+            }
+        }
+
+        ChipInfo {
+            model: crate::chip!(),
+            revision: Self::chip_revision(),
+            cores,
+            mac_address: Self::read_base_mac_address(),
+            package,
+            embedded_flash,
+            embedded_psram,
+            #[cfg(esp32)]
+            bluetooth: Self::is_bluetooth_enabled(),
+            #[cfg(not(esp32))]
+            bluetooth: cfg!(bt),
+            wifi: cfg!(wifi),
+        }
+    }
 }
 
 // Indicates the state of setting the mac address
@@ -244,6 +367,403 @@ static MAC_OVERRIDE_STATE: AtomicU8 = AtomicU8::new(0);
 #[cfg_attr(not(feature = "unstable"), allow(unused))]
 static mut MAC_OVERRIDE: [u8; 6] = [0; 6];
 
+/// Capacity of the flash embedded in the chip package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub enum FlashSize {
+    /// 1 MB of embedded flash.
+    _1MB,
+    /// 2 MB of embedded flash.
+    _2MB,
+    /// 4 MB of embedded flash.
+    _4MB,
+    /// 8 MB of embedded flash.
+    _8MB,
+}
+
+impl FlashSize {
+    /// Returns the capacity in bytes.
+    #[instability::unstable]
+    pub const fn bytes(self) -> usize {
+        match self {
+            Self::_1MB => 1024 * 1024,
+            Self::_2MB => 2 * 1024 * 1024,
+            Self::_4MB => 4 * 1024 * 1024,
+            Self::_8MB => 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// Capacity of the PSRAM embedded in the chip package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub enum PsramSize {
+    /// 2 MB of embedded PSRAM.
+    _2MB,
+    /// 4 MB of embedded PSRAM.
+    _4MB,
+    /// 8 MB of embedded PSRAM.
+    _8MB,
+    /// 16 MB of embedded PSRAM.
+    _16MB,
+}
+
+impl PsramSize {
+    /// Returns the capacity in bytes.
+    #[instability::unstable]
+    pub const fn bytes(self) -> usize {
+        match self {
+            Self::_2MB => 2 * 1024 * 1024,
+            Self::_4MB => 4 * 1024 * 1024,
+            Self::_8MB => 8 * 1024 * 1024,
+            Self::_16MB => 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// Number of key blocks (`BLOCK_KEY0` to `BLOCK_KEY5`).
+#[cfg(not(any(esp32, esp32c2)))]
+pub const KEY_BLOCK_COUNT: usize = 6;
+
+/// Purpose of a key block, as encoded in the `KEY_PURPOSE_n` eFuse fields.
+#[cfg(not(any(esp32, esp32c2)))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub enum KeyPurpose {
+    /// User purposes, also the purpose of unused key blocks.
+    User,
+    /// Reserved.
+    #[cfg(not(esp32h2))]
+    Reserved,
+    /// ECDSA private key.
+    #[cfg(esp32h2)]
+    EcdsaKey,
+    /// First half of an XTS-AES-256 flash encryption key.
+    #[cfg(any(esp32s2, esp32s3))]
+    XtsAes256Key1,
+    /// Second half of an XTS-AES-256 flash encryption key.
+    #[cfg(any(esp32s2, esp32s3))]
+    XtsAes256Key2,
+    /// XTS-AES-128 flash encryption key.
+    XtsAes128Key,
+    /// HMAC downstream key for both JTAG and the digital signature peripheral.
+    HmacDownAll,
+    /// HMAC downstream key for re-enabling JTAG.
+    HmacDownJtag,
+    /// HMAC downstream key for the digital signature peripheral.
+    HmacDownDigitalSignature,
+    /// HMAC upstream key.
+    HmacUp,
+    /// First secure boot key digest.
+    SecureBootDigest0,
+    /// Second secure boot key digest.
+    SecureBootDigest1,
+    /// Third secure boot key digest.
+    SecureBootDigest2,
+    /// A purpose this driver doesn't know about.
+    Unknown(u8),
+}
+
+#[cfg(not(any(esp32, esp32c2)))]
+impl KeyPurpose {
+    fn from_raw(raw: u8) -> Self {
+        match raw {
+            0 => Self::User,
+            #[cfg(not(esp32h2))]
+            1 => Self::Reserved,
+            #[cfg(esp32h2)]
+            1 => Self::EcdsaKey,
+            #[cfg(any(esp32s2, esp32s3))]
+            2 => Self::XtsAes256Key1,
+            #[cfg(any(esp32s2, esp32s3))]
+            3 => Self::XtsAes256Key2,
+            4 => Self::XtsAes128Key,
+            5 => Self::HmacDownAll,
+            6 => Self::HmacDownJtag,
+            7 => Self::HmacDownDigitalSignature,
+            8 => Self::HmacUp,
+            9 => Self::SecureBootDigest0,
+            10 => Self::SecureBootDigest1,
+            11 => Self::SecureBootDigest2,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+/// Summary of the security-related eFuses, see [`Efuse::security_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+#[non_exhaustive]
+pub struct SecurityInfo {
+    /// Flash encryption is enabled.
+    pub flash_encryption: bool,
+    /// Secure boot V2 is enabled.
+    pub secure_boot: bool,
+    /// JTAG is permanently disabled.
+    pub jtag_disabled: bool,
+    /// The ROM's UART download mode is permanently disabled.
+    pub download_mode_disabled: bool,
+    /// The purpose of each key block.
+    #[cfg(not(any(esp32, esp32c2)))]
+    pub key_purposes: [KeyPurpose; KEY_BLOCK_COUNT],
+}
+
+impl SecurityInfo {
+    /// Returns whether flash encryption and secure boot are enabled, and JTAG
+    /// and the download mode are disabled.
+    #[instability::unstable]
+    pub fn is_locked_down(&self) -> bool {
+        self.flash_encryption
+            && self.secure_boot
+            && self.jtag_disabled
+            && self.download_mode_disabled
+    }
+}
+
+/// Identity and capabilities of the chip, see [`Efuse::chip_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+#[non_exhaustive]
+pub struct ChipInfo {
+    /// The name of the chip model, for example `"esp32c6"`.
+    pub model: &'static str,
+    /// The hardware revision, as `MAJOR * 100 + MINOR`.
+    pub revision: u16,
+    /// The number of CPUs available on the chip.
+    pub cores: u32,
+    /// The base MAC address programmed in eFuse.
+    pub mac_address: [u8; 6],
+    /// The chip package.
+    #[cfg(not(esp32))]
+    pub package: Package,
+    /// The chip package.
+    #[cfg(esp32)]
+    pub package: ChipType,
+    /// The capacity of the flash embedded in the chip package.
+    ///
+    /// Always `None` on the ESP32 and the ESP32-C2, which don't record it in
+    /// eFuse.
+    pub embedded_flash: Option<FlashSize>,
+    /// The capacity of the PSRAM embedded in the chip package.
+    ///
+    /// Always `None` on chips other than the ESP32-S2 and the ESP32-S3.
+    pub embedded_psram: Option<PsramSize>,
+    /// The chip has a Bluetooth radio, and it is not disabled in eFuse.
+    pub bluetooth: bool,
+    /// The chip has a Wi-Fi radio.
+    pub wifi: bool,
+}
+
+/// Bit of the `WR_DIS` field that write-protects the user data block.
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+const WR_DIS_BLOCK_USR_DATA: u32 = 1 << 22;
+
+/// Number of 32-bit words in a block with Reed-Solomon error correction.
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+const BLOCK_WORDS: usize = 8;
+
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+const EFUSE_WRITE_OP_CODE: u16 = 0x5A5A;
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+const EFUSE_READ_OP_CODE: u16 = 0x5AA5;
+
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+impl Efuse {
+    /// Program a field of the user data block (`BLOCK3`).
+    ///
+    /// `data` is written to the field in little-endian order, like
+    /// [`Self::read_field_le`] reads it. Bits beyond the end of `data` are
+    /// left unprogrammed (`0`).
+    ///
+    /// The user data block is protected by a Reed-Solomon code, which is
+    /// computed over the whole block when it is programmed. The block can
+    /// therefore only be programmed once: after a successful call, any other
+    /// field of the block, for example `CUSTOM_MAC`, can no longer be written.
+    /// This function refuses to write the block if any of its bits are
+    /// already programmed, or if it has been write-protected.
+    ///
+    /// The user data block can't be read-protected, so the written data can
+    /// always be read back with [`Self::read_field_le`].
+    ///
+    /// # Errors
+    ///
+    /// - [`WriteError::UnsupportedBlock`] if the field is not in the user data block.
+    /// - [`WriteError::DataTooLong`] if `data` has bits set beyond the end of the field.
+    /// - [`WriteError::WriteProtected`] if the block is write-protected.
+    /// - [`WriteError::AlreadyProgrammed`] if any bit of the block is already programmed.
+    /// - [`WriteError::VerificationFailed`] if the block doesn't read back as written.
+    ///
+    /// # Safety
+    ///
+    /// Programming eFuses can't be undone. The caller must make sure that the
+    /// data and the field are correct, and that this function is only called
+    /// intentionally, for example once during provisioning.
+    ///
+    /// The eFuse controller must not be used by anything else while this
+    /// function runs.
+    #[instability::unstable]
+    pub unsafe fn write_field(field: EfuseField, data: &[u8]) -> Result<(), WriteError> {
+        let EfuseField {
+            block,
+            bit_start,
+            bit_count,
+            ..
+        } = field;
+
+        if !matches!(block, EfuseBlock::Block3) {
+            return Err(WriteError::UnsupportedBlock);
+        }
+
+        let mut words = [0u32; BLOCK_WORDS];
+        for (i, byte) in data.iter().enumerate() {
+            for bit in 0..8 {
+                if byte & (1 << bit) == 0 {
+                    continue;
+                }
+
+                let offset = (i * 8 + bit) as u32;
+                if offset >= bit_count {
+                    return Err(WriteError::DataTooLong);
+                }
+
+                let pos = (bit_start + offset) as usize;
+                words[pos / 32] |= 1 << (pos % 32);
+            }
+        }
+
+        if Self::read_field_le::<u32>(WR_DIS) & WR_DIS_BLOCK_USR_DATA != 0 {
+            return Err(WriteError::WriteProtected);
+        }
+
+        let programmed =
+            (0..BLOCK_WORDS).any(|i| unsafe { block.address().add(i).read_volatile() } != 0);
+        if programmed {
+            return Err(WriteError::AlreadyProgrammed);
+        }
+
+        if words.iter().all(|word| *word == 0) {
+            // Nothing to program.
+            return Ok(());
+        }
+
+        unsafe extern "C" {
+            fn ets_efuse_rs_calculate(data: *const u32, rs_values: *mut u32);
+        }
+
+        let mut check_values = [0u32; 3];
+        unsafe { ets_efuse_rs_calculate(words.as_ptr(), check_values.as_mut_ptr()) };
+
+        critical_section::with(|_| {
+            let efuse = EFUSE::regs();
+
+            // Programming timing, see `efuse_hal_set_timing` in esp-idf.
+            efuse
+                .dac_conf()
+                .modify(|_, w| unsafe { w.dac_num().bits(0xFF).dac_clk_div().bits(0x28) });
+            efuse
+                .wr_tim_conf1()
+                .modify(|_, w| unsafe { w.pwr_on_num().bits(0x3000) });
+            efuse
+                .wr_tim_conf2()
+                .modify(|_, w| unsafe { w.pwr_off_num().bits(0x190) });
+
+            let pgm_data = efuse.pgm_data0().as_ptr();
+            let pgm_check_value = efuse.pgm_check_value0().as_ptr();
+            for (i, word) in words.iter().enumerate() {
+                unsafe { pgm_data.add(i).write_volatile(*word) };
+            }
+            for (i, value) in check_values.iter().enumerate() {
+                unsafe { pgm_check_value.add(i).write_volatile(*value) };
+            }
+
+            efuse
+                .conf()
+                .write(|w| unsafe { w.op_code().bits(EFUSE_WRITE_OP_CODE) });
+            efuse.cmd().write(|w| unsafe {
+                w.blk_num()
+                    .bits(EfuseBlock::Block3 as u8)
+                    .pgm_cmd()
+                    .set_bit()
+            });
+            while efuse.cmd().read().pgm_cmd().bit_is_set() {}
+
+            // Don't leave the data in the programming registers.
+            for i in 0..BLOCK_WORDS {
+                unsafe { pgm_data.add(i).write_volatile(0) };
+            }
+            for i in 0..check_values.len() {
+                unsafe { pgm_check_value.add(i).write_volatile(0) };
+            }
+
+            // Reload the eFuse values into the read registers.
+            efuse
+                .conf()
+                .write(|w| unsafe { w.op_code().bits(EFUSE_READ_OP_CODE) });
+            efuse.cmd().write(|w| w.read_cmd().set_bit());
+            while efuse.cmd().read().read_cmd().bit_is_set() {}
+        });
+
+        let matches = words
+            .iter()
+            .enumerate()
+            .all(|(i, word)| unsafe { block.address().add(i).read_volatile() } == *word);
+        let rs_failed = EFUSE::regs()
+            .rd_rs_err0()
+            .read()
+            .usr_data_fail()
+            .bit_is_set();
+        if !matches || rs_failed {
+            return Err(WriteError::VerificationFailed);
+        }
+
+        Ok(())
+    }
+}
+
+/// Errors that can occur when programming eFuses.
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+#[non_exhaustive]
+pub enum WriteError {
+    /// Only fields of the user data block (`BLOCK3`) can be programmed.
+    UnsupportedBlock,
+    /// The data has bits set beyond the end of the field.
+    DataTooLong,
+    /// The block has been write-protected.
+    WriteProtected,
+    /// Some bits of the block are already programmed.
+    AlreadyProgrammed,
+    /// The block doesn't read back as it was programmed.
+    VerificationFailed,
+}
+
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+impl core::error::Error for WriteError {}
+
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+impl core::fmt::Display for WriteError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WriteError::UnsupportedBlock => {
+                write!(f, "Only fields of the user data block can be programmed")
+            }
+            WriteError::DataTooLong => write!(f, "The data doesn't fit into the field"),
+            WriteError::WriteProtected => write!(f, "The block is write-protected"),
+            WriteError::AlreadyProgrammed => write!(f, "The block is already programmed"),
+            WriteError::VerificationFailed => {
+                write!(f, "The block doesn't read back as it was programmed")
+            }
+        }
+    }
+}
+
 /// Error indicating issues with setting the MAC address.
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 #[instability::unstable]
@@ -0,0 +1,278 @@
+#![cfg_attr(docsrs, procmacros::doc_replace)]
+//! # Dedicated GPIO
+//!
+//! ## Overview
+//!
+//! Dedicated GPIO connects up to 8 input and 8 output signals of the GPIO
+//! matrix directly to CPU registers. Reading or writing all of them takes a
+//! single CSR instruction, which is much faster than going through the GPIO
+//! peripheral's registers. This makes dedicated GPIO useful for bit-banging
+//! protocols at high rates.
+//!
+//! The ESP32-C2, ESP32-C3, ESP32-C6 and ESP32-H2 have 8 dedicated input and 8
+//! dedicated output channels. Bit `n` of the values read and written by
+//! [`DedicatedPin`] corresponds to channel `n`. The dedicated GPIO of the
+//! ESP32-S2 and ESP32-S3 is driven by Xtensa-specific instructions and is not
+//! supported by this driver.
+//!
+//! The channels are connected to pins through the GPIO matrix, so any GPIO can
+//! be used. The latency of the GPIO matrix still applies.
+//!
+//! ## Example
+//!
+//! ```rust, no_run
+//! # {before_snippet}
+//! use esp_hal::gpio::dedicated::DedicatedPin;
+//!
+//! let mut gpio = DedicatedPin::new(peripherals.DEDICATED_GPIO)
+//!     .with_output(0, peripherals.GPIO2)
+//!     .with_output(1, peripherals.GPIO3)
+//!     .with_input(0, peripherals.GPIO4);
+//!
+//! // Drive channel 0 high and channel 1 low.
+//! gpio.write_all(0b01);
+//! // Toggle channel 1 without affecting channel 0.
+//! gpio.set_high(0b10);
+//! gpio.set_low(0b10);
+//!
+//! let level = gpio.read_all() & 1;
+//! # {after_snippet}
+//! ```
+
+use core::arch::asm;
+
+use crate::{
+    gpio::{
+        InputSignal,
+        OutputConfig,
+        OutputSignal,
+        PinGuard,
+        interconnect::{PeripheralInput, PeripheralOutput},
+    },
+    peripherals::DEDICATED_GPIO,
+};
+
+/// The number of dedicated input and output channels.
+pub const CHANNEL_COUNT: usize = 8;
+
+cfg_if::cfg_if! {
+    if #[cfg(esp32c6)] {
+        const INPUT_SIGNALS: [InputSignal; CHANNEL_COUNT] = [
+            InputSignal::CPU_GPIO_IN0,
+            InputSignal::CPU_GPIO_IN1,
+            InputSignal::CPU_GPIO_IN2,
+            InputSignal::CPU_GPIO_IN3,
+            InputSignal::CPU_GPIO_IN4,
+            InputSignal::CPU_GPIO_IN5,
+            InputSignal::CPU_GPIO_IN6,
+            InputSignal::CPU_GPIO_IN7,
+        ];
+    } else if #[cfg(esp32h2)] {
+        const INPUT_SIGNALS: [InputSignal; CHANNEL_COUNT] = [
+            InputSignal::CPU_GPIO0,
+            InputSignal::CPU_GPIO1,
+            InputSignal::CPU_GPIO2,
+            InputSignal::CPU_GPIO3,
+            InputSignal::CPU_GPIO4,
+            InputSignal::CPU_GPIO5,
+            InputSignal::CPU_GPIO6,
+            InputSignal::CPU_GPIO7,
+        ];
+    } else {
+        const INPUT_SIGNALS: [InputSignal; CHANNEL_COUNT] = [
+            InputSignal::CPU_GPIO_0,
+            InputSignal::CPU_GPIO_1,
+            InputSignal::CPU_GPIO_2,
+            InputSignal::CPU_GPIO_3,
+            InputSignal::CPU_GPIO_4,
+            InputSignal::CPU_GPIO_5,
+            InputSignal::CPU_GPIO_6,
+            InputSignal::CPU_GPIO_7,
+        ];
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(any(esp32c6, esp32h2))] {
+        const OUTPUT_SIGNALS: [OutputSignal; CHANNEL_COUNT] = [
+            OutputSignal::CPU_GPIO_OUT0,
+            OutputSignal::CPU_GPIO_OUT1,
+            OutputSignal::CPU_GPIO_OUT2,
+            OutputSignal::CPU_GPIO_OUT3,
+            OutputSignal::CPU_GPIO_OUT4,
+            OutputSignal::CPU_GPIO_OUT5,
+            OutputSignal::CPU_GPIO_OUT6,
+            OutputSignal::CPU_GPIO_OUT7,
+        ];
+    } else {
+        const OUTPUT_SIGNALS: [OutputSignal; CHANNEL_COUNT] = [
+            OutputSignal::CPU_GPIO_0,
+            OutputSignal::CPU_GPIO_1,
+            OutputSignal::CPU_GPIO_2,
+            OutputSignal::CPU_GPIO_3,
+            OutputSignal::CPU_GPIO_4,
+            OutputSignal::CPU_GPIO_5,
+            OutputSignal::CPU_GPIO_6,
+            OutputSignal::CPU_GPIO_7,
+        ];
+    }
+}
+
+/// Dedicated GPIO driver, which binds up to 8 input and 8 output pins to the
+/// dedicated GPIO channels.
+///
+/// See the [module-level documentation](self) for more details.
+pub struct DedicatedPin<'d> {
+    _peripheral: DEDICATED_GPIO<'d>,
+    output_pins: [PinGuard; CHANNEL_COUNT],
+}
+
+impl<'d> DedicatedPin<'d> {
+    /// Creates a new driver with no channels connected to pins.
+    pub fn new(peripheral: DEDICATED_GPIO<'d>) -> Self {
+        // Disable all outputs, in case a previous driver was leaked.
+        csr::write_output_enable(0);
+
+        Self {
+            _peripheral: peripheral,
+            output_pins: OUTPUT_SIGNALS.map(PinGuard::new_unconnected),
+        }
+    }
+
+    /// Sets the pin to push-pull output and connects it to the given output
+    /// channel.
+    ///
+    /// The pin is set to the level the channel currently drives. The pin that
+    /// was previously connected to the channel is disconnected.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `channel` is not less than [`CHANNEL_COUNT`].
+    pub fn with_output(mut self, channel: usize, pin: impl PeripheralOutput<'d>) -> Self {
+        assert!(channel < CHANNEL_COUNT, "Invalid dedicated GPIO channel");
+
+        let pin = pin.into();
+        pin.apply_output_config(&OutputConfig::default());
+        pin.set_output_enable(true);
+
+        self.output_pins[channel] = pin.connect_with_guard(OUTPUT_SIGNALS[channel]);
+        csr::write_output_enable(csr::read_output_enable() | (1 << channel));
+
+        self
+    }
+
+    /// Enables the pin's input and connects it to the given input channel.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `channel` is not less than [`CHANNEL_COUNT`].
+    pub fn with_input(self, channel: usize, pin: impl PeripheralInput<'d>) -> Self {
+        assert!(channel < CHANNEL_COUNT, "Invalid dedicated GPIO channel");
+
+        let pin = pin.into();
+        pin.set_input_enable(true);
+
+        INPUT_SIGNALS[channel].connect_to(&pin);
+
+        self
+    }
+
+    /// Sets the level of every output channel: bit `n` of `value` is the level
+    /// of channel `n`.
+    #[inline(always)]
+    pub fn write_all(&mut self, value: u8) {
+        csr::write_output(value as u32);
+    }
+
+    /// Drives the output channels set in `mask` high, and leaves the other
+    /// channels unchanged.
+    #[inline(always)]
+    pub fn set_high(&mut self, mask: u8) {
+        csr::set_output(mask as u32);
+    }
+
+    /// Drives the output channels set in `mask` low, and leaves the other
+    /// channels unchanged.
+    #[inline(always)]
+    pub fn set_low(&mut self, mask: u8) {
+        csr::clear_output(mask as u32);
+    }
+
+    /// Returns the levels the output channels are set to.
+    #[inline(always)]
+    pub fn output_levels(&self) -> u8 {
+        csr::read_output() as u8
+    }
+
+    /// Returns the levels of every input channel: bit `n` is the level of
+    /// channel `n`.
+    #[inline(always)]
+    pub fn read_all(&self) -> u8 {
+        csr::read_input() as u8
+    }
+}
+
+impl Drop for DedicatedPin<'_> {
+    fn drop(&mut self) {
+        csr::write_output_enable(0);
+    }
+}
+
+impl core::fmt::Debug for DedicatedPin<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DedicatedPin").finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for DedicatedPin<'_> {
+    fn format(&self, fmt: defmt::Formatter<'_>) {
+        defmt::write!(fmt, "DedicatedPin")
+    }
+}
+
+/// Accessors of the dedicated GPIO CSRs.
+mod csr {
+    use super::asm;
+
+    #[inline(always)]
+    pub(super) fn read_output_enable() -> u32 {
+        let value: u32;
+        unsafe { asm!("csrr {0}, 0x803", out(reg) value) };
+        value
+    }
+
+    #[inline(always)]
+    pub(super) fn write_output_enable(value: u32) {
+        unsafe { asm!("csrw 0x803, {0}", in(reg) value) };
+    }
+
+    #[inline(always)]
+    pub(super) fn read_input() -> u32 {
+        let value: u32;
+        unsafe { asm!("csrr {0}, 0x804", out(reg) value) };
+        value
+    }
+
+    #[inline(always)]
+    pub(super) fn read_output() -> u32 {
+        let value: u32;
+        unsafe { asm!("csrr {0}, 0x805", out(reg) value) };
+        value
+    }
+
+    #[inline(always)]
+    pub(super) fn write_output(value: u32) {
+        unsafe { asm!("csrw 0x805, {0}", in(reg) value) };
+    }
+
+    #[inline(always)]
+    pub(super) fn set_output(mask: u32) {
+        unsafe { asm!("csrs 0x805, {0}", in(reg) mask) };
+    }
+
+    #[inline(always)]
+    pub(super) fn clear_output(mask: u32) {
+        unsafe { asm!("csrc 0x805, {0}", in(reg) mask) };
+    }
+}
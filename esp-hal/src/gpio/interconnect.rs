@@ -87,11 +87,16 @@
 //! - A peripheral output signal can be connected to any number of GPIOs. These GPIOs can be
 //!   configured differently. The peripheral drivers will only support a single connection (that is,
 //!   they disconnect previously configured signals on repeat calls to the same function), but you
-//!   can use `esp_hal::gpio::OutputSignal::connect_to` (note that the type is currently hidden from
-//!   the documentation) to connect multiple GPIOs to the same output signal.
+//!   can use [`gpio::OutputSignal::connect_to`] to connect multiple GPIOs to the same output
+//!   signal.
 //! - A GPIO input signal can be connected to any number of peripheral inputs.
 //! - A GPIO output can be driven by only one peripheral output.
 //!
+//! [`Flex::connect_input_to_peripheral`] and [`Flex::connect_peripheral_to_output`]
+//! check the first and last rule before routing a signal, and return a
+//! [`RoutingError`](gpio::RoutingError) instead of replacing an existing
+//! connection.
+//!
 //! [`GPIO0`]: crate::peripherals::GPIO0
 //! [`Spi::with_mosi`]: crate::spi::master::Spi::with_mosi
 
@@ -209,7 +214,7 @@ impl<'d> PeripheralOutput<'d> for Flex<'d> {
 #[instability::unstable]
 impl<'d> PeripheralSignal<'d> for Input<'d> {
     fn connect_input_to_peripheral(&self, signal: gpio::InputSignal) {
-        self.pin.connect_input_to_peripheral(signal);
+        self.pin.pin.connect_input_to_peripheral(signal);
     }
 }
 #[instability::unstable]
@@ -218,13 +223,13 @@ impl<'d> PeripheralInput<'d> for Input<'d> {}
 #[instability::unstable]
 impl<'d> PeripheralSignal<'d> for Output<'d> {
     fn connect_input_to_peripheral(&self, signal: gpio::InputSignal) {
-        self.pin.connect_input_to_peripheral(signal);
+        self.pin.pin.connect_input_to_peripheral(signal);
     }
 }
 #[instability::unstable]
 impl<'d> PeripheralOutput<'d> for Output<'d> {
     fn connect_peripheral_to_output(&self, signal: gpio::OutputSignal) {
-        self.pin.connect_peripheral_to_output(signal);
+        self.pin.pin.connect_peripheral_to_output(signal);
     }
     fn disconnect_from_peripheral_output(&self) {
         self.pin.disconnect_from_peripheral_output();
@@ -312,10 +317,26 @@ impl<'d> PeripheralOutput<'d> for OutputSignal<'d> {
 }
 
 impl gpio::InputSignal {
-    fn can_use_gpio_matrix(self) -> bool {
+    pub(crate) fn can_use_gpio_matrix(self) -> bool {
         self as usize <= property!("gpio.input_signal_max")
     }
 
+    /// Returns the GPIO that drives this peripheral input through the GPIO
+    /// matrix, if any.
+    pub(crate) fn gpio_matrix_source(self) -> Option<u8> {
+        if !self.can_use_gpio_matrix() {
+            return None;
+        }
+
+        let offset = property!("gpio.func_in_sel_offset");
+        let cfg = GPIO::regs().func_in_sel_cfg(self as usize - offset).read();
+        let input = cfg.in_sel().bits();
+        let is_constant = input == property!("gpio.constant_0_input")
+            || input == property!("gpio.constant_1_input");
+
+        (cfg.sel().bit_is_set() && !is_constant).then_some(input)
+    }
+
     /// Connects a peripheral input signal to a GPIO or a constant level.
     ///
     /// Note that connecting multiple GPIOs to a single peripheral input is not
@@ -335,7 +356,7 @@ impl gpio::InputSignal {
 }
 
 impl gpio::OutputSignal {
-    fn can_use_gpio_matrix(self) -> bool {
+    pub(crate) fn can_use_gpio_matrix(self) -> bool {
         self as usize <= property!("gpio.output_signal_max")
     }
 
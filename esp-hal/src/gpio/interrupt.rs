@@ -51,6 +51,37 @@
 //! (If the user were to clear the interrupt status, we would need to re-enable
 //! it, for PinFuture to detect the completion).
 //!
+//! ## Per-pin handlers
+//!
+//! Pins can have their own handler, set by `Flex::set_interrupt_handler`. Both
+//! built-in interrupt handlers call it for each pending pin interrupt that does
+//! not belong to an async operation. The same contract applies as for the user
+//! handler: the pin's handler is responsible for clearing the interrupt status
+//! or disabling the interrupt, and the default handler doesn't disable the
+//! pins that have a handler. This allows pins used with the async API and pins
+//! handled in a blocking way to coexist without a global user handler.
+//!
+//! The handler is unregistered when the pin driver is dropped, so it can't be
+//! called for a pin that has since been handed to a different driver.
+//!
+//! ## Pin event callbacks
+//!
+//! `Flex::set_event_handler` stores a callback in the same per-pin slot, and
+//! marks the pin in `GpioBank::event_callbacks`. For these pins, the built-in
+//! handlers clear the interrupt status, then call the callback with the pin
+//! number and the latched edge. The status is cleared before the callback
+//! runs, so an event that arrives while it runs triggers the interrupt again
+//! instead of being lost. Like other per-pin handlers, callbacks are never
+//! called for pins used by async operations.
+//!
+//! ## Edge direction
+//!
+//! The hardware doesn't record which edge triggered an `AnyEdge` interrupt.
+//! Both built-in handlers sample the input levels of the pending pins first
+//! thing, before any user code runs, and `Flex::last_edge` reports them. This
+//! is racy for pulses shorter than the interrupt latency, but it's the best we
+//! can do.
+//!
 //! TODO: currently, direct-binding a GPIO interrupt handler will completely
 //! break the async API. We will need to expose a way to handle async events.
 
@@ -61,7 +92,7 @@ use strum::EnumCount;
 #[cfg(feature = "rt")]
 use crate::interrupt::{self, DEFAULT_INTERRUPT_HANDLER};
 use crate::{
-    gpio::{AnyPin, GPIO_LOCK, GpioBank, InputPin, set_int_enable},
+    gpio::{AnyPin, Edge, GPIO_LOCK, GpioBank, InputPin, Pin, disable_int},
     interrupt::Priority,
     peripherals::{GPIO, Interrupt},
 };
@@ -69,6 +100,23 @@ use crate::{
 /// Convenience constant for `Option::None` pin
 pub(super) static USER_INTERRUPT_HANDLER: CFnPtr = CFnPtr::new();
 
+/// Handlers of individual pins, indexed by the pin number.
+///
+/// A slot holds either an `extern "C" fn()` or, if the pin is set in
+/// `GpioBank::event_callbacks`, a `fn(u8, Edge)`. Both are only modified in the
+/// GPIO critical section.
+static PIN_HANDLERS: [AtomicPtr<()>; GpioBank::COUNT * 32] =
+    [const { AtomicPtr::new(core::ptr::null_mut()) }; GpioBank::COUNT * 32];
+
+/// A handler of an individual pin.
+pub(super) enum PinHandler {
+    /// Called without arguments, the handler clears the interrupt status.
+    Interrupt(extern "C" fn()),
+    /// Called with the pin number and the edge, after clearing the
+    /// interrupt status.
+    Event(fn(u8, Edge)),
+}
+
 pub(super) struct CFnPtr(AtomicPtr<()>);
 impl CFnPtr {
     pub const fn new() -> Self {
@@ -87,6 +135,68 @@ impl CFnPtr {
     }
 }
 
+pub(super) fn set_pin_handler(pin: &AnyPin<'_>, handler: Option<PinHandler>) {
+    let slot = &PIN_HANDLERS[pin.number() as usize];
+    let events = pin.bank().event_callbacks();
+
+    GPIO_LOCK.lock(|| match handler {
+        Some(PinHandler::Interrupt(handler)) => {
+            slot.store(handler as *mut (), Ordering::Relaxed);
+            events.fetch_and(!pin.mask(), Ordering::Relaxed);
+        }
+        Some(PinHandler::Event(handler)) => {
+            slot.store(handler as *mut (), Ordering::Relaxed);
+            events.fetch_or(pin.mask(), Ordering::Relaxed);
+        }
+        None => {
+            slot.store(core::ptr::null_mut(), Ordering::Relaxed);
+            events.fetch_and(!pin.mask(), Ordering::Relaxed);
+        }
+    });
+}
+
+/// Calls the handlers of the pins in `intrs`, and returns the mask of the pins
+/// that have one.
+fn handle_pin_handlers(bank: GpioBank, intrs: u32) -> u32 {
+    let events = bank.event_callbacks().load(Ordering::Relaxed) & intrs;
+
+    // Clear before calling the callbacks, so that an event during a callback
+    // isn't lost.
+    bank.write_interrupt_status_clear(events);
+
+    let levels = bank.latched_levels().load(Ordering::Relaxed);
+
+    let mut handled = 0;
+
+    let mut pending = intrs;
+    while pending != 0 {
+        let pin_pos = pending.trailing_zeros();
+        pending -= 1 << pin_pos;
+
+        let pin_nr = pin_pos as u8 + bank.offset();
+
+        let ptr = PIN_HANDLERS[pin_nr as usize].load(Ordering::Relaxed);
+        if ptr.is_null() {
+            continue;
+        }
+        handled |= 1 << pin_pos;
+
+        if events & (1 << pin_pos) != 0 {
+            let edge = if levels & (1 << pin_pos) != 0 {
+                Edge::Rising
+            } else {
+                Edge::Falling
+            };
+            let callback = unsafe { core::mem::transmute::<*mut (), fn(u8, Edge)>(ptr) };
+            callback(pin_nr, edge);
+        } else {
+            unsafe { (core::mem::transmute::<*mut (), extern "C" fn()>(ptr))() };
+        }
+    }
+
+    handled
+}
+
 #[cfg(feature = "rt")]
 pub(crate) fn bind_default_interrupt_handler() {
     // We first check if a handler is set in the vector table.
@@ -149,6 +259,7 @@ pub(super) fn set_interrupt_priority(interrupt: Interrupt, priority: Priority) {
 extern "C" fn default_gpio_interrupt_handler() {
     GPIO_LOCK.lock(|| {
         let banks = interrupt_status();
+        latch_levels(banks);
 
         // Handle the async interrupts
         for (bank, intrs) in banks {
@@ -160,8 +271,11 @@ extern "C" fn default_gpio_interrupt_handler() {
             // Wake up the tasks
             handle_async_pins(bank, async_pins, intrs);
 
+            // Pins with their own handler are left to it.
+            let handled = handle_pin_handlers(bank, intrs & !async_pins);
+
             // Disable the remaining interrupts.
-            let mut intrs = intrs & !async_pins;
+            let mut intrs = intrs & !async_pins & !handled;
             while intrs != 0 {
                 let pin_pos = intrs.trailing_zeros();
                 intrs -= 1 << pin_pos;
@@ -169,7 +283,7 @@ extern "C" fn default_gpio_interrupt_handler() {
                 let pin_nr = pin_pos as u8 + bank.offset();
 
                 // The remaining interrupts are not async, we treat them as single-shot.
-                set_int_enable(pin_nr, Some(0), 0, false);
+                disable_int(pin_nr);
             }
         }
     });
@@ -185,6 +299,7 @@ pub(super) extern "C" fn user_gpio_interrupt_handler() {
     GPIO_LOCK.lock(|| {
         // Read interrupt status before the user has a chance to modify them.
         let banks = interrupt_status();
+        latch_levels(banks);
 
         // Call the user handler before clearing interrupts. The user can use the enable
         // bits to determine which interrupts they are interested in. Clearing the
@@ -201,6 +316,8 @@ pub(super) extern "C" fn user_gpio_interrupt_handler() {
 
             // Wake up the tasks
             handle_async_pins(bank, async_pins, intrs);
+
+            handle_pin_handlers(bank, intrs & !async_pins);
         }
     });
 }
@@ -234,7 +351,7 @@ impl InterruptStatusRegisterAccess {
     }
 }
 
-fn interrupt_status() -> [(GpioBank, u32); GpioBank::COUNT] {
+pub(super) fn interrupt_status() -> [(GpioBank, u32); GpioBank::COUNT] {
     let intrs_bank0 = InterruptStatusRegisterAccess::Bank0.interrupt_status_read();
 
     #[cfg(gpio_has_bank_1)]
@@ -247,6 +364,23 @@ fn interrupt_status() -> [(GpioBank, u32); GpioBank::COUNT] {
     ]
 }
 
+/// Records the input level of the pins with a pending interrupt, so that
+/// `Flex::last_edge` can tell which edge triggered it.
+fn latch_levels(banks: [(GpioBank, u32); GpioBank::COUNT]) {
+    for (bank, intrs) in banks {
+        if intrs == 0 {
+            continue;
+        }
+
+        let levels = bank.read_input();
+
+        // We're in the GPIO critical section, nothing else writes the latched levels.
+        let latched = bank.latched_levels();
+        let old = latched.load(Ordering::Relaxed);
+        latched.store((old & !intrs) | (levels & intrs), Ordering::Relaxed);
+    }
+}
+
 // We have separate variants for single-core and multi-core async pin handling.
 // Single core can be much simpler because no code is running in parallel, so we
 // don't have to be so careful with the order of operations. On multi-core,
@@ -264,7 +398,7 @@ fn handle_async_pins(bank: GpioBank, async_pins: u32, intrs: u32) {
         let pin_nr = pin_pos as u8 + bank.offset();
 
         // Disable the interrupt for this pin.
-        set_int_enable(pin_nr, Some(0), 0, false);
+        disable_int(pin_nr);
 
         unsafe { AnyPin::steal(pin_nr) }.waker().wake();
     }
@@ -295,7 +429,7 @@ fn handle_async_pins(bank: GpioBank, async_pins: u32, intrs: u32) {
         let pin_nr = pin_pos as u8 + bank.offset();
 
         // Disable the interrupt for this pin.
-        set_int_enable(pin_nr, Some(0), 0, false);
+        disable_int(pin_nr);
     }
 
     // This is an optimization (in case multiple pin interrupts are handled at once)
@@ -208,18 +208,21 @@ macro_rules! lp_gpio {
                 }
 
                 fn rtcio_pad_hold(&self, enable: bool) {
-                    let mask = 1 << $gpionum;
-                    unsafe {
-                        let lp_aon = $crate::peripherals::LP_AON::regs();
+                    // The register is shared with other pins.
+                    $crate::gpio::GPIO_LOCK.lock(|| {
+                        let mask = 1 << $gpionum;
+                        unsafe {
+                            let lp_aon = $crate::peripherals::LP_AON::regs();
 
-                        lp_aon.gpio_hold0().modify(|r, w| {
-                            if enable {
-                                w.gpio_hold0().bits(r.gpio_hold0().bits() | mask)
-                            } else {
-                                w.gpio_hold0().bits(r.gpio_hold0().bits() & !mask)
-                            }
-                        });
-                    }
+                            lp_aon.gpio_hold0().modify(|r, w| {
+                                if enable {
+                                    w.gpio_hold0().bits(r.gpio_hold0().bits() | mask)
+                                } else {
+                                    w.gpio_hold0().bits(r.gpio_hold0().bits() & !mask)
+                                }
+                            });
+                        }
+                    });
                 }
 
                 /// Set the LP properties of the pin. If `mux` is true then then pin is
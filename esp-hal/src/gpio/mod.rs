@@ -35,6 +35,16 @@
 //! This driver also implements pin-related traits from [embedded-hal] and
 //! [Wait](embedded_hal_async::digital::Wait) trait from [embedded-hal-async].
 //!
+//! ## Concurrent access
+//!
+//! Pin drivers of different pins can be used from different execution
+//! contexts (for example, from a thread and an interrupt handler, or from
+//! both cores) at the same time. Setting the output level uses the
+//! write-1-to-set and write-1-to-clear registers, which only affect the
+//! selected pin, so it does not need a critical section. Changing the
+//! configuration of a pin may modify registers that are shared with other
+//! pins; the driver serializes these accesses internally.
+//!
 //! ## GPIO interconnect
 //!
 //! Sometimes you may want to connect peripherals together without using
@@ -55,12 +65,18 @@
 crate::unstable_module! {
     pub mod interconnect;
 
+    #[cfg(all(soc_has_dedicated_gpio, riscv))]
+    pub mod dedicated;
+
     #[cfg(soc_has_etm)]
     pub mod etm;
 
     #[cfg(soc_has_lp_io)]
     pub mod lp_io;
 
+    #[cfg(soc_has_gpio_sd)]
+    pub mod sdm;
+
     #[cfg(all(soc_has_rtc_io, not(esp32)))]
     pub mod rtc_io;
 }
@@ -138,6 +154,19 @@ impl Drop for PinGuard {
     }
 }
 
+/// The direction of a pin edge.
+///
+/// See [`Input::last_edge`].
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub enum Edge {
+    /// The pin went from low to high.
+    Rising,
+    /// The pin went from high to low.
+    Falling,
+}
+
 /// Event used to trigger interrupts.
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -239,31 +268,41 @@ impl From<Level> for bool {
     }
 }
 
-/// Errors that can occur when configuring a pin to be a wakeup source.
+/// Errors that can occur when routing a peripheral signal through the GPIO
+/// matrix.
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[instability::unstable]
 #[non_exhaustive]
-pub enum WakeConfigError {
-    /// Returned when trying to configure a pin to wake up from light sleep on
-    /// an edge trigger, which is not supported.
-    EdgeTriggeringNotSupported,
+pub enum RoutingError {
+    /// The peripheral input is already connected to a different GPIO.
+    InputAlreadyConnected,
+    /// The GPIO is already driven by a different peripheral output.
+    OutputAlreadyConnected,
+    /// The signal can not be routed through the GPIO matrix. It can only be
+    /// connected to the pins that provide it as an alternate function.
+    NotRoutable,
 }
 
-impl Display for WakeConfigError {
+impl Display for RoutingError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            WakeConfigError::EdgeTriggeringNotSupported => {
-                write!(
-                    f,
-                    "Edge triggering is not supported for wake-up from light sleep"
-                )
+            RoutingError::InputAlreadyConnected => write!(
+                f,
+                "The peripheral input is already connected to a different GPIO"
+            ),
+            RoutingError::OutputAlreadyConnected => write!(
+                f,
+                "The GPIO is already driven by a different peripheral output"
+            ),
+            RoutingError::NotRoutable => {
+                write!(f, "The signal can not be routed through the GPIO matrix")
             }
         }
     }
 }
 
-impl core::error::Error for WakeConfigError {}
+impl core::error::Error for RoutingError {}
 
 /// Pull setting for a GPIO.
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
@@ -505,6 +544,24 @@ impl GpioBank {
         &FLAGS[self as usize]
     }
 
+    /// The input levels of the pins, as sampled by the interrupt handler when
+    /// their interrupt was last handled.
+    fn latched_levels(self) -> &'static AtomicU32 {
+        static LEVELS: [AtomicU32; GpioBank::COUNT] =
+            [const { AtomicU32::new(0) }; GpioBank::COUNT];
+
+        &LEVELS[self as usize]
+    }
+
+    /// The pins whose handler is an event callback, see
+    /// [`Flex::set_event_handler`].
+    fn event_callbacks(self) -> &'static AtomicU32 {
+        static EVENTS: [AtomicU32; GpioBank::COUNT] =
+            [const { AtomicU32::new(0) }; GpioBank::COUNT];
+
+        &EVENTS[self as usize]
+    }
+
     fn offset(self) -> u8 {
         match self {
             Self::_0 => 0,
@@ -683,6 +740,36 @@ impl<'d> Io<'d> {
         };
         USER_INTERRUPT_HANDLER.store(handler.handler().aligned_ptr());
     }
+
+    /// Returns the pins with a pending interrupt.
+    ///
+    /// Bit `n` of the returned value is set if GPIO`n` has a pending
+    /// interrupt. This allows an interrupt handler shared by many pins to find
+    /// the pins that triggered without checking each of them. Pass the
+    /// returned value to [`Self::clear_interrupts`] to clear the interrupt
+    /// status of all of these pins.
+    #[inline]
+    #[instability::unstable]
+    pub fn interrupt_status(&self) -> u64 {
+        interrupt::interrupt_status()
+            .into_iter()
+            .fold(0, |status, (bank, intrs)| {
+                status | ((intrs as u64) << bank.offset())
+            })
+    }
+
+    /// Clears the interrupt status of the pins in `mask`.
+    ///
+    /// Bit `n` of `mask` corresponds to GPIO`n`, see
+    /// [`Self::interrupt_status`]. The pins of each GPIO bank are cleared with
+    /// a single register write.
+    #[inline]
+    #[instability::unstable]
+    pub fn clear_interrupts(&mut self, mask: u64) {
+        GpioBank::_0.write_interrupt_status_clear(mask as u32);
+        #[cfg(gpio_has_bank_1)]
+        GpioBank::_1.write_interrupt_status_clear((mask >> 32) as u32);
+    }
 }
 
 impl crate::private::Sealed for Io<'_> {}
@@ -738,7 +825,7 @@ pub enum DriveMode {
     /// resistors.
     #[cfg_attr(
         feature = "unstable",
-        doc = "\n\nEnable the input related functionality by using [Output::into_flex] and enabling input via [Flex::set_input_enable]"
+        doc = "\n\nUse [Output::into_open_drain] to configure an open-drain pin that can also read the level of the line."
     )]
     OpenDrain,
 }
@@ -858,6 +945,30 @@ impl<'d> Output<'d> {
         self.pin.into_peripheral_output()
     }
 
+    #[procmacros::doc_replace]
+    /// Connects this pin to a peripheral output signal through the GPIO matrix.
+    ///
+    /// See [`Flex::connect_peripheral_to_output`] for more details.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::gpio::{Level, Output, OutputConfig, OutputSignal};
+    /// # let config = OutputConfig::default();
+    /// let mut tx = Output::new(peripherals.GPIO4, Level::High, config);
+    /// tx.connect_peripheral_to_output(OutputSignal::U0TXD)?;
+    /// # {after_snippet}
+    /// ```
+    #[inline]
+    #[instability::unstable]
+    pub fn connect_peripheral_to_output(
+        &mut self,
+        signal: OutputSignal,
+    ) -> Result<(), RoutingError> {
+        self.pin.connect_peripheral_to_output(signal)
+    }
+
     #[procmacros::doc_replace]
     /// Change the configuration.
     ///
@@ -913,7 +1024,12 @@ impl<'d> Output<'d> {
     }
 
     #[procmacros::doc_replace]
-    /// Set the output level.ç
+    /// Set the output level.
+    ///
+    /// Only the level of this pin is changed, without a read-modify-write of
+    /// the shared output register. This means that this function does not need
+    /// a critical section, and it is safe to change the level of other pins
+    /// concurrently.
     ///
     /// ## Example
     ///
@@ -1014,12 +1130,72 @@ impl<'d> Output<'d> {
         self.pin.toggle();
     }
 
+    /// Latches the current state of the pin, or releases it.
+    ///
+    /// See [`Flex::hold`] for more information and an example.
+    #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2))]
+    #[inline]
+    #[instability::unstable]
+    pub fn hold(&mut self, enable: bool) {
+        self.pin.hold(enable);
+    }
+
     /// Converts the pin driver into a [`Flex`] driver.
     #[inline]
     #[instability::unstable]
     pub fn into_flex(self) -> Flex<'d> {
         self.pin
     }
+
+    #[procmacros::doc_replace]
+    /// Reconfigures the pin as an open-drain output, and enables its input
+    /// buffer.
+    ///
+    /// The pin only drives the line low. When its output is set high, the pin
+    /// releases the line, and [`Flex::level`] returns the level that is set
+    /// by other devices or by the pull resistors. This is what shared
+    /// interrupt lines and I2C-like buses need.
+    ///
+    /// `pull` selects the internal pull resistor. Use [`Pull::None`] if the
+    /// line has an external pull-up resistor. The drive strength is reset to
+    /// its default value.
+    ///
+    /// Note that the internal pull-up resistor is weak (in the order of tens of
+    /// kΩ), which results in slow rising edges. This limits the usable bus
+    /// speed, especially with long wires or multiple devices on the line. Use
+    /// external pull-up resistors for anything beyond short, slow buses.
+    ///
+    /// The following register bits are set:
+    /// - `GPIO_PINn_PAD_DRIVER` in the GPIO matrix selects the open-drain driver.
+    /// - `FUN_WPU`/`FUN_WPD` in the pin's `IO_MUX` register enable the pull resistors.
+    /// - `FUN_IE` in the pin's `IO_MUX` register enables the input buffer.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::gpio::{Level, Output, OutputConfig, Pull};
+    /// let output = Output::new(peripherals.GPIO5, Level::High, OutputConfig::default());
+    ///
+    /// // The line has an external pull-up resistor.
+    /// let irq = output.into_open_drain(Pull::None);
+    /// if irq.is_low() {
+    ///     // Another device is signalling an interrupt.
+    /// }
+    /// # {after_snippet}
+    /// ```
+    #[inline]
+    #[instability::unstable]
+    pub fn into_open_drain(self, pull: Pull) -> Flex<'d> {
+        let mut pin = self.pin;
+        pin.apply_output_config(
+            &OutputConfig::default()
+                .with_drive_mode(DriveMode::OpenDrain)
+                .with_pull(pull),
+        );
+        pin.set_input_enable(true);
+        pin
+    }
 }
 
 /// Input pin configuration.
@@ -1126,6 +1302,30 @@ impl<'d> Input<'d> {
         self.pin.peripheral_input()
     }
 
+    #[procmacros::doc_replace]
+    /// Connects a peripheral input signal to this pin through the GPIO matrix.
+    ///
+    /// See [`Flex::connect_input_to_peripheral`] for more details.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::gpio::{Input, InputConfig, InputSignal, Pull};
+    /// let config = InputConfig::default().with_pull(Pull::Up);
+    /// let mut rx = Input::new(peripherals.GPIO5, config);
+    /// rx.connect_input_to_peripheral(InputSignal::U0RXD)?;
+    /// # {after_snippet}
+    /// ```
+    #[inline]
+    #[instability::unstable]
+    pub fn connect_input_to_peripheral(
+        &mut self,
+        signal: InputSignal,
+    ) -> Result<(), RoutingError> {
+        self.pin.connect_input_to_peripheral(signal)
+    }
+
     #[procmacros::doc_replace]
     /// Get whether the pin input level is high.
     ///
@@ -1197,6 +1397,32 @@ impl<'d> Input<'d> {
         self.pin.apply_input_config(config)
     }
 
+    #[procmacros::doc_replace]
+    /// Enable or disable the input hysteresis (Schmitt trigger) of the pin.
+    ///
+    /// With hysteresis, the input switches at a higher threshold on a rising
+    /// edge than on a falling one. This suppresses the spurious edges that a
+    /// slow or noisy signal causes around the threshold.
+    ///
+    /// By default, the eFuse configuration decides whether hysteresis is
+    /// enabled. Calling this function overrides it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::gpio::{Input, InputConfig};
+    /// let mut pin = Input::new(peripherals.GPIO5, InputConfig::default());
+    /// pin.enable_hysteresis(true);
+    /// # {after_snippet}
+    /// ```
+    #[cfg(esp32h2)]
+    #[inline]
+    #[instability::unstable]
+    pub fn enable_hysteresis(&mut self, enable: bool) {
+        self.pin.enable_hysteresis(enable)
+    }
+
     #[procmacros::doc_replace]
     /// Listen for interrupts.
     ///
@@ -1295,18 +1521,63 @@ impl<'d> Input<'d> {
         self.pin.is_interrupt_set()
     }
 
-    /// Enable as a wake-up source.
+    /// Returns the direction of the edge that last triggered this pin's
+    /// interrupt.
+    ///
+    /// See [`Flex::last_edge`] for more information.
+    #[inline]
+    #[instability::unstable]
+    pub fn last_edge(&self) -> Edge {
+        self.pin.last_edge()
+    }
+
+    /// Registers an interrupt handler for this pin only.
+    ///
+    /// See [`Flex::set_interrupt_handler`] for more information.
+    #[instability::unstable]
+    pub fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        self.pin.set_interrupt_handler(handler);
+    }
+
+    /// Registers a callback that is called for each event of this pin.
+    ///
+    /// See [`Flex::set_event_handler`] for more information and an example.
+    #[instability::unstable]
+    pub fn set_event_handler(&mut self, handler: fn(u8, Edge)) {
+        self.pin.set_event_handler(handler);
+    }
+
+    /// Unregisters the interrupt handler of this pin.
+    #[instability::unstable]
+    pub fn remove_interrupt_handler(&mut self) {
+        self.pin.remove_interrupt_handler();
+    }
+
+    /// Enable or disable the pin as a wake-up source.
+    ///
+    /// While enabled, the pin wakes up the chip from light sleep when its
+    /// level matches `event`. Pass `GpioWakeupSource` to `Rtc::sleep_light`
+    /// to enable waking up by GPIO.
+    ///
+    /// Any GPIO can be used, as the GPIO peripheral stays powered in light
+    /// sleep. Pins can't wake up the chip from deep sleep this way, use the
+    /// `Ext0WakeupSource`, `Ext1WakeupSource` or `RtcioWakeupSource` wake-up
+    /// sources with RTC capable pins for that. Sleep is not yet supported on
+    /// the ESP32-H2.
     ///
-    /// This will unlisten for interrupts
+    /// The wake-up source works independently of interrupts: enabling it
+    /// doesn't change whether the pin is listening for interrupts, and
+    /// [`Self::unlisten`] doesn't disable it. However, the pin has a single
+    /// trigger, so listening for `event` is replaced by listening for the
+    /// wake-up level, and listening for an edge afterwards disables the wake-up
+    /// source.
     ///
-    /// # Error
-    /// Configuring pin to wake up from light sleep on an edge
-    /// trigger is currently not supported, corresponding variant of
-    /// [`WakeConfigError`] will be returned.
+    /// Waking up on an edge is not supported by the hardware, which is why
+    /// [`WakeEvent`] only has level variants.
     #[instability::unstable]
     #[inline]
-    pub fn wakeup_enable(&mut self, enable: bool, event: WakeEvent) -> Result<(), WakeConfigError> {
-        self.pin.wakeup_enable(enable, event)
+    pub fn wakeup_enable(&mut self, enable: bool, event: WakeEvent) {
+        self.pin.wakeup_enable(enable, event);
     }
 
     /// Converts the pin driver into a [`Flex`] driver.
@@ -1332,10 +1603,22 @@ impl<'d> Input<'d> {
 #[instability::unstable]
 pub struct Flex<'d> {
     pin: AnyPin<'d>,
+    /// Whether this driver registered the pin's handler.
+    owns_handler: bool,
 }
 
 impl private::Sealed for Flex<'_> {}
 
+impl Drop for Flex<'_> {
+    fn drop(&mut self) {
+        // Don't leave the pin's handler behind for the next driver of the pin. Other drivers
+        // of the same pin, like the halves of `split_into_drivers`, keep their handler.
+        if self.owns_handler {
+            set_pin_handler(&self.pin, None);
+        }
+    }
+}
+
 impl<'d> Flex<'d> {
     /// Create flexible pin driver for a [Pin].
     /// No mode change happens.
@@ -1347,7 +1630,10 @@ impl<'d> Flex<'d> {
         // Before each use, reset the GPIO to a known state.
         pin.init_gpio();
 
-        Self { pin }
+        Self {
+            pin,
+            owns_handler: false,
+        }
     }
 
     // Input functions
@@ -1370,6 +1656,16 @@ impl<'d> Flex<'d> {
         self.pin.set_input_enable(enable_input);
     }
 
+    /// Enable or disable the input hysteresis (Schmitt trigger) of the pin.
+    ///
+    /// See [`Input::enable_hysteresis`] for more information.
+    #[cfg(esp32h2)]
+    #[inline]
+    #[instability::unstable]
+    pub fn enable_hysteresis(&mut self, enable: bool) {
+        self.pin.enable_hysteresis(enable);
+    }
+
     /// Get whether the pin input level is high.
     #[inline]
     #[instability::unstable]
@@ -1397,23 +1693,28 @@ impl<'d> Flex<'d> {
     #[inline]
     #[instability::unstable]
     pub fn listen(&mut self, event: Event) {
-        // Unwrap can't fail currently as listen_with_options is only supposed to return
-        // an error if wake_up_from_light_sleep is true.
-        unwrap!(self.pin.listen_with_options(event, true, false, false));
+        // The wake-up source shares the trigger type with the interrupt, and
+        // only works with level triggers.
+        let wakeup = is_wakeup_enabled(self.pin.number())
+            && matches!(event, Event::LowLevel | Event::HighLevel);
+
+        self.pin.listen_with_options(event, true, false, wakeup);
     }
 
     /// Stop listening for interrupts.
+    ///
+    /// If the pin is enabled as a wake-up source, it keeps waking up the chip.
     #[inline]
     #[instability::unstable]
     pub fn unlisten(&mut self) {
         GPIO_LOCK.lock(|| {
-            set_int_enable(self.pin.number(), Some(0), 0, false);
+            disable_int(self.pin.number());
         });
     }
 
     fn unlisten_and_clear(&mut self) {
         GPIO_LOCK.lock(|| {
-            set_int_enable(self.pin.number(), Some(0), 0, false);
+            disable_int(self.pin.number());
             self.clear_interrupt();
         });
     }
@@ -1425,6 +1726,101 @@ impl<'d> Flex<'d> {
         is_int_enabled(self.pin.number())
     }
 
+    /// Registers an interrupt handler for this pin only.
+    ///
+    /// The handler is called from the GPIO interrupt handler when an interrupt
+    /// of this pin is pending, unless the pin is used by an async operation at
+    /// that time. This allows pins with blocking handlers to be used alongside
+    /// pins used with the async API, without a global handler set by
+    /// [`Io::set_interrupt_handler`]. If a global handler is set, both are
+    /// called.
+    ///
+    /// Like the global handler, the pin's handler is responsible for clearing
+    /// the interrupt status with [`Self::clear_interrupt`] or disabling the
+    /// interrupt with [`Self::unlisten`]. Otherwise, the handler will be called
+    /// again.
+    ///
+    /// The priority of the handler is ignored, all GPIO interrupts run at the
+    /// priority set by [`Io::set_interrupt_priority`]. The handler is not
+    /// called if the GPIO interrupt is bound by defining a
+    /// `#[no_mangle] unsafe extern "C" fn GPIO()` function.
+    ///
+    /// The handler is unregistered when the driver that registered it is
+    /// dropped.
+    #[instability::unstable]
+    pub fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        set_pin_handler(
+            &self.pin,
+            Some(PinHandler::Interrupt(handler.handler().aligned_ptr())),
+        );
+        self.owns_handler = true;
+    }
+
+    #[procmacros::doc_replace]
+    /// Registers a callback that is called for each event of this pin.
+    ///
+    /// This is an alternative to [`Self::set_interrupt_handler`] that takes
+    /// care of clearing the interrupt status: the GPIO interrupt handler clears
+    /// the status of the pin, then calls `handler` with the pin number and the
+    /// edge that triggered it (see [`Self::last_edge`]). The interrupt stays
+    /// enabled, so the callback runs for every event until the pin is
+    /// unlistened. The same callback can be registered for many pins.
+    ///
+    /// Like other pin handlers, the callback isn't called while the pin is
+    /// used by an async operation, and it replaces the handler set by
+    /// [`Self::set_interrupt_handler`]. It runs in the GPIO interrupt
+    /// handler's critical section, at the priority set by
+    /// [`Io::set_interrupt_priority`]. Pins listening for a level [`Event`]
+    /// trigger again immediately while the level persists, so the callback
+    /// should unlisten them.
+    ///
+    /// The callback is unregistered when the driver that registered it is
+    /// dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use core::cell::Cell;
+    ///
+    /// use critical_section::Mutex;
+    /// use esp_hal::gpio::{Edge, Event, Input, InputConfig, Pull};
+    ///
+    /// static PRESSED: Mutex<Cell<u64>> = Mutex::new(Cell::new(0));
+    ///
+    /// fn on_event(pin: u8, edge: Edge) {
+    ///     if edge == Edge::Falling {
+    ///         critical_section::with(|cs| {
+    ///             let pressed = PRESSED.borrow(cs);
+    ///             pressed.set(pressed.get() | (1 << pin));
+    ///         });
+    ///     }
+    /// }
+    ///
+    /// let config = InputConfig::default().with_pull(Pull::Up);
+    /// let mut row0 = Input::new(peripherals.GPIO4, config);
+    /// let mut row1 = Input::new(peripherals.GPIO5, config);
+    /// row0.set_event_handler(on_event);
+    /// row1.set_event_handler(on_event);
+    /// row0.listen(Event::FallingEdge);
+    /// row1.listen(Event::FallingEdge);
+    /// # {after_snippet}
+    /// ```
+    #[instability::unstable]
+    pub fn set_event_handler(&mut self, handler: fn(u8, Edge)) {
+        set_pin_handler(&self.pin, Some(PinHandler::Event(handler)));
+        self.owns_handler = true;
+    }
+
+    /// Unregisters the interrupt handler or event callback of this pin.
+    ///
+    /// See [`Self::set_interrupt_handler`] and [`Self::set_event_handler`].
+    #[instability::unstable]
+    pub fn remove_interrupt_handler(&mut self) {
+        set_pin_handler(&self.pin, None);
+        self.owns_handler = false;
+    }
+
     /// Clear the interrupt status bit for this Pin
     #[inline]
     #[instability::unstable]
@@ -1441,19 +1837,57 @@ impl<'d> Flex<'d> {
         self.pin.bank().read_interrupt_status() & self.pin.mask() != 0
     }
 
-    /// Enable as a wake-up source.
+    /// Returns the direction of the edge that last triggered this pin's
+    /// interrupt.
+    ///
+    /// The GPIO peripheral doesn't record which edge raised an
+    /// [`Event::AnyEdge`] interrupt. Instead, the GPIO interrupt handler
+    /// samples the level of every pin with a pending interrupt as soon as it
+    /// starts, before calling the user's or the pin's own handler. A high level
+    /// is reported as [`Edge::Rising`], a low level as [`Edge::Falling`].
     ///
-    /// This will unlisten for interrupts
+    /// This is a best-effort measure: if the pin changes again before the
+    /// handler starts, i.e. the pulse is shorter than the interrupt latency,
+    /// the reported edge is the wrong one. It is, however, sampled before any
+    /// handler code runs, unlike reading the pin from the handler.
     ///
-    /// # Error
-    /// Configuring pin to wake up from light sleep on an edge
-    /// trigger is currently not supported, corresponding variant of
-    /// [`WakeConfigError`] will be returned.
+    /// The level is only recorded by the interrupt handlers provided by
+    /// esp-hal, not when a handler is bound to the GPIO interrupt directly.
+    /// Before the pin's interrupt has been handled for the first time, this
+    /// returns [`Edge::Falling`].
     #[inline]
     #[instability::unstable]
-    pub fn wakeup_enable(&mut self, enable: bool, event: WakeEvent) -> Result<(), WakeConfigError> {
-        self.pin
-            .listen_with_options(event.into(), false, false, enable)
+    pub fn last_edge(&self) -> Edge {
+        let levels = self
+            .pin
+            .bank()
+            .latched_levels()
+            .load(portable_atomic::Ordering::Relaxed);
+
+        if levels & self.pin.mask() != 0 {
+            Edge::Rising
+        } else {
+            Edge::Falling
+        }
+    }
+
+    /// Enable or disable the pin as a wake-up source.
+    ///
+    /// See [`Input::wakeup_enable`] for more information.
+    #[inline]
+    #[instability::unstable]
+    pub fn wakeup_enable(&mut self, enable: bool, event: WakeEvent) {
+        let gpio_num = self.pin.number();
+        GPIO_LOCK.lock(|| {
+            GPIO::regs().pin(gpio_num as usize).modify(|r, w| unsafe {
+                if enable {
+                    w.int_type().bits(event as u8);
+                } else if r.int_ena().bits() == 0 {
+                    w.int_type().bits(0);
+                }
+                w.wakeup_enable().bit(enable)
+            });
+        });
     }
 
     // Output functions
@@ -1532,6 +1966,51 @@ impl<'d> Flex<'d> {
         self.set_level(!level);
     }
 
+    #[procmacros::doc_replace]
+    /// Latches the current state of the pin, or releases it.
+    ///
+    /// While the pin is held, its output level, output enable, and pull
+    /// resistors are frozen at the value they had when the hold was enabled.
+    /// Changing the level, reconfiguring the pin, connecting it to a
+    /// peripheral, or dropping the driver has no effect on the pad until the
+    /// hold is released. The new configuration takes effect when `hold(false)`
+    /// is called.
+    ///
+    /// This keeps a line at a known level while the driver that owns it is
+    /// replaced, for example to keep a reset line asserted until the new
+    /// driver is set up. Dropping the driver does not release the hold.
+    ///
+    /// To keep the state of the pin in deep sleep, use the wake-up source
+    /// configuration of the `rtc_cntl::sleep` module instead.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::gpio::{Flex, Level, Output, OutputConfig};
+    ///
+    /// let mut reset = Output::new(
+    ///     peripherals.GPIO5.reborrow(),
+    ///     Level::Low,
+    ///     OutputConfig::default(),
+    /// );
+    /// reset.hold(true);
+    /// drop(reset);
+    ///
+    /// // The line stays low while the pin is reconfigured.
+    /// let mut reset = Flex::new(peripherals.GPIO5);
+    /// reset.set_low();
+    /// reset.set_output_enable(true);
+    /// reset.hold(false);
+    /// # {after_snippet}
+    /// ```
+    #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2))]
+    #[inline]
+    #[instability::unstable]
+    pub fn hold(&mut self, enable: bool) {
+        self.pin.set_hold(enable);
+    }
+
     // Other/common functions
 
     #[procmacros::doc_replace]
@@ -1620,6 +2099,7 @@ impl<'d> Flex<'d> {
         let input = Input {
             pin: Flex {
                 pin: unsafe { self.pin.clone_unchecked() },
+                owns_handler: false,
             },
         };
         let output = Output { pin: self };
@@ -1649,9 +2129,102 @@ impl<'d> Flex<'d> {
     #[instability::unstable]
     pub fn into_peripheral_output(self) -> interconnect::OutputSignal<'d> {
         unsafe {
-            // Safety: the signals are frozen by this function.
-            self.pin.split_no_init().1.freeze()
+            // Safety: the signals are frozen by this function, and `self` is
+            // dropped here.
+            self.pin.clone_unchecked().split_no_init().1.freeze()
+        }
+    }
+
+    #[procmacros::doc_replace]
+    /// Connects a peripheral input signal to this pin through the GPIO matrix.
+    ///
+    /// This allows using the pin for any peripheral input, not just the ones
+    /// the peripheral drivers offer. The input buffer of the pin is enabled
+    /// by this function.
+    ///
+    /// A peripheral input can only be driven by a single GPIO. If `signal` is
+    /// already connected to a different GPIO through the GPIO matrix, this
+    /// function returns [`RoutingError::InputAlreadyConnected`] and leaves the
+    /// existing connection intact. To move the signal to a different pin,
+    /// disconnect it first by connecting it to a constant [`Level`] with
+    /// [`InputSignal::connect_to`].
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::gpio::{Flex, InputSignal};
+    /// let mut rx = Flex::new(peripherals.GPIO5);
+    /// rx.connect_input_to_peripheral(InputSignal::U0RXD)?;
+    /// # {after_snippet}
+    /// ```
+    #[inline]
+    #[instability::unstable]
+    pub fn connect_input_to_peripheral(
+        &mut self,
+        signal: InputSignal,
+    ) -> Result<(), RoutingError> {
+        if !signal.can_use_gpio_matrix() {
+            return Err(RoutingError::NotRoutable);
+        }
+        if signal
+            .gpio_matrix_source()
+            .is_some_and(|gpio| gpio != self.pin.number())
+        {
+            return Err(RoutingError::InputAlreadyConnected);
         }
+
+        let input = self.peripheral_input().with_gpio_matrix_forced(true);
+        interconnect::PeripheralSignal::connect_input_to_peripheral(&input, signal);
+
+        Ok(())
+    }
+
+    #[procmacros::doc_replace]
+    /// Connects this pin to a peripheral output signal through the GPIO matrix.
+    ///
+    /// This allows using the pin for any peripheral output, not just the ones
+    /// the peripheral drivers offer. The pin's output must be enabled with
+    /// [`Self::set_output_enable`] for the signal to appear on it.
+    ///
+    /// A peripheral output can drive any number of pins, but a pin can only be
+    /// driven by a single peripheral output. If the pin is already driven by
+    /// a different peripheral output, this function returns
+    /// [`RoutingError::OutputAlreadyConnected`] and leaves the existing
+    /// connection intact. Use [`OutputSignal::disconnect_from`] to release the
+    /// pin first.
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::gpio::{Flex, OutputSignal};
+    /// let mut tx = Flex::new(peripherals.GPIO4);
+    /// tx.connect_peripheral_to_output(OutputSignal::U0TXD)?;
+    /// tx.set_output_enable(true);
+    /// # {after_snippet}
+    /// ```
+    #[inline]
+    #[instability::unstable]
+    pub fn connect_peripheral_to_output(
+        &mut self,
+        signal: OutputSignal,
+    ) -> Result<(), RoutingError> {
+        if !signal.can_use_gpio_matrix() {
+            return Err(RoutingError::NotRoutable);
+        }
+        let current = GPIO::regs()
+            .func_out_sel_cfg(self.pin.number() as usize)
+            .read()
+            .out_sel()
+            .bits();
+        if current != OutputSignal::GPIO as _ && current != signal as _ {
+            return Err(RoutingError::OutputAlreadyConnected);
+        }
+
+        // Safety: the signal is only used to configure the GPIO matrix, and it is
+        // dropped right away.
+        let output = unsafe { self.pin.clone_unchecked().split_no_init().1 };
+        let output = output.with_gpio_matrix_forced(true);
+        interconnect::PeripheralOutput::connect_peripheral_to_output(&output, signal);
+
+        Ok(())
     }
 }
 
@@ -1861,6 +2434,47 @@ impl<'lt> AnyPin<'lt> {
         io_mux_reg(self.number()).modify(|_, w| w.fun_ie().bit(on));
     }
 
+    /// Enable or disable the input hysteresis of the pin, overriding the eFuse
+    /// configuration.
+    #[cfg(esp32h2)]
+    #[inline]
+    pub(crate) fn enable_hysteresis(&self, enable: bool) {
+        io_mux_reg(self.number()).modify(|_, w| {
+            w.hys_sel().set_bit();
+            w.hys_en().bit(enable)
+        });
+    }
+
+    /// Latches or releases the state of the pad.
+    #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2))]
+    pub(crate) fn set_hold(&self, enable: bool) {
+        let mask = 1 << self.number();
+        let update = |bits: u32| if enable { bits | mask } else { bits & !mask };
+
+        // The registers are shared with other pins.
+        GPIO_LOCK.lock(|| {
+            cfg_if::cfg_if! {
+                if #[cfg(any(esp32c6, esp32h2))] {
+                    crate::peripherals::LP_AON::regs()
+                        .gpio_hold0()
+                        .modify(|r, w| unsafe { w.gpio_hold0().bits(update(r.gpio_hold0().bits())) });
+                } else {
+                    // The RTC pins are held through the RTC controller, like in sleep.
+                    let rtc_cntl = crate::peripherals::LPWR::regs();
+                    if self.number() <= 5 {
+                        rtc_cntl
+                            .pad_hold()
+                            .modify(|r, w| unsafe { w.bits(update(r.bits())) });
+                    } else {
+                        rtc_cntl
+                            .dig_pad_hold()
+                            .modify(|r, w| unsafe { w.dig_pad_hold().bits(update(r.dig_pad_hold().bits())) });
+                    }
+                }
+            }
+        });
+    }
+
     #[inline]
     pub(crate) fn apply_input_config(&self, config: &InputConfig) {
         let pull_up = config.pull == Pull::Up;
@@ -1898,7 +2512,7 @@ impl<'lt> AnyPin<'lt> {
         int_enable: bool,
         nmi_enable: bool,
         wake_up_from_light_sleep: bool,
-    ) -> Result<(), WakeConfigError> {
+    ) {
         /// Assembles a valid value for the int_ena pin register field.
         fn gpio_intr_enable(int_enable: bool, nmi_enable: bool) -> u8 {
             cfg_if::cfg_if! {
@@ -1914,14 +2528,11 @@ impl<'lt> AnyPin<'lt> {
             }
         }
 
-        if wake_up_from_light_sleep {
-            match event {
-                Event::AnyEdge | Event::RisingEdge | Event::FallingEdge => {
-                    return Err(WakeConfigError::EdgeTriggeringNotSupported);
-                }
-                _ => {}
-            }
-        }
+        // The hardware can't wake up from light sleep on an edge.
+        debug_assert!(
+            !wake_up_from_light_sleep || matches!(event, Event::LowLevel | Event::HighLevel),
+            "Edge triggering is not supported for wake-up from light sleep"
+        );
 
         self.with_gpio_lock(|| {
             // Clear the interrupt status bit for this Pin, just in case the user forgot.
@@ -1936,7 +2547,6 @@ impl<'lt> AnyPin<'lt> {
                 wake_up_from_light_sleep,
             );
         });
-        Ok(())
     }
 
     #[inline]
@@ -2319,10 +2929,31 @@ fn set_int_enable(gpio_num: u8, int_ena: Option<u8>, int_type: u8, wake_up_from_
     });
 }
 
+/// Disable GPIO event listening, leaving the pin's wake-up configuration
+/// intact.
+fn disable_int(gpio_num: u8) {
+    GPIO::regs().pin(gpio_num as usize).modify(|r, w| unsafe {
+        w.int_ena().bits(0);
+        // The wake-up source needs the trigger type.
+        if r.wakeup_enable().bit_is_clear() {
+            w.int_type().bits(0);
+        }
+        w
+    });
+}
+
 fn is_int_enabled(gpio_num: u8) -> bool {
     GPIO::regs().pin(gpio_num as usize).read().int_ena().bits() != 0
 }
 
+fn is_wakeup_enabled(gpio_num: u8) -> bool {
+    GPIO::regs()
+        .pin(gpio_num as usize)
+        .read()
+        .wakeup_enable()
+        .bit_is_set()
+}
+
 for_each_gpio! {
     ($n:literal, $gpio:ident $af_ins:tt $af_outs:tt ([Input] $output:tt)) => {
         impl InputPin for crate::peripherals::$gpio<'_> {
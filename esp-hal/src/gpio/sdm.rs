@@ -0,0 +1,236 @@
+#![cfg_attr(docsrs, procmacros::doc_replace)]
+//! # Sigma-Delta Modulation (SDM)
+//!
+//! ## Overview
+//!
+//! The sigma-delta modulator turns an 8-bit duty value into a pulse density
+//! modulated signal, and drives it to any pin through the GPIO matrix. Once
+//! configured, the hardware generates the signal on its own, without any CPU
+//! involvement. With a low-pass filter on the pin, the output becomes an
+//! analog voltage, which makes the modulator a cheap DAC for audio or LED
+//! dimming when all the LEDC channels are in use.
+//!
+//! The ESP32, ESP32-S2 and ESP32-S3 have 8 channels, the ESP32-C3, ESP32-C6
+//! and ESP32-H2 have 4.
+//!
+//! ## Output signal
+//!
+//! Unlike a PWM signal, the output doesn't have a fixed period. On every clock
+//! cycle the modulator decides whether the output is high or low, so that the
+//! ratio of high cycles over time is `(duty + 128) / 256`. A duty of -128
+//! keeps the output low, 0 outputs a square wave at half the modulator clock
+//! frequency, and 127 is high for 255 out of 256 cycles.
+//!
+//! The modulator clock is divided from the APB clock (80 MHz) on the ESP32,
+//! ESP32-S2, ESP32-S3 and ESP32-C3, and from the IO MUX clock (the crystal
+//! clock) on the ESP32-C6 and ESP32-H2. The divider is `prescale + 1`, see
+//! [`Channel::set_prescale`]. The energy of the output is concentrated around
+//! the modulator clock frequency, far above the signal that is encoded, so a
+//! higher clock frequency makes the output easier to filter. GPIO pads can't
+//! produce clean edges at tens of MHz though, so a prescale that keeps the
+//! modulator clock in the low MHz range is a good starting point.
+//!
+//! ## Filtering
+//!
+//! To get an analog voltage, filter the pin with a low-pass filter whose
+//! cutoff frequency is above the bandwidth of the signal, and well below the
+//! modulator clock frequency. A first order RC filter is enough for dimming
+//! an LED or generating a DC voltage, for example 1 kΩ and 100 nF for a cutoff
+//! frequency of about 1.6 kHz. Audio needs a cutoff around 20 kHz and benefits
+//! from a second order (or active) filter, to attenuate the modulation noise
+//! more steeply. The output voltage swings between 0 V and the supply voltage
+//! of the pin, and the filter should be followed by a buffer if the load draws
+//! a significant current.
+//!
+//! ## Example
+//!
+//! ```rust, no_run
+//! # {before_snippet}
+//! use esp_hal::gpio::sdm::SigmaDelta;
+//!
+//! let sdm = SigmaDelta::new(peripherals.GPIO_SD);
+//! let mut channel = sdm.channel0.with_pin(peripherals.GPIO4);
+//!
+//! // Divide the clock by 80: 1 MHz on chips with an 80 MHz APB clock.
+//! channel.set_prescale(79);
+//! // 75% of the supply voltage, once filtered.
+//! channel.set_duty(64);
+//! # {after_snippet}
+//! ```
+//!
+//! ## Limitations
+//!
+//! On the ESP32-C6 and ESP32-H2, the GPIO ETM driver
+//! ([`gpio::etm::Channels`](crate::gpio::etm::Channels)) also takes the
+//! `GPIO_SD` peripheral, so the two can't be used at the same time.
+
+use core::marker::PhantomData;
+
+use crate::{
+    gpio::{OutputConfig, OutputSignal, PinGuard, interconnect::PeripheralOutput},
+    peripherals::GPIO_SD,
+};
+
+/// The number of sigma-delta channels.
+#[cfg(any(esp32, esp32s2, esp32s3))]
+pub const CHANNEL_COUNT: usize = 8;
+/// The number of sigma-delta channels.
+#[cfg(not(any(esp32, esp32s2, esp32s3)))]
+pub const CHANNEL_COUNT: usize = 4;
+
+const OUTPUT_SIGNALS: [OutputSignal; CHANNEL_COUNT] = [
+    OutputSignal::GPIO_SD0,
+    OutputSignal::GPIO_SD1,
+    OutputSignal::GPIO_SD2,
+    OutputSignal::GPIO_SD3,
+    #[cfg(any(esp32, esp32s2, esp32s3))]
+    OutputSignal::GPIO_SD4,
+    #[cfg(any(esp32, esp32s2, esp32s3))]
+    OutputSignal::GPIO_SD5,
+    #[cfg(any(esp32, esp32s2, esp32s3))]
+    OutputSignal::GPIO_SD6,
+    #[cfg(any(esp32, esp32s2, esp32s3))]
+    OutputSignal::GPIO_SD7,
+];
+
+/// The sigma-delta modulator channels.
+///
+/// See the [module-level documentation](self) for more details.
+#[non_exhaustive]
+pub struct SigmaDelta<'d> {
+    _peripheral: GPIO_SD<'d>,
+    /// Channel 0.
+    pub channel0: Channel<'d, 0>,
+    /// Channel 1.
+    pub channel1: Channel<'d, 1>,
+    /// Channel 2.
+    pub channel2: Channel<'d, 2>,
+    /// Channel 3.
+    pub channel3: Channel<'d, 3>,
+    /// Channel 4.
+    #[cfg(any(esp32, esp32s2, esp32s3))]
+    pub channel4: Channel<'d, 4>,
+    /// Channel 5.
+    #[cfg(any(esp32, esp32s2, esp32s3))]
+    pub channel5: Channel<'d, 5>,
+    /// Channel 6.
+    #[cfg(any(esp32, esp32s2, esp32s3))]
+    pub channel6: Channel<'d, 6>,
+    /// Channel 7.
+    #[cfg(any(esp32, esp32s2, esp32s3))]
+    pub channel7: Channel<'d, 7>,
+}
+
+impl<'d> SigmaDelta<'d> {
+    /// Enables the modulator clock and returns its channels, which are not
+    /// connected to any pin.
+    pub fn new(peripheral: GPIO_SD<'d>) -> Self {
+        let regs = GPIO_SD::regs();
+        regs.clock_gate().modify(|_, w| w.clk_en().set_bit());
+        #[cfg(not(esp32))]
+        regs.sigmadelta_misc()
+            .modify(|_, w| w.function_clk_en().set_bit());
+
+        Self {
+            _peripheral: peripheral,
+            channel0: Channel::new(),
+            channel1: Channel::new(),
+            channel2: Channel::new(),
+            channel3: Channel::new(),
+            #[cfg(any(esp32, esp32s2, esp32s3))]
+            channel4: Channel::new(),
+            #[cfg(any(esp32, esp32s2, esp32s3))]
+            channel5: Channel::new(),
+            #[cfg(any(esp32, esp32s2, esp32s3))]
+            channel6: Channel::new(),
+            #[cfg(any(esp32, esp32s2, esp32s3))]
+            channel7: Channel::new(),
+        }
+    }
+}
+
+impl core::fmt::Debug for SigmaDelta<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SigmaDelta").finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for SigmaDelta<'_> {
+    fn format(&self, fmt: defmt::Formatter<'_>) {
+        defmt::write!(fmt, "SigmaDelta")
+    }
+}
+
+/// A sigma-delta modulator channel.
+///
+/// A new channel has a duty of 0 and a prescale of 255.
+///
+/// The channel keeps modulating after the pin is connected, until it's
+/// dropped, which disconnects the pin.
+pub struct Channel<'d, const CH: usize> {
+    pin: PinGuard,
+    _lifetime: PhantomData<&'d mut ()>,
+}
+
+impl<'d, const CH: usize> Channel<'d, CH> {
+    // Restores the reset state, in case a previous driver was leaked.
+    fn new() -> Self {
+        let mut this = Self {
+            pin: PinGuard::new_unconnected(OUTPUT_SIGNALS[CH]),
+            _lifetime: PhantomData,
+        };
+        this.set_duty(0);
+        this.set_prescale(u8::MAX);
+        this
+    }
+
+    /// Sets the pin to push-pull output and connects it to the channel.
+    ///
+    /// The pin that was previously connected to the channel is disconnected.
+    pub fn with_pin(mut self, pin: impl PeripheralOutput<'d>) -> Self {
+        let pin = pin.into();
+        pin.apply_output_config(&OutputConfig::default());
+        pin.set_output_enable(true);
+
+        self.pin = pin.connect_with_guard(OUTPUT_SIGNALS[CH]);
+
+        self
+    }
+
+    /// Sets the pulse density of the output: the output is high for
+    /// `(duty + 128) / 256` of the modulator clock cycles.
+    pub fn set_duty(&mut self, duty: i8) {
+        GPIO_SD::regs()
+            .sigmadelta(CH)
+            .modify(|_, w| unsafe { w.in_().bits(duty as u8) });
+    }
+
+    /// Returns the current duty.
+    pub fn duty(&self) -> i8 {
+        GPIO_SD::regs().sigmadelta(CH).read().in_().bits() as i8
+    }
+
+    /// Sets the divider of the modulator clock to `prescale + 1`.
+    ///
+    /// See the [module-level documentation](self) for the clock source of
+    /// each chip.
+    pub fn set_prescale(&mut self, prescale: u8) {
+        GPIO_SD::regs()
+            .sigmadelta(CH)
+            .modify(|_, w| unsafe { w.prescale().bits(prescale) });
+    }
+}
+
+impl<const CH: usize> core::fmt::Debug for Channel<'_, CH> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Channel").field("channel", &CH).finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<const CH: usize> defmt::Format for Channel<'_, CH> {
+    fn format(&self, fmt: defmt::Formatter<'_>) {
+        defmt::write!(fmt, "Channel {}", CH)
+    }
+}
@@ -151,6 +151,27 @@ impl<'d> Hmac<'d> {
         Ok(())
     }
 
+    /// Computes the HMAC-SHA-256 of `message` in upstream mode, using the key
+    /// stored in the given eFuse key block.
+    ///
+    /// This is a convenience function that runs all the steps of the
+    /// calculation. The purpose of the key block must be
+    /// [HmacPurpose::ToUser].
+    pub fn sign(&mut self, key_id: KeyId, message: &[u8]) -> Result<[u8; 32], Error> {
+        self.init();
+        nb::block!(self.configure(HmacPurpose::ToUser, key_id))?;
+
+        let mut remaining = message;
+        while !remaining.is_empty() {
+            remaining = nb::block!(self.update(remaining)).unwrap();
+        }
+
+        let mut output = [0; 32];
+        nb::block!(self.finalize(&mut output)).unwrap();
+
+        Ok(output)
+    }
+
     /// Process the msg block after block
     ///
     /// Call this function as many times as necessary (msg.len() > 0)
@@ -116,6 +116,7 @@
 
 use core::{
     marker::PhantomData,
+    ops::{Bound, RangeBounds},
     pin::Pin,
     task::{Context, Poll},
 };
@@ -217,6 +218,11 @@ pub enum BusTimeout {
 
     /// Timeout in bus clock cycles.
     BusCycles(u32),
+
+    /// Timeout as a duration, rounded up to whole bus clock cycles.
+    ///
+    /// This makes the timeout independent of the configured bus frequency.
+    Duration(Duration),
 }
 
 impl BusTimeout {
@@ -227,21 +233,30 @@ impl BusTimeout {
     /// longer (at most ~double) timeouts than configured. We may provide an
     /// `ApbCycles` variant in the future to allow specifying the timeout in
     /// APB cycles directly.
-    fn apb_cycles(self, half_bus_cycle: u32) -> Result<Option<u32>, ConfigError> {
+    fn apb_cycles(self, half_bus_cycle: u32, bus_freq: u32) -> Result<Option<u32>, ConfigError> {
         match self {
             BusTimeout::Maximum => Ok(Some(property!("i2c_master.max_bus_timeout"))),
 
             #[cfg(i2c_master_has_bus_timeout_enable)]
             BusTimeout::Disabled => Ok(None),
 
+            BusTimeout::Duration(duration) => {
+                let cycles = (duration.as_micros() * bus_freq as u64).div_ceil(1_000_000);
+                let cycles = u32::try_from(cycles).map_err(|_| ConfigError::TimeoutTooLong)?;
+                BusTimeout::BusCycles(cycles).apb_cycles(half_bus_cycle, bus_freq)
+            }
+
             BusTimeout::BusCycles(cycles) => {
+                let to_peri = cycles
+                    .checked_mul(2 * half_bus_cycle)
+                    .ok_or(ConfigError::TimeoutTooLong)?;
                 let raw = if cfg!(i2c_master_bus_timeout_is_exponential) {
-                    let to_peri = (cycles * 2 * half_bus_cycle).max(1);
+                    let to_peri = to_peri.max(1);
                     let log2 = to_peri.ilog2();
                     // If not a power of 2, round up so that we don't shorten timeouts.
                     if to_peri != 1 << log2 { log2 + 1 } else { log2 }
                 } else {
-                    cycles * 2 * half_bus_cycle
+                    to_peri
                 };
 
                 if raw <= property!("i2c_master.max_bus_timeout") {
@@ -1193,6 +1208,70 @@ where
         self.transaction(address, &mut [Operation::Write(buffer)])
     }
 
+    #[procmacros::doc_replace]
+    /// Scans the bus for devices with a 7-bit address in `addresses`.
+    ///
+    /// Each address is probed with a zero-length write, and the addresses of
+    /// devices that acknowledge it are returned. The bus is probed lazily, as
+    /// the iterator is advanced. Addresses above `0x7F` are ignored.
+    ///
+    /// If the arbitration is lost to another master, the address is probed
+    /// again, up to 3 times. Any other error is treated as an absent device.
+    /// Every probe ends with a stop condition or, after an error, with the
+    /// peripheral being reset, so the bus is left idle after scanning.
+    ///
+    /// Note that some devices do not acknowledge zero-length writes, these
+    /// devices are not found by this function. Passing `0x08..=0x77` skips the
+    /// addresses reserved by the I2C specification.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::i2c::master::{Config, I2c};
+    /// # let mut i2c = I2c::new(
+    /// #   peripherals.I2C0,
+    /// #   Config::default(),
+    /// # )?;
+    /// for address in i2c.scan(0x08..=0x77) {
+    ///     println!("Found a device at {:#04x}", address);
+    /// }
+    /// # {after_snippet}
+    /// ```
+    #[instability::unstable]
+    pub fn scan(&mut self, addresses: impl RangeBounds<u8>) -> impl Iterator<Item = u8> {
+        let start = match addresses.start_bound() {
+            Bound::Included(&address) => address as u16,
+            Bound::Excluded(&address) => address as u16 + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match addresses.end_bound() {
+            Bound::Included(&address) => address as u16 + 1,
+            Bound::Excluded(&address) => address as u16,
+            Bound::Unbounded => 0x80,
+        };
+
+        (start..end.min(0x80))
+            .map(|address| address as u8)
+            .filter(move |&address| self.probe(address))
+    }
+
+    /// Returns whether a device acknowledges `address`.
+    fn probe(&mut self, address: u8) -> bool {
+        const ATTEMPTS: usize = 3;
+
+        for _ in 0..ATTEMPTS {
+            match self.write(address, &[]) {
+                Ok(()) => return true,
+                // Another master was using the bus, try again.
+                Err(Error::ArbitrationLost) => {}
+                Err(_) => return false,
+            }
+        }
+
+        false
+    }
+
     #[procmacros::doc_replace]
     /// Reads enough bytes from slave with `address` to fill `buffer`
     ///
@@ -1335,6 +1414,45 @@ where
         self.driver().reset_fsm(false);
         Ok(())
     }
+
+    #[procmacros::doc_replace]
+    /// Frees a bus that is held by a stuck device.
+    ///
+    /// If a device was interrupted while sending data, for example by a reset
+    /// of the controller, it may keep SDA low indefinitely. Every transaction
+    /// then fails, usually with [`Error::Timeout`] if a [`BusTimeout`] is
+    /// configured. This function resets the controller, clocks out up to 9 SCL
+    /// pulses until the device releases SDA, and then generates a STOP
+    /// condition.
+    ///
+    /// The driver already does this after a transaction failed with
+    /// [`Error::Timeout`]. Calling this function is useful when the bus may
+    /// have been left in an unknown state by something else, for example
+    /// after a reset of the chip.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::{
+    ///     i2c::master::{BusTimeout, Config, Error, I2c},
+    ///     time::Duration,
+    /// };
+    /// # let mut i2c = I2c::new(
+    /// #   peripherals.I2C0,
+    /// #   Config::default().with_timeout(BusTimeout::Duration(Duration::from_millis(1))),
+    /// # )?;
+    /// # const DEVICE_ADDR: u8 = 0x77;
+    /// let mut data = [0u8; 22];
+    /// if i2c.write_read(DEVICE_ADDR, &[0xaa], &mut data) == Err(Error::Timeout) {
+    ///     i2c.recover_bus();
+    /// }
+    /// # {after_snippet}
+    /// ```
+    #[instability::unstable]
+    pub fn recover_bus(&mut self) {
+        self.driver().clear_bus_blocking(true);
+    }
 }
 
 impl embedded_hal_async::i2c::I2c for I2c<'_, Async> {
@@ -1841,7 +1959,7 @@ impl Driver<'_> {
             scl_stop_setup_time,
             scl_start_hold_time,
             scl_stop_hold_time,
-            timeout.apb_cycles(half_cycle)?,
+            timeout.apb_cycles(half_cycle, bus_freq)?,
         )?;
 
         Ok(())
@@ -1896,7 +2014,7 @@ impl Driver<'_> {
             scl_stop_setup_time,
             scl_start_hold_time,
             scl_stop_hold_time,
-            timeout.apb_cycles(half_cycle)?,
+            timeout.apb_cycles(half_cycle, bus_freq)?,
         )?;
 
         Ok(())
@@ -1965,7 +2083,7 @@ impl Driver<'_> {
             scl_stop_setup_time,
             scl_start_hold_time,
             scl_stop_hold_time,
-            timeout.apb_cycles(half_cycle)?,
+            timeout.apb_cycles(half_cycle, bus_freq)?,
         )?;
 
         Ok(())
@@ -261,6 +261,7 @@ where
     pub i2s_rx: RxCreator<'d, Dm>,
     /// Handles the transmission (TX) side of the I2S peripheral.
     pub i2s_tx: TxCreator<'d, Dm>,
+    sample_rate: Rate,
 }
 
 impl<Dm> I2s<'_, Dm>
@@ -287,6 +288,14 @@ where
         self.i2s_tx.i2s.set_interrupt_handler(handler);
     }
 
+    /// Returns the sample rate the clock has been configured to.
+    ///
+    /// The clock dividers can't produce every sample rate exactly, so the
+    /// actual rate may differ slightly from the one passed to [`I2s::new`].
+    pub fn sample_rate(&self) -> Rate {
+        self.sample_rate
+    }
+
     /// Listen for the given interrupts
     #[instability::unstable]
     pub fn listen(&mut self, interrupts: impl Into<EnumSet<I2sInterrupt>>) {
@@ -351,7 +360,9 @@ impl<'d> I2s<'d, Blocking> {
         let rx_guard = PeripheralGuard::new(peripheral);
         let tx_guard = PeripheralGuard::new(peripheral);
 
-        i2s.set_clock(calculate_clock(sample_rate, 2, data_format.channel_bits()));
+        let clock = calculate_clock(sample_rate, 2, data_format.channel_bits());
+        let sample_rate = clock.sample_rate(2, data_format.channel_bits());
+        i2s.set_clock(clock);
         i2s.configure(&standard, &data_format);
         i2s.set_master();
         i2s.update();
@@ -367,6 +378,7 @@ impl<'d> I2s<'d, Blocking> {
                 tx_channel: channel.tx,
                 guard: tx_guard,
             },
+            sample_rate,
         }
     }
 
@@ -383,6 +395,7 @@ impl<'d> I2s<'d, Blocking> {
                 tx_channel: self.i2s_tx.tx_channel.into_async(),
                 guard: self.i2s_tx.guard,
             },
+            sample_rate: self.sample_rate,
         }
     }
 }
@@ -1735,6 +1748,24 @@ mod private {
         numerator: u32,
     }
 
+    impl I2sClockDividers {
+        /// Returns the sample rate these dividers produce.
+        pub fn sample_rate(&self, channels: u8, data_bits: u8) -> Rate {
+            let sclk = crate::soc::constants::I2S_SCLK as u64;
+
+            // MCLK = SCLK / (mclk_divider + numerator / denominator)
+            let mclk = if self.denominator == 0 {
+                sclk / self.mclk_divider as u64
+            } else {
+                sclk * self.denominator as u64
+                    / (self.mclk_divider as u64 * self.denominator as u64 + self.numerator as u64)
+            };
+            let bclk = mclk / self.bclk_divider as u64;
+
+            Rate::from_hz((bclk / (channels as u64 * data_bits as u64)) as u32)
+        }
+    }
+
     pub fn calculate_clock(sample_rate: Rate, channels: u8, data_bits: u8) -> I2sClockDividers {
         // this loosely corresponds to `i2s_std_calculate_clock` and
         // `i2s_ll_tx_set_mclk` in esp-idf
@@ -1783,7 +1814,7 @@ mod private {
 
                     if mb.abs_diff(ma) < min {
                         denominator = a as u32;
-                        numerator = b as u32;
+                        numerator = (b / 10000) as u32;
                         min = mb.abs_diff(ma);
                     }
                 }
@@ -57,7 +57,15 @@ use core::{
 use crate::{
     Blocking,
     clock::Clocks,
-    dma::{ChannelRx, DmaError, DmaPeripheral, DmaRxBuffer, PeripheralRxChannel, RxChannelFor},
+    dma::{
+        ChannelRx,
+        DmaError,
+        DmaPeripheral,
+        DmaRxBuf,
+        DmaRxBuffer,
+        PeripheralRxChannel,
+        RxChannelFor,
+    },
     gpio::{
         InputConfig,
         InputSignal,
@@ -394,11 +402,7 @@ impl<'d> Camera<'d> {
         self.with_data_pin(InputSignal::CAM_DATA_15, pin)
     }
 
-    /// Starts a DMA transfer to receive data from the camera peripheral.
-    pub fn receive<BUF: DmaRxBuffer>(
-        mut self,
-        mut buf: BUF,
-    ) -> Result<CameraTransfer<'d, BUF>, (DmaError, Self, BUF)> {
+    fn reset_unit(&self) {
         // Reset Camera control unit and Async Rx FIFO
         self.regs()
             .cam_ctrl1()
@@ -412,6 +416,118 @@ impl<'d> Camera<'d> {
         self.regs()
             .cam_ctrl1()
             .modify(|_, w| w.cam_afifo_reset().clear_bit());
+    }
+
+    fn start_unit(&self) {
+        // Start the Camera unit to listen for incoming DVP stream.
+        self.regs().cam_ctrl().modify(|_, w| {
+            // Automatically stops the camera unit once the GDMA Rx FIFO is full.
+            w.cam_stop_en().set_bit();
+
+            w.cam_update().set_bit()
+        });
+        self.regs()
+            .cam_ctrl1()
+            .modify(|_, w| w.cam_start().set_bit());
+    }
+
+    fn is_unit_running(&self) -> bool {
+        self.regs().cam_ctrl1().read().cam_start().bit_is_set()
+    }
+
+    #[procmacros::doc_replace]
+    /// Captures a single frame into `buf`, and returns the number of bytes
+    /// received.
+    ///
+    /// The capture starts at the next VSYNC pulse, so that the buffer holds a
+    /// complete frame, and ends when the DMA receives a SUC_EOF as configured
+    /// by [`Config::with_eof_mode`]. With the default
+    /// [`EofMode::VsyncSignal`], this is the VSYNC pulse that starts the
+    /// following frame. This function blocks until the frame has been
+    /// captured.
+    ///
+    /// ## Errors
+    ///
+    /// - [`DmaError::BufferTooSmall`] if the frame doesn't fit into `buf`. The buffer contains the
+    ///   beginning of the frame.
+    /// - [`DmaError::DescriptorError`] if the DMA reported an error.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// # use esp_hal::lcd_cam::{cam::{Camera, Config}, LcdCam};
+    /// use esp_hal::{dma::DmaRxBuf, dma_buffers};
+    ///
+    /// // A 160x120 frame with 2 bytes per pixel.
+    /// let (rx_buffer, rx_descriptors, _, _) = dma_buffers!(160 * 120 * 2, 0);
+    /// let mut frame = DmaRxBuf::new(rx_descriptors, rx_buffer)?;
+    ///
+    /// let lcd_cam = LcdCam::new(peripherals.LCD_CAM);
+    /// let mut camera = Camera::new(lcd_cam.cam, peripherals.DMA_CH0, Config::default())?
+    ///     .with_pixel_clock(peripherals.GPIO13)
+    ///     .with_vsync(peripherals.GPIO6)
+    ///     .with_h_enable(peripherals.GPIO7)
+    ///     .with_data0(peripherals.GPIO11)
+    ///     .with_data1(peripherals.GPIO9)
+    ///     .with_data2(peripherals.GPIO8)
+    ///     .with_data3(peripherals.GPIO10)
+    ///     .with_data4(peripherals.GPIO12)
+    ///     .with_data5(peripherals.GPIO18)
+    ///     .with_data6(peripherals.GPIO17)
+    ///     .with_data7(peripherals.GPIO16);
+    ///
+    /// let len = camera.capture_frame(&mut frame)?;
+    /// let image = &frame.as_slice()[..len];
+    /// # {after_snippet}
+    /// ```
+    pub fn capture_frame(&mut self, buf: &mut DmaRxBuf) -> Result<usize, DmaError> {
+        self.reset_unit();
+
+        // Wait for the start of the next frame.
+        self.regs()
+            .lc_dma_int_clr()
+            .write(|w| w.cam_vsync_int_clr().set_bit());
+        while self
+            .regs()
+            .lc_dma_int_raw()
+            .read()
+            .cam_vsync_int_raw()
+            .bit_is_clear()
+        {}
+
+        self.rx_channel.clear_interrupts();
+        unsafe {
+            self.rx_channel
+                .prepare_transfer(DmaPeripheral::LcdCam, buf)?;
+        }
+        self.rx_channel.start_transfer()?;
+        self.start_unit();
+
+        // The camera unit stops by itself if the buffer is full before the end of the frame.
+        while !self.rx_channel.is_done() && self.is_unit_running() {}
+        let frame_complete = self.rx_channel.is_done();
+
+        self.regs()
+            .cam_ctrl1()
+            .modify(|_, w| w.cam_start().clear_bit());
+        self.rx_channel.stop_transfer();
+
+        if self.rx_channel.has_error() {
+            Err(DmaError::DescriptorError)
+        } else if !frame_complete {
+            Err(DmaError::BufferTooSmall)
+        } else {
+            Ok(buf.number_of_received_bytes())
+        }
+    }
+
+    /// Starts a DMA transfer to receive data from the camera peripheral.
+    pub fn receive<BUF: DmaRxBuffer>(
+        mut self,
+        mut buf: BUF,
+    ) -> Result<CameraTransfer<'d, BUF>, (DmaError, Self, BUF)> {
+        self.reset_unit();
 
         // Start DMA to receive incoming transfer.
         let result = unsafe {
@@ -424,16 +540,7 @@ impl<'d> Camera<'d> {
             return Err((e, self, buf));
         }
 
-        // Start the Camera unit to listen for incoming DVP stream.
-        self.regs().cam_ctrl().modify(|_, w| {
-            // Automatically stops the camera unit once the GDMA Rx FIFO is full.
-            w.cam_stop_en().set_bit();
-
-            w.cam_update().set_bit()
-        });
-        self.regs()
-            .cam_ctrl1()
-            .modify(|_, w| w.cam_start().set_bit());
+        self.start_unit();
 
         Ok(CameraTransfer {
             camera: ManuallyDrop::new(self),
@@ -468,12 +575,7 @@ impl<'d, BUF: DmaRxBuffer> CameraTransfer<'d, BUF> {
         // For now, most people probably want this behaviour, so it shall be kept for
         // the sake of familiarity and similarity with other drivers.
 
-        self.camera
-            .regs()
-            .cam_ctrl1()
-            .read()
-            .cam_start()
-            .bit_is_clear()
+        !self.camera.is_unit_running()
     }
 
     /// Stops this transfer on the spot and returns the peripheral and buffer.
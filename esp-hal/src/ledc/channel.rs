@@ -9,6 +9,8 @@
 //! `Pulse-Width Modulation (PWM)` applications by offering configurable duty
 //! cycles and frequencies.
 
+use portable_atomic::{AtomicU16, Ordering};
+
 use super::timer::{TimerIFace, TimerSpeed};
 use crate::{
     gpio::{
@@ -49,6 +51,14 @@ pub enum Error {
     Fade(FadeError),
 }
 
+/// Channels whose fade-end interrupt has been cleared after their last fade
+/// completed.
+///
+/// The hardware uses the same flag to report the end of a fade and to raise
+/// the interrupt, so clearing the interrupt would make the fade look like it
+/// is still running.
+static FADE_ENDED: AtomicU16 = AtomicU16::new(0);
+
 /// Channel number
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -148,6 +158,122 @@ pub trait ChannelHW {
     fn is_duty_fade_running_hw(&self) -> bool;
 }
 
+/// The fade-end interrupt of a channel.
+///
+/// Obtained from [`Channel::fade_end_interrupt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub struct FadeEndInterrupt {
+    number: Number,
+    high_speed: bool,
+}
+
+impl FadeEndInterrupt {
+    /// Returns whether the interrupt is enabled and pending.
+    pub fn is_set(&self) -> bool {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                let st = LEDC::regs().int_st().read();
+                if self.high_speed {
+                    st.duty_chng_end_hsch(self.number as u8).bit_is_set()
+                } else {
+                    st.duty_chng_end_lsch(self.number as u8).bit_is_set()
+                }
+            } else {
+                LEDC::regs()
+                    .int_st()
+                    .read()
+                    .duty_chng_end_ch(self.number as u8)
+                    .bit_is_set()
+            }
+        }
+    }
+
+    /// Clears the interrupt.
+    ///
+    /// [`ChannelIFace::is_duty_fade_running`] keeps reporting the completed
+    /// fade as done.
+    pub fn clear(&self) {
+        if self.is_raw_set() {
+            FADE_ENDED.fetch_or(self.mask(), Ordering::Relaxed);
+        }
+        self.clear_raw();
+    }
+
+    fn set_enabled(&self, enable: bool) {
+        critical_section::with(|_| {
+            LEDC::regs().int_ena().modify(|_, w| {
+                cfg_if::cfg_if! {
+                    if #[cfg(esp32)] {
+                        if self.high_speed {
+                            w.duty_chng_end_hsch(self.number as u8).bit(enable)
+                        } else {
+                            w.duty_chng_end_lsch(self.number as u8).bit(enable)
+                        }
+                    } else {
+                        w.duty_chng_end_ch(self.number as u8).bit(enable)
+                    }
+                }
+            })
+        });
+    }
+
+    /// Returns whether the last fade has ended, and its end has not been
+    /// cleared yet.
+    fn is_raw_set(&self) -> bool {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                let raw = LEDC::regs().int_raw().read();
+                if self.high_speed {
+                    raw.duty_chng_end_hsch(self.number as u8).bit_is_set()
+                } else {
+                    raw.duty_chng_end_lsch(self.number as u8).bit_is_set()
+                }
+            } else {
+                LEDC::regs()
+                    .int_raw()
+                    .read()
+                    .duty_chng_end_ch(self.number as u8)
+                    .bit_is_set()
+            }
+        }
+    }
+
+    fn clear_raw(&self) {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                LEDC::regs().int_clr().write(|w| {
+                    if self.high_speed {
+                        w.duty_chng_end_hsch(self.number as u8).clear_bit_by_one()
+                    } else {
+                        w.duty_chng_end_lsch(self.number as u8).clear_bit_by_one()
+                    }
+                });
+            } else {
+                LEDC::regs()
+                    .int_clr()
+                    .write(|w| w.duty_chng_end_ch(self.number as u8).clear_bit_by_one());
+            }
+        }
+    }
+
+    fn mask(&self) -> u16 {
+        let offset = if self.high_speed { 8 } else { 0 };
+        1 << (self.number as u16 + offset)
+    }
+
+    /// Forgets the end of the previous fade, before a new one is started.
+    fn reset(&self) {
+        self.clear_raw();
+        FADE_ENDED.fetch_and(!self.mask(), Ordering::Relaxed);
+    }
+
+    fn is_fade_running(&self) -> bool {
+        !self.is_raw_set() && FADE_ENDED.load(Ordering::Relaxed) & self.mask() == 0
+    }
+}
+
 /// Channel struct
 pub struct Channel<'a, S: TimerSpeed> {
     ledc: &'a RegisterBlock,
@@ -167,6 +293,41 @@ impl<'a, S: TimerSpeed> Channel<'a, S> {
             output_pin: output_pin.into(),
         }
     }
+
+    /// Enables the fade-end interrupt of this channel.
+    ///
+    /// The interrupt fires when a fade started by
+    /// [`ChannelIFace::start_duty_fade`] completes. Register a handler with
+    /// [`Ledc::set_interrupt_handler`](super::Ledc::set_interrupt_handler),
+    /// and clear the interrupt in it through the [`FadeEndInterrupt`]
+    /// returned by [`Self::fade_end_interrupt`], otherwise the handler is
+    /// called again immediately.
+    ///
+    /// The hardware applies a new duty through a single-step fade, so setting
+    /// the duty raises this interrupt, too. Enable it after starting a fade
+    /// to only be notified about fades.
+    #[instability::unstable]
+    pub fn listen_fade_end(&self) {
+        self.fade_end_interrupt().set_enabled(true);
+    }
+
+    /// Disables the fade-end interrupt of this channel.
+    #[instability::unstable]
+    pub fn unlisten_fade_end(&self) {
+        self.fade_end_interrupt().set_enabled(false);
+    }
+
+    /// Returns a handle to the fade-end interrupt of this channel.
+    ///
+    /// Unlike the channel, the handle can be moved into an interrupt
+    /// handler.
+    #[instability::unstable]
+    pub fn fade_end_interrupt(&self) -> FadeEndInterrupt {
+        FadeEndInterrupt {
+            number: self.number,
+            high_speed: S::IS_HS,
+        }
+    }
 }
 
 impl<'a, S: TimerSpeed> ChannelIFace<'a, S> for Channel<'a, S>
@@ -668,18 +829,13 @@ where
                 .hsch(self.number as usize)
                 .duty()
                 .write(|w| unsafe { w.duty().bits(start_duty << 4) });
-            self.ledc
-                .int_clr()
-                .write(|w| w.duty_chng_end_hsch(self.number as u8).clear_bit_by_one());
         } else {
             self.ledc
                 .lsch(self.number as usize)
                 .duty()
                 .write(|w| unsafe { w.duty().bits(start_duty << 4) });
-            self.ledc
-                .int_clr()
-                .write(|w| w.duty_chng_end_lsch(self.number as u8).clear_bit_by_one());
         }
+        self.fade_end_interrupt().reset();
         self.start_duty_fade_inner(duty_inc, duty_steps, cycles_per_step, duty_per_cycle);
         self.update_channel();
     }
@@ -698,29 +854,12 @@ where
             .ch(self.number as usize)
             .duty()
             .write(|w| unsafe { w.duty().bits(start_duty << 4) });
-        self.ledc
-            .int_clr()
-            .write(|w| w.duty_chng_end_ch(self.number as u8).clear_bit_by_one());
+        self.fade_end_interrupt().reset();
         self.start_duty_fade_inner(duty_inc, duty_steps, cycles_per_step, duty_per_cycle);
         self.update_channel();
     }
 
-    #[cfg(esp32)]
     fn is_duty_fade_running_hw(&self) -> bool {
-        let reg = self.ledc.int_raw().read();
-        if S::IS_HS {
-            reg.duty_chng_end_hsch(self.number as u8).bit_is_clear()
-        } else {
-            reg.duty_chng_end_lsch(self.number as u8).bit_is_clear()
-        }
-    }
-
-    #[cfg(not(esp32))]
-    fn is_duty_fade_running_hw(&self) -> bool {
-        self.ledc
-            .int_raw()
-            .read()
-            .duty_chng_end_ch(self.number as u8)
-            .bit_is_clear()
+        self.fade_end_interrupt().is_fade_running()
     }
 }
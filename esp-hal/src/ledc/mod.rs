@@ -64,7 +64,8 @@
 //!
 //! ## Implementation State
 //! - Source clock selection is not supported
-//! - Interrupts are not supported
+//! - Only the fade-end interrupt is supported, see
+//!   [`Channel::listen_fade_end`](channel::Channel::listen_fade_end)
 
 use self::{
     channel::Channel,
@@ -72,8 +73,9 @@ use self::{
 };
 use crate::{
     gpio::interconnect::PeripheralOutput,
+    interrupt::{self, InterruptHandler},
     pac,
-    peripherals::LEDC,
+    peripherals::{Interrupt, LEDC},
     system::{Peripheral as PeripheralEnable, PeripheralClockControl},
 };
 
@@ -180,4 +182,26 @@ impl<'d> Ledc<'d> {
     ) -> Channel<'d, S> {
         Channel::new(number, output_pin)
     }
+
+    /// Set the interrupt handler for the LEDC peripheral.
+    ///
+    /// Note that this will replace any previously registered interrupt
+    /// handlers.
+    #[instability::unstable]
+    pub fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        for core in crate::system::Cpu::other() {
+            crate::interrupt::disable(core, Interrupt::LEDC);
+        }
+        unsafe { interrupt::bind_interrupt(Interrupt::LEDC, handler.handler()) };
+        unwrap!(interrupt::enable(Interrupt::LEDC, handler.priority()));
+    }
+}
+
+impl crate::private::Sealed for Ledc<'_> {}
+
+#[instability::unstable]
+impl crate::interrupt::InterruptConfigurable for Ledc<'_> {
+    fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        self.set_interrupt_handler(handler);
+    }
 }
@@ -366,6 +366,8 @@ unstable_driver! {
     pub mod rsa;
     #[cfg(soc_has_sha)]
     pub mod sha;
+    #[cfg(timergroup)]
+    pub mod soft_pwm;
     #[cfg(touch)]
     pub mod touch;
     #[cfg(soc_has_trace0)]
@@ -0,0 +1,210 @@
+//! # MCPWM Fault Detection Module
+//!
+//! ## Overview
+//! The fault detection module monitors up to three fault input signals, for
+//! example the over-current output of a gate driver. Each operator can be
+//! configured to respond to a fault by forcing its outputs to a safe level,
+//! see [`Operator::set_fault_response`].
+//!
+//! An operator can handle a fault in one of two modes:
+//! * Cycle-by-cycle: the outputs are forced while the fault is present, and the operator resumes
+//!   normal operation at the start of the next PWM period once the fault is gone.
+//! * One-shot: the outputs are forced until the fault is cleared by software, even if the fault
+//!   signal is no longer active.
+//!
+//! [`Operator::set_fault_response`]: super::operator::Operator::set_fault_response
+
+use core::marker::PhantomData;
+
+use super::PeripheralGuard;
+use crate::{
+    gpio::{Level, interconnect::PeripheralInput},
+    mcpwm::{PwmPeripheral, operator::PWMStream},
+};
+
+/// A MCPWM fault detector
+///
+/// Monitors the fault input signal `FAULTx`.
+pub struct FaultDetector<'d, const F: u8, PWM> {
+    phantom: PhantomData<&'d PWM>,
+    _guard: PeripheralGuard,
+}
+
+impl<'d, const F: u8, PWM: PwmPeripheral> FaultDetector<'d, F, PWM> {
+    pub(super) fn new() -> Self {
+        let guard = PeripheralGuard::new(PWM::peripheral());
+
+        FaultDetector {
+            phantom: PhantomData,
+            _guard: guard,
+        }
+    }
+
+    /// Connect the fault signal to the given input and enable the detector
+    ///
+    /// A fault is present while the input is at `active_level`.
+    pub fn enable(&mut self, input: impl PeripheralInput<'d>, active_level: Level) {
+        let input = input.into();
+        input.set_input_enable(true);
+        PWM::fault_signal::<F>().connect_to(&input);
+
+        let active_high = active_level == Level::High;
+
+        // SAFETY:
+        // We only write to our FAULT_DETECT.Fx_EN and FAULT_DETECT.Fx_POLE bits
+        let block = unsafe { &*PWM::block() };
+        // The register is shared with the other fault detectors.
+        critical_section::with(|_| {
+            block.fault_detect().modify(|_, w| match F {
+                0 => w.f0_pole().bit(active_high).f0_en().set_bit(),
+                1 => w.f1_pole().bit(active_high).f1_en().set_bit(),
+                2 => w.f2_pole().bit(active_high).f2_en().set_bit(),
+                _ => unreachable!(),
+            });
+        });
+    }
+
+    /// Disable the detector
+    ///
+    /// The fault is no longer reported to the operators.
+    pub fn disable(&mut self) {
+        // SAFETY:
+        // We only write to our FAULT_DETECT.Fx_EN bit
+        let block = unsafe { &*PWM::block() };
+        // The register is shared with the other fault detectors.
+        critical_section::with(|_| {
+            block.fault_detect().modify(|_, w| match F {
+                0 => w.f0_en().clear_bit(),
+                1 => w.f1_en().clear_bit(),
+                2 => w.f2_en().clear_bit(),
+                _ => unreachable!(),
+            });
+        });
+    }
+
+    /// Returns whether a fault is currently present
+    pub fn is_active(&self) -> bool {
+        // SAFETY:
+        // We only read our FAULT_DETECT.EVENT_Fx bit
+        let block = unsafe { &*PWM::block() };
+        let fault_detect = block.fault_detect().read();
+        match F {
+            0 => fault_detect.event_f0().bit_is_set(),
+            1 => fault_detect.event_f1().bit_is_set(),
+            2 => fault_detect.event_f2().bit_is_set(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// A source of faults an operator can respond to
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FaultSource {
+    /// The fault detected by [`FaultDetector`] 0
+    Fault0,
+    /// The fault detected by [`FaultDetector`] 1
+    Fault1,
+    /// The fault detected by [`FaultDetector`] 2
+    Fault2,
+    /// A fault triggered by software
+    Software,
+}
+
+/// How an operator handles a fault
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FaultMode {
+    /// The fault is ignored
+    Disabled,
+    /// The outputs are forced while the fault is present, and recover at the
+    /// start of the next PWM period after the fault is gone
+    CycleByCycle,
+    /// The outputs are forced until the fault is cleared by software
+    OneShot,
+}
+
+/// The level an output is forced to on a fault
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u32)]
+pub enum FaultAction {
+    /// The output is not changed
+    None   = 0,
+    /// The output is forced low
+    Low    = 1,
+    /// The output is forced high
+    High   = 2,
+    /// The output is toggled
+    Toggle = 3,
+}
+
+/// Configuration of how an operator responds to faults
+///
+/// By default, all faults are ignored.
+#[derive(Copy, Clone)]
+pub struct FaultResponse {
+    pub(super) cfg_reg: u32,
+}
+
+impl FaultResponse {
+    /// One-shot enable bits, shifted by the [`FaultSource`]
+    const OST: u32 = 1 << 4;
+    /// Cycle-by-cycle enable bits, shifted by the [`FaultSource`]
+    const CBC: u32 = 1 << 0;
+    /// Offsets of the CBC_D, CBC_U, OST_D and OST_U action fields of output A
+    const ACTIONS_A: [u32; 4] = [8, 10, 12, 14];
+    /// Offsets of the CBC_D, CBC_U, OST_D and OST_U action fields of output B
+    const ACTIONS_B: [u32; 4] = [16, 18, 20, 22];
+
+    /// A configuration that ignores all faults
+    pub const fn new() -> Self {
+        FaultResponse { cfg_reg: 0 }
+    }
+
+    /// Set how the operator handles faults of the given source
+    #[must_use]
+    pub const fn with_mode(mut self, source: FaultSource, mode: FaultMode) -> Self {
+        // The bits are ordered SW, F2, F1, F0
+        let shift = match source {
+            FaultSource::Software => 0,
+            FaultSource::Fault2 => 1,
+            FaultSource::Fault1 => 2,
+            FaultSource::Fault0 => 3,
+        };
+
+        self.cfg_reg &= !((Self::CBC | Self::OST) << shift);
+        match mode {
+            FaultMode::Disabled => {}
+            FaultMode::CycleByCycle => self.cfg_reg |= Self::CBC << shift,
+            FaultMode::OneShot => self.cfg_reg |= Self::OST << shift,
+        }
+        self
+    }
+
+    /// Set the level the given output is forced to while a fault is handled
+    ///
+    /// The action applies to both fault modes, regardless of the direction
+    /// the timer counts in.
+    #[must_use]
+    pub const fn with_action(mut self, stream: PWMStream, action: FaultAction) -> Self {
+        let offsets = match stream {
+            PWMStream::PWMA => Self::ACTIONS_A,
+            PWMStream::PWMB => Self::ACTIONS_B,
+        };
+
+        let mut i = 0;
+        while i < offsets.len() {
+            self.cfg_reg &= !(0b11 << offsets[i]);
+            self.cfg_reg |= (action as u32) << offsets[i];
+            i += 1;
+        }
+        self
+    }
+}
+
+impl Default for FaultResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -43,7 +43,10 @@
 //!       insulated with a transformer. (Not yet implemented)
 //!     * Period, time stamps and important control registers have shadow registers with flexible
 //!       updating methods.
-//! * Fault Detection Module (Not yet implemented)
+//! * Fault Detection Module
+//!     * Three fault inputs, with configurable polarity.
+//!     * Every PWM operator can force its outputs to a safe level on a fault, either cycle-by-cycle
+//!       or until cleared by software (one-shot).
 //! * Capture Module (Not yet implemented)
 //!
 //! # {clock_src}
@@ -83,17 +86,20 @@
 //! # {after_snippet}
 //! ```
 
+use fault::FaultDetector;
 use operator::Operator;
 use timer::Timer;
 
 use crate::{
     clock::Clocks,
-    gpio::OutputSignal,
+    gpio::{InputSignal, OutputSignal},
     pac,
     system::{self, PeripheralGuard},
     time::Rate,
 };
 
+/// MCPWM fault detection
+pub mod fault;
 /// MCPWM operators
 pub mod operator;
 /// MCPWM timers
@@ -117,6 +123,12 @@ pub struct McPwm<'d, PWM> {
     pub operator1: Operator<'d, 1, PWM>,
     /// Operator2
     pub operator2: Operator<'d, 2, PWM>,
+    /// Fault detector 0
+    pub fault0: FaultDetector<'d, 0, PWM>,
+    /// Fault detector 1
+    pub fault1: FaultDetector<'d, 1, PWM>,
+    /// Fault detector 2
+    pub fault2: FaultDetector<'d, 2, PWM>,
     _guard: PeripheralGuard,
 }
 
@@ -175,6 +187,9 @@ impl<'d, PWM: PwmPeripheral + 'd> McPwm<'d, PWM> {
             operator0: Operator::new(),
             operator1: Operator::new(),
             operator2: Operator::new(),
+            fault0: FaultDetector::new(),
+            fault1: FaultDetector::new(),
+            fault2: FaultDetector::new(),
             _guard: guard,
         }
     }
@@ -312,6 +327,8 @@ pub trait PwmPeripheral: crate::private::Sealed {
     fn block() -> *const RegisterBlock;
     /// Get operator GPIO mux output signal
     fn output_signal<const OP: u8, const IS_A: bool>() -> OutputSignal;
+    /// Get fault detector GPIO mux input signal
+    fn fault_signal<const F: u8>() -> InputSignal;
     /// Peripheral
     fn peripheral() -> system::Peripheral;
 }
@@ -334,6 +351,15 @@ impl PwmPeripheral for crate::peripherals::MCPWM0<'_> {
         }
     }
 
+    fn fault_signal<const F: u8>() -> InputSignal {
+        match F {
+            0 => InputSignal::PWM0_F0,
+            1 => InputSignal::PWM0_F1,
+            2 => InputSignal::PWM0_F2,
+            _ => unreachable!(),
+        }
+    }
+
     fn peripheral() -> system::Peripheral {
         system::Peripheral::Mcpwm0
     }
@@ -357,6 +383,15 @@ impl PwmPeripheral for crate::peripherals::MCPWM1<'_> {
         }
     }
 
+    fn fault_signal<const F: u8>() -> InputSignal {
+        match F {
+            0 => InputSignal::PWM1_F0,
+            1 => InputSignal::PWM1_F1,
+            2 => InputSignal::PWM1_F2,
+            _ => unreachable!(),
+        }
+    }
+
     fn peripheral() -> system::Peripheral {
         system::Peripheral::Mcpwm1
     }
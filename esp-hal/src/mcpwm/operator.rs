@@ -14,7 +14,7 @@ use core::marker::PhantomData;
 use super::PeripheralGuard;
 use crate::{
     gpio::interconnect::{OutputSignal, PeripheralOutput},
-    mcpwm::{PwmPeripheral, timer::Timer},
+    mcpwm::{PwmPeripheral, fault::FaultResponse, timer::Timer},
     pac,
 };
 
@@ -166,7 +166,7 @@ impl DeadTimeCfg {
 /// * Each signal out of the PWM signal pair includes a specific pattern of dead time. (Not yet
 ///   implemented)
 /// * Superimposes a carrier on the PWM signal, if configured to do so. (Not yet implemented)
-/// * Handles response under fault conditions. (Not yet implemented)
+/// * Handles response under fault conditions.
 pub struct Operator<'d, const OP: u8, PWM> {
     phantom: PhantomData<&'d PWM>,
     _guard: PeripheralGuard,
@@ -207,6 +207,26 @@ impl<'d, const OP: u8, PWM: PwmPeripheral> Operator<'d, OP, PWM> {
         });
     }
 
+    /// Configure how the operator responds to faults
+    ///
+    /// See the [fault module](super::fault) for details.
+    pub fn set_fault_response(&mut self, response: FaultResponse) {
+        // SAFETY:
+        // We only write to our FH_CFGx registers
+        let ch = unsafe { &*PWM::block() }.ch(OP as usize);
+
+        #[cfg(esp32s3)]
+        let (fh_cfg0, fh_cfg1) = (ch.tz_cfg0(), ch.tz_cfg1());
+        #[cfg(not(esp32s3))]
+        let (fh_cfg0, fh_cfg1) = (ch.fh_cfg0(), ch.fh_cfg1());
+
+        // SAFETY:
+        // `cfg_reg` is a valid bit pattern
+        fh_cfg0.write(|w| unsafe { w.bits(response.cfg_reg) });
+        // Re-evaluate cycle-by-cycle faults when the timer reaches zero or its period
+        fh_cfg1.modify(|_, w| unsafe { w.cbcpulse().bits(0b11) });
+    }
+
     /// Use the A output with the given pin and configuration
     pub fn with_pin_a(
         self,
@@ -411,6 +431,94 @@ impl<'d, PWM: PwmPeripheral, const OP: u8, const IS_A: bool> PwmPin<'d, PWM, OP,
         block.timer(tim as usize).cfg0().read().period().bits()
     }
 
+    /// Returns whether the operator is handling a one-shot fault.
+    ///
+    /// Both outputs of an operator share the fault handling.
+    pub fn is_one_shot_fault_active(&self) -> bool {
+        // SAFETY:
+        // We only read our FH_STATUS register
+        let ch = unsafe { Self::ch() };
+
+        #[cfg(esp32s3)]
+        let status = ch.tz_status();
+        #[cfg(not(esp32s3))]
+        let status = ch.fh_status();
+        status.read().ost_on().bit_is_set()
+    }
+
+    /// Returns whether the operator is handling a cycle-by-cycle fault.
+    ///
+    /// Both outputs of an operator share the fault handling.
+    pub fn is_cycle_by_cycle_fault_active(&self) -> bool {
+        // SAFETY:
+        // We only read our FH_STATUS register
+        let ch = unsafe { Self::ch() };
+
+        #[cfg(esp32s3)]
+        let status = ch.tz_status();
+        #[cfg(not(esp32s3))]
+        let status = ch.fh_status();
+        status.read().cbc_on().bit_is_set()
+    }
+
+    /// Clear a one-shot fault, resuming normal operation of both outputs.
+    ///
+    /// The fault is only cleared if its cause is no longer present.
+    pub fn clear_one_shot_fault(&mut self) {
+        // SAFETY:
+        // We only write to our FH_CFG1 register
+        let ch = unsafe { Self::ch() };
+
+        #[cfg(esp32s3)]
+        let fh_cfg1 = ch.tz_cfg1();
+        #[cfg(not(esp32s3))]
+        let fh_cfg1 = ch.fh_cfg1();
+
+        // A rising edge clears the fault
+        fh_cfg1.modify(|_, w| w.clr_ost().set_bit());
+        fh_cfg1.modify(|_, w| w.clr_ost().clear_bit());
+    }
+
+    /// Trigger a one-shot fault by software.
+    ///
+    /// Has no effect unless [`FaultSource::Software`] is configured as a
+    /// one-shot fault in the operator's [`FaultResponse`].
+    ///
+    /// [`FaultSource::Software`]: crate::mcpwm::fault::FaultSource::Software
+    pub fn trigger_one_shot_fault(&mut self) {
+        // SAFETY:
+        // We only write to our FH_CFG1 register
+        let ch = unsafe { Self::ch() };
+
+        #[cfg(esp32s3)]
+        let fh_cfg1 = ch.tz_cfg1();
+        #[cfg(not(esp32s3))]
+        let fh_cfg1 = ch.fh_cfg1();
+
+        // Toggling the bit triggers the fault
+        fh_cfg1.modify(|r, w| w.force_ost().bit(!r.force_ost().bit()));
+    }
+
+    /// Trigger a cycle-by-cycle fault by software.
+    ///
+    /// Has no effect unless [`FaultSource::Software`] is configured as a
+    /// cycle-by-cycle fault in the operator's [`FaultResponse`].
+    ///
+    /// [`FaultSource::Software`]: crate::mcpwm::fault::FaultSource::Software
+    pub fn trigger_cycle_by_cycle_fault(&mut self) {
+        // SAFETY:
+        // We only write to our FH_CFG1 register
+        let ch = unsafe { Self::ch() };
+
+        #[cfg(esp32s3)]
+        let fh_cfg1 = ch.tz_cfg1();
+        #[cfg(not(esp32s3))]
+        let fh_cfg1 = ch.fh_cfg1();
+
+        // Toggling the bit triggers the fault
+        fh_cfg1.modify(|r, w| w.force_cbc().bit(!r.force_cbc().bit()));
+    }
+
     unsafe fn ch() -> &'static pac::mcpwm0::CH {
         let block = unsafe { &*PWM::block() };
         block.ch(OP as usize)
@@ -536,7 +644,7 @@ impl<'d, PWM: PwmPeripheral, const OP: u8> LinkedPins<'d, PWM, OP> {
     /// The written value will take effect according to the set
     /// [`PwmUpdateMethod`].
     pub fn set_timestamp_b(&mut self, value: u16) {
-        self.pin_a.set_timestamp(value)
+        self.pin_b.set_timestamp(value)
     }
 
     /// Configure the deadtime generator
@@ -565,6 +673,37 @@ impl<'d, PWM: PwmPeripheral, const OP: u8> LinkedPins<'d, PWM, OP> {
         dt_fed.write(|w| unsafe { w.fed().bits(dead_time) });
     }
 
+    /// Returns whether the operator is handling a one-shot fault.
+    pub fn is_one_shot_fault_active(&self) -> bool {
+        self.pin_a.is_one_shot_fault_active()
+    }
+
+    /// Returns whether the operator is handling a cycle-by-cycle fault.
+    pub fn is_cycle_by_cycle_fault_active(&self) -> bool {
+        self.pin_a.is_cycle_by_cycle_fault_active()
+    }
+
+    /// Clear a one-shot fault, resuming normal operation of both outputs.
+    ///
+    /// The fault is only cleared if its cause is no longer present.
+    pub fn clear_one_shot_fault(&mut self) {
+        self.pin_a.clear_one_shot_fault()
+    }
+
+    /// Trigger a one-shot fault by software.
+    ///
+    /// See [`PwmPin::trigger_one_shot_fault`].
+    pub fn trigger_one_shot_fault(&mut self) {
+        self.pin_a.trigger_one_shot_fault()
+    }
+
+    /// Trigger a cycle-by-cycle fault by software.
+    ///
+    /// See [`PwmPin::trigger_cycle_by_cycle_fault`].
+    pub fn trigger_cycle_by_cycle_fault(&mut self) {
+        self.pin_a.trigger_cycle_by_cycle_fault()
+    }
+
     unsafe fn ch() -> &'static pac::mcpwm0::CH {
         let block = unsafe { &*PWM::block() };
         block.ch(OP as usize)
@@ -27,7 +27,11 @@ impl EndianessConverter for NativeEndianess {
     }
 }
 
-/// Use BE for ESP32, NE otherwise
+/// Use BE for ESP32, NE otherwise.
+///
+/// The crypto peripherals of the other chips lay out their registers so that
+/// native-endian words hold the bytes in the canonical order, e.g. the
+/// big-endian SHA digest.
 #[derive(Debug, Clone)]
 pub(crate) struct SocDependentEndianess;
 
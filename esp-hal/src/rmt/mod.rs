@@ -195,6 +195,13 @@
 //! ```
 //!
 //! > Note: on ESP32 and ESP32-S2 you cannot specify a base frequency other than 80 MHz
+//!
+//! ### 1-Wire
+//!
+//! A 1-Wire bus master built on a pair of RMT channels is available in the
+//! [`onewire`] module.
+
+pub mod onewire;
 
 use core::{
     default::Default,
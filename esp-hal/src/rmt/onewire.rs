@@ -0,0 +1,526 @@
+#![cfg_attr(docsrs, procmacros::doc_replace(
+    "channels" => {
+        cfg(any(esp32, esp32s2)) => "let (tx, rx) = (rmt.channel0, rmt.channel1);",
+        cfg(esp32s3) => "let (tx, rx) = (rmt.channel0, rmt.channel4);",
+        _ => "let (tx, rx) = (rmt.channel0, rmt.channel2);"
+    },
+    "freq" => {
+        cfg(esp32h2) => "let (freq, divider) = (Rate::from_mhz(32), 32);",
+        _ => "let (freq, divider) = (Rate::from_mhz(80), 80);"
+    }
+))]
+//! # 1-Wire bus master
+//!
+//! ## Overview
+//!
+//! [`OneWire`] drives a Dallas/Maxim 1-Wire bus using a pair of RMT channels
+//! connected to the same open-drain pin: the TX channel generates the reset
+//! pulse and the time slots, while the RX channel samples the bus to detect
+//! presence pulses and the bits driven by the devices.
+//!
+//! Reset and presence detection, byte-level reads and writes and the ROM
+//! search algorithm are provided. Higher-level commands, for example those of
+//! a DS18B20 temperature sensor, are built on top of these.
+//!
+//! ## Timing
+//!
+//! The driver generates the standard-speed slot timings (a 480 µs reset pulse
+//! and ~70 µs read/write slots) and expects both channels to count one tick
+//! per microsecond. The RMT channel clock is the RMT source clock passed to
+//! [`Rmt::new`](super::Rmt::new) divided by the `clk_divider` passed to
+//! [`OneWire::new`]:
+//!
+//! | RMT source clock | `clk_divider` |
+//! |------------------|---------------|
+//! | 80 MHz           | 80            |
+//! | 32 MHz (ESP32-H2)| 32            |
+//!
+//! The bus needs a pull-up resistor, typically 4.7 kΩ. The internal pull-up
+//! of the GPIO is enabled, but is too weak for anything but very short wires.
+//!
+//! ## Example
+//!
+//! Reading the temperature of a single DS18B20 on the bus:
+//!
+//! ```rust, no_run
+//! # {before_snippet}
+//! # use esp_hal::{delay::Delay, rmt::{Rmt, onewire::OneWire}};
+//! # {freq}
+//! let rmt = Rmt::new(peripherals.RMT, freq)?;
+//! # {channels}
+//! let mut bus = OneWire::new(tx, rx, peripherals.GPIO4, divider)?;
+//!
+//! let address = bus.read_rom()?;
+//! assert_eq!(address.family_code(), 0x28);
+//!
+//! // Start a temperature conversion and wait for it to complete.
+//! bus.reset()?;
+//! bus.skip_rom()?;
+//! bus.write_bytes(&[0x44])?;
+//! Delay::new().delay_millis(750);
+//!
+//! // Read the scratchpad. The first two bytes hold the temperature in
+//! // 1/16 °C.
+//! bus.reset()?;
+//! bus.select(address)?;
+//! bus.write_bytes(&[0xBE])?;
+//! let mut scratchpad = [0u8; 9];
+//! bus.read_bytes(&mut scratchpad)?;
+//!
+//! let raw = i16::from_le_bytes([scratchpad[0], scratchpad[1]]);
+//! let celsius = raw as f32 / 16.0;
+//! # {after_snippet}
+//! ```
+
+use core::marker::PhantomData;
+
+use super::{
+    AnyRxChannel,
+    AnyTxChannel,
+    PulseCode,
+    RawChannelAccess,
+    Rx,
+    RxChannel,
+    RxChannelConfig,
+    RxChannelCreator,
+    RxChannelInternal,
+    Tx,
+    TxChannel,
+    TxChannelConfig,
+    TxChannelCreator,
+    TxChannelInternal,
+};
+use crate::{
+    Blocking,
+    gpio::{
+        DriveMode,
+        Level,
+        NoPin,
+        OutputConfig,
+        PinGuard,
+        Pull,
+        interconnect::{OutputSignal, PeripheralOutput},
+    },
+};
+
+// Standard speed timings, in microseconds.
+const RESET_LOW: u16 = 480;
+const SLOT_START: u16 = 6;
+const SLOT_ZERO_LOW: u16 = 60;
+const SLOT_DURATION: u16 = 70;
+// A device answering a read slot with a 0 holds the bus low past this point.
+const SLOT_SAMPLE: u16 = 15;
+// Long enough to cover the reset pulse, so that reception only ends once the
+// bus has been released for good.
+const IDLE_THRESHOLD: u16 = 500;
+
+const CMD_READ_ROM: u8 = 0x33;
+const CMD_MATCH_ROM: u8 = 0x55;
+const CMD_SKIP_ROM: u8 = 0xCC;
+const CMD_SEARCH_ROM: u8 = 0xF0;
+
+/// 1-Wire errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Error {
+    /// No device answered the reset pulse.
+    NoDevice,
+    /// The CRC of the received data does not match.
+    CrcMismatch,
+    /// The bus returned data that no device could have sent, e.g. during a
+    /// ROM search. This usually means the bus is shorted or lacks a pull-up.
+    InvalidResponse,
+    /// The RMT peripheral reported an error.
+    Rmt(super::Error),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::NoDevice => write!(f, "No device answered the reset pulse"),
+            Error::CrcMismatch => write!(f, "CRC mismatch"),
+            Error::InvalidResponse => write!(f, "Invalid data on the bus"),
+            Error::Rmt(e) => write!(f, "RMT error: {e:?}"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+impl From<super::Error> for Error {
+    fn from(error: super::Error) -> Self {
+        Error::Rmt(error)
+    }
+}
+
+/// The 64-bit ROM code of a 1-Wire device.
+///
+/// The family code is stored in the least significant byte and the CRC in the
+/// most significant byte, i.e. the byte order on the bus is little-endian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Address(pub u64);
+
+impl Address {
+    /// Returns the family code, which identifies the type of the device.
+    pub fn family_code(&self) -> u8 {
+        self.0 as u8
+    }
+
+    /// Returns the 48-bit serial number of the device.
+    pub fn serial_number(&self) -> u64 {
+        (self.0 >> 8) & 0xFFFF_FFFF_FFFF
+    }
+
+    fn from_bytes(bytes: [u8; 8]) -> Result<Self, Error> {
+        if crc8(&bytes) != 0 {
+            return Err(Error::CrcMismatch);
+        }
+
+        Ok(Self(u64::from_le_bytes(bytes)))
+    }
+}
+
+/// Computes the Dallas/Maxim CRC-8 of `data`.
+///
+/// Running the CRC over data that ends in its own CRC byte yields 0.
+pub fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0;
+    for byte in data {
+        let mut byte = *byte;
+        for _ in 0..8 {
+            let mix = (crc ^ byte) & 1;
+            crc >>= 1;
+            if mix != 0 {
+                crc ^= 0x8C;
+            }
+            byte >>= 1;
+        }
+    }
+    crc
+}
+
+/// A 1-Wire bus master using an RMT TX and RX channel.
+///
+/// See the [module-level documentation](self) for more details.
+pub struct OneWire<'d> {
+    tx: Option<AnyTxChannel<Blocking>>,
+    rx: Option<AnyRxChannel<Blocking>>,
+    _guard: PinGuard,
+    _lifetime: PhantomData<&'d mut ()>,
+}
+
+impl<'d> OneWire<'d> {
+    /// Creates a new 1-Wire bus master on `pin`.
+    ///
+    /// The pin is configured as an open-drain output with its input enabled,
+    /// and both channels are connected to it. `clk_divider` must divide the
+    /// RMT source clock down to 1 MHz, see the [module-level
+    /// documentation](self#timing).
+    pub fn new<TX, RX>(
+        tx: TX,
+        rx: RX,
+        pin: impl PeripheralOutput<'d>,
+        clk_divider: u8,
+    ) -> Result<Self, Error>
+    where
+        TX: TxChannelCreator<'d, Blocking>,
+        TX::Raw: RawChannelAccess<Dir = Tx>,
+        RX: RxChannelCreator<'d, Blocking>,
+        RX::Raw: RawChannelAccess<Dir = Rx>,
+    {
+        let tx = tx
+            .configure_tx(
+                NoPin,
+                TxChannelConfig::default()
+                    .with_clk_divider(clk_divider)
+                    .with_idle_output(true)
+                    .with_idle_output_level(Level::High),
+            )?
+            .degrade();
+        let rx = rx
+            .configure_rx(
+                NoPin,
+                RxChannelConfig::default()
+                    .with_clk_divider(clk_divider)
+                    .with_idle_threshold(IDLE_THRESHOLD),
+            )?
+            .degrade();
+
+        let pin: OutputSignal<'d> = pin.into();
+
+        // Release the bus while the pin is being configured.
+        pin.set_output_high(true);
+        pin.apply_output_config(
+            &OutputConfig::default()
+                .with_drive_mode(DriveMode::OpenDrain)
+                .with_pull(Pull::Up),
+        );
+        pin.set_output_enable(true);
+        pin.set_input_enable(true);
+
+        rx.raw.input_signal().connect_to(&pin);
+        let guard = OutputSignal::connect_with_guard(pin, tx.raw.output_signal());
+
+        Ok(Self {
+            tx: Some(tx),
+            rx: Some(rx),
+            _guard: guard,
+            _lifetime: PhantomData,
+        })
+    }
+
+    /// Sends a reset pulse and returns whether any device answered it with a
+    /// presence pulse.
+    pub fn reset(&mut self) -> Result<bool, Error> {
+        let mut received = [PulseCode::end_marker(); 4];
+        self.transfer(
+            &[PulseCode::new(Level::Low, RESET_LOW, Level::High, 0)],
+            &mut received,
+        )?;
+
+        // The first low period is our own reset pulse, any further one is a
+        // presence pulse.
+        Ok(low_periods(&received).nth(1).is_some())
+    }
+
+    /// Writes `data` to the bus, least significant bit first.
+    pub fn write_bytes(&mut self, data: &[u8]) -> Result<(), Error> {
+        for byte in data {
+            let mut codes = [PulseCode::end_marker(); 9];
+            for (bit, code) in codes.iter_mut().take(8).enumerate() {
+                *code = write_slot(byte & (1 << bit) != 0);
+            }
+            self.transmit(&codes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Fills `buffer` with data read from the bus, least significant bit
+    /// first.
+    pub fn read_bytes(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+        for byte in buffer {
+            *byte = self.read_bits(8)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the ROM code of the only device on the bus.
+    ///
+    /// If there is more than one device on the bus, their answers collide and
+    /// this will most likely fail with [`Error::CrcMismatch`]. Use
+    /// [`Self::search`] instead in that case.
+    pub fn read_rom(&mut self) -> Result<Address, Error> {
+        if !self.reset()? {
+            return Err(Error::NoDevice);
+        }
+
+        self.write_bytes(&[CMD_READ_ROM])?;
+        let mut bytes = [0; 8];
+        self.read_bytes(&mut bytes)?;
+
+        Address::from_bytes(bytes)
+    }
+
+    /// Addresses all devices on the bus for the next command.
+    ///
+    /// This must be preceded by a call to [`Self::reset`].
+    pub fn skip_rom(&mut self) -> Result<(), Error> {
+        self.write_bytes(&[CMD_SKIP_ROM])
+    }
+
+    /// Addresses the device with the given ROM code for the next command.
+    ///
+    /// This must be preceded by a call to [`Self::reset`].
+    pub fn select(&mut self, address: Address) -> Result<(), Error> {
+        self.write_bytes(&[CMD_MATCH_ROM])?;
+        self.write_bytes(&address.0.to_le_bytes())
+    }
+
+    /// Returns an iterator over the ROM codes of all devices on the bus.
+    ///
+    /// Each step of the iteration performs one pass of the ROM search
+    /// algorithm. The bus must not be used for anything else while the
+    /// search is in progress.
+    pub fn search(&mut self) -> Search<'_, 'd> {
+        Search {
+            bus: self,
+            last_address: 0,
+            last_discrepancy: None,
+            done: false,
+        }
+    }
+
+    fn search_next(
+        &mut self,
+        last_address: u64,
+        last_discrepancy: Option<u8>,
+    ) -> Result<Option<(Address, Option<u8>)>, Error> {
+        if !self.reset()? {
+            return Ok(None);
+        }
+        self.write_bytes(&[CMD_SEARCH_ROM])?;
+
+        let mut address = 0u64;
+        let mut discrepancy = None;
+        for bit in 0..64 {
+            // Every device sends its bit followed by the complement of it.
+            let bits = self.read_bits(2)?;
+            let direction = match (bits & 1 != 0, bits & 2 != 0) {
+                (false, true) => false,
+                (true, false) => true,
+                (false, false) => {
+                    // Devices disagree on this bit. Take the path not taken at
+                    // the last discrepancy, and repeat the previous choices before it.
+                    let direction = match last_discrepancy {
+                        Some(last) if bit < last => last_address & (1 << bit) != 0,
+                        Some(last) => bit == last,
+                        None => false,
+                    };
+                    if !direction {
+                        discrepancy = Some(bit);
+                    }
+                    direction
+                }
+                // No device is participating anymore.
+                (true, true) => return Err(Error::InvalidResponse),
+            };
+
+            if direction {
+                address |= 1 << bit;
+            }
+            self.transmit(&[write_slot(direction), PulseCode::end_marker()])?;
+        }
+
+        let address = Address::from_bytes(address.to_le_bytes())?;
+        Ok(Some((address, discrepancy)))
+    }
+
+    fn read_bits(&mut self, count: usize) -> Result<u8, Error> {
+        debug_assert!(count <= 8);
+
+        let mut codes = [PulseCode::end_marker(); 9];
+        for code in codes.iter_mut().take(count) {
+            // A read slot looks just like writing a 1, but a device may keep
+            // the bus low after the master releases it.
+            *code = write_slot(true);
+        }
+
+        let mut received = [PulseCode::end_marker(); 12];
+        self.transfer(&codes[..count + 1], &mut received)?;
+
+        let mut value = 0;
+        let mut slots = 0;
+        for (bit, low) in low_periods(&received).enumerate().take(count) {
+            if low < SLOT_SAMPLE {
+                value |= 1 << bit;
+            }
+            slots += 1;
+        }
+
+        // Every slot is started by us, so a missing one means the bus is
+        // stuck or reception overflowed.
+        if slots != count {
+            return Err(Error::InvalidResponse);
+        }
+
+        Ok(value)
+    }
+
+    fn transmit(&mut self, codes: &[PulseCode]) -> Result<(), Error> {
+        let tx = unwrap!(self.tx.take());
+        match tx.transmit(codes)?.wait() {
+            Ok(tx) => {
+                self.tx = Some(tx);
+                Ok(())
+            }
+            Err((e, tx)) => {
+                self.tx = Some(tx);
+                Err(e.into())
+            }
+        }
+    }
+
+    fn transfer(&mut self, codes: &[PulseCode], received: &mut [PulseCode]) -> Result<(), Error> {
+        let rx = unwrap!(self.rx.take());
+        let rx_transaction = rx.receive(received)?;
+
+        let tx_result = self.transmit(codes);
+
+        let rx_result = match rx_transaction.wait() {
+            Ok(rx) => {
+                self.rx = Some(rx);
+                Ok(())
+            }
+            Err((e, rx)) => {
+                self.rx = Some(rx);
+                Err(e.into())
+            }
+        };
+
+        tx_result.and(rx_result)
+    }
+}
+
+/// An iterator over the devices on a 1-Wire bus.
+///
+/// Created by [`OneWire::search`].
+pub struct Search<'a, 'd> {
+    bus: &'a mut OneWire<'d>,
+    last_address: u64,
+    last_discrepancy: Option<u8>,
+    done: bool,
+}
+
+impl Iterator for Search<'_, '_> {
+    type Item = Result<Address, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self
+            .bus
+            .search_next(self.last_address, self.last_discrepancy)
+        {
+            Ok(Some((address, discrepancy))) => {
+                self.last_address = address.0;
+                self.last_discrepancy = discrepancy;
+                self.done = discrepancy.is_none();
+                Some(Ok(address))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn write_slot(bit: bool) -> PulseCode {
+    let low = if bit { SLOT_START } else { SLOT_ZERO_LOW };
+    PulseCode::new(Level::Low, low, Level::High, SLOT_DURATION - low)
+}
+
+// Returns the lengths of the low periods in the received pulse codes.
+fn low_periods(codes: &[PulseCode]) -> impl Iterator<Item = u16> + '_ {
+    codes
+        .iter()
+        .flat_map(|code| {
+            [
+                (code.level1(), code.length1()),
+                (code.level2(), code.length2()),
+            ]
+        })
+        .take_while(|(_, length)| *length != 0)
+        .filter(|(level, _)| *level == Level::Low)
+        .map(|(_, length)| length)
+}
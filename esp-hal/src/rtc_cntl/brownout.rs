@@ -0,0 +1,136 @@
+//! Brownout detector register access.
+//!
+//! ESP32 keeps the threshold in `RTC_CNTL_BROWN_OUT_REG`. The other chips set
+//! it through the analog I2C bus, and ESP32-C6 has its control register in
+//! `LP_ANA`.
+
+#[cfg(esp32c6)]
+use crate::peripherals::LP_ANA;
+#[cfg(not(esp32c6))]
+use crate::peripherals::LPWR;
+#[cfg(not(esp32))]
+use crate::soc::regi2c;
+
+/// The number of slow clock cycles the detector waits before resetting the
+/// chip, same as ESP-IDF.
+const RESET_WAIT: u16 = 0x3ff;
+
+fn set_threshold(level: u8) {
+    cfg_if::cfg_if! {
+        if #[cfg(esp32)] {
+            LPWR::regs()
+                .brown_out()
+                .modify(|_, w| unsafe { w.dbrown_out_thres().bits(level) });
+        } else if #[cfg(esp32s2)] {
+            regi2c::I2C_BOD_REG_THRESHOLD.write_field(level);
+        } else {
+            regi2c::I2C_BOD_THRESHOLD.write_field(level);
+        }
+    }
+}
+
+pub(super) fn configure(level: u8, reset: bool) {
+    disable();
+    set_threshold(level);
+
+    // Powering down the flash protects it from writes at a low voltage, but the
+    // interrupt handler may need it to save state, so it's only done when the
+    // chip is going to be reset anyway. Powering down the RF reduces the load,
+    // which buys the interrupt handler some time.
+    cfg_if::cfg_if! {
+        if #[cfg(esp32c6)] {
+            LP_ANA::regs().bod_mode0_cntl().modify(|_, w| unsafe {
+                w.bod_mode0_close_flash_ena().bit(reset);
+                w.bod_mode0_pd_rf_ena().set_bit();
+                w.bod_mode0_reset_wait().bits(RESET_WAIT);
+                w.bod_mode0_reset_sel().set_bit();
+                w.bod_mode0_reset_ena().bit(reset);
+                w.bod_mode0_intr_ena().set_bit()
+            });
+            clear_interrupt();
+            LP_ANA::regs()
+                .int_ena()
+                .modify(|_, w| w.bod_mode0().bit(!reset));
+        } else if #[cfg(any(esp32c2, esp32c3, esp32s3))] {
+            LPWR::regs().brown_out().modify(|_, w| unsafe {
+                w.brown_out_close_flash_ena().bit(reset);
+                w.brown_out_pd_rf_ena().set_bit();
+                w.brown_out_rst_wait().bits(RESET_WAIT);
+                w.brown_out_rst_sel().set_bit();
+                w.brown_out_rst_ena().bit(reset);
+                w.brown_out_ena().set_bit()
+            });
+            clear_interrupt();
+            LPWR::regs()
+                .int_ena()
+                .modify(|_, w| w.brown_out().bit(!reset));
+        } else {
+            LPWR::regs().brown_out().modify(|_, w| unsafe {
+                w.close_flash_ena().bit(reset);
+                w.pd_rf_ena().set_bit();
+                w.rst_wait().bits(RESET_WAIT);
+                #[cfg(esp32s2)]
+                w.rst_sel().set_bit();
+                #[cfg(esp32s2)]
+                w.brown_out2_ena().set_bit();
+                w.rst_ena().bit(reset);
+                w.ena().set_bit()
+            });
+            clear_interrupt();
+            LPWR::regs()
+                .int_ena()
+                .modify(|_, w| w.brown_out().bit(!reset));
+        }
+    }
+}
+
+pub(super) fn disable() {
+    cfg_if::cfg_if! {
+        if #[cfg(esp32c6)] {
+            LP_ANA::regs()
+                .int_ena()
+                .modify(|_, w| w.bod_mode0().clear_bit());
+            LP_ANA::regs().bod_mode0_cntl().modify(|_, w| {
+                w.bod_mode0_reset_ena().clear_bit();
+                w.bod_mode0_intr_ena().clear_bit()
+            });
+        } else if #[cfg(any(esp32c2, esp32c3, esp32s3))] {
+            LPWR::regs()
+                .int_ena()
+                .modify(|_, w| w.brown_out().clear_bit());
+            LPWR::regs().brown_out().modify(|_, w| {
+                w.brown_out_rst_ena().clear_bit();
+                w.brown_out_ena().clear_bit()
+            });
+        } else {
+            LPWR::regs()
+                .int_ena()
+                .modify(|_, w| w.brown_out().clear_bit());
+            LPWR::regs().brown_out().modify(|_, w| {
+                w.rst_ena().clear_bit();
+                w.ena().clear_bit()
+            });
+        }
+    }
+    clear_interrupt();
+}
+
+pub(super) fn clear_interrupt() {
+    cfg_if::cfg_if! {
+        if #[cfg(esp32c6)] {
+            LP_ANA::regs().int_clr().write(|w| w.bod_mode0().clear_bit_by_one());
+        } else {
+            LPWR::regs().int_clr().write(|w| w.brown_out().clear_bit_by_one());
+        }
+    }
+}
+
+pub(super) fn is_interrupt_set() -> bool {
+    cfg_if::cfg_if! {
+        if #[cfg(esp32c6)] {
+            LP_ANA::regs().int_st().read().bod_mode0().bit_is_set()
+        } else {
+            LPWR::regs().int_st().read().brown_out().bit_is_set()
+        }
+    }
+}
@@ -137,6 +137,13 @@ pub mod sleep;
 #[cfg_attr(esp32s3, path = "rtc/esp32s3.rs")]
 pub(crate) mod rtc;
 
+#[cfg(not(esp32h2))]
+mod brownout;
+#[cfg(any(esp32, esp32s2, esp32s3, esp32c3, esp32c6))]
+mod retention;
+#[cfg(esp32c6)]
+mod wake_stub;
+
 cfg_if::cfg_if! {
     if #[cfg(any(esp32c6, esp32h2))] {
         use crate::peripherals::LP_WDT;
@@ -310,6 +317,230 @@ pub(crate) enum RtcCalSel {
     RtcCalRcFast,
 }
 
+/// The RTC_SLOW_CLK source did not start.
+///
+/// This error is returned by [`Rtc::set_slow_clock_source`] and
+/// [`Rtc::recalibrate_slow_clock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub struct SlowClockNotRunning;
+
+impl core::error::Error for SlowClockNotRunning {}
+
+impl core::fmt::Display for SlowClockNotRunning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The RTC slow clock source did not start")
+    }
+}
+
+/// Peripherals whose configuration can be retained through deep sleep.
+///
+/// See [`Rtc::retain_peripheral_config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumCount)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+#[cfg(any(esp32, esp32s2, esp32s3, esp32c3, esp32c6))]
+#[instability::unstable]
+pub enum RetainedPeripheral {
+    /// The timers of timer group 0.
+    Timg0,
+    /// The timers of timer group 1.
+    #[cfg(timergroup_timg1)]
+    Timg1,
+}
+
+/// The peripheral is not enabled.
+///
+/// This error is returned by [`Rtc::retain_peripheral_config`] and
+/// [`Rtc::restore_peripheral_config`] if the peripheral's driver has not been
+/// created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg(any(esp32, esp32s2, esp32s3, esp32c3, esp32c6))]
+#[instability::unstable]
+pub struct PeripheralDisabled;
+
+#[cfg(any(esp32, esp32s2, esp32s3, esp32c3, esp32c6))]
+impl core::error::Error for PeripheralDisabled {}
+
+#[cfg(any(esp32, esp32s2, esp32s3, esp32c3, esp32c6))]
+impl core::fmt::Display for PeripheralDisabled {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The peripheral is not enabled")
+    }
+}
+
+/// A function that runs when the chip wakes up from deep sleep, before the
+/// bootloader.
+///
+/// See [`Rtc::set_wake_stub`].
+#[cfg(esp32c6)]
+#[instability::unstable]
+pub type WakeStub = unsafe extern "C" fn();
+
+/// The wake stub is not located in RTC FAST memory.
+///
+/// This error is returned by [`Rtc::set_wake_stub`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg(esp32c6)]
+#[instability::unstable]
+pub struct InvalidWakeStub;
+
+#[cfg(esp32c6)]
+impl core::error::Error for InvalidWakeStub {}
+
+#[cfg(esp32c6)]
+impl core::fmt::Display for InvalidWakeStub {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The wake stub must be placed in RTC FAST memory")
+    }
+}
+
+/// Brownout detector threshold.
+///
+/// The detector triggers when the supply voltage drops below the threshold.
+/// The nominal voltages of the levels, as listed by ESP-IDF, are:
+///
+/// | Level | ESP32  | ESP32-S2, ESP32-S3 | ESP32-C2, ESP32-C3, ESP32-C6 |
+/// |-------|--------|--------------------|------------------------------|
+/// | 0     | 2.43 V | -                  | -                            |
+/// | 1     | 2.48 V | 3.30 V             | -                            |
+/// | 2     | 2.58 V | 3.19 V             | 3.27 V                       |
+/// | 3     | 2.62 V | 2.98 V             | 3.10 V                       |
+/// | 4     | 2.67 V | 2.84 V             | 2.92 V                       |
+/// | 5     | 2.70 V | 2.67 V             | 2.76 V                       |
+/// | 6     | 2.77 V | 2.56 V             | 2.64 V                       |
+/// | 7     | 2.80 V | 2.44 V             | 2.51 V                       |
+///
+/// Levels without a voltage are not characterized. The actual threshold can
+/// differ by about 0.05 V between chips.
+///
+/// esp-hal does not configure the brownout detector on startup, it keeps the
+/// configuration left by the ROM and the bootloader. ESP-IDF applications
+/// default to level 0 on ESP32 and level 7 on the other chips, which is the
+/// lowest voltage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg(not(esp32h2))]
+#[instability::unstable]
+pub enum BrownoutLevel {
+    /// Level 0.
+    Level0,
+    /// Level 1.
+    Level1,
+    /// Level 2.
+    Level2,
+    /// Level 3.
+    Level3,
+    /// Level 4.
+    Level4,
+    /// Level 5.
+    Level5,
+    /// Level 6.
+    Level6,
+    /// Level 7.
+    Level7,
+}
+
+/// What the brownout detector does when the supply voltage drops below the
+/// threshold.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg(not(esp32h2))]
+#[instability::unstable]
+pub enum BrownoutAction {
+    /// Reset the chip.
+    ///
+    /// The flash and the RF circuits are powered down while the voltage is
+    /// low, to protect the flash from corrupted writes.
+    Reset,
+
+    /// Call an interrupt handler.
+    ///
+    /// The handler is bound to the RTC interrupt, replacing any previously
+    /// registered handler, see [`Rtc::set_interrupt_handler`]. The flash stays
+    /// powered, so the handler can save state before the voltage drops
+    /// further. The RF circuits are powered down to reduce the load.
+    ///
+    /// The interrupt stays pending while the voltage is below the threshold.
+    /// The handler should either reset the chip, or disable the detector with
+    /// [`Rtc::disable_brownout`], or clear the interrupt with
+    /// [`Rtc::clear_brownout_interrupt`].
+    Interrupt(InterruptHandler),
+}
+
+/// The cause of the last reset, common to all chips.
+///
+/// This is a simplified view of [`SocResetReason`], which also tells whether
+/// a CPU, the digital core or the whole system was reset.
+///
+/// See [`Rtc::reset_reason`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+#[instability::unstable]
+pub enum ResetReason {
+    /// The chip was powered on.
+    ///
+    /// On the ESP32-C6 and ESP32-H2, a brownout reset is also reported as a
+    /// power-on reset.
+    PowerOn,
+
+    /// Software requested the reset, e.g. with
+    /// [`software_reset`](crate::system::software_reset).
+    Software,
+
+    /// The chip woke up from deep sleep.
+    DeepSleep,
+
+    /// The supply voltage dropped below the brownout threshold.
+    Brownout,
+
+    /// A watchdog timer expired.
+    Watchdog(ResetWatchdog),
+
+    /// A glitch was detected on the clock or the power supply.
+    Glitch,
+
+    /// An eFuse CRC error was detected.
+    EfuseCrc,
+
+    /// The USB Serial/JTAG peripheral requested the reset.
+    Usb,
+
+    /// The reset was requested through JTAG.
+    Jtag,
+
+    /// The SDIO peripheral requested the reset.
+    Sdio,
+
+    /// The reset cause is not known to esp-hal. Contains the raw cause code.
+    Unknown(u32),
+}
+
+/// The watchdog timer that caused a reset.
+///
+/// See [`ResetReason::Watchdog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+#[instability::unstable]
+pub enum ResetWatchdog {
+    /// The watchdog of timer group 0.
+    Timg0,
+
+    /// The watchdog of timer group 1.
+    Timg1,
+
+    /// The RTC watchdog, see [`Rwdt`].
+    Rtc,
+
+    /// The super watchdog.
+    Super,
+}
+
 /// Low-power Management
 pub struct Rtc<'d> {
     _inner: crate::peripherals::LPWR<'d>,
@@ -338,6 +569,122 @@ impl<'d> Rtc<'d> {
         RtcClock::estimate_xtal_frequency()
     }
 
+    /// Select the source of RTC_SLOW_CLK.
+    ///
+    /// RTC_SLOW_CLK drives the RTC timer, and with it the timer wakeup source
+    /// of the sleep modes. The oscillator of the new source is powered up if
+    /// necessary, then the clock is calibrated against the main XTAL. The
+    /// calibrated frequency can be read with [`Self::rtc_slow_frequency`].
+    ///
+    /// An external 32 kHz crystal needs time to start oscillating after being
+    /// powered up, typically a few hundred milliseconds, depending on the
+    /// crystal and its load capacitors. This function blocks until the
+    /// crystal runs, for up to one second.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`SlowClockNotRunning`] if the new source did not start, for
+    /// example because no 32 kHz crystal is populated, or if it could not be
+    /// calibrated. The previous source stays selected in this case.
+    #[instability::unstable]
+    pub fn set_slow_clock_source(
+        &mut self,
+        source: RtcSlowClock,
+    ) -> Result<(), SlowClockNotRunning> {
+        RtcClock::enable_slow_clock(source);
+
+        if matches!(source, RtcSlowClock::RtcSlowClock32kXtal) {
+            const STARTUP_ATTEMPTS: u32 = 100;
+
+            let mut attempts = 0;
+            while RtcClock::calibrate(RtcCalSel::RtcCal32kXtal, 32) == 0 {
+                attempts += 1;
+                if attempts == STARTUP_ATTEMPTS {
+                    return Err(SlowClockNotRunning);
+                }
+                crate::rom::ets_delay_us(10_000);
+            }
+        }
+
+        let now = self.current_time_us();
+        let previous = RtcClock::slow_freq();
+        RtcClock::set_slow_freq(source);
+        if let Err(error) = self.store_slow_clock_calibration() {
+            RtcClock::set_slow_freq(previous);
+            return Err(error);
+        }
+        self.set_current_time_us(now);
+
+        Ok(())
+    }
+
+    /// Measures the frequency of RTC_SLOW_CLK again.
+    ///
+    /// The frequency of the slow clock, particularly of the internal RC
+    /// oscillators, changes with the temperature and the supply voltage. The
+    /// RTC time is derived from the frequency measured at start-up, so it
+    /// drifts when the conditions change. Calling this function periodically,
+    /// for example every few minutes, bounds the drift to the accuracy of the
+    /// measurement, which is better than 0.1%.
+    ///
+    /// [`Self::current_time_us`] stays continuous, only the rate at which it
+    /// advances is corrected. [`Self::time_since_boot`] may jump, as it is
+    /// derived from the number of slow clock cycles counted since boot.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`SlowClockNotRunning`] if the slow clock could not be
+    /// measured. The previous calibration is kept in this case.
+    #[instability::unstable]
+    pub fn recalibrate_slow_clock(&mut self) -> Result<(), SlowClockNotRunning> {
+        let now = self.current_time_us();
+        self.store_slow_clock_calibration()?;
+        self.set_current_time_us(now);
+
+        Ok(())
+    }
+
+    fn store_slow_clock_calibration(&self) -> Result<(), SlowClockNotRunning> {
+        // The calibration returns 0 if the clock doesn't tick during the
+        // measurement.
+        const CALIBRATION_ATTEMPTS: u32 = 10;
+
+        let mut attempts = 0;
+        let cal_val = loop {
+            let res = RtcClock::calibrate(RtcCalSel::RtcCalRtcMux, 1024);
+            if res != 0 {
+                break res;
+            }
+            attempts += 1;
+            if attempts == CALIBRATION_ATTEMPTS {
+                return Err(SlowClockNotRunning);
+            }
+        };
+        LP_AON::regs()
+            .store1()
+            .write(|w| unsafe { w.bits(cal_val) });
+
+        Ok(())
+    }
+
+    /// Returns the calibrated frequency of RTC_SLOW_CLK in Hz.
+    ///
+    /// The frequency is measured against the main XTAL during start-up and
+    /// whenever the source is changed with [`Self::set_slow_clock_source`].
+    /// The internal RC oscillators in particular can deviate significantly
+    /// from their nominal frequency. If the clock has not been calibrated, the
+    /// nominal frequency of the source is returned.
+    #[instability::unstable]
+    pub fn rtc_slow_frequency(&self) -> u32 {
+        let period_13q19 = LP_AON::regs().store1().read().bits() as u64;
+        if period_13q19 == 0 {
+            // Not calibrated, fall back to the nominal frequency.
+            return RtcClock::slow_freq().frequency().as_hz();
+        }
+
+        ((1_000_000 << RtcClock::CAL_FRACT) / period_13q19) as u32
+    }
+
     /// Get the time since boot in the raw register units.
     fn time_since_boot_raw(&self) -> u64 {
         let rtc_cntl = LP_TIMER::regs();
@@ -375,11 +722,28 @@ impl<'d> Rtc<'d> {
     }
 
     /// Get the time since boot.
+    ///
+    /// The time is derived from the RTC timer, which counts RTC_SLOW_CLK
+    /// cycles, using the calibrated frequency of the clock (see
+    /// [`Self::rtc_slow_frequency`]).
     pub fn time_since_boot(&self) -> Duration {
-        Duration::from_micros(
-            self.time_since_boot_raw() * 1_000_000
-                / RtcClock::slow_freq().frequency().as_hz() as u64,
-        )
+        Duration::from_micros(Self::slow_ticks_to_us(self.time_since_boot_raw()))
+    }
+
+    /// Converts RTC_SLOW_CLK cycles to microseconds.
+    fn slow_ticks_to_us(ticks: u64) -> u64 {
+        let period_13q19 = LP_AON::regs().store1().read().bits() as u64;
+        if period_13q19 == 0 {
+            // Not calibrated, fall back to the nominal frequency.
+            return ticks * 1_000_000 / RtcClock::slow_freq().frequency().as_hz() as u64;
+        }
+
+        // Split the multiplication so that it does not overflow.
+        let whole = (ticks >> RtcClock::CAL_FRACT) * period_13q19;
+        let fraction =
+            ((ticks & ((1 << RtcClock::CAL_FRACT) - 1)) * period_13q19) >> RtcClock::CAL_FRACT;
+
+        whole + fraction
     }
 
     /// Read the current value of the boot time registers in microseconds.
@@ -426,6 +790,19 @@ impl<'d> Rtc<'d> {
     #[procmacros::doc_replace]
     /// Get the current time in microseconds.
     ///
+    /// The current time is the time set with [`Self::set_current_time_us`],
+    /// for example the Unix time received from an SNTP server, advanced by
+    /// the RTC timer. The RTC timer keeps running in light sleep and deep
+    /// sleep, and the time base is stored in RTC registers that are retained
+    /// through both, so the time stays valid until the chip is powered off
+    /// or reset by a system reset.
+    ///
+    /// The accuracy depends on RTC_SLOW_CLK, see
+    /// [`Self::set_slow_clock_source`]. With the internal RC oscillator the
+    /// time may drift by up to a few percent when the temperature changes,
+    /// which [`Self::recalibrate_slow_clock`] compensates. An external 32 kHz
+    /// crystal typically stays within 20 ppm.
+    ///
     /// # Example
     ///
     /// This example shows how to get the weekday of the current time in
@@ -483,6 +860,20 @@ impl<'d> Rtc<'d> {
         }
     }
 
+    /// Get the current time in seconds.
+    ///
+    /// See [`Self::current_time_us`].
+    pub fn current_time_secs(&self) -> u64 {
+        self.current_time_us() / 1_000_000
+    }
+
+    /// Set the current time in seconds, for example the Unix time.
+    ///
+    /// See [`Self::current_time_us`].
+    pub fn set_current_time_secs(&self, current_time_secs: u64) {
+        self.set_current_time_us(current_time_secs * 1_000_000)
+    }
+
     /// Enter deep sleep and wake with the provided `wake_sources`.
     ///
     /// In Deep-sleep mode, the CPUs, most of the RAM, and all digital
@@ -492,12 +883,137 @@ impl<'d> Rtc<'d> {
     /// attribute to persist a variable though deep sleep.
     #[cfg(any(esp32, esp32s2, esp32s3, esp32c3, esp32c6, esp32c2))]
     pub fn sleep_deep(&mut self, wake_sources: &[&dyn WakeSource]) -> ! {
+        #[cfg(not(esp32c2))]
+        retention::save();
+        #[cfg(esp32c6)]
+        wake_stub::arm();
+
         let config = RtcSleepConfig::deep();
         self.sleep(&config, wake_sources);
         unreachable!();
     }
 
+    #[procmacros::doc_replace]
+    /// Registers a function to run when the chip wakes up from deep sleep.
+    ///
+    /// After waking up from deep sleep, the ROM bootloader calls the wake stub
+    /// before loading the application from flash. The stub can, for example,
+    /// check a sensor and only let the chip boot the application when
+    /// needed. When the stub returns, the chip boots normally. The stub is
+    /// registered when entering deep sleep with [`Self::sleep_deep`], and
+    /// applies to the next wake-up only: register it again before going back
+    /// to sleep. Pass `None` to remove a previously registered stub.
+    ///
+    /// The stub must be placed in RTC FAST memory using
+    /// [`#[esp_hal::ram(rtc_fast)]`][procmacros::ram]. It runs in a very
+    /// limited environment:
+    /// - Flash and the main SRAM are not accessible. The stub can only use statics placed in RTC
+    ///   FAST memory, and must only call functions that are placed in RTC FAST memory or inlined.
+    ///   This excludes panicking and formatting, and most of `esp-hal`.
+    /// - The peripherals are in their reset state, except for the always-on ones.
+    /// - The stub runs on the ROM bootloader's stack, which is small.
+    ///
+    /// The ROM only calls the stub if the contents of RTC FAST memory, from
+    /// the start of the stub to the end of the code placed there, did not
+    /// change during deep sleep.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`InvalidWakeStub`] if the stub is not located in RTC FAST
+    /// memory.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::{ram, rtc_cntl::Rtc};
+    ///
+    /// #[ram(rtc_fast, persistent)]
+    /// static mut WAKE_COUNT: u32 = 0;
+    ///
+    /// #[ram(rtc_fast)]
+    /// unsafe extern "C" fn wake_stub() {
+    ///     unsafe { WAKE_COUNT += 1 };
+    /// }
+    ///
+    /// let mut rtc = Rtc::new(peripherals.LPWR);
+    /// rtc.set_wake_stub(Some(wake_stub))?;
+    /// # {after_snippet}
+    /// ```
+    #[cfg(esp32c6)]
+    #[instability::unstable]
+    pub fn set_wake_stub(&mut self, stub: Option<WakeStub>) -> Result<(), InvalidWakeStub> {
+        wake_stub::set(stub)
+    }
+
+    /// Retain the configuration of a peripheral through deep sleep.
+    ///
+    /// Deep sleep powers down the digital peripherals, which lose their
+    /// configuration. The configuration registers of the peripherals passed
+    /// to this function are saved to RTC FAST memory when entering deep sleep
+    /// with [`Self::sleep_deep`]. After waking up, create the peripheral's
+    /// driver again, then call [`Self::restore_peripheral_config`] to write
+    /// the saved configuration back.
+    ///
+    /// Only the peripherals listed in [`RetainedPeripheral`] are supported.
+    /// The counter values of timers are not retained, the timers restart
+    /// counting from zero.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PeripheralDisabled`] if the peripheral's driver has not been
+    /// created.
+    #[cfg(any(esp32, esp32s2, esp32s3, esp32c3, esp32c6))]
+    #[instability::unstable]
+    pub fn retain_peripheral_config(
+        &mut self,
+        peripheral: RetainedPeripheral,
+    ) -> Result<(), PeripheralDisabled> {
+        retention::request(peripheral)
+    }
+
+    /// Restore the configuration of a peripheral retained through deep sleep.
+    ///
+    /// Returns `true` if the configuration was restored, and `false` if there
+    /// is no retained configuration, for example because the chip did not
+    /// wake up from deep sleep, or [`Self::retain_peripheral_config`] was not
+    /// called for this peripheral before entering deep sleep. The retained
+    /// configuration can only be restored once.
+    ///
+    /// The peripheral's driver must be created before calling this function,
+    /// as creating the driver resets the peripheral.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PeripheralDisabled`] if the peripheral's driver has not been
+    /// created.
+    #[cfg(any(esp32, esp32s2, esp32s3, esp32c3, esp32c6))]
+    #[instability::unstable]
+    pub fn restore_peripheral_config(
+        &mut self,
+        peripheral: RetainedPeripheral,
+    ) -> Result<bool, PeripheralDisabled> {
+        retention::restore(peripheral)
+    }
+
     /// Enter light sleep and wake with the provided `wake_sources`.
+    ///
+    /// In Light-sleep mode, the clocks of the CPUs and of the digital
+    /// peripherals are gated, and the power of the digital domain and the RAM
+    /// is retained. This function returns once one of the `wake_sources` has
+    /// triggered, and the program continues where it left off, with the
+    /// configuration of the peripherals preserved.
+    ///
+    /// Peripherals are stopped in the middle of what they are doing, so make
+    /// sure they are idle before entering light sleep:
+    ///
+    /// - Wait for UART transmissions to finish with `flush`, otherwise the transmitted data may be
+    ///   corrupted. Data received while asleep is lost.
+    /// - Wait for DMA transfers, and SPI, I2C and I2S transactions to complete.
+    /// - Timers and [`Instant`](crate::time::Instant) may not advance while the chip is asleep,
+    ///   because their clock source is gated. Use [`TimerWakeupSource`](sleep::TimerWakeupSource)
+    ///   to wake up after a given time instead of a timer alarm.
+    /// - Radio peripherals (Wi-Fi, Bluetooth, IEEE 802.15.4) must be stopped.
     #[cfg(any(esp32, esp32s2, esp32s3, esp32c3, esp32c6, esp32c2))]
     pub fn sleep_light(&mut self, wake_sources: &[&dyn WakeSource]) {
         let config = RtcSleepConfig::default();
@@ -538,6 +1054,115 @@ impl<'d> Rtc<'d> {
             .modify(|r, w| unsafe { w.bits(r.bits() | Self::RTC_DISABLE_ROM_LOG) });
     }
 
+    /// Enable the power glitch detector.
+    ///
+    /// Once enabled, a glitch on the supply voltage is latched in a status
+    /// flag that can be read with [`Self::glitch_detected`]. Security-sensitive
+    /// firmware can use this to react to potential fault-injection attempts.
+    ///
+    /// The detector does not reset the chip, the [`SocResetReason`] is only
+    /// affected if the glitch itself causes a reset.
+    #[cfg(any(esp32c3, esp32s3))]
+    #[instability::unstable]
+    pub fn enable_glitch_detection(&mut self) {
+        LPWR::regs()
+            .pg_ctrl()
+            .modify(|_, w| w.power_glitch_en().set_bit());
+    }
+
+    /// Returns whether a power glitch was detected since the flag was last
+    /// cleared.
+    ///
+    /// The detector must be enabled with [`Self::enable_glitch_detection`]
+    /// first.
+    #[cfg(any(esp32c3, esp32s3))]
+    #[instability::unstable]
+    pub fn glitch_detected(&self) -> bool {
+        LPWR::regs().int_raw().read().glitch_det().bit_is_set()
+    }
+
+    /// Clear the power glitch detection flag.
+    #[cfg(any(esp32c3, esp32s3))]
+    #[instability::unstable]
+    pub fn clear_glitch_detected(&mut self) {
+        LPWR::regs()
+            .int_clr()
+            .write(|w| w.glitch_det().clear_bit_by_one());
+    }
+
+    #[procmacros::doc_replace]
+    /// Configure the brownout detector.
+    ///
+    /// See [`BrownoutLevel`] for the available thresholds.
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::rtc_cntl::{BrownoutAction, BrownoutLevel, Rtc};
+    ///
+    /// let mut rtc = Rtc::new(peripherals.LPWR);
+    /// rtc.configure_brownout(
+    ///     BrownoutLevel::Level5,
+    ///     BrownoutAction::Interrupt(brownout_handler),
+    /// );
+    /// # {after_snippet}
+    ///
+    /// #[handler]
+    /// fn brownout_handler() {
+    ///     // Save the application state here, then restart.
+    ///     esp_hal::system::software_reset();
+    /// }
+    /// ```
+    #[cfg(not(esp32h2))]
+    #[instability::unstable]
+    pub fn configure_brownout(&mut self, level: BrownoutLevel, action: BrownoutAction) {
+        match action {
+            BrownoutAction::Reset => brownout::configure(level as u8, true),
+            BrownoutAction::Interrupt(handler) => {
+                self.set_interrupt_handler(handler);
+                brownout::configure(level as u8, false);
+            }
+        }
+    }
+
+    /// Disable the brownout detector.
+    #[cfg(not(esp32h2))]
+    #[instability::unstable]
+    pub fn disable_brownout(&mut self) {
+        brownout::disable();
+    }
+
+    /// Returns whether the brownout interrupt is pending.
+    #[cfg(not(esp32h2))]
+    #[instability::unstable]
+    pub fn is_brownout_interrupt_set(&self) -> bool {
+        brownout::is_interrupt_set()
+    }
+
+    /// Clear the brownout interrupt.
+    #[cfg(not(esp32h2))]
+    #[instability::unstable]
+    pub fn clear_brownout_interrupt(&mut self) {
+        brownout::clear_interrupt();
+    }
+
+    /// Returns the cause of the last reset of the given CPU.
+    ///
+    /// Every CPU keeps its own reset cause. Resets of the digital core or the
+    /// whole system are reported on all CPUs, while a CPU reset (for example
+    /// by a watchdog configured to reset only the CPU) is only reported on the
+    /// CPU that was reset.
+    ///
+    /// See [`reset_reason`] for the chip-specific cause.
+    #[instability::unstable]
+    pub fn reset_reason(&self, cpu: Cpu) -> ResetReason {
+        let raw = crate::rom::rtc_get_reset_reason(cpu as u32);
+
+        match SocResetReason::from_repr(raw as usize) {
+            Some(reason) => reason.portable(),
+            None => ResetReason::Unknown(raw as u32),
+        }
+    }
+
     /// Register an interrupt handler for the RTC.
     ///
     /// Note that this will replace any previously registered interrupt
@@ -649,6 +1274,94 @@ impl RtcClock {
         crate::rom::ets_delay_us(300u32);
     }
 
+    /// Power up the oscillator of an RTC_SLOW_CLK source.
+    fn enable_slow_clock(source: RtcSlowClock) {
+        match source {
+            RtcSlowClock::RtcSlowClock32kXtal => Self::enable_xtal32k(),
+            #[cfg(not(any(esp32c6, esp32h2)))]
+            RtcSlowClock::RtcSlowClock8mD256 => {
+                LPWR::regs().clk_conf().modify(|_, w| {
+                    w.enb_ck8m().clear_bit();
+                    w.enb_ck8m_div().clear_bit()
+                });
+                crate::rom::ets_delay_us(50);
+            }
+            #[cfg(any(esp32c6, esp32h2))]
+            RtcSlowClock::RtcSlowClock32kRc => {
+                crate::peripherals::PMU::regs()
+                    .hp_sleep_lp_ck_power()
+                    .modify(|_, w| w.hp_sleep_xpd_rc32k().set_bit());
+                crate::rom::ets_delay_us(300);
+            }
+            // RC_SLOW is always on, OSC_SLOW is an external signal.
+            _ => {}
+        }
+    }
+
+    /// Power up the 32 kHz crystal oscillator.
+    fn enable_xtal32k() {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                crate::peripherals::RTC_IO::regs()
+                    .xtal_32k_pad()
+                    .modify(|_, w| unsafe {
+                        w.x32n_mux_sel().set_bit();
+                        w.x32p_mux_sel().set_bit();
+                        w.x32n_rde().clear_bit();
+                        w.x32n_rue().clear_bit();
+                        w.x32p_rde().clear_bit();
+                        w.x32p_rue().clear_bit();
+                        w.x32n_fun_ie().clear_bit();
+                        w.x32p_fun_ie().clear_bit();
+                        w.dac_xtal_32k().bits(1);
+                        w.dres_xtal_32k().bits(3);
+                        w.dbias_xtal_32k().bits(0);
+                        w.xpd_xtal_32k().set_bit()
+                    });
+            } else if #[cfg(esp32c2)] {
+                // The ESP32-C2 has no 32 kHz crystal oscillator, the clock is
+                // an external signal.
+            } else if #[cfg(any(esp32c6, esp32h2))] {
+                crate::peripherals::LP_CLKRST::regs()
+                    .xtal32k()
+                    .modify(|_, w| unsafe {
+                        w.dac_xtal32k().bits(3);
+                        w.dres_xtal32k().bits(3);
+                        w.dgm_xtal32k().bits(3);
+                        w.dbuf_xtal32k().set_bit()
+                    });
+                crate::peripherals::PMU::regs()
+                    .hp_sleep_lp_ck_power()
+                    .modify(|_, w| w.hp_sleep_xpd_xtal32k().set_bit());
+            } else {
+                #[cfg(any(esp32s2, esp32s3))]
+                {
+                    let rtc_io = crate::peripherals::RTC_IO::regs();
+                    rtc_io.xtal_32p_pad().modify(|_, w| {
+                        w.mux_sel().set_bit();
+                        w.rde().clear_bit();
+                        w.rue().clear_bit();
+                        w.fun_ie().clear_bit()
+                    });
+                    rtc_io.xtal_32n_pad().modify(|_, w| {
+                        w.mux_sel().set_bit();
+                        w.rde().clear_bit();
+                        w.rue().clear_bit();
+                        w.fun_ie().clear_bit()
+                    });
+                }
+
+                LPWR::regs().ext_xtl_conf().modify(|_, w| unsafe {
+                    w.dac_xtal_32k().bits(3);
+                    w.dres_xtal_32k().bits(3);
+                    w.dgm_xtal_32k().bits(3);
+                    w.dbuf_xtal_32k().set_bit();
+                    w.xpd_xtal_32k().set_bit()
+                });
+            }
+        }
+    }
+
     /// Select source for RTC_FAST_CLK.
     #[cfg(not(any(esp32c6, esp32h2)))]
     fn set_fast_freq(fast_freq: RtcFastClock) {
@@ -1235,6 +1948,48 @@ impl RtcClock {
 
         (cali_value * (calibration_clock.frequency().as_hz() / SLOW_CLOCK_CYCLES)) / 1_000_000
     }
+
+    /// Measures the frequency of RC_FAST_CLK against the XTAL clock.
+    ///
+    /// Returns `None` if the oscillator doesn't tick during the measurement.
+    pub(crate) fn measure_rc_fast_frequency() -> Option<Rate> {
+        // The calibration returns 0 if the clock doesn't tick during the
+        // measurement.
+        const CALIBRATION_ATTEMPTS: u32 = 10;
+
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32c6, esp32h2))] {
+                // The clock is measured directly, so count a lot of cycles to get
+                // a reasonably precise result.
+                let (cal_clk, cycles, divider) = (RtcCalSel::RtcCalRcFast, 32767, 1);
+            } else {
+                // Only RC_FAST_CLK divided by 256 can be measured. Make sure the
+                // divider is enabled, and restore its state afterwards.
+                let clk_conf = LPWR::regs().clk_conf().read();
+                let rc_fast_disabled = clk_conf.enb_ck8m().bit_is_set();
+                let divider_disabled = clk_conf.enb_ck8m_div().bit_is_set();
+                Self::enable_slow_clock(RtcSlowClock::RtcSlowClock8mD256);
+
+                let (cal_clk, cycles, divider) = (RtcCalSel::RtcCal8mD256, 1024, 256);
+            }
+        }
+
+        let period_13q19 = (0..CALIBRATION_ATTEMPTS)
+            .map(|_| RtcClock::calibrate(cal_clk, cycles))
+            .find(|&res| res != 0);
+
+        #[cfg(not(any(esp32c6, esp32h2)))]
+        LPWR::regs().clk_conf().modify(|_, w| {
+            w.enb_ck8m().bit(rc_fast_disabled);
+            w.enb_ck8m_div().bit(divider_disabled)
+        });
+
+        let period_13q19 = period_13q19? as u64;
+
+        Some(Rate::from_hz(
+            (((divider * 1_000_000) << RtcClock::CAL_FRACT) / period_13q19) as u32,
+        ))
+    }
 }
 
 /// Behavior of the RWDT stage if it times out.
@@ -0,0 +1,142 @@
+//! Retention of peripheral configuration through deep sleep.
+//!
+//! The configuration registers of the requested peripherals are copied to RTC
+//! FAST memory right before entering deep sleep. After waking up, the copy is
+//! written back on request, once the peripheral's driver has been created
+//! again.
+
+use portable_atomic::{AtomicU8, AtomicU32, Ordering};
+use strum::EnumCount;
+
+use super::{PeripheralDisabled, RetainedPeripheral, SocResetReason, reset_reason};
+use crate::{
+    pac::timg0::RegisterBlock,
+    system::{Cpu, Peripheral, PeripheralClockControl},
+};
+
+/// Marks a valid snapshot.
+const VALID: u32 = 0x5245_544E;
+
+/// The number of registers saved per timer.
+const TIMER_WORDS: usize = 5;
+
+#[cfg(timergroup_timg_has_timer1)]
+const TIMERS: usize = 2;
+#[cfg(not(timergroup_timg_has_timer1))]
+const TIMERS: usize = 1;
+
+/// The marker, the registers of each timer, and the interrupt enable register.
+const SNAPSHOT_WORDS: usize = 1 + TIMERS * TIMER_WORDS + 1;
+
+/// The peripherals to save when entering deep sleep.
+static REQUESTED: AtomicU8 = AtomicU8::new(0);
+
+#[procmacros::ram(rtc_fast, persistent)]
+static SNAPSHOTS: [[AtomicU32; SNAPSHOT_WORDS]; RetainedPeripheral::COUNT] =
+    [const { [const { AtomicU32::new(0) }; SNAPSHOT_WORDS] }; RetainedPeripheral::COUNT];
+
+impl RetainedPeripheral {
+    fn clock(self) -> Peripheral {
+        match self {
+            Self::Timg0 => Peripheral::Timg0,
+            #[cfg(timergroup_timg1)]
+            Self::Timg1 => Peripheral::Timg1,
+        }
+    }
+
+    fn regs(self) -> &'static RegisterBlock {
+        match self {
+            Self::Timg0 => crate::peripherals::TIMG0::regs(),
+            #[cfg(timergroup_timg1)]
+            Self::Timg1 => crate::peripherals::TIMG1::regs(),
+        }
+    }
+
+    fn snapshot(self) -> &'static [AtomicU32; SNAPSHOT_WORDS] {
+        &SNAPSHOTS[self as usize]
+    }
+
+    fn ensure_enabled(self) -> Result<(), PeripheralDisabled> {
+        if PeripheralClockControl::is_enabled(self.clock()) {
+            Ok(())
+        } else {
+            Err(PeripheralDisabled)
+        }
+    }
+}
+
+const ALL: [RetainedPeripheral; RetainedPeripheral::COUNT] = [
+    RetainedPeripheral::Timg0,
+    #[cfg(timergroup_timg1)]
+    RetainedPeripheral::Timg1,
+];
+
+pub(super) fn request(peripheral: RetainedPeripheral) -> Result<(), PeripheralDisabled> {
+    peripheral.ensure_enabled()?;
+    REQUESTED.fetch_or(1 << peripheral as u8, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Saves the requested peripherals, and invalidates the snapshots of the
+/// others.
+pub(super) fn save() {
+    let requested = REQUESTED.load(Ordering::Relaxed);
+
+    for peripheral in ALL {
+        let snapshot = peripheral.snapshot();
+        snapshot[0].store(0, Ordering::Relaxed);
+
+        // The registers of a disabled peripheral are in their reset state anyway.
+        if requested & (1 << peripheral as u8) == 0 || peripheral.ensure_enabled().is_err() {
+            continue;
+        }
+
+        let regs = peripheral.regs();
+        for timer in 0..TIMERS {
+            let t = regs.t(timer);
+            let words = &snapshot[1 + timer * TIMER_WORDS..][..TIMER_WORDS];
+            words[0].store(t.config().read().bits(), Ordering::Relaxed);
+            words[1].store(t.loadlo().read().bits(), Ordering::Relaxed);
+            words[2].store(t.loadhi().read().bits(), Ordering::Relaxed);
+            words[3].store(t.alarmlo().read().bits(), Ordering::Relaxed);
+            words[4].store(t.alarmhi().read().bits(), Ordering::Relaxed);
+        }
+        snapshot[SNAPSHOT_WORDS - 1].store(regs.int_ena().read().bits(), Ordering::Relaxed);
+
+        snapshot[0].store(VALID, Ordering::Relaxed);
+    }
+}
+
+pub(super) fn restore(peripheral: RetainedPeripheral) -> Result<bool, PeripheralDisabled> {
+    peripheral.ensure_enabled()?;
+
+    // RTC memory is not initialized after other kinds of reset.
+    let snapshot = peripheral.snapshot();
+    if reset_reason(Cpu::ProCpu) != Some(SocResetReason::CoreDeepSleep)
+        || snapshot[0].load(Ordering::Relaxed) != VALID
+    {
+        return Ok(false);
+    }
+    snapshot[0].store(0, Ordering::Relaxed);
+
+    let regs = peripheral.regs();
+    regs.int_ena()
+        .write(|w| unsafe { w.bits(snapshot[SNAPSHOT_WORDS - 1].load(Ordering::Relaxed)) });
+    for timer in 0..TIMERS {
+        let t = regs.t(timer);
+        let words = &snapshot[1 + timer * TIMER_WORDS..][..TIMER_WORDS];
+        t.loadlo()
+            .write(|w| unsafe { w.bits(words[1].load(Ordering::Relaxed)) });
+        t.loadhi()
+            .write(|w| unsafe { w.bits(words[2].load(Ordering::Relaxed)) });
+        t.alarmlo()
+            .write(|w| unsafe { w.bits(words[3].load(Ordering::Relaxed)) });
+        t.alarmhi()
+            .write(|w| unsafe { w.bits(words[4].load(Ordering::Relaxed)) });
+        // The configuration goes last, as it may enable the timer.
+        t.config()
+            .write(|w| unsafe { w.bits(words[0].load(Ordering::Relaxed)) });
+    }
+
+    Ok(true)
+}
@@ -2,7 +2,7 @@ use strum::FromRepr;
 
 use crate::{
     peripherals::LPWR,
-    rtc_cntl::{RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
+    rtc_cntl::{ResetReason, ResetWatchdog, RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
 };
 
 pub(crate) fn init() {
@@ -68,3 +68,25 @@ pub enum SocResetReason {
     /// RTC watch dog resets digital core and rtc module
     SysRtcWdt     = 0x10,
 }
+
+impl SocResetReason {
+    /// Maps the reset reason to its chip-independent equivalent.
+    pub(crate) fn portable(self) -> ResetReason {
+        match self {
+            SocResetReason::ChipPowerOn => ResetReason::PowerOn,
+            SocResetReason::CoreSw | SocResetReason::Cpu0Sw | SocResetReason::Cpu1Cpu0 => {
+                ResetReason::Software
+            }
+            SocResetReason::CoreDeepSleep => ResetReason::DeepSleep,
+            SocResetReason::CoreSdio => ResetReason::Sdio,
+            SocResetReason::CoreMwdt0 | SocResetReason::CpuMwdt0 => {
+                ResetReason::Watchdog(ResetWatchdog::Timg0)
+            }
+            SocResetReason::CoreMwdt1 => ResetReason::Watchdog(ResetWatchdog::Timg1),
+            SocResetReason::CoreRtcWdt | SocResetReason::Cpu0RtcWdt | SocResetReason::SysRtcWdt => {
+                ResetReason::Watchdog(ResetWatchdog::Rtc)
+            }
+            SocResetReason::SysBrownOut => ResetReason::Brownout,
+        }
+    }
+}
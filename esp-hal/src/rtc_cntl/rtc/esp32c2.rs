@@ -2,7 +2,7 @@ use strum::FromRepr;
 
 use crate::{
     peripherals::{APB_CTRL, EXTMEM, LPWR, SPI0, SPI1, SYSTEM},
-    rtc_cntl::{RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
+    rtc_cntl::{ResetReason, ResetWatchdog, RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
     soc::regi2c,
 };
 
@@ -206,3 +206,25 @@ pub enum SocResetReason {
     /// JTAG resets the CPU 0
     Cpu0Jtag      = 0x18,
 }
+
+impl SocResetReason {
+    /// Maps the reset reason to its chip-independent equivalent.
+    pub(crate) fn portable(self) -> ResetReason {
+        match self {
+            SocResetReason::ChipPowerOn => ResetReason::PowerOn,
+            SocResetReason::CoreSw | SocResetReason::Cpu0Sw => ResetReason::Software,
+            SocResetReason::CoreDeepSleep => ResetReason::DeepSleep,
+            SocResetReason::CoreMwdt0 | SocResetReason::Cpu0Mwdt0 => {
+                ResetReason::Watchdog(ResetWatchdog::Timg0)
+            }
+            SocResetReason::CoreRtcWdt | SocResetReason::Cpu0RtcWdt | SocResetReason::SysRtcWdt => {
+                ResetReason::Watchdog(ResetWatchdog::Rtc)
+            }
+            SocResetReason::SysBrownOut => ResetReason::Brownout,
+            SocResetReason::SysSuperWdt => ResetReason::Watchdog(ResetWatchdog::Super),
+            SocResetReason::SysClkGlitch => ResetReason::Glitch,
+            SocResetReason::CoreEfuseCrc => ResetReason::EfuseCrc,
+            SocResetReason::Cpu0Jtag => ResetReason::Jtag,
+        }
+    }
+}
@@ -18,7 +18,7 @@ use crate::{
         },
     },
     peripherals::{LP_AON, LP_CLKRST, MODEM_LPCON, MODEM_SYSCON, PCR, PMU},
-    rtc_cntl::{RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
+    rtc_cntl::{ResetReason, ResetWatchdog, RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
     soc::regi2c,
 };
 
@@ -1183,6 +1183,32 @@ pub enum SocResetReason {
     Cpu0JtagCpu   = 0x18,
 }
 
+impl SocResetReason {
+    /// Maps the reset reason to its chip-independent equivalent.
+    pub(crate) fn portable(self) -> ResetReason {
+        match self {
+            SocResetReason::ChipPowerOn => ResetReason::PowerOn,
+            SocResetReason::CoreSw | SocResetReason::Cpu0Sw => ResetReason::Software,
+            SocResetReason::CoreDeepSleep => ResetReason::DeepSleep,
+            SocResetReason::CoreSDIO => ResetReason::Sdio,
+            SocResetReason::CoreMwdt0 | SocResetReason::Cpu0Mwdt0 => {
+                ResetReason::Watchdog(ResetWatchdog::Timg0)
+            }
+            SocResetReason::CoreMwdt1 | SocResetReason::Cpu0Mwdt1 => {
+                ResetReason::Watchdog(ResetWatchdog::Timg1)
+            }
+            SocResetReason::CoreRtcWdt | SocResetReason::Cpu0RtcWdt | SocResetReason::SysRtcWdt => {
+                ResetReason::Watchdog(ResetWatchdog::Rtc)
+            }
+            SocResetReason::SysBrownOut => ResetReason::Brownout,
+            SocResetReason::SysSuperWdt => ResetReason::Watchdog(ResetWatchdog::Super),
+            SocResetReason::CoreEfuseCrc => ResetReason::EfuseCrc,
+            SocResetReason::CoreUsbUart | SocResetReason::CoreUsbJtag => ResetReason::Usb,
+            SocResetReason::Cpu0JtagCpu => ResetReason::Jtag,
+        }
+    }
+}
+
 pub(crate) fn rtc_clk_cpu_freq_set_xtal() {
     // rtc_clk_cpu_set_to_default_config
     let freq = RtcClock::xtal_freq().mhz();
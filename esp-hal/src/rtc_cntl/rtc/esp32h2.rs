@@ -3,7 +3,7 @@ use strum::FromRepr;
 use crate::{
     clock::clocks_ll::regi2c_write_mask,
     peripherals::{LP_AON, PMU},
-    rtc_cntl::{RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
+    rtc_cntl::{ResetReason, ResetWatchdog, RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
 };
 
 const I2C_PMU: u8 = 0x6d;
@@ -185,3 +185,28 @@ pub enum SocResetReason {
     /// Glitch on power resets the digital core
     CorePwrGlitch = 0x17,
 }
+
+impl SocResetReason {
+    /// Maps the reset reason to its chip-independent equivalent.
+    pub(crate) fn portable(self) -> ResetReason {
+        match self {
+            SocResetReason::ChipPowerOn => ResetReason::PowerOn,
+            SocResetReason::CoreSw | SocResetReason::Cpu0Sw => ResetReason::Software,
+            SocResetReason::CoreDeepSleep => ResetReason::DeepSleep,
+            SocResetReason::CoreMwdt0 | SocResetReason::Cpu0Mwdt0 => {
+                ResetReason::Watchdog(ResetWatchdog::Timg0)
+            }
+            SocResetReason::CoreMwdt1 | SocResetReason::Cpu0Mwdt1 => {
+                ResetReason::Watchdog(ResetWatchdog::Timg1)
+            }
+            SocResetReason::CoreRtcWdt | SocResetReason::Cpu0RtcWdt | SocResetReason::SysRtcWdt => {
+                ResetReason::Watchdog(ResetWatchdog::Rtc)
+            }
+            SocResetReason::SysBrownOut => ResetReason::Brownout,
+            SocResetReason::SysSuperWdt => ResetReason::Watchdog(ResetWatchdog::Super),
+            SocResetReason::SysClkGlitch | SocResetReason::CorePwrGlitch => ResetReason::Glitch,
+            SocResetReason::CoreEfuseCrc => ResetReason::EfuseCrc,
+            SocResetReason::CoreUsbUart | SocResetReason::CoreUsbJtag => ResetReason::Usb,
+        }
+    }
+}
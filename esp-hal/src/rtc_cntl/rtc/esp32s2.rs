@@ -2,7 +2,7 @@ use strum::FromRepr;
 
 use crate::{
     peripherals::LPWR,
-    rtc_cntl::{RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
+    rtc_cntl::{ResetReason, ResetWatchdog, RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
 };
 
 pub(crate) fn init() {
@@ -67,3 +67,27 @@ pub enum SocResetReason {
     /// eFuse CRC error resets the digital core
     CoreEfuseCrc  = 0x14,
 }
+
+impl SocResetReason {
+    /// Maps the reset reason to its chip-independent equivalent.
+    pub(crate) fn portable(self) -> ResetReason {
+        match self {
+            SocResetReason::ChipPowerOn => ResetReason::PowerOn,
+            SocResetReason::CoreSw | SocResetReason::Cpu0Sw => ResetReason::Software,
+            SocResetReason::CoreDeepSleep => ResetReason::DeepSleep,
+            SocResetReason::CoreMwdt0 | SocResetReason::Cpu0Mwdt0 => {
+                ResetReason::Watchdog(ResetWatchdog::Timg0)
+            }
+            SocResetReason::CoreMwdt1 | SocResetReason::Cpu0Mwdt1 => {
+                ResetReason::Watchdog(ResetWatchdog::Timg1)
+            }
+            SocResetReason::CoreRtcWdt | SocResetReason::Cpu0RtcWdt | SocResetReason::SysRtcWdt => {
+                ResetReason::Watchdog(ResetWatchdog::Rtc)
+            }
+            SocResetReason::SysBrownOut => ResetReason::Brownout,
+            SocResetReason::SysSuperWdt => ResetReason::Watchdog(ResetWatchdog::Super),
+            SocResetReason::SysClkGlitch => ResetReason::Glitch,
+            SocResetReason::CoreEfuseCrc => ResetReason::EfuseCrc,
+        }
+    }
+}
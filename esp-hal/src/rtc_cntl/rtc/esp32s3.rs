@@ -2,7 +2,7 @@ use strum::FromRepr;
 
 use crate::{
     peripherals::LPWR,
-    rtc_cntl::{RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
+    rtc_cntl::{ResetReason, ResetWatchdog, RtcCalSel, RtcClock, RtcFastClock, RtcSlowClock},
 };
 
 pub(crate) fn init() {
@@ -85,3 +85,28 @@ pub enum SocResetReason {
     /// Glitch on power resets the digital core
     CorePwrGlitch = 0x17,
 }
+
+impl SocResetReason {
+    /// Maps the reset reason to its chip-independent equivalent.
+    pub(crate) fn portable(self) -> ResetReason {
+        match self {
+            SocResetReason::ChipPowerOn => ResetReason::PowerOn,
+            SocResetReason::CoreSw | SocResetReason::CpuSw => ResetReason::Software,
+            SocResetReason::CoreDeepSleep => ResetReason::DeepSleep,
+            SocResetReason::CoreMwdt0 | SocResetReason::CpuMwdt0 => {
+                ResetReason::Watchdog(ResetWatchdog::Timg0)
+            }
+            SocResetReason::CoreMwdt1 | SocResetReason::CpuMwdt1 => {
+                ResetReason::Watchdog(ResetWatchdog::Timg1)
+            }
+            SocResetReason::CoreRtcWdt | SocResetReason::CpuRtcWdt | SocResetReason::SysRtcWdt => {
+                ResetReason::Watchdog(ResetWatchdog::Rtc)
+            }
+            SocResetReason::SysBrownOut => ResetReason::Brownout,
+            SocResetReason::SysSuperWdt => ResetReason::Watchdog(ResetWatchdog::Super),
+            SocResetReason::SysClkGlitch | SocResetReason::CorePwrGlitch => ResetReason::Glitch,
+            SocResetReason::CoreEfuseCrc => ResetReason::EfuseCrc,
+            SocResetReason::CoreUsbUart | SocResetReason::CoreUsbJtag => ResetReason::Usb,
+        }
+    }
+}
@@ -1,4 +1,11 @@
-use super::{Ext0WakeupSource, Ext1WakeupSource, TimerWakeupSource, WakeSource, WakeTriggers};
+use super::{
+    Ext0WakeupSource,
+    Ext1WakeupSource,
+    TimerWakeupSource,
+    TouchWakeupSource,
+    WakeSource,
+    WakeTriggers,
+};
 use crate::{
     gpio::{RtcFunction, RtcPin},
     peripherals::{BB, DPORT, I2S0, LPWR, NRX, RTC_IO},
@@ -139,6 +146,19 @@ impl<P: RtcPin> Drop for Ext0WakeupSource<P> {
     }
 }
 
+impl WakeSource for TouchWakeupSource<'_> {
+    fn apply(
+        &self,
+        _rtc: &Rtc<'_>,
+        triggers: &mut WakeTriggers,
+        sleep_config: &mut RtcSleepConfig,
+    ) {
+        // the touch FSM lives in the RTC peripheral domain, keep it powered
+        sleep_config.set_rtc_peri_pd_en(false);
+        triggers.set_touch(true);
+    }
+}
+
 impl WakeSource for Ext1WakeupSource<'_, '_> {
     fn apply(
         &self,
@@ -15,6 +15,8 @@
 //!    * `BT (Bluetooth) wake` - light sleep only
 
 use core::cell::RefCell;
+#[cfg(touch)]
+use core::marker::PhantomData;
 #[cfg(any(esp32, esp32c3, esp32s2, esp32s3, esp32c6, esp32c2))]
 use core::time::Duration;
 
@@ -205,6 +207,67 @@ impl<'a, 'b> Ext1WakeupSource<'a, 'b> {
     }
 }
 
+#[procmacros::doc_replace]
+/// Touch pad wake-up source.
+///
+/// Wakes the chip when any touch pad that has been set up with
+/// [`TouchPad::listen`](crate::touch::TouchPad::listen) crosses its
+/// threshold. The touch FSM has to run from its timer for the pads to be
+/// measured during sleep, which is why a [`Continuous`] [`Touch`] driver is
+/// required.
+///
+/// The RTC peripheral power domain stays powered while this source is
+/// enabled, which increases the current drawn during deep sleep.
+///
+/// Touch wake-up can be combined with a [`TimerWakeupSource`]; use
+/// [`wakeup_cause`](crate::rtc_cntl::wakeup_cause) after waking up to tell
+/// [`SleepSource::TouchPad`](crate::system::SleepSource::TouchPad) and
+/// [`SleepSource::Timer`](crate::system::SleepSource::Timer) apart.
+///
+/// ```rust, no_run
+/// # {before_snippet}
+/// # use core::time::Duration;
+/// # use esp_hal::delay::Delay;
+/// # use esp_hal::rtc_cntl::{sleep::{TimerWakeupSource, TouchWakeupSource}, Rtc};
+/// # use esp_hal::touch::{Touch, TouchPad};
+///
+/// let delay = Delay::new();
+/// let mut rtc = Rtc::new(peripherals.LPWR);
+///
+/// let touch = Touch::continuous_mode(peripherals.TOUCH, None);
+/// let mut touch_pad = TouchPad::new(peripherals.GPIO4, &touch);
+/// // A good threshold is 2/3 of the reading when the pad is not touched.
+/// let baseline = touch_pad.read();
+/// touch_pad.listen(baseline * 2 / 3);
+///
+/// let timer = TimerWakeupSource::new(Duration::from_secs(30));
+/// let touch_wakeup = TouchWakeupSource::new(&touch);
+///
+/// delay.delay_millis(100);
+/// rtc.sleep_deep(&[&timer, &touch_wakeup]);
+///
+/// # {after_snippet}
+/// ```
+///
+/// [`Continuous`]: crate::touch::Continuous
+/// [`Touch`]: crate::touch::Touch
+#[cfg(touch)]
+pub struct TouchWakeupSource<'a> {
+    _touch: PhantomData<&'a ()>,
+}
+
+#[cfg(touch)]
+impl<'a> TouchWakeupSource<'a> {
+    /// Creates a new touch pad wake-up source.
+    pub fn new<Dm: crate::DriverMode>(
+        _touch: &'a crate::touch::Touch<'_, crate::touch::Continuous, Dm>,
+    ) -> Self {
+        Self {
+            _touch: PhantomData,
+        }
+    }
+}
+
 #[procmacros::doc_replace]
 /// External wake-up source (Ext1).
 /// ```rust, no_run
@@ -0,0 +1,51 @@
+//! Registration of the deep sleep wake stub with the ROM bootloader.
+//!
+//! The ROM keeps the entry address of the stub, and a CRC of the memory the
+//! stub occupies, in the always-on registers. After waking up from deep sleep
+//! it recomputes the CRC, and only calls the stub if they match. The stub is
+//! registered right before entering deep sleep, so that the CRC is computed
+//! over the final contents of the memory.
+
+use portable_atomic::{AtomicUsize, Ordering};
+
+use super::{InvalidWakeStub, WakeStub};
+
+/// The address of the registered stub, or 0.
+static STUB: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" {
+    static _rtc_fast_text_start: u32;
+    static _rtc_fast_text_end: u32;
+
+    fn esp_rom_set_rtc_wake_addr(entry: usize, length: usize);
+}
+
+fn text_end() -> usize {
+    core::ptr::addr_of!(_rtc_fast_text_end) as usize
+}
+
+pub(super) fn set(stub: Option<WakeStub>) -> Result<(), InvalidWakeStub> {
+    let address = stub.map_or(0, |stub| stub as usize);
+
+    if address != 0 {
+        let text_start = core::ptr::addr_of!(_rtc_fast_text_start) as usize;
+        if !(text_start..text_end()).contains(&address) {
+            return Err(InvalidWakeStub);
+        }
+    }
+
+    STUB.store(address, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Passes the registered stub to the ROM, or clears the previous one.
+pub(super) fn arm() {
+    let address = STUB.load(Ordering::Relaxed);
+    let length = if address != 0 {
+        text_end() - address
+    } else {
+        0
+    };
+
+    unsafe { esp_rom_set_rtc_wake_addr(address, length) };
+}
@@ -16,7 +16,7 @@
 //!
 //! The driver supports two working modes:
 //! * Typical SHA (CPU-driven)
-//! * DMA-SHA (not supported yet)
+//! * DMA-SHA (see [`dma::ShaDma`])
 //!
 //! It provides functions to update the hash calculation with input data, finish
 //! the hash calculation and retrieve the resulting hash value. The SHA
@@ -43,40 +43,81 @@
 //! let mut output = [0u8; 32];
 //!
 //! while !source_data.is_empty() {
-//!     // All the HW Sha functions are infallible so unwrap is fine to use if
-//!     // you use block!
 //!     source_data = block!(hasher.update(source_data))?;
 //! }
 //!
-//! // Finish can be called as many times as desired to get multiple copies of
-//! // the output.
-//! block!(hasher.finish(output.as_mut_slice()))?;
+//! // Finishing the hash consumes the hasher. Call `reset` on the returned value
+//! // to compute a new hash.
+//! hasher.finish(output.as_mut_slice())?;
 //!
 //! # {after_snippet}
 //! ```
+//!
+//! ## Computing multiple hashes
+//!
+//! Every chip has a single SHA engine with a single hash context, so only one
+//! digest can be processed by the peripheral at a time. The driver enforces
+//! this: a digest borrows (or owns) the [`Sha`] driver, and there is only one
+//! `SHA` peripheral singleton to create the driver from.
+//!
+//! To compute several hashes at once, interleave them by saving the state of
+//! one digest into a [`Context`] with [`ShaDigest::save`], and later continuing
+//! it with [`ShaDigest::restore`]. This is not supported on the ESP32.
+//!
+//! ## Errors
+//!
+//! Feeding the peripheral by the CPU can't fail, but a failed DMA operation
+//! (see [`dma::ShaDma`]) can leave the peripheral in an unknown state. Until the
+//! driver is recovered, the digest functions return [`Error::BadState`] instead
+//! of waiting for the peripheral forever.
 
-use core::{borrow::Borrow, convert::Infallible, marker::PhantomData, mem::size_of};
+use core::{borrow::Borrow, marker::PhantomData, mem::size_of};
 
 /// Re-export digest for convenience
 pub use digest::Digest;
 
 use crate::{
     peripherals::SHA,
-    reg_access::{AlignmentHelper, SocDependentEndianess},
-    system::GenericPeripheralGuard,
+    reg_access::{AlignmentHelper, EndianessConverter, SocDependentEndianess},
+    system::{GenericPeripheralGuard, Peripheral, PeripheralClockControl},
 };
 
 // ESP32 quirks:
-// - Big endian text register (what about hash?)
+// - Big endian text and hash registers, handled by `SocDependentEndianess`
 // - Text and hash is in the same register -> needs an additional load operation to place the hash
 //   in the text registers
 // - Each algorithm has its own register cluster
 // - No support for interleaved operation
 
+#[procmacros::doc_replace]
 /// The SHA Accelerator driver instance
+///
+/// When the driver is dropped, the message and hash registers are cleared and the
+/// peripheral is reset, so that the last processed block and digest (which may be
+/// derived from secrets, e.g. HMAC keys) don't linger in the hardware. This is a
+/// best-effort measure: it does not happen if the driver is leaked, for example by
+/// [`core::mem::forget`].
+///
+/// The peripheral has a single hash context, so only one digest can be in
+/// progress at a time. See [Computing multiple hashes](crate::sha#computing-multiple-hashes)
+/// for how to interleave hashes.
+///
+/// ```rust,compile_fail
+/// # {before_snippet}
+/// # use esp_hal::sha::{Sha, Sha1, Sha256};
+/// let mut sha = Sha::new(peripherals.SHA);
+///
+/// let mut sha256 = sha.start::<Sha256>();
+/// // error[E0499]: cannot borrow `sha` as mutable more than once at a time
+/// let mut sha1 = sha.start::<Sha1>();
+/// nb::block!(sha256.update(b"HELLO, ESPRESSIF!"))?;
+/// # {after_snippet}
+/// ```
 pub struct Sha<'d> {
     sha: SHA<'d>,
-    _guard: GenericPeripheralGuard<{ crate::system::Peripheral::Sha as u8 }>,
+    _guard: GenericPeripheralGuard<{ Peripheral::Sha as u8 }>,
+    /// Set when a failed DMA operation left the peripheral in an unknown state.
+    bad_state: bool,
 }
 
 impl<'d> Sha<'d> {
@@ -84,7 +125,11 @@ impl<'d> Sha<'d> {
     pub fn new(sha: SHA<'d>) -> Self {
         let guard = GenericPeripheralGuard::new();
 
-        Self { sha, _guard: guard }
+        Self {
+            sha,
+            _guard: guard,
+            bad_state: false,
+        }
     }
 
     /// Start a new digest.
@@ -99,6 +144,294 @@ impl<'d> Sha<'d> {
         ShaDigest::new(self)
     }
 
+    #[procmacros::doc_replace]
+    /// Hashes `input` and returns the digest.
+    ///
+    /// `N` is typically [ShaAlgorithm::DIGEST_LENGTH], but a smaller value
+    /// can be given to get a "short hash".
+    ///
+    /// The CPU feeds the message to the peripheral, so `input` can be located
+    /// anywhere, including memory-mapped flash.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::BadState`] if a failed DMA operation left the
+    /// peripheral in an unknown state.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::sha::{Sha, Sha256};
+    ///
+    /// let mut sha = Sha::new(peripherals.SHA);
+    ///
+    /// let digest: [u8; 32] = sha.hash::<Sha256, _>(b"hello world")?;
+    /// # {after_snippet}
+    /// ```
+    pub fn hash<A: ShaAlgorithm, const N: usize>(
+        &mut self,
+        input: &[u8],
+    ) -> Result<[u8; N], Error> {
+        const {
+            assert!(
+                N <= A::DIGEST_LENGTH,
+                "The output can't be longer than the digest"
+            )
+        };
+
+        let mut output = [0; N];
+        hash_parts::<A>(self, [input], &mut output)?;
+        Ok(output)
+    }
+
+    #[procmacros::doc_replace]
+    /// Derive a key from input keying material using HKDF-SHA-256.
+    ///
+    /// This implements the extract-and-expand key derivation function of
+    /// [RFC 5869] on top of [`ShaHmac`]. `ikm` is the input keying material,
+    /// for example a shared secret, `salt` is an optional (possibly empty)
+    /// non-secret random value, and `info` binds the derived key to an
+    /// application-specific context. The derived key fills `output`.
+    ///
+    /// [RFC 5869]: https://www.rfc-editor.org/rfc/rfc5869
+    ///
+    /// ## Errors
+    ///
+    /// - [`KdfError::OutputTooLong`] if `output` is longer than 255 times the SHA-256 digest
+    ///   length, i.e. 8160 bytes.
+    /// - [`KdfError::Sha`] if a failed DMA operation left the peripheral in an unknown state.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::sha::Sha;
+    ///
+    /// let mut sha = Sha::new(peripherals.SHA);
+    ///
+    /// let mut key = [0u8; 16];
+    /// sha.derive_key(b"salt", b"session key", b"shared secret", &mut key)?;
+    /// # {after_snippet}
+    /// ```
+    #[cfg(feature = "sha-kdf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sha-kdf")))]
+    pub fn derive_key(
+        &mut self,
+        salt: &[u8],
+        info: &[u8],
+        ikm: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), KdfError> {
+        if output.len() > 255 * Sha256::DIGEST_LENGTH {
+            return Err(KdfError::OutputTooLong);
+        }
+
+        let mut prk = [0; 32];
+        let result = hkdf_extract::<Sha256>(self, salt, ikm, &mut prk)
+            .map_err(KdfError::from)
+            .and_then(|_| hkdf_expand::<Sha256>(self, &prk, info, output));
+        zeroize(&mut prk);
+
+        result
+    }
+
+    #[procmacros::doc_replace]
+    /// Extract a pseudorandom key from input keying material, the first step
+    /// of HKDF.
+    ///
+    /// This implements the extract step of [RFC 5869] on top of [`ShaHmac`].
+    /// `ikm` is the input keying material, for example a shared secret, and
+    /// `salt` is an optional (possibly empty) non-secret random value. The
+    /// returned key is meant to be passed to [`Self::hkdf_expand`]. `N` must be
+    /// [ShaAlgorithm::DIGEST_LENGTH].
+    ///
+    /// See [`Self::derive_key`] for a function that performs both steps with
+    /// SHA-256.
+    ///
+    /// [RFC 5869]: https://www.rfc-editor.org/rfc/rfc5869
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::BadState`] if a failed DMA operation left the
+    /// peripheral in an unknown state.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::sha::{Sha, Sha256};
+    ///
+    /// let mut sha = Sha::new(peripherals.SHA);
+    ///
+    /// let prk: [u8; 32] = sha.hkdf_extract::<Sha256, _>(b"salt", b"shared secret")?;
+    /// let mut key = [0u8; 16];
+    /// sha.hkdf_expand::<Sha256>(&prk, b"session key", &mut key)?;
+    /// # {after_snippet}
+    /// ```
+    #[cfg(feature = "sha-kdf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sha-kdf")))]
+    pub fn hkdf_extract<A: ShaAlgorithm, const N: usize>(
+        &mut self,
+        salt: &[u8],
+        ikm: &[u8],
+    ) -> Result<[u8; N], Error> {
+        const {
+            assert!(
+                N == A::DIGEST_LENGTH,
+                "The pseudorandom key must be as long as the digest"
+            )
+        };
+
+        let mut prk = [0; N];
+        hkdf_extract::<A>(self, salt, ikm, &mut prk)?;
+        Ok(prk)
+    }
+
+    /// Expand a pseudorandom key into output keying material, the second step
+    /// of HKDF.
+    ///
+    /// This implements the expand step of [RFC 5869] on top of [`ShaHmac`].
+    /// `prk` is a pseudorandom key of at least [ShaAlgorithm::DIGEST_LENGTH]
+    /// bytes, usually the output of [`Self::hkdf_extract`], and `info` binds
+    /// the derived key to an application-specific context. The derived key
+    /// fills `output`.
+    ///
+    /// [RFC 5869]: https://www.rfc-editor.org/rfc/rfc5869
+    ///
+    /// ## Errors
+    ///
+    /// - [`KdfError::PrkTooShort`] if `prk` is shorter than the digest length of the algorithm.
+    /// - [`KdfError::OutputTooLong`] if `output` is longer than 255 times the digest length of
+    ///   the algorithm.
+    /// - [`KdfError::Sha`] if a failed DMA operation left the peripheral in an unknown state.
+    #[cfg(feature = "sha-kdf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sha-kdf")))]
+    pub fn hkdf_expand<A: ShaAlgorithm>(
+        &mut self,
+        prk: &[u8],
+        info: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), KdfError> {
+        if prk.len() < A::DIGEST_LENGTH {
+            return Err(KdfError::PrkTooShort);
+        }
+
+        hkdf_expand::<A>(self, prk, info, output)
+    }
+
+    #[procmacros::doc_replace]
+    /// Derive a key from a password using PBKDF2.
+    ///
+    /// This implements PBKDF2 of [RFC 8018], with HMAC using the given
+    /// algorithm as the pseudorandom function. Every iteration computes an
+    /// HMAC on the accelerator, so a high iteration count is considerably
+    /// faster than with a software implementation. The derived key fills
+    /// `output`.
+    ///
+    /// A password longer than the block size of the algorithm is hashed once
+    /// before the iterations, so it takes slightly longer than a short one.
+    ///
+    /// [RFC 8018]: https://www.rfc-editor.org/rfc/rfc8018#section-5.2
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::BadState`] if a failed DMA operation left the
+    /// peripheral in an unknown state.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use core::num::NonZeroU32;
+    ///
+    /// use esp_hal::sha::{Sha, Sha256};
+    ///
+    /// let mut sha = Sha::new(peripherals.SHA);
+    ///
+    /// let mut key = [0u8; 32];
+    /// let iterations = NonZeroU32::new(4096).unwrap();
+    /// sha.pbkdf2_hmac::<Sha256>(b"password", b"salt", iterations, &mut key)?;
+    /// # {after_snippet}
+    /// ```
+    #[cfg(feature = "sha-kdf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sha-kdf")))]
+    pub fn pbkdf2_hmac<A: ShaAlgorithm>(
+        &mut self,
+        password: &[u8],
+        salt: &[u8],
+        iterations: core::num::NonZeroU32,
+        output: &mut [u8],
+    ) -> Result<(), Error> {
+        let mut previous = [0; MAX_DIGEST_LENGTH];
+        let previous = &mut previous[..A::DIGEST_LENGTH];
+        let mut current = [0; MAX_DIGEST_LENGTH];
+        let current = &mut current[..A::DIGEST_LENGTH];
+
+        let mut hmac = ShaHmac::<A>::new(self, password)?;
+        let result = pbkdf2_blocks(&mut hmac, salt, iterations, output, previous, current);
+
+        zeroize(previous);
+        zeroize(current);
+
+        result
+    }
+
+    #[procmacros::doc_replace]
+    /// Verifies that `region` hashes to the digest stored in `signature_block`.
+    ///
+    /// The first [`ShaAlgorithm::DIGEST_LENGTH`] bytes of `signature_block`
+    /// hold the expected digest, any remaining bytes are ignored. This matches
+    /// signed firmware layouts, where the signature block is stored right
+    /// after the signed region. Both slices may point to memory-mapped flash,
+    /// the region is hashed in place without being copied to RAM.
+    ///
+    /// The digests are compared in constant time. Returns `Ok(true)` if they
+    /// are equal, like [`ShaDigest::finish_verify`].
+    ///
+    /// ## Errors
+    ///
+    /// - [`VerifyError::SignatureBlockTooShort`] if `signature_block` can't hold a digest.
+    /// - [`VerifyError::SignatureInRegion`] if `region` and the digest overlap. The signature block
+    ///   must not be part of the signed region.
+    /// - [`VerifyError::Sha`] if a failed DMA operation left the peripheral in an unknown state.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::sha::{Sha, Sha256};
+    ///
+    /// # let image = [0u8; 64];
+    /// let mut sha = Sha::new(peripherals.SHA);
+    ///
+    /// // The last 32 bytes of the image are the digest of the rest.
+    /// let (region, signature_block) = image.split_at(image.len() - 32);
+    /// let valid = sha.verify_signed_region::<Sha256>(region, signature_block)?;
+    /// # {after_snippet}
+    /// ```
+    pub fn verify_signed_region<A: ShaAlgorithm>(
+        &mut self,
+        region: &[u8],
+        signature_block: &[u8],
+    ) -> Result<bool, VerifyError> {
+        let Some(expected) = signature_block.get(..A::DIGEST_LENGTH) else {
+            return Err(VerifyError::SignatureBlockTooShort);
+        };
+
+        let region_range = region.as_ptr_range();
+        let expected_range = expected.as_ptr_range();
+        if region_range.start < expected_range.end && expected_range.start < region_range.end {
+            return Err(VerifyError::SignatureInRegion);
+        }
+
+        let mut actual = [0; MAX_DIGEST_LENGTH];
+        let actual = &mut actual[..A::DIGEST_LENGTH];
+        hash_parts::<A>(self, [region], actual)?;
+
+        Ok(digests_equal(actual, expected))
+    }
+
     /// Returns true if the hardware is processing the next message.
     fn is_busy(&self, algo: ShaAlgorithmKind) -> bool {
         algo.is_busy(&self.sha)
@@ -113,11 +446,12 @@ impl<'d> Sha<'d> {
         }
     }
 
-    fn write_data<'a>(
-        &self,
-        state: &mut DigestState,
-        incoming: &'a [u8],
-    ) -> nb::Result<&'a [u8], Infallible> {
+    /// Makes room in the message buffer for new data.
+    fn prepare_write(&self, state: &mut DigestState) -> nb::Result<(), Error> {
+        if self.bad_state {
+            return Err(nb::Error::Other(Error::BadState));
+        }
+
         if state.message_buffer_is_full {
             if self.is_busy(state.algorithm) {
                 // The message buffer is full and the hardware is still processing the previous
@@ -131,6 +465,16 @@ impl<'d> Sha<'d> {
             }
         }
 
+        Ok(())
+    }
+
+    fn write_data<'a>(
+        &self,
+        state: &mut DigestState,
+        incoming: &'a [u8],
+    ) -> nb::Result<&'a [u8], Error> {
+        self.prepare_write(state)?;
+
         let chunk_len = state.algorithm.chunk_length();
         let mod_cursor = state.cursor % chunk_len;
 
@@ -152,7 +496,7 @@ impl<'d> Sha<'d> {
         Ok(remaining)
     }
 
-    fn process_buffer_or_wait(&self, state: &mut DigestState) -> nb::Result<(), Infallible> {
+    fn process_buffer_or_wait(&self, state: &mut DigestState) -> nb::Result<(), Error> {
         if self.is_busy(state.algorithm) {
             // The message buffer is full and the hardware is still processing the
             // previous message. There's nothing to be done besides wait for the
@@ -167,7 +511,11 @@ impl<'d> Sha<'d> {
         Ok(())
     }
 
-    fn finish(&self, state: &mut DigestState, output: &mut [u8]) -> nb::Result<(), Infallible> {
+    fn finish(&self, state: &mut DigestState, output: &mut [u8]) -> nb::Result<(), Error> {
+        if self.bad_state {
+            return Err(nb::Error::Other(Error::BadState));
+        }
+
         if state.message_buffer_is_full {
             // Wait for the hardware to become idle.
             if self.is_busy(state.algorithm) {
@@ -261,10 +609,12 @@ impl<'d> Sha<'d> {
                 while self.is_busy(state.algorithm) {}
             }
 
+            // Never read past the digest, which is 64 bytes long for SHA-512.
+            let digest_len = output.len().min(state.algorithm.digest_length());
             state.alignment_helper.volatile_read_regset(
                 h_mem(&self.sha, 0),
-                output,
-                core::cmp::min(output.len(), 32),
+                &mut output[..digest_len],
+                digest_len,
             );
 
             state.first_run = true;
@@ -282,10 +632,108 @@ impl<'d> Sha<'d> {
         &self,
         state: &mut DigestState,
         incoming: &'a [u8],
-    ) -> nb::Result<&'a [u8], Infallible> {
+    ) -> nb::Result<&'a [u8], Error> {
         state.finalize_state = FinalizeState::default();
         self.write_data(state, incoming)
     }
+
+    fn update_words<'a>(
+        &self,
+        state: &mut DigestState,
+        incoming: &'a [u32],
+    ) -> nb::Result<&'a [u32], Error> {
+        state.finalize_state = FinalizeState::default();
+
+        if !state.cursor.is_multiple_of(size_of::<u32>()) {
+            // The alignment helper holds a partial word, so the words need to be shifted
+            // byte by byte.
+            self.write_all(state, bytemuck::cast_slice(incoming))?;
+            return Ok(&[]);
+        }
+
+        self.prepare_write(state)?;
+
+        let chunk_words = state.algorithm.chunk_length() / size_of::<u32>();
+        let offset = (state.cursor / size_of::<u32>()) % chunk_words;
+        let (words, remaining) = incoming.split_at(incoming.len().min(chunk_words - offset));
+
+        let m_mem = m_mem(&self.sha, 0);
+        for (i, word) in words.iter().enumerate() {
+            let word = SocDependentEndianess::u32_from_bytes(word.to_ne_bytes());
+            unsafe { m_mem.add(offset + i).write_volatile(word) };
+        }
+
+        state.cursor += size_of_val(words);
+
+        if offset + words.len() == chunk_words {
+            // Message is full now. We don't have to wait for the result, just start the processing
+            // or set the flag.
+            _ = self.process_buffer_or_wait(state);
+        }
+
+        Ok(remaining)
+    }
+
+    fn update_iter(
+        &self,
+        state: &mut DigestState,
+        incoming: impl Iterator<Item = u8>,
+    ) -> Result<(), Error> {
+        state.finalize_state = FinalizeState::default();
+
+        // Collect the bytes into words, so that the alignment helper doesn't have to be
+        // invoked for every single byte.
+        let mut word = [0u8; size_of::<u32>()];
+        let mut len = 0;
+        for byte in incoming {
+            word[len] = byte;
+            len += 1;
+            if len == word.len() {
+                self.write_all(state, &word)?;
+                len = 0;
+            }
+        }
+        self.write_all(state, &word[..len])
+    }
+
+    fn write_all(&self, state: &mut DigestState, mut incoming: &[u8]) -> Result<(), Error> {
+        while !incoming.is_empty() {
+            incoming = nb::block!(self.write_data(state, incoming))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Sha<'_> {
+    fn drop(&mut self) {
+        // Writing the registers while an operation is in progress would corrupt it, and the
+        // data would be overwritten by the hardware afterwards. A peripheral in a bad state may
+        // never become idle, but the reset below stops it anyway.
+        while !self.bad_state
+            && ShaAlgorithmKind::ALL
+                .iter()
+                .any(|algo| algo.is_busy(&self.sha))
+        {}
+
+        let regs = self.sha.register_block();
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                for reg in regs.text_iter() {
+                    reg.write(|w| unsafe { w.bits(0) });
+                }
+            } else {
+                for reg in regs.m_mem_iter() {
+                    reg.write(|w| unsafe { w.bits(0) });
+                }
+                for reg in regs.h_mem_iter() {
+                    reg.write(|w| unsafe { w.bits(0) });
+                }
+            }
+        }
+
+        PeripheralClockControl::reset(Peripheral::Sha);
+    }
 }
 
 impl crate::private::Sealed for Sha<'_> {}
@@ -404,25 +852,249 @@ impl<'d, A: ShaAlgorithm, S: Borrow<Sha<'d>>> ShaDigest<'d, A, S> {
         A::ALGORITHM_KIND.is_busy(&self.sha.borrow().sha)
     }
 
+    /// Returns the number of message bytes added to the digest so far.
+    ///
+    /// This includes the bytes that are buffered and not hashed yet, so it
+    /// can be compared with the length of the message to report progress.
+    /// Restoring a digest from a [`Context`] keeps the count.
+    pub fn bytes_processed(&self) -> u64 {
+        self.state.cursor as u64
+    }
+
     /// Updates the SHA digest with the provided data buffer.
-    pub fn update<'a>(&mut self, incoming: &'a [u8]) -> nb::Result<&'a [u8], Infallible> {
+    ///
+    /// Returns the part of `incoming` that has not been written to the
+    /// peripheral yet.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::BadState`] if a failed DMA operation left the
+    /// peripheral in an unknown state.
+    pub fn update<'a>(&mut self, incoming: &'a [u8]) -> nb::Result<&'a [u8], Error> {
         self.sha.borrow().update(&mut self.state, incoming)
     }
 
-    /// Finish of the calculation (if not already) and copy result to output
-    /// After `finish()` is called `update()`s will contribute to a new hash
-    /// which can be calculated again with `finish()`.
+    /// Updates the SHA digest with the provided words.
+    ///
+    /// The words are hashed in their in-memory byte order, so the result is
+    /// the same as passing the bytes of `incoming` to [`Self::update`]. Data
+    /// that is already stored in words is copied into the peripheral directly,
+    /// without the byte-wise realignment of [`Self::update`].
+    ///
+    /// Returns the part of `incoming` that has not been written to the
+    /// peripheral yet.
+    ///
+    /// The fast path is only available if the total length of the data hashed
+    /// so far is a multiple of 4 bytes. Otherwise, the words are written
+    /// through the byte path, and this function blocks until all of them have
+    /// been written.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::BadState`] if a failed DMA operation left the
+    /// peripheral in an unknown state.
+    pub fn update_words<'a>(&mut self, incoming: &'a [u32]) -> nb::Result<&'a [u32], Error> {
+        self.sha.borrow().update_words(&mut self.state, incoming)
+    }
+
+    /// Updates the SHA digest with the bytes produced by an iterator.
+    ///
+    /// This is useful when the data is generated on the fly, for example while
+    /// decompressing or decrypting a stream, and is not available as a
+    /// contiguous slice. The resulting digest is the same as if the bytes
+    /// were passed to [`Self::update`].
+    ///
+    /// Unlike [`Self::update`], this function blocks until every byte of the
+    /// iterator has been written to the hardware.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::BadState`] if a failed DMA operation left the
+    /// peripheral in an unknown state.
+    pub fn update_iter(&mut self, incoming: impl Iterator<Item = u8>) -> Result<(), Error> {
+        self.sha.borrow().update_iter(&mut self.state, incoming)
+    }
+
+    /// Finish the calculation and copy the result to `output`.
+    ///
+    /// This consumes the digest, so that no more data can be added to a
+    /// finished hash by accident. To compute a new hash with the same
+    /// algorithm, call [`FinishedDigest::reset`] on the returned value.
     ///
     /// Typically, output is expected to be the size of
     /// [ShaAlgorithm::DIGEST_LENGTH], but smaller inputs can be given to
     /// get a "short hash"
-    pub fn finish(&mut self, output: &mut [u8]) -> nb::Result<(), Infallible> {
-        self.sha.borrow().finish(&mut self.state, output)
+    ///
+    /// The digest is written in the big-endian byte order defined by
+    /// FIPS 180-4, on every chip, so it can be compared with the output of
+    /// other implementations as-is.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::BadState`] if a failed DMA operation left the
+    /// peripheral in an unknown state. The digest, and the driver if it is
+    /// owned by the digest, are dropped in this case.
+    pub fn finish(mut self, output: &mut [u8]) -> Result<FinishedDigest<'d, A, S>, Error> {
+        nb::block!(self.sha.borrow().finish(&mut self.state, output))?;
+
+        Ok(FinishedDigest {
+            sha: self.sha,
+            phantom: PhantomData,
+        })
+    }
+
+    #[procmacros::doc_replace]
+    /// Finish the hash calculation and compare the digest with `expected`.
+    ///
+    /// The digests are compared in constant time, so that the time taken
+    /// does not reveal how many bytes of `expected` are correct. Returns
+    /// `Ok(true)` if the digests are equal, and `Ok(false)` if they are not.
+    ///
+    /// This consumes the digest, and the driver if it is owned by the digest.
+    /// Use [`Self::finish`] to keep the driver.
+    ///
+    /// ## Errors
+    ///
+    /// - [`VerifyError::WrongDigestLength`] if `expected` is not [`ShaAlgorithm::DIGEST_LENGTH`]
+    ///   bytes long.
+    /// - [`VerifyError::Sha`] if a failed DMA operation left the peripheral in an unknown state.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::sha::{Sha, Sha256};
+    ///
+    /// # let expected = [0u8; 32];
+    /// let mut sha = Sha::new(peripherals.SHA);
+    ///
+    /// let mut hasher = sha.start::<Sha256>();
+    /// nb::block!(hasher.update(b"firmware image"))?;
+    /// let valid = hasher.finish_verify(&expected)?;
+    /// # {after_snippet}
+    /// ```
+    pub fn finish_verify(self, expected: &[u8]) -> Result<bool, VerifyError> {
+        if expected.len() != A::DIGEST_LENGTH {
+            return Err(VerifyError::WrongDigestLength);
+        }
+
+        let mut actual = [0; MAX_DIGEST_LENGTH];
+        let actual = &mut actual[..A::DIGEST_LENGTH];
+        self.finish(actual)?;
+
+        Ok(digests_equal(actual, expected))
+    }
+
+    #[procmacros::doc_replace]
+    /// Finish the hash calculation after checking that exactly `total_bytes`
+    /// bytes have been fed to the digest.
+    ///
+    /// This is useful when the length of the message is known up front and
+    /// the message is fed in chunks, for example from a DMA pipeline: a chunk
+    /// that was lost or truncated on the way would otherwise silently produce
+    /// the digest of a shorter message.
+    ///
+    /// Like [`Self::finish`], this consumes the digest.
+    ///
+    /// ## Errors
+    ///
+    /// - [`FinishError::LengthMismatch`] if the number of bytes passed to
+    ///   [`Self::update`] and [`Self::update_iter`] is not `total_bytes`. The
+    ///   digest is finished regardless, and `output` contains the digest of
+    ///   the bytes that were actually fed.
+    /// - [`FinishError::Sha`] with [`Error::BadState`] if a failed DMA
+    ///   operation left the peripheral in an unknown state, like
+    ///   [`Self::finish`].
+    ///
+    /// The digest, and the driver if it is owned by the digest, are dropped
+    /// if an error is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::sha::{Sha, Sha256};
+    ///
+    /// let mut sha = Sha::new(peripherals.SHA);
+    /// let mut output = [0u8; 32];
+    ///
+    /// let mut hasher = sha.start::<Sha256>();
+    /// for chunk in [b"HELLO, ", b"WORLD! "] {
+    ///     let mut remaining = &chunk[..];
+    ///     while !remaining.is_empty() {
+    ///         remaining = nb::block!(hasher.update(remaining))?;
+    ///     }
+    /// }
+    /// hasher.finish_with_total_len(&mut output, 14)?;
+    /// # {after_snippet}
+    /// ```
+    pub fn finish_with_total_len(
+        self,
+        output: &mut [u8],
+        total_bytes: usize,
+    ) -> Result<FinishedDigest<'d, A, S>, FinishError> {
+        let actual = self.state.cursor;
+        let finished = self.finish(output)?;
+
+        if actual != total_bytes {
+            return Err(FinishError::LengthMismatch {
+                expected: total_bytes,
+                actual,
+            });
+        }
+
+        Ok(finished)
+    }
+
+    /// Reads the intermediate hash state, without finishing the digest.
+    ///
+    /// This is the raw chaining value of the hash function after the last
+    /// complete message block, **not** a valid digest of the data fed so far:
+    /// the bytes of an incomplete block, and the padding, are not included.
+    /// It is useful to compare the streaming implementation against the
+    /// intermediate values of a reference implementation. The digest can be
+    /// updated and finished normally afterwards.
+    ///
+    /// The chaining value is 20 bytes long for SHA-1, 32 bytes long for
+    /// SHA-224 and SHA-256, and 64 bytes long for the algorithms with
+    /// 1024-bit blocks. At most that many bytes, rounded down to whole 32-bit
+    /// words, are written to `out`, and the number of bytes written is
+    /// returned. The content is unspecified until
+    /// the first block has been fed to the digest.
+    #[cfg(not(esp32))]
+    pub fn peek_state(&mut self, out: &mut [u8]) -> nb::Result<usize, Error> {
+        if self.sha.borrow().bad_state {
+            return Err(nb::Error::Other(Error::BadState));
+        }
+
+        if self.is_busy() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        if self.state.message_buffer_is_full {
+            // Process the pending block, so that the state covers every complete block.
+            self.sha.borrow().process_buffer(&mut self.state);
+            self.state.message_buffer_is_full = false;
+            return Err(nb::Error::WouldBlock);
+        }
+
+        let len = out.len().min(self.state.algorithm.state_length()) & !3;
+        self.state.alignment_helper.volatile_read_regset(
+            h_mem(&self.sha.borrow().sha, 0),
+            &mut out[..len],
+            len,
+        );
+
+        Ok(len)
     }
 
     /// Save the current state of the digest for later continuation.
     #[cfg(not(esp32))]
-    pub fn save(&mut self, context: &mut Context<A>) -> nb::Result<(), Infallible> {
+    pub fn save(&mut self, context: &mut Context<A>) -> nb::Result<(), Error> {
+        if self.sha.borrow().bad_state {
+            return Err(nb::Error::Other(Error::BadState));
+        }
+
         if self.is_busy() {
             return Err(nb::Error::WouldBlock);
         }
@@ -454,8 +1126,73 @@ impl<'d, A: ShaAlgorithm, S: Borrow<Sha<'d>>> ShaDigest<'d, A, S> {
     }
 }
 
+#[procmacros::doc_replace]
+/// A digest whose result has been read out by [`ShaDigest::finish`].
+///
+/// A finished digest can't be updated: call [`reset`](Self::reset) to start
+/// a new hash, or [`release`](Self::release) to get the peripheral back.
+///
+/// ```rust,compile_fail
+/// # {before_snippet}
+/// # use esp_hal::sha::{Sha, Sha256};
+/// let mut sha = Sha::new(peripherals.SHA);
+/// let mut output = [0u8; 32];
+///
+/// let mut hasher = sha.start::<Sha256>();
+/// nb::block!(hasher.update(b"HELLO, "))?;
+/// let mut hasher = hasher.finish(&mut output)?;
+/// // error[E0599]: no method named `update` found for struct `FinishedDigest`
+/// nb::block!(hasher.update(b"ESPRESSIF!"))?;
+/// # {after_snippet}
+/// ```
+pub struct FinishedDigest<'d, A, S: Borrow<Sha<'d>>> {
+    sha: S,
+    phantom: PhantomData<(&'d (), A)>,
+}
+
+impl<'d, A: ShaAlgorithm, S: Borrow<Sha<'d>>> FinishedDigest<'d, A, S> {
+    /// Starts a new digest with the same algorithm.
+    pub fn reset(self) -> ShaDigest<'d, A, S> {
+        ShaDigest::new(self.sha)
+    }
+
+    /// Returns the peripheral.
+    pub fn release(self) -> S {
+        self.sha
+    }
+}
+
 #[cfg(not(esp32))]
+#[procmacros::doc_replace]
 /// Context for a SHA Accelerator driver instance
+///
+/// Holds the state of a saved digest, so that the peripheral can be used for
+/// other hashes in the meantime.
+///
+/// ```rust, no_run
+/// # {before_snippet}
+/// use esp_hal::sha::{Context, Sha, Sha1, Sha256, ShaDigest};
+///
+/// let mut sha = Sha::new(peripherals.SHA);
+/// let mut sha1 = Context::<Sha1>::new();
+/// let mut sha256 = Context::<Sha256>::new();
+///
+/// for chunk in [b"HELLO, ".as_slice(), b"ESPRESSIF!".as_slice()] {
+///     let mut digest = ShaDigest::restore(&mut sha, &mut sha1);
+///     digest.update_iter(chunk.iter().copied())?;
+///     nb::block!(digest.save(&mut sha1))?;
+///
+///     let mut digest = ShaDigest::restore(&mut sha, &mut sha256);
+///     digest.update_iter(chunk.iter().copied())?;
+///     nb::block!(digest.save(&mut sha256))?;
+/// }
+///
+/// let mut sha1_output = [0u8; 20];
+/// ShaDigest::restore(&mut sha, &mut sha1).finish(&mut sha1_output)?;
+/// let mut sha256_output = [0u8; 32];
+/// ShaDigest::restore(&mut sha, &mut sha256).finish(&mut sha256_output)?;
+/// # {after_snippet}
+/// ```
 #[derive(Debug, Clone)]
 pub struct Context<A: ShaAlgorithm> {
     state: DigestState,
@@ -494,25 +1231,1318 @@ impl<A: ShaAlgorithm> Default for Context<A> {
     }
 }
 
-/// This trait encapsulates the configuration for a specific SHA algorithm.
-pub trait ShaAlgorithm: crate::private::Sealed {
-    /// Constant containing the name of the algorithm as a string.
-    const ALGORITHM: &'static str;
+/// The largest block size of all SHA algorithms, in bytes.
+const MAX_CHUNK_LENGTH: usize = 128;
 
-    /// Constant containing the kind of the algorithm.
-    const ALGORITHM_KIND: ShaAlgorithmKind;
+/// The largest digest size of all SHA algorithms, in bytes.
+const MAX_DIGEST_LENGTH: usize = 64;
 
-    /// The length of the chunk that the algorithm processes at a time.
-    ///
-    /// For example, in SHA-256, this would typically be 64 bytes.
-    const CHUNK_LENGTH: usize;
+#[procmacros::doc_replace]
+/// Keyed-hash message authentication code (HMAC, RFC 2104) computed on top of
+/// the SHA accelerator.
+///
+/// This is useful on devices that don't have a dedicated HMAC peripheral, or
+/// when the key is not stored in an eFuse key block. On devices that do, the
+/// `hmac` module can be used to compute HMAC-SHA-256 with a key that is not
+/// readable by software.
+///
+/// The key is cleared from memory when this object is dropped.
+///
+/// ## Performance
+///
+/// Each MAC is computed in two passes: the inner hash over `(K ^ ipad) ||
+/// message`, and the outer hash over `(K ^ opad) || inner hash`. The
+/// peripheral is not re-initialized between the two passes, only the mode
+/// register is written to start the second hash.
+///
+/// On devices that support saving the state of a digest (see [`Context`]),
+/// the states after the two key blocks are computed once, in
+/// [`ShaHmac::new`], and restored for every MAC. Signing a message then
+/// costs as many block operations as hashing the message, plus one or two
+/// blocks for the outer hash. Reusing an instance for many messages with
+/// the same key is cheaper than creating a new one each time. On the ESP32,
+/// both key blocks are hashed again for every MAC, which adds two block
+/// operations.
+///
+/// ## Example
+///
+/// ```rust, no_run
+/// # {before_snippet}
+/// use esp_hal::sha::{Sha, Sha256, ShaHmac};
+///
+/// let mut sha = Sha::new(peripherals.SHA);
+/// let mut hmac = ShaHmac::<Sha256>::new(&mut sha, b"key")?;
+///
+/// let mac: [u8; 32] = hmac.sign(b"The quick brown fox jumps over the lazy dog")?;
+/// # {after_snippet}
+/// ```
+pub struct ShaHmac<'a, 'd, A: ShaAlgorithm> {
+    sha: &'a mut Sha<'d>,
+    /// The key, padded with zeros (or hashed, if it's longer than a block) to the block size.
+    #[cfg(esp32)]
+    key: [u8; MAX_CHUNK_LENGTH],
+    /// The state after hashing `K ^ ipad`.
+    #[cfg(not(esp32))]
+    inner: Context<A>,
+    /// The state after hashing `K ^ opad`.
+    #[cfg(not(esp32))]
+    outer: Context<A>,
+    phantom: PhantomData<A>,
+}
 
-    /// The length of the resulting digest produced by the algorithm.
-    ///
-    /// For example, in SHA-256, this would be 32 bytes.
-    const DIGEST_LENGTH: usize;
+impl<'a, 'd, A: ShaAlgorithm> ShaHmac<'a, 'd, A> {
+    const IPAD: u8 = 0x36;
+    const OPAD: u8 = 0x5c;
 
-    #[doc(hidden)]
+    /// Creates a new HMAC instance with the given key.
+    ///
+    /// Keys longer than the block size of the algorithm are hashed first, as
+    /// specified by RFC 2104.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::BadState`] if a failed DMA operation left the
+    /// peripheral in an unknown state.
+    pub fn new(sha: &'a mut Sha<'d>, key: &[u8]) -> Result<Self, Error> {
+        let mut padded_key = [0; MAX_CHUNK_LENGTH];
+        if key.len() > A::CHUNK_LENGTH {
+            hash_parts::<A>(sha, [key], &mut padded_key[..A::DIGEST_LENGTH])?;
+        } else {
+            padded_key[..key.len()].copy_from_slice(key);
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                Ok(Self {
+                    sha,
+                    key: padded_key,
+                    phantom: PhantomData,
+                })
+            } else {
+                // The contexts are created empty, so that dropping `hmac` clears whatever
+                // was saved if an error occurs half-way.
+                let mut hmac = Self {
+                    sha,
+                    inner: Context::new(),
+                    outer: Context::new(),
+                    phantom: PhantomData,
+                };
+                let mut result = Ok(());
+                for (context, pad_byte) in
+                    [(&mut hmac.inner, Self::IPAD), (&mut hmac.outer, Self::OPAD)]
+                {
+                    let mut pad = Self::pad(&padded_key, pad_byte);
+                    let mut digest = hmac.sha.start::<A>();
+                    result = update_parts(&mut digest, [&pad[..A::CHUNK_LENGTH]])
+                        .and_then(|_| nb::block!(digest.save(context)));
+                    zeroize(&mut pad);
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                zeroize(&mut padded_key);
+
+                result.map(|_| hmac)
+            }
+        }
+    }
+
+    /// Returns `key ^ pad_byte`.
+    fn pad(key: &[u8; MAX_CHUNK_LENGTH], pad_byte: u8) -> [u8; MAX_CHUNK_LENGTH] {
+        key.map(|byte| byte ^ pad_byte)
+    }
+
+    /// Computes the message authentication code of `message`.
+    ///
+    /// `N` is typically [ShaAlgorithm::DIGEST_LENGTH], but a smaller value can
+    /// be given to get a truncated MAC.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::BadState`] if a failed DMA operation left the
+    /// peripheral in an unknown state.
+    pub fn sign<const N: usize>(&mut self, message: &[u8]) -> Result<[u8; N], Error> {
+        const {
+            assert!(
+                N <= A::DIGEST_LENGTH,
+                "The output can't be longer than the digest"
+            )
+        };
+
+        let mut output = [0; N];
+        self.sign_parts(&[message], &mut output)?;
+
+        Ok(output)
+    }
+
+    /// Computes the MAC of the concatenation of `message` into `output`.
+    fn sign_parts(&mut self, message: &[&[u8]], output: &mut [u8]) -> Result<(), Error> {
+        let mut inner = [0; MAX_DIGEST_LENGTH];
+        let inner = &mut inner[..A::DIGEST_LENGTH];
+
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                // H((K ^ ipad) || message)
+                let mut pad = Self::pad(&self.key, Self::IPAD);
+                let mut result = hash_parts::<A>(
+                    self.sha,
+                    [&pad[..A::CHUNK_LENGTH]].into_iter().chain(message.iter().copied()),
+                    inner,
+                );
+
+                // H((K ^ opad) || H((K ^ ipad) || message))
+                if result.is_ok() {
+                    pad = Self::pad(&self.key, Self::OPAD);
+                    result = hash_parts::<A>(self.sha, [&pad[..A::CHUNK_LENGTH], &*inner], output);
+                }
+                zeroize(&mut pad);
+
+                result
+            } else {
+                // H((K ^ ipad) || message)
+                let mut digest = ShaDigest::restore(&mut *self.sha, &mut self.inner);
+                update_parts(&mut digest, message.iter().copied())?;
+                digest.finish(inner)?;
+
+                // H((K ^ opad) || H((K ^ ipad) || message))
+                let mut digest = ShaDigest::restore(&mut *self.sha, &mut self.outer);
+                update_parts(&mut digest, [&*inner])?;
+                digest.finish(output)?;
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Computes the HKDF pseudorandom key of `ikm` into `prk`, see RFC 5869.
+#[cfg(feature = "sha-kdf")]
+fn hkdf_extract<A: ShaAlgorithm>(
+    sha: &mut Sha<'_>,
+    salt: &[u8],
+    ikm: &[u8],
+    prk: &mut [u8],
+) -> Result<(), Error> {
+    // PRK = HMAC(salt, IKM). An empty salt is equivalent to HASH_LEN zeros, as HMAC pads the
+    // key with zeros anyway.
+    ShaHmac::<A>::new(sha, salt)?.sign_parts(&[ikm], prk)
+}
+
+/// Expands the HKDF pseudorandom key `prk` into `output`, see RFC 5869.
+#[cfg(feature = "sha-kdf")]
+fn hkdf_expand<A: ShaAlgorithm>(
+    sha: &mut Sha<'_>,
+    prk: &[u8],
+    info: &[u8],
+    output: &mut [u8],
+) -> Result<(), KdfError> {
+    if output.len() > 255 * A::DIGEST_LENGTH {
+        return Err(KdfError::OutputTooLong);
+    }
+
+    // T(i) = HMAC(PRK, T(i - 1) || info || i)
+    let mut block = [0; MAX_DIGEST_LENGTH];
+    let block = &mut block[..A::DIGEST_LENGTH];
+    let mut previous = [0; MAX_DIGEST_LENGTH];
+    let previous = &mut previous[..A::DIGEST_LENGTH];
+    let mut hmac = ShaHmac::<A>::new(sha, prk)?;
+    let mut result = Ok(());
+    for (i, chunk) in output.chunks_mut(A::DIGEST_LENGTH).enumerate() {
+        previous.copy_from_slice(block);
+        let t_previous: &[u8] = if i == 0 { &[] } else { previous };
+        result = hmac.sign_parts(&[t_previous, info, &[i as u8 + 1]], block);
+        if result.is_err() {
+            break;
+        }
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+
+    // Both buffers hold output keying material.
+    zeroize(block);
+    zeroize(previous);
+
+    result.map_err(KdfError::from)
+}
+
+/// Computes the blocks of PBKDF2 into `output`, using `previous` and `current`
+/// as scratch space for the intermediate MACs.
+#[cfg(feature = "sha-kdf")]
+fn pbkdf2_blocks<A: ShaAlgorithm>(
+    hmac: &mut ShaHmac<'_, '_, A>,
+    salt: &[u8],
+    iterations: core::num::NonZeroU32,
+    output: &mut [u8],
+    previous: &mut [u8],
+    current: &mut [u8],
+) -> Result<(), Error> {
+    for (i, chunk) in output.chunks_mut(A::DIGEST_LENGTH).enumerate() {
+        // U_1 = PRF(P, S || INT(i)), T_i = U_1 ^ U_2 ^ ... ^ U_c
+        let index = (i as u32 + 1).to_be_bytes();
+        hmac.sign_parts(&[salt, &index], current)?;
+        chunk.copy_from_slice(&current[..chunk.len()]);
+
+        for _ in 1..iterations.get() {
+            // U_j = PRF(P, U_{j-1})
+            previous.copy_from_slice(current);
+            hmac.sign_parts(&[previous], current)?;
+            for (out, u) in chunk.iter_mut().zip(current.iter()) {
+                *out ^= u;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Hashes the concatenation of `parts` into `output`.
+fn hash_parts<'p, A: ShaAlgorithm>(
+    sha: &mut Sha<'_>,
+    parts: impl IntoIterator<Item = &'p [u8]>,
+    output: &mut [u8],
+) -> Result<(), Error> {
+    let mut digest = sha.start::<A>();
+    update_parts(&mut digest, parts)?;
+    digest.finish(output)?;
+
+    Ok(())
+}
+
+/// Feeds the concatenation of `parts` into `digest`.
+fn update_parts<'d, 'p, A: ShaAlgorithm, S: Borrow<Sha<'d>>>(
+    digest: &mut ShaDigest<'d, A, S>,
+    parts: impl IntoIterator<Item = &'p [u8]>,
+) -> Result<(), Error> {
+    for part in parts {
+        let mut remaining = part;
+        while !remaining.is_empty() {
+            remaining = nb::block!(digest.update(remaining))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Clears `buffer` in a way the compiler can't optimize out.
+fn zeroize(buffer: &mut [u8]) {
+    for byte in buffer {
+        unsafe { core::ptr::write_volatile(byte, 0) };
+    }
+}
+
+/// The height of the largest tree [`MerkleHasher`] can build.
+const MERKLE_MAX_HEIGHT: usize = 16;
+
+/// While a leaf is added, one more subtree than the height limit exists.
+const MERKLE_MAX_SUBTREES: usize = MERKLE_MAX_HEIGHT + 1;
+
+#[procmacros::doc_replace]
+/// Computes the root of a Merkle tree over fixed-size leaves.
+///
+/// The tree is the Merkle Tree Hash of [RFC 6962], section 2.1. Leaves and
+/// inner nodes are hashed with different prefixes, so that a leaf can't be
+/// passed off as an inner node:
+///
+/// - a leaf is hashed as `H(0x00 || leaf)`,
+/// - an inner node is hashed as `H(0x01 || left || right)`.
+///
+/// If a level has an odd number of nodes, the last node is not duplicated,
+/// but carried up to the next level unchanged. This ensures that different
+/// lists of leaves never produce the same root. The root of an empty tree is
+/// the hash of the empty string.
+///
+/// All leaves must be `leaf_size` bytes long, except for the last one, which
+/// may be shorter. This allows splitting an image into chunks of a fixed size.
+/// At most 65536 leaves can be added.
+///
+/// [RFC 6962]: https://www.rfc-editor.org/rfc/rfc6962#section-2.1
+///
+/// ## Example
+///
+/// ```rust, no_run
+/// # {before_snippet}
+/// use esp_hal::sha::{MerkleHasher, Sha, Sha256};
+///
+/// let mut sha = Sha::new(peripherals.SHA);
+/// let image = [0xA5; 10_000];
+///
+/// let mut hasher = MerkleHasher::<Sha256>::new(&mut sha, 4096);
+/// for chunk in image.chunks(4096) {
+///     hasher.push_leaf(chunk)?;
+/// }
+/// let root: [u8; 32] = hasher.root()?;
+/// # {after_snippet}
+/// ```
+pub struct MerkleHasher<'a, 'd, A: ShaAlgorithm> {
+    sha: &'a mut Sha<'d>,
+    leaf_size: usize,
+    leaf_count: usize,
+    /// Set once a leaf shorter than `leaf_size` has been added.
+    short_leaf: bool,
+    /// The roots of the complete subtrees that have been built so far, with
+    /// their heights. The heights are strictly decreasing.
+    subtrees: [[u8; MAX_DIGEST_LENGTH]; MERKLE_MAX_SUBTREES],
+    heights: [u8; MERKLE_MAX_SUBTREES],
+    subtree_count: usize,
+    phantom: PhantomData<A>,
+}
+
+impl<'a, 'd, A: ShaAlgorithm> MerkleHasher<'a, 'd, A> {
+    const LEAF_PREFIX: u8 = 0x00;
+    const NODE_PREFIX: u8 = 0x01;
+
+    /// Creates a new hasher for leaves of `leaf_size` bytes.
+    pub fn new(sha: &'a mut Sha<'d>, leaf_size: usize) -> Self {
+        Self {
+            sha,
+            leaf_size,
+            leaf_count: 0,
+            short_leaf: false,
+            subtrees: [[0; MAX_DIGEST_LENGTH]; MERKLE_MAX_SUBTREES],
+            heights: [0; MERKLE_MAX_SUBTREES],
+            subtree_count: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Adds the next leaf to the tree.
+    ///
+    /// ## Errors
+    ///
+    /// - [`MerkleError::LeafSizeMismatch`] if the leaf is longer than the leaf size, or if a
+    ///   shorter leaf has been added before.
+    /// - [`MerkleError::TooManyLeaves`] if the tree is full.
+    /// - [`MerkleError::Sha`] if a failed DMA operation left the peripheral in an unknown state.
+    pub fn push_leaf(&mut self, leaf: &[u8]) -> Result<(), MerkleError> {
+        if leaf.len() > self.leaf_size || self.short_leaf {
+            return Err(MerkleError::LeafSizeMismatch);
+        }
+        if self.leaf_count == 1 << MERKLE_MAX_HEIGHT {
+            return Err(MerkleError::TooManyLeaves);
+        }
+        self.short_leaf = leaf.len() < self.leaf_size;
+        self.leaf_count += 1;
+
+        let index = self.subtree_count;
+        hash_parts::<A>(
+            self.sha,
+            [&[Self::LEAF_PREFIX][..], leaf],
+            &mut self.subtrees[index][..A::DIGEST_LENGTH],
+        )?;
+        self.heights[index] = 0;
+        self.subtree_count += 1;
+
+        // Merge subtrees of the same size, like carrying in a binary addition.
+        while self.subtree_count >= 2
+            && self.heights[self.subtree_count - 1] == self.heights[self.subtree_count - 2]
+        {
+            let right = self.subtree_count - 1;
+            let left = right - 1;
+            self.merge(left, right)?;
+            self.heights[left] += 1;
+            self.subtree_count -= 1;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of leaves added so far.
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// Computes the root of the tree.
+    ///
+    /// `N` is typically [ShaAlgorithm::DIGEST_LENGTH], but a smaller value
+    /// can be given to get a truncated root.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::BadState`] if a failed DMA operation left the
+    /// peripheral in an unknown state.
+    pub fn root<const N: usize>(mut self) -> Result<[u8; N], Error> {
+        const {
+            assert!(
+                N <= A::DIGEST_LENGTH,
+                "The output can't be longer than the digest"
+            )
+        };
+
+        let mut output = [0; N];
+        if self.subtree_count == 0 {
+            hash_parts::<A>(self.sha, [], &mut output)?;
+            return Ok(output);
+        }
+
+        // The incomplete subtrees on the right are combined with the larger ones on their
+        // left, which carries the odd nodes up.
+        for right in (1..self.subtree_count).rev() {
+            self.merge(right - 1, right)?;
+        }
+        output.copy_from_slice(&self.subtrees[0][..N]);
+
+        Ok(output)
+    }
+
+    /// Replaces the subtree at `left` with the parent of `left` and `right`.
+    fn merge(&mut self, left: usize, right: usize) -> Result<(), Error> {
+        let mut parent = [0; MAX_DIGEST_LENGTH];
+        hash_parts::<A>(
+            self.sha,
+            [
+                &[Self::NODE_PREFIX][..],
+                &self.subtrees[left][..A::DIGEST_LENGTH],
+                &self.subtrees[right][..A::DIGEST_LENGTH],
+            ],
+            &mut parent[..A::DIGEST_LENGTH],
+        )?;
+        self.subtrees[left] = parent;
+
+        Ok(())
+    }
+}
+
+/// Errors returned by [`MerkleHasher::push_leaf`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum MerkleError {
+    /// The leaf is longer than the leaf size, or follows a shorter leaf.
+    LeafSizeMismatch,
+
+    /// The tree can't hold more leaves.
+    TooManyLeaves,
+
+    /// The leaf could not be hashed.
+    Sha(Error),
+}
+
+impl From<Error> for MerkleError {
+    fn from(error: Error) -> Self {
+        MerkleError::Sha(error)
+    }
+}
+
+impl core::error::Error for MerkleError {}
+
+impl core::fmt::Display for MerkleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MerkleError::LeafSizeMismatch => {
+                write!(f, "Only the last leaf may be shorter than the leaf size")
+            }
+            MerkleError::TooManyLeaves => write!(f, "The tree can't hold more leaves"),
+            MerkleError::Sha(error) => write!(f, "SHA error: {error}"),
+        }
+    }
+}
+
+/// Compares two digests of the same length in constant time.
+fn digests_equal(actual: &[u8], expected: &[u8]) -> bool {
+    // Accumulate the differences of every byte, so that the time taken does not depend
+    // on the position of the first mismatch.
+    let difference = actual
+        .iter()
+        .zip(expected)
+        .fold(0, |difference, (a, b)| difference | (a ^ b));
+
+    core::hint::black_box(difference) == 0
+}
+
+/// Errors returned by [`Sha::verify_signed_region`] and
+/// [`ShaDigest::finish_verify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum VerifyError {
+    /// The signature block is shorter than the digest.
+    SignatureBlockTooShort,
+
+    /// The signed region contains the digest it is verified against.
+    SignatureInRegion,
+
+    /// The expected digest is not as long as the algorithm's digest.
+    WrongDigestLength,
+
+    /// The digest could not be computed.
+    Sha(Error),
+}
+
+impl From<Error> for VerifyError {
+    fn from(error: Error) -> Self {
+        VerifyError::Sha(error)
+    }
+}
+
+impl core::error::Error for VerifyError {}
+
+impl core::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            VerifyError::SignatureBlockTooShort => {
+                write!(f, "The signature block is shorter than the digest")
+            }
+            VerifyError::SignatureInRegion => {
+                write!(f, "The signed region must not contain the signature block")
+            }
+            VerifyError::WrongDigestLength => {
+                write!(f, "The expected digest has the wrong length")
+            }
+            VerifyError::Sha(error) => write!(f, "SHA error: {error}"),
+        }
+    }
+}
+
+/// Errors returned by [`ShaDigest::finish_with_total_len`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum FinishError {
+    /// The number of bytes fed to the digest does not match the length of
+    /// the message.
+    LengthMismatch {
+        /// The length of the message, as passed to
+        /// [`ShaDigest::finish_with_total_len`].
+        expected: usize,
+
+        /// The number of bytes that were fed to the digest.
+        actual: usize,
+    },
+
+    /// The digest could not be computed.
+    Sha(Error),
+}
+
+impl From<Error> for FinishError {
+    fn from(error: Error) -> Self {
+        FinishError::Sha(error)
+    }
+}
+
+impl core::error::Error for FinishError {}
+
+impl core::fmt::Display for FinishError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FinishError::LengthMismatch { expected, actual } => write!(
+                f,
+                "Expected a message of {expected} bytes, but {actual} bytes were fed to the digest"
+            ),
+            FinishError::Sha(error) => write!(f, "SHA error: {error}"),
+        }
+    }
+}
+
+/// Errors returned by [`Sha::derive_key`] and [`Sha::hkdf_expand`].
+#[cfg(feature = "sha-kdf")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sha-kdf")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum KdfError {
+    /// The requested key is longer than HKDF can derive.
+    OutputTooLong,
+
+    /// The pseudorandom key is shorter than the digest.
+    PrkTooShort,
+
+    /// The key could not be derived.
+    Sha(Error),
+}
+
+#[cfg(feature = "sha-kdf")]
+impl From<Error> for KdfError {
+    fn from(error: Error) -> Self {
+        KdfError::Sha(error)
+    }
+}
+
+#[cfg(feature = "sha-kdf")]
+impl core::error::Error for KdfError {}
+
+#[cfg(feature = "sha-kdf")]
+impl core::fmt::Display for KdfError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KdfError::OutputTooLong => {
+                write!(f, "The derived key can be at most 255 digests long")
+            }
+            KdfError::PrkTooShort => {
+                write!(
+                    f,
+                    "The pseudorandom key must be at least as long as the digest"
+                )
+            }
+            KdfError::Sha(error) => write!(f, "SHA error: {error}"),
+        }
+    }
+}
+
+/// SHA errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Error {
+    /// A failed DMA operation left the peripheral in an unknown state.
+    ///
+    /// Call `ShaDma::recover` to reset the peripheral.
+    BadState,
+
+    /// The DMA stopped before it delivered every message block to the
+    /// peripheral.
+    #[cfg(sha_dma)]
+    DmaTransferIncomplete,
+
+    /// The DMA transfer could not be set up.
+    #[cfg(sha_dma)]
+    Dma(crate::dma::DmaError),
+}
+
+#[cfg(sha_dma)]
+impl From<crate::dma::DmaError> for Error {
+    fn from(error: crate::dma::DmaError) -> Self {
+        Error::Dma(error)
+    }
+}
+
+impl core::error::Error for Error {}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::BadState => write!(
+                f,
+                "The peripheral is in an unknown state after a failed DMA operation"
+            ),
+            #[cfg(sha_dma)]
+            Error::DmaTransferIncomplete => write!(f, "The DMA transfer did not complete"),
+            #[cfg(sha_dma)]
+            Error::Dma(error) => write!(f, "DMA error: {error:?}"),
+        }
+    }
+}
+
+impl<A: ShaAlgorithm> Drop for ShaHmac<'_, '_, A> {
+    fn drop(&mut self) {
+        #[cfg(esp32)]
+        zeroize(&mut self.key);
+        // The saved states are derived from the key, and are enough to compute MACs.
+        #[cfg(not(esp32))]
+        for context in [&mut self.inner, &mut self.outer] {
+            zeroize(&mut context.saved_digest);
+            zeroize(bytemuck::cast_slice_mut(&mut context.buffer));
+        }
+    }
+}
+
+/// Provides DMA (Direct Memory Access) support for SHA operations.
+///
+/// In DMA-SHA mode the peripheral fetches complete message blocks from memory on
+/// its own, which is significantly faster than feeding the message registers
+/// word-by-word when hashing large buffers.
+#[cfg(sha_dma)]
+pub mod dma {
+    use core::{marker::PhantomData, ptr::NonNull};
+
+    use super::{DigestState, Error, MAX_CHUNK_LENGTH, Sha, ShaAlgorithm};
+    use crate::{
+        Blocking,
+        dma::{
+            Channel,
+            DmaChannelFor,
+            DmaDescriptorSet,
+            DmaError,
+            DmaPeripheral,
+            PeripheralDmaChannel,
+            descriptor_count,
+        },
+        peripherals::SHA,
+        system::{Peripheral, PeripheralClockControl},
+    };
+
+    /// The shortest message, in blocks, that [`ShaDma::hash`] hashes using DMA.
+    /// For shorter messages, setting up the transfer costs more than feeding
+    /// the blocks by the CPU.
+    const DMA_MIN_BLOCKS: usize = 4;
+
+    /// The largest number of blocks a single DMA-SHA operation can process
+    /// (`SHA_DMA_BLOCK_NUM` is 6 bits wide).
+    const MAX_DMA_BLOCKS: usize = 63;
+
+    /// Enough descriptors to process [`MAX_DMA_BLOCKS`] blocks of any
+    /// algorithm in a single DMA operation.
+    const DESCRIPTOR_COUNT: usize = descriptor_count(
+        MAX_DMA_BLOCKS * MAX_CHUNK_LENGTH,
+        4096 - MAX_CHUNK_LENGTH,
+        false,
+    );
+
+    /// A DMA capable SHA instance.
+    #[instability::unstable]
+    pub struct ShaDma<'d> {
+        sha: Sha<'d>,
+
+        channel: Channel<Blocking, PeripheralDmaChannel<SHA<'d>>>,
+        descriptors: DmaDescriptorSet<DESCRIPTOR_COUNT>,
+    }
+
+    impl<'d> Sha<'d> {
+        /// Enable DMA for the current instance of the SHA driver
+        pub fn with_dma(self, channel: impl DmaChannelFor<SHA<'d>>) -> ShaDma<'d> {
+            let channel = Channel::new(channel.degrade());
+            channel.runtime_ensure_compatible(&self.sha);
+            ShaDma {
+                sha: self,
+                channel,
+                descriptors: DmaDescriptorSet::new(),
+            }
+        }
+    }
+
+    impl core::fmt::Debug for ShaDma<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("ShaDma").finish()
+        }
+    }
+
+    impl<'d> ShaDma<'d> {
+        /// Returns the underlying [`Sha`](super::Sha) driver.
+        ///
+        /// A peripheral left in a bad state by a failed DMA operation is
+        /// recovered first.
+        pub fn sha(&mut self) -> &mut Sha<'d> {
+            if self.sha.bad_state {
+                self.recover();
+            }
+
+            &mut self.sha
+        }
+
+        /// Releases the underlying [`Sha`](super::Sha) driver.
+        ///
+        /// A peripheral left in a bad state by a failed DMA operation is
+        /// recovered first.
+        pub fn release(mut self) -> Sha<'d> {
+            if self.sha.bad_state {
+                self.recover();
+            }
+
+            self.sha
+        }
+
+        #[procmacros::doc_replace(
+            "dma_channel" => {
+                cfg(esp32s2) => "let dma_channel = peripherals.DMA_CRYPTO;",
+                _ => "let dma_channel = peripherals.DMA_CH0;"
+            }
+        )]
+        /// Hashes `input` and returns the digest, using DMA when it is
+        /// beneficial.
+        ///
+        /// DMA is used if `input` is at least a few blocks long and is located
+        /// in memory that the DMA can access. Otherwise, for example for
+        /// short messages or messages in memory-mapped flash, the CPU feeds
+        /// the message to the peripheral like [`Sha::hash`] does. If the DMA
+        /// transfer fails, the peripheral is recovered and the message is
+        /// hashed by the CPU instead. A peripheral left in a bad state by a
+        /// previous operation is recovered first.
+        ///
+        /// Use [`Self::hash_oneshot`] to require DMA.
+        ///
+        /// ## Example
+        ///
+        /// ```rust, no_run
+        /// # {before_snippet}
+        /// use esp_hal::sha::{Sha, Sha256};
+        ///
+        /// # {dma_channel}
+        /// let mut sha = Sha::new(peripherals.SHA).with_dma(dma_channel);
+        ///
+        /// // Hashed using DMA
+        /// let input = [0xA5; 4096];
+        /// let digest: [u8; 32] = sha.hash::<Sha256, _>(&input)?;
+        ///
+        /// // Hashed by the CPU
+        /// let digest: [u8; 32] = sha.hash::<Sha256, _>(b"hello world")?;
+        /// # {after_snippet}
+        /// ```
+        pub fn hash<A: ShaAlgorithm, const N: usize>(
+            &mut self,
+            input: &[u8],
+        ) -> Result<[u8; N], Error> {
+            if self.sha.bad_state {
+                self.recover();
+            }
+
+            if input.len() >= DMA_MIN_BLOCKS * A::CHUNK_LENGTH && is_dma_accessible(input) {
+                let mut output = [0; N];
+                match self.hash_dma::<A>(input, &mut output) {
+                    Ok(()) => return Ok(output),
+                    Err(_) => self.recover(),
+                }
+            }
+
+            self.sha.hash::<A, N>(input)
+        }
+
+        #[procmacros::doc_replace(
+            "dma_channel" => {
+                cfg(esp32s2) => "let dma_channel = peripherals.DMA_CRYPTO;",
+                _ => "let dma_channel = peripherals.DMA_CH0;"
+            }
+        )]
+        /// Hashes `input` and returns the digest.
+        ///
+        /// The complete message blocks are fed to the peripheral using DMA, the
+        /// trailing partial block and the padding are processed by the CPU.
+        ///
+        /// `N` is typically [ShaAlgorithm::DIGEST_LENGTH], but a smaller value
+        /// can be given to get a "short hash".
+        ///
+        /// The input must be located in memory that is accessible by the DMA.
+        /// Otherwise, [Error::Dma] with [DmaError::UnsupportedMemoryRegion] is
+        /// returned.
+        ///
+        /// ## Errors
+        ///
+        /// If the DMA stops while the blocks are transferred,
+        /// [Error::DmaTransferIncomplete] is returned, and the peripheral is
+        /// left in a bad state: until [`Self::recover`] is called, this
+        /// function and the digests started with [`Self::start`] return
+        /// [Error::BadState].
+        ///
+        /// ## Memory regions
+        ///
+        /// Internal RAM can always be used. On chips that support DMA transfers
+        /// from PSRAM (ESP32-S2 and ESP32-S3), the input may be in PSRAM if the
+        /// `psram` feature is enabled. The driver writes the input back from
+        /// the data cache before starting the transfer, so data written by the
+        /// CPU is hashed correctly. The input must not be modified while it is
+        /// being hashed.
+        ///
+        /// None of the supported chips can fetch data from memory-mapped flash
+        /// using DMA. To hash flash contents (for example an OTA image) without
+        /// copying them to RAM first, use the CPU driven [`Sha::start`] API.
+        ///
+        /// ## Example
+        ///
+        /// ```rust, no_run
+        /// # {before_snippet}
+        /// use esp_hal::sha::{Sha, Sha256};
+        ///
+        /// # {dma_channel}
+        /// let mut sha = Sha::new(peripherals.SHA).with_dma(dma_channel);
+        ///
+        /// // DMA source data must be in RAM
+        /// let input = [0xA5; 4096];
+        /// let digest: [u8; 32] = sha.hash_oneshot::<Sha256, _>(&input)?;
+        /// # {after_snippet}
+        /// ```
+        pub fn hash_oneshot<A: ShaAlgorithm, const N: usize>(
+            &mut self,
+            input: &[u8],
+        ) -> Result<[u8; N], Error> {
+            const {
+                assert!(
+                    N <= A::DIGEST_LENGTH,
+                    "The output can't be longer than the digest"
+                )
+            };
+
+            let mut output = [0; N];
+            self.hash_dma::<A>(input, &mut output)?;
+
+            Ok(output)
+        }
+
+        #[procmacros::doc_replace(
+            "dma_channel" => {
+                cfg(esp32s2) => "let dma_channel = peripherals.DMA_CRYPTO;",
+                _ => "let dma_channel = peripherals.DMA_CH0;"
+            }
+        )]
+        /// Hashes `input` into `output`, retrying on transient DMA errors.
+        ///
+        /// `output` is typically [ShaAlgorithm::DIGEST_LENGTH] bytes long, but
+        /// a shorter buffer can be given to get a "short hash".
+        ///
+        /// Works like [`Self::hash_oneshot`], but if the DMA reports an error
+        /// while the blocks are transferred, the peripheral and the DMA
+        /// channel are reset and the message is hashed again from the start,
+        /// up to `max_retries` times. The last error is returned if every
+        /// attempt fails.
+        ///
+        /// Errors caused by the arguments, for example
+        /// [DmaError::UnsupportedMemoryRegion] for an input that the DMA
+        /// can't access, and [Error::BadState] left by a previous operation,
+        /// are returned immediately, without retrying.
+        ///
+        /// ## Panics
+        ///
+        /// Panics if `output` is longer than [ShaAlgorithm::DIGEST_LENGTH].
+        ///
+        /// ## Example
+        ///
+        /// ```rust, no_run
+        /// # {before_snippet}
+        /// use esp_hal::sha::{Sha, Sha256};
+        ///
+        /// # {dma_channel}
+        /// let mut sha = Sha::new(peripherals.SHA).with_dma(dma_channel);
+        ///
+        /// let input = [0xA5; 4096];
+        /// let mut digest = [0; 32];
+        /// sha.process_with_retry::<Sha256>(&input, &mut digest, 3)?;
+        /// # {after_snippet}
+        /// ```
+        pub fn process_with_retry<A: ShaAlgorithm>(
+            &mut self,
+            input: &[u8],
+            output: &mut [u8],
+            max_retries: usize,
+        ) -> Result<(), Error> {
+            assert!(
+                output.len() <= A::DIGEST_LENGTH,
+                "The output can't be longer than the digest"
+            );
+
+            hash_with_retry::<A>(self, input, output, max_retries)
+        }
+
+        #[procmacros::doc_replace(
+            "dma_channel" => {
+                cfg(esp32s2) => "let dma_channel = peripherals.DMA_CRYPTO;",
+                _ => "let dma_channel = peripherals.DMA_CH0;"
+            }
+        )]
+        /// Starts a digest that is fed in multiple parts, using DMA for the
+        /// long ones.
+        ///
+        /// This is useful to hash a message that doesn't fit in memory at
+        /// once, for example a firmware image that is received in chunks. The
+        /// descriptors of the driver are reused for every part, so the setup
+        /// cost of each DMA transfer is only writing the descriptors and
+        /// starting the channel.
+        ///
+        /// ## Example
+        ///
+        /// ```rust, no_run
+        /// # {before_snippet}
+        /// use esp_hal::sha::{Sha, Sha256};
+        ///
+        /// # {dma_channel}
+        /// let mut sha = Sha::new(peripherals.SHA).with_dma(dma_channel);
+        ///
+        /// let mut chunk = [0; 4096];
+        /// let mut digest = sha.start::<Sha256>();
+        /// for _ in 0..16 {
+        ///     // Fill `chunk` with the next part of the message.
+        ///     digest.update(&chunk)?;
+        /// }
+        /// let mut output = [0u8; 32];
+        /// digest.finish(&mut output)?;
+        /// # {after_snippet}
+        /// ```
+        pub fn start<A: ShaAlgorithm>(&mut self) -> ShaDmaDigest<'_, 'd, A> {
+            self.sha
+                .sha
+                .register_block()
+                .mode()
+                .write(|w| unsafe { w.mode().bits(A::ALGORITHM_KIND.mode_bits()) });
+
+            ShaDmaDigest {
+                sha: self,
+                state: DigestState::new(A::ALGORITHM_KIND),
+                phantom: PhantomData,
+            }
+        }
+
+        fn hash_dma<A: ShaAlgorithm>(
+            &mut self,
+            input: &[u8],
+            output: &mut [u8],
+        ) -> Result<(), Error> {
+            if self.sha.bad_state {
+                return Err(Error::BadState);
+            }
+
+            self.sha
+                .sha
+                .register_block()
+                .mode()
+                .write(|w| unsafe { w.mode().bits(A::ALGORITHM_KIND.mode_bits()) });
+
+            let mut state = DigestState::new(A::ALGORITHM_KIND);
+
+            let (mut blocks, mut remainder) =
+                input.split_at(input.len() - input.len() % A::CHUNK_LENGTH);
+            while !blocks.is_empty() {
+                let processed = self.process_blocks(&mut state, blocks)?;
+                blocks = &blocks[processed..];
+            }
+
+            while !remainder.is_empty() {
+                remainder = nb::block!(self.sha.update(&mut state, remainder))?;
+            }
+
+            nb::block!(self.sha.finish(&mut state, output))?;
+
+            Ok(())
+        }
+
+        /// Processes as many complete blocks of `data` as a single DMA
+        /// operation can handle and returns the number of bytes consumed.
+        fn process_blocks(&mut self, state: &mut DigestState, data: &[u8]) -> Result<usize, Error> {
+            let chunk_len = state.algorithm.chunk_length();
+            let data = &data[..data.len().min(MAX_DMA_BLOCKS * chunk_len)];
+
+            if !is_dma_accessible(data) {
+                return Err(Error::Dma(DmaError::UnsupportedMemoryRegion));
+            }
+
+            let (mut buffer, data_len) = unsafe {
+                self.descriptors
+                    .prepare_for_tx(NonNull::from(data), chunk_len)?
+            };
+
+            unsafe {
+                self.channel
+                    .tx
+                    .prepare_transfer(DmaPeripheral::Sha, &mut buffer)?;
+            }
+            self.channel.tx.start_transfer()?;
+
+            let regs = self.sha.sha.register_block();
+            regs.dma_block_num()
+                .write(|w| unsafe { w.dma_block_num().bits((data_len / chunk_len) as _) });
+
+            if state.first_run {
+                regs.dma_start().write(|w| w.dma_start().set_bit());
+                state.first_run = false;
+            } else {
+                regs.dma_continue().write(|w| w.dma_continue().set_bit());
+            }
+
+            // The busy flag may not be set yet right after starting the
+            // operation, so wait until the DMA has delivered every block
+            // first. The peripheral never finishes if the DMA stops feeding
+            // it.
+            while !self.channel.tx.is_done() {
+                if self.channel.tx.has_error() {
+                    self.abort_transfer();
+                    return Err(Error::DmaTransferIncomplete);
+                }
+            }
+            while state.algorithm.is_busy(&self.sha.sha) {}
+
+            self.channel.tx.stop_transfer();
+            state.cursor += data_len;
+
+            Ok(data_len)
+        }
+
+        /// Stops a transfer that didn't deliver every block. The peripheral
+        /// is left in an unknown state until [`Self::recover`] is called.
+        fn abort_transfer(&mut self) {
+            self.channel.tx.stop_transfer();
+            self.sha.bad_state = true;
+        }
+
+        /// Returns the DMA channel and the peripheral to their initial state
+        /// after a failed operation.
+        ///
+        /// This clears [Error::BadState]. Digests that were in progress when
+        /// the peripheral was reset are lost.
+        pub fn recover(&mut self) {
+            self.channel.tx.stop_transfer();
+            self.channel.tx.clear_interrupts();
+            PeripheralClockControl::reset(Peripheral::Sha);
+            self.sha.bad_state = false;
+        }
+    }
+
+    /// A digest that is fed to the peripheral using DMA, see
+    /// [`ShaDma::start`].
+    #[instability::unstable]
+    pub struct ShaDmaDigest<'a, 'd, A: ShaAlgorithm> {
+        sha: &'a mut ShaDma<'d>,
+        state: DigestState,
+        phantom: PhantomData<A>,
+    }
+
+    impl<A: ShaAlgorithm> ShaDmaDigest<'_, '_, A> {
+        /// Returns the number of message bytes added to the digest so far.
+        pub fn bytes_processed(&self) -> u64 {
+            self.state.cursor as u64
+        }
+
+        /// Adds `data` to the digest.
+        ///
+        /// The complete message blocks of `data` are transferred using DMA if
+        /// there are enough of them, and if `data` is located in memory that
+        /// the DMA can access. Everything else is written by the CPU, so
+        /// `data` can have any length, and can be in any memory. The result is
+        /// the same as if the message was hashed in one part.
+        ///
+        /// ## Errors
+        ///
+        /// If the DMA stops while the blocks are transferred,
+        /// [Error::DmaTransferIncomplete] is returned, and the digest is lost.
+        /// See [`ShaDma::hash_oneshot`] for more information. Until
+        /// [`ShaDma::recover`] is called, [Error::BadState] is returned.
+        pub fn update(&mut self, mut data: &[u8]) -> Result<(), Error> {
+            if self.sha.sha.bad_state {
+                return Err(Error::BadState);
+            }
+
+            let chunk_len = A::CHUNK_LENGTH;
+
+            // The block that the previous parts have started is completed by the CPU.
+            let partial = self.state.cursor % chunk_len;
+            if partial != 0 {
+                let (head, rest) = data.split_at(data.len().min(chunk_len - partial));
+                self.sha.sha.write_all(&mut self.state, head)?;
+                data = rest;
+            }
+
+            let (mut blocks, tail) = data.split_at(data.len() - data.len() % chunk_len);
+            if blocks.len() < DMA_MIN_BLOCKS * chunk_len || !is_dma_accessible(blocks) {
+                return self.sha.sha.write_all(&mut self.state, data);
+            }
+
+            // The DMA continues from the result of the blocks written by the CPU.
+            if self.state.message_buffer_is_full {
+                while self.state.algorithm.is_busy(&self.sha.sha.sha) {}
+                self.sha.sha.process_buffer(&mut self.state);
+                self.state.message_buffer_is_full = false;
+            }
+            while self.state.algorithm.is_busy(&self.sha.sha.sha) {}
+
+            while !blocks.is_empty() {
+                let processed = self.sha.process_blocks(&mut self.state, blocks)?;
+                blocks = &blocks[processed..];
+            }
+
+            self.sha.sha.write_all(&mut self.state, tail)
+        }
+
+        /// Finishes the calculation and copies the result to `output`.
+        ///
+        /// `output` is typically [ShaAlgorithm::DIGEST_LENGTH] bytes long, but
+        /// a shorter buffer can be given to get a "short hash".
+        pub fn finish(mut self, output: &mut [u8]) -> Result<(), Error> {
+            nb::block!(self.sha.sha.finish(&mut self.state, output))
+        }
+    }
+
+    /// Returns whether the DMA can read all of `data`, the same check that
+    /// [`DmaTxBuf`](crate::dma::DmaTxBuf) applies to its buffer.
+    fn is_dma_accessible(data: &[u8]) -> bool {
+        let is_in_dram = crate::soc::is_slice_in_dram(data);
+        cfg_if::cfg_if! {
+            if #[cfg(psram_dma)] {
+                let is_in_psram = crate::soc::is_slice_in_psram(data);
+            } else {
+                let is_in_psram = false;
+            }
+        }
+
+        is_in_dram || is_in_psram
+    }
+
+    /// The DMA operations that [`ShaDma::process_with_retry`] is built on.
+    ///
+    /// This trait is not part of the public API. It only exists so that the
+    /// HIL tests can check the retry logic and the recovery with a driver whose
+    /// transfers fail on purpose, which the hardware can't be made to do.
+    #[doc(hidden)]
+    pub trait ShaDmaOps {
+        /// Hashes `input` into `output` using DMA, without retrying.
+        fn hash_once<A: ShaAlgorithm>(
+            &mut self,
+            input: &[u8],
+            output: &mut [u8],
+        ) -> Result<(), Error>;
+
+        /// Leaves the driver in the state of a transfer that stopped early.
+        fn abort_transfer(&mut self);
+
+        /// Returns the driver to its initial state, see [`ShaDma::recover`].
+        fn recover(&mut self);
+    }
+
+    impl ShaDmaOps for ShaDma<'_> {
+        fn hash_once<A: ShaAlgorithm>(
+            &mut self,
+            input: &[u8],
+            output: &mut [u8],
+        ) -> Result<(), Error> {
+            self.hash_dma::<A>(input, output)
+        }
+
+        fn abort_transfer(&mut self) {
+            ShaDma::abort_transfer(self);
+        }
+
+        fn recover(&mut self) {
+            ShaDma::recover(self);
+        }
+    }
+
+    /// Hashes `input` into `output`, recovering and retrying on transient
+    /// errors. See [`ShaDma::process_with_retry`].
+    #[doc(hidden)]
+    pub fn hash_with_retry<A: ShaAlgorithm>(
+        sha: &mut impl ShaDmaOps,
+        input: &[u8],
+        output: &mut [u8],
+        max_retries: usize,
+    ) -> Result<(), Error> {
+        let mut retries = 0;
+        loop {
+            match sha.hash_once::<A>(input, output) {
+                Ok(()) => return Ok(()),
+                Err(error) if is_transient(error) => {
+                    sha.recover();
+                    if retries == max_retries {
+                        return Err(error);
+                    }
+                    retries += 1;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Returns whether `error` was reported by the hardware during a transfer,
+    /// as opposed to being caused by the arguments. Only the former can
+    /// succeed when the operation is repeated.
+    fn is_transient(error: Error) -> bool {
+        matches!(
+            error,
+            Error::DmaTransferIncomplete | Error::Dma(DmaError::DescriptorError | DmaError::Late)
+        )
+    }
+}
+
+/// This trait encapsulates the configuration for a specific SHA algorithm.
+pub trait ShaAlgorithm: crate::private::Sealed {
+    /// Constant containing the name of the algorithm as a string.
+    const ALGORITHM: &'static str;
+
+    /// Constant containing the kind of the algorithm.
+    const ALGORITHM_KIND: ShaAlgorithmKind;
+
+    /// The length of the chunk that the algorithm processes at a time.
+    ///
+    /// For example, in SHA-256, this would typically be 64 bytes.
+    const CHUNK_LENGTH: usize;
+
+    /// The length of the resulting digest produced by the algorithm.
+    ///
+    /// For example, in SHA-256, this would be 32 bytes.
+    const DIGEST_LENGTH: usize;
+
+    #[doc(hidden)]
     type DigestOutputSize: digest::generic_array::ArrayLength<u8> + 'static;
 }
 
@@ -524,17 +2554,24 @@ impl<'d, A: ShaAlgorithm, S: Borrow<Sha<'d>>> digest::OutputSizeUser for ShaDige
     type OutputSize = A::DigestOutputSize;
 }
 
+// Only a `ShaDma` can leave the peripheral in a bad state, and it recovers the
+// peripheral before it hands out the `Sha` driver (see `ShaDma::sha` and
+// `ShaDma::release`). A `ShaDigest` can therefore not observe `Error::BadState`,
+// and the infallible trait methods can't fail.
+
 impl<'d, A: ShaAlgorithm, S: Borrow<Sha<'d>>> digest::Update for ShaDigest<'d, A, S> {
-    fn update(&mut self, mut remaining: &[u8]) {
-        while !remaining.is_empty() {
-            remaining = nb::block!(Self::update(self, remaining)).unwrap();
+    fn update(&mut self, remaining: &[u8]) {
+        if update_parts(self, [remaining]).is_err() {
+            unreachable!("The SHA peripheral is in a bad state");
         }
     }
 }
 
 impl<'d, A: ShaAlgorithm, S: Borrow<Sha<'d>>> digest::FixedOutput for ShaDigest<'d, A, S> {
-    fn finalize_into(mut self, out: &mut digest::Output<Self>) {
-        nb::block!(self.finish(out)).unwrap();
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        if self.finish(out).is_err() {
+            unreachable!("The SHA peripheral is in a bad state");
+        }
     }
 }
 
@@ -557,6 +2594,9 @@ for_each_sha_algorithm! {
 for_each_sha_algorithm! {
     (algos $( ( $name:ident, $full_name:literal (sizes: $block_size:literal, $digest_words:literal, $message_length_bytes:literal) $security:tt, $mode_bits:literal ) ),*) => {
         impl ShaAlgorithmKind {
+            /// All the algorithms supported by the device.
+            const ALL: &[ShaAlgorithmKind] = &[$(ShaAlgorithmKind::$name,)*];
+
             #[cfg(not(esp32))]
             const fn mode_bits(self) -> u8 {
                 match self {
@@ -587,6 +2627,19 @@ for_each_sha_algorithm! {
 }
 
 impl ShaAlgorithmKind {
+    /// The length of the chaining value of the hash function.
+    #[cfg(not(esp32))]
+    const fn state_length(self) -> usize {
+        match (self.chunk_length(), self.digest_length()) {
+            // SHA-1
+            (64, 20) => 20,
+            // SHA-224, SHA-256
+            (64, _) => 32,
+            // The algorithms with 1024-bit blocks
+            _ => 64,
+        }
+    }
+
     fn start(self, sha: &crate::peripherals::SHA<'_>) {
         let regs = sha.register_block();
         cfg_if::cfg_if! {
@@ -40,19 +40,25 @@ macro_rules! rtcio_analog {
                 /// Set the RTC properties of the pin. If `mux` is true then then pin is
                 /// routed to RTC, when false it is routed to IO_MUX.
                 fn rtc_set_config(&self, input_enable: bool, mux: bool, func: $crate::gpio::RtcFunction) {
-                    // disable input
-                    $crate::peripherals::RTC_IO::regs()
-                        .$pin_reg.modify(|_,w| unsafe {
-                            w.[<$prefix fun_ie>]().bit(input_enable);
-                            w.[<$prefix mux_sel>]().bit(mux);
-                            w.[<$prefix fun_sel>]().bits(func as u8)
-                        });
+                    // The register is shared with other pins.
+                    $crate::gpio::GPIO_LOCK.lock(|| {
+                        // disable input
+                        $crate::peripherals::RTC_IO::regs()
+                            .$pin_reg.modify(|_,w| unsafe {
+                                w.[<$prefix fun_ie>]().bit(input_enable);
+                                w.[<$prefix mux_sel>]().bit(mux);
+                                w.[<$prefix fun_sel>]().bits(func as u8)
+                            });
+                    });
                 }
 
                 fn rtcio_pad_hold(&self, enable: bool) {
-                    $crate::peripherals::LPWR::regs()
-                        .hold_force()
-                        .modify(|_, w| w.$hold().bit(enable));
+                    // The register is shared with other pins.
+                    $crate::gpio::GPIO_LOCK.lock(|| {
+                        $crate::peripherals::LPWR::regs()
+                            .hold_force()
+                            .modify(|_, w| w.$hold().bit(enable));
+                    });
                 }
             }
 
@@ -62,13 +68,19 @@ macro_rules! rtcio_analog {
                     #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
                     impl $crate::gpio::RtcPinWithResistors for $crate::peripherals::$pin_peri<'_> {
                         fn rtcio_pullup(&self, enable: bool) {
-                            $crate::peripherals::RTC_IO::regs()
-                                .$pin_reg.modify(|_, w| w.[< $prefix rue >]().bit(enable));
+                            // The register is shared with other pins.
+                            $crate::gpio::GPIO_LOCK.lock(|| {
+                                $crate::peripherals::RTC_IO::regs()
+                                    .$pin_reg.modify(|_, w| w.[< $prefix rue >]().bit(enable));
+                            });
                         }
 
                         fn rtcio_pulldown(&self, enable: bool) {
-                            $crate::peripherals::RTC_IO::regs()
-                                .$pin_reg.modify(|_, w| w.[< $prefix rde >]().bit(enable));
+                            // The register is shared with other pins.
+                            $crate::gpio::GPIO_LOCK.lock(|| {
+                                $crate::peripherals::RTC_IO::regs()
+                                    .$pin_reg.modify(|_, w| w.[< $prefix rde >]().bit(enable));
+                            });
                         }
                     }
                 };
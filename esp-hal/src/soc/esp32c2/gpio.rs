@@ -42,10 +42,13 @@ macro_rules! rtc_pins {
                 }
 
                 fn rtcio_pad_hold(&self, enable: bool) {
-                    paste::paste! {
-                        $crate::peripherals::LPWR::regs()
-                            .pad_hold().modify(|_, w| w.[< gpio_pin $pin_num _hold >]().bit(enable));
-                    }
+                    // The register is shared with other pins.
+                    $crate::gpio::GPIO_LOCK.lock(|| {
+                        paste::paste! {
+                            $crate::peripherals::LPWR::regs()
+                                .pad_hold().modify(|_, w| w.[< gpio_pin $pin_num _hold >]().bit(enable));
+                        }
+                    });
                 }
             }
 
@@ -110,9 +110,12 @@ for_each_lp_function! {
             }
 
             fn rtcio_pad_hold(&self, enable: bool) {
-                crate::peripherals::LPWR::regs()
-                    .pad_hold()
-                    .modify(|_, w| hold_field!(w, $gpio).bit(enable));
+                // The register is shared with other pins.
+                crate::gpio::GPIO_LOCK.lock(|| {
+                    crate::peripherals::LPWR::regs()
+                        .pad_hold()
+                        .modify(|_, w| hold_field!(w, $gpio).bit(enable));
+                });
             }
         }
 
@@ -51,9 +51,12 @@ macro_rules! rtcio_analog {
                 }
 
                 fn rtcio_pad_hold(&self, enable: bool) {
-                    $crate::peripherals::LPWR::regs()
-                        .pad_hold()
-                        .modify(|_, w| w.$hold().bit(enable));
+                    // The register is shared with other pins.
+                    $crate::gpio::GPIO_LOCK.lock(|| {
+                        $crate::peripherals::LPWR::regs()
+                            .pad_hold()
+                            .modify(|_, w| w.$hold().bit(enable));
+                    });
                 }
             }
 
@@ -34,6 +34,11 @@ define_regi2c! {
             field: ADC_SAR_DTEST_RTC(1..0)
         }
     }
+    master: REGI2C_BOD(0x61, 1) {
+        reg: I2C_BOD_REG5(5) {
+            field: I2C_BOD_THRESHOLD(2..0)
+        }
+    }
     master: I2C_DIG_REG(0x6d, 1) {
         reg: I2C_DIG_REG4(4) {
             field: I2C_DIG_REG_EXT_RTC_DREG(4..0)
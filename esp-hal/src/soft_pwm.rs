@@ -0,0 +1,263 @@
+#![cfg_attr(docsrs, procmacros::doc_replace)]
+//! # Software PWM
+//!
+//! ## Overview
+//!
+//! [`SoftPwm`] generates a PWM signal on any output pin by toggling it from the
+//! alarm interrupt of a TIMG timer. It is meant as a fallback for when a pin
+//! can't be routed to the LEDC or MCPWM peripherals, or when all of their
+//! channels are in use. It is good enough for servos and dimming LEDs, but it
+//! is not as precise as a hardware PWM:
+//!
+//! - Every edge is generated by an interrupt handler, so edges are delayed by the interrupt
+//!   latency. Other interrupts with the same or a higher priority, and critical sections, add
+//!   jitter.
+//! - The timer reloads itself in hardware at every edge, so the jitter does not accumulate, and the
+//!   frequency stays exact on average.
+//!
+//! ## CPU cost and frequency limit
+//!
+//! The alarm interrupt fires twice per period, and each run takes on the order
+//! of a hundred CPU cycles plus the interrupt entry and exit. At the maximum
+//! frequency of [`MAX_FREQUENCY`] this is 2000 interrupts per second, well
+//! under one percent of the CPU time. A duty cycle of 0% or 100% stops the
+//! timer and costs nothing.
+//!
+//! At [`MAX_FREQUENCY`], a duty cycle of 1% results in a 10 µs long phase,
+//! which still comfortably fits the interrupt latency. Higher frequencies
+//! would make the shortest phases shorter than the time it takes to handle
+//! the interrupt, so they are rejected.
+//!
+//! The interrupt handler runs at the highest priority, to keep the jitter
+//! low.
+//!
+//! ## Example
+//!
+//! ```rust, no_run
+//! # {before_snippet}
+//! # use esp_hal::{soft_pwm::SoftPwm, time::Rate, timer::timg::TimerGroup};
+//! let timg0 = TimerGroup::new(peripherals.TIMG0);
+//! let mut servo = SoftPwm::new(peripherals.GPIO4, timg0.timer0, Rate::from_hz(50))?;
+//!
+//! // 1.4 ms pulses, close to the center position of a typical servo.
+//! servo.set_duty(7)?;
+//! # {after_snippet}
+//! ```
+
+use portable_atomic::{AtomicBool, AtomicU8, AtomicU32, Ordering};
+use procmacros::handler;
+
+use crate::{
+    gpio::{AnyPin, Level, Output, OutputConfig, OutputPin},
+    interrupt::Priority,
+    time::Rate,
+    timer::{Timer as _, timg::Timer},
+};
+
+/// The highest frequency [`SoftPwm`] accepts.
+pub const MAX_FREQUENCY: Rate = Rate::from_khz(1);
+
+/// Software PWM errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Error {
+    /// The frequency is zero or higher than [`MAX_FREQUENCY`].
+    UnsupportedFrequency,
+    /// The duty cycle is higher than 100%.
+    InvalidDuty,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::UnsupportedFrequency => write!(
+                f,
+                "The frequency must be between 1 Hz and {} Hz",
+                MAX_FREQUENCY.as_hz()
+            ),
+            Error::InvalidDuty => write!(f, "The duty cycle must be between 0 and 100%"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// The state shared with the interrupt handler of a timer.
+struct Slot {
+    pin: AtomicU8,
+    high: AtomicBool,
+    high_ticks: AtomicU32,
+    low_ticks: AtomicU32,
+}
+
+impl Slot {
+    const fn new() -> Self {
+        Self {
+            pin: AtomicU8::new(0),
+            high: AtomicBool::new(false),
+            high_ticks: AtomicU32::new(0),
+            low_ticks: AtomicU32::new(0),
+        }
+    }
+}
+
+// Indexed by `timer_group * 2 + timer_number`.
+static SLOTS: [Slot; 4] = [const { Slot::new() }; 4];
+
+fn on_alarm(tg: u8, timer: u8) {
+    let slot = &SLOTS[(tg * 2 + timer) as usize];
+    let timer = unsafe { Timer::steal(tg, timer) };
+
+    let high = !slot.high.load(Ordering::Relaxed);
+    slot.high.store(high, Ordering::Relaxed);
+    unsafe { AnyPin::steal(slot.pin.load(Ordering::Relaxed)) }.set_output_high(high);
+
+    // The counter has been reloaded to 0 by the alarm, so this is the length
+    // of the phase that just started.
+    let ticks = if high {
+        slot.high_ticks.load(Ordering::Relaxed)
+    } else {
+        slot.low_ticks.load(Ordering::Relaxed)
+    };
+    timer.set_alarm_ticks(ticks as u64);
+
+    // Also re-arms the alarm, as auto-reload is enabled.
+    timer.clear_interrupt();
+}
+
+#[handler(priority = Priority::max())]
+fn timg0_timer0_handler() {
+    on_alarm(0, 0);
+}
+
+#[cfg(timergroup_timg_has_timer1)]
+#[handler(priority = Priority::max())]
+fn timg0_timer1_handler() {
+    on_alarm(0, 1);
+}
+
+#[cfg(timergroup_timg1)]
+#[handler(priority = Priority::max())]
+fn timg1_timer0_handler() {
+    on_alarm(1, 0);
+}
+
+#[cfg(all(timergroup_timg1, timergroup_timg_has_timer1))]
+#[handler(priority = Priority::max())]
+fn timg1_timer1_handler() {
+    on_alarm(1, 1);
+}
+
+/// A PWM output driven by a TIMG timer interrupt.
+///
+/// See the [module-level documentation](self) for more details.
+pub struct SoftPwm<'d> {
+    pin: Output<'d>,
+    timer: Timer<'d>,
+    period_ticks: u32,
+    duty: u8,
+}
+
+impl<'d> SoftPwm<'d> {
+    /// Creates a new software PWM on `pin`, driven by `timer`.
+    ///
+    /// The output starts out low, with a duty cycle of 0%. Use
+    /// [`Self::set_duty`] to start generating pulses.
+    ///
+    /// This replaces the interrupt handler of `timer`.
+    pub fn new(pin: impl OutputPin + 'd, timer: Timer<'d>, frequency: Rate) -> Result<Self, Error> {
+        if frequency.as_hz() == 0 || frequency > MAX_FREQUENCY {
+            return Err(Error::UnsupportedFrequency);
+        }
+
+        let pin_number = pin.number();
+        let pin = Output::new(pin, Level::Low, OutputConfig::default());
+
+        timer.stop();
+        timer.enable_interrupt(false);
+        timer.enable_auto_reload(true);
+
+        let slot = &SLOTS[(timer.timer_group() * 2 + timer.timer_number()) as usize];
+        slot.pin.store(pin_number, Ordering::Relaxed);
+
+        let handler = match (timer.timer_group(), timer.timer_number()) {
+            (0, 0) => timg0_timer0_handler,
+            #[cfg(timergroup_timg_has_timer1)]
+            (0, 1) => timg0_timer1_handler,
+            #[cfg(timergroup_timg1)]
+            (1, 0) => timg1_timer0_handler,
+            #[cfg(all(timergroup_timg1, timergroup_timg_has_timer1))]
+            (1, 1) => timg1_timer1_handler,
+            _ => unreachable!(),
+        };
+        timer.set_interrupt_handler(handler);
+
+        Ok(Self {
+            period_ticks: timer.ticks_per_second() / frequency.as_hz(),
+            pin,
+            timer,
+            duty: 0,
+        })
+    }
+
+    /// Sets the duty cycle, in percent.
+    ///
+    /// The new duty cycle takes effect at the next edge. 0% and 100% keep the
+    /// output at a constant level and stop the timer.
+    pub fn set_duty(&mut self, duty: u8) -> Result<(), Error> {
+        if duty > 100 {
+            return Err(Error::InvalidDuty);
+        }
+
+        let high_ticks = (self.period_ticks as u64 * duty as u64 / 100) as u32;
+        let low_ticks = self.period_ticks - high_ticks;
+        let slot = self.slot();
+
+        critical_section::with(|_| {
+            if high_ticks == 0 || low_ticks == 0 {
+                self.stop();
+                slot.high.store(low_ticks == 0, Ordering::Relaxed);
+                self.pin.set_level(Level::from(low_ticks == 0));
+                return;
+            }
+
+            slot.high_ticks.store(high_ticks, Ordering::Relaxed);
+            slot.low_ticks.store(low_ticks, Ordering::Relaxed);
+
+            if !self.timer.is_running() {
+                slot.high.store(true, Ordering::Relaxed);
+                self.pin.set_high();
+
+                self.timer.set_alarm_ticks(high_ticks as u64);
+                self.timer.clear_interrupt();
+                self.timer.enable_interrupt(true);
+                self.timer.start();
+            }
+        });
+
+        self.duty = duty;
+        Ok(())
+    }
+
+    /// Returns the current duty cycle, in percent.
+    pub fn duty(&self) -> u8 {
+        self.duty
+    }
+
+    fn slot(&self) -> &'static Slot {
+        &SLOTS[(self.timer.timer_group() * 2 + self.timer.timer_number()) as usize]
+    }
+
+    fn stop(&self) {
+        self.timer.stop();
+        self.timer.enable_interrupt(false);
+        self.timer.clear_interrupt();
+    }
+}
+
+impl Drop for SoftPwm<'_> {
+    fn drop(&mut self) {
+        critical_section::with(|_| self.stop());
+    }
+}
@@ -1379,7 +1379,15 @@ mod dma {
 
     use super::*;
     use crate::{
-        dma::{Channel, DmaRxBuf, DmaTxBuf, EmptyBuf, PeripheralDmaChannel, asynch::DmaRxFuture},
+        dma::{
+            Channel,
+            DmaBufferProgress,
+            DmaRxBuf,
+            DmaTxBuf,
+            EmptyBuf,
+            PeripheralDmaChannel,
+            asynch::DmaRxFuture,
+        },
         spi::master::dma::asynch::DropGuard,
     };
 
@@ -1838,6 +1846,31 @@ mod dma {
             self.spi_dma.is_done()
         }
 
+        /// Returns the number of bytes transferred so far.
+        ///
+        /// While the transfer is in progress, the count advances one DMA
+        /// descriptor at a time, i.e. in steps of up to
+        /// [CHUNK_SIZE](crate::dma::CHUNK_SIZE) bytes. For transfers that only
+        /// transmit, bytes are counted once they have been pushed into the SPI
+        /// FIFO, so the count may run slightly ahead of the bus. See
+        /// [DmaBufferProgress] for details.
+        ///
+        /// Once the transfer is done, this returns its full length. This is
+        /// also the case after [Self::cancel], so the count is only
+        /// meaningful for transfers that run to completion.
+        #[instability::unstable]
+        pub fn transferred_bytes(&self) -> usize
+        where
+            Buf: DmaBufferProgress,
+        {
+            if self.is_done() {
+                return self.dma_buf.total_bytes();
+            }
+
+            let current_out_descriptor = self.spi_dma.channel.tx.current_out_dscr_address();
+            self.dma_buf.completed_bytes(current_out_descriptor)
+        }
+
         /// Waits for the DMA transfer to complete.
         ///
         /// This method blocks until the transfer is finished and returns the
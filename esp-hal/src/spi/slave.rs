@@ -43,7 +43,10 @@
 //!
 //! let transfer = spi.transfer(50, dma_rx_buf, 50, dma_tx_buf)?;
 //!
-//! transfer.wait();
+//! let (spi, (dma_rx_buf, _)) = transfer.wait();
+//!
+//! // The master may deassert CS before all 50 bytes are clocked.
+//! let received = &dma_rx_buf.as_slice()[..spi.transferred_bytes()];
 //! # {after_snippet}
 //! ```
 //!
@@ -62,6 +65,9 @@
 //! then the `SpiDmaTransfer` object can be `wait()`ed on or polled for
 //! `is_done()`.
 //!
+//! The master ends a transaction by deasserting CS. Afterwards,
+//! `SpiDma::transferred_bytes()` reports how many bytes it clocked.
+//!
 //! See [tracking issue](https://github.com/esp-rs/esp-hal/issues/469) for more information.
 
 use core::marker::PhantomData;
@@ -234,6 +240,18 @@ pub mod dma {
             }
         }
 
+        /// Returns the number of bytes the master clocked in the last
+        /// transaction.
+        ///
+        /// The master ends a transaction by deasserting CS, which may happen
+        /// before the DMA buffers are exhausted. Call this after
+        /// [SpiDmaTransfer::wait] to find out how much of the RX buffer
+        /// holds valid data, and how much of the TX buffer was sent.
+        #[instability::unstable]
+        pub fn transferred_bytes(&self) -> usize {
+            self.spi.info().transferred_bytes()
+        }
+
         /// Register a buffer for a DMA write.
         ///
         /// This will return a [SpiDmaTransfer]. The maximum amount of data to
@@ -310,7 +328,9 @@ pub mod dma {
         /// be sent/received is 32736 bytes.
         ///
         /// The data transfer is driven by the SPI master's sclk signal and cs
-        /// line.
+        /// line. The transfer completes when the master deasserts CS, even if
+        /// it clocked fewer bytes than requested; use
+        /// [SpiDma::transferred_bytes] to find out how many it clocked.
         #[instability::unstable]
         #[allow(clippy::type_complexity)]
         pub fn transfer<RX, TX>(
@@ -763,6 +783,23 @@ impl Info {
         }
     }
 
+    /// Returns the number of bytes the master clocked in the last transaction,
+    /// i.e. before it deasserted CS.
+    fn transferred_bytes(&self) -> usize {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                // The register holds the bit length minus one, and reads zero
+                // for an empty transaction.
+                let bits = self.regs().slv_rd_bit().read().slv_rdata_bit().bits() as usize;
+                if bits == 0 { 0 } else { (bits + 1) / 8 }
+            } else if #[cfg(esp32s2)] {
+                self.regs().slv_rd_byte().read().slv_data_bytelen().bits() as usize
+            } else {
+                self.regs().slave1().read().slv_data_bitlen().bits() as usize / 8
+            }
+        }
+    }
+
     // Clear the transaction-done interrupt flag so flush() can work properly. Not
     // used in DMA mode.
     fn setup_for_flush(&self) {
@@ -311,6 +311,72 @@ impl<const P: u8> Drop for GenericPeripheralGuard<P> {
     }
 }
 
+/// A peripheral whose clock esp-hal keeps enabled while no driver uses it.
+///
+/// [`crate::init`] gates the clock of every peripheral that is not in use,
+/// and drivers gate it again when they are dropped. These peripherals are the
+/// exception, because code outside of their drivers may access them, e.g. a
+/// logger printing to UART0. Use [`ClockGate`] to gate them when they are not
+/// needed.
+#[instability::unstable]
+pub trait AlwaysOnPeripheral: crate::private::Sealed {
+    #[doc(hidden)]
+    const PERIPHERAL: Peripheral;
+}
+
+#[cfg(soc_has_uart0)]
+#[instability::unstable]
+impl AlwaysOnPeripheral for crate::peripherals::UART0<'_> {
+    const PERIPHERAL: Peripheral = Peripheral::Uart0;
+}
+
+#[procmacros::doc_replace]
+/// Gates the clock of an [`AlwaysOnPeripheral`] while it is not needed.
+///
+/// The gate holds the peripheral singleton, so no driver can use the
+/// peripheral while its clock is gated. Dropping the gate enables the clock
+/// again. The peripheral is reset while gated, so a driver created afterwards
+/// starts from the reset configuration.
+///
+/// Don't gate UART0 if anything writes to it directly, like `esp-println`
+/// with the `uart` feature or the ROM's print functions: their output is
+/// lost while the clock is gated.
+///
+/// ## Example
+///
+/// ```rust, no_run
+/// # {before_snippet}
+/// use esp_hal::system::ClockGate;
+///
+/// let uart0_gate = ClockGate::new(peripherals.UART0);
+/// // The clock of UART0 is gated until `uart0_gate` is dropped.
+/// # {after_snippet}
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub struct ClockGate<P: AlwaysOnPeripheral> {
+    _peripheral: P,
+}
+
+impl<P: AlwaysOnPeripheral> ClockGate<P> {
+    /// Gates the clock of `peripheral`.
+    #[instability::unstable]
+    pub fn new(peripheral: P) -> Self {
+        PeripheralClockControl::enable_forced(P::PERIPHERAL, false, true);
+
+        Self {
+            _peripheral: peripheral,
+        }
+    }
+}
+
+impl<P: AlwaysOnPeripheral> Drop for ClockGate<P> {
+    fn drop(&mut self) {
+        PeripheralClockControl::enable_forced(P::PERIPHERAL, true, true);
+    }
+}
+
 /// Controls the enablement of peripheral clocks.
 pub(crate) struct PeripheralClockControl;
 
@@ -994,6 +1060,16 @@ impl PeripheralClockControl {
         Self::enable_forced(peripheral, false, false)
     }
 
+    /// Returns whether the given peripheral is enabled.
+    // Only used to retain peripheral configuration through deep sleep.
+    #[cfg(any(esp32, esp32s2, esp32s3, esp32c3, esp32c6))]
+    pub(crate) fn is_enabled(peripheral: Peripheral) -> bool {
+        Peripheral::KEEP_ENABLED.contains(&peripheral)
+            || critical_section::with(|cs| {
+                PERIPHERAL_REF_COUNT.borrow_ref(cs)[peripheral as usize] > 0
+            })
+    }
+
     pub(crate) fn enable_forced(peripheral: Peripheral, enable: bool, force: bool) -> bool {
         critical_section::with(|cs| Self::enable_forced_with_cs(peripheral, enable, force, cs))
     }
@@ -1173,6 +1249,14 @@ pub enum SleepSource {
 #[procmacros::doc_replace]
 /// Performs a software reset on the chip.
 ///
+/// The CPUs and the peripherals are reset, and the chip boots again. After
+/// the reset, [`Rtc::reset_reason`](crate::rtc_cntl::Rtc::reset_reason)
+/// reports [`ResetReason::Software`](crate::rtc_cntl::ResetReason::Software).
+///
+/// Data that is still in the TX FIFO of a UART is lost. To keep the last log
+/// messages, call `flush` on the UART drivers, and [`flush_uart0`] for
+/// messages that were written to UART0 without a driver, before resetting.
+///
 /// # Example
 ///
 /// ```rust, no_run
@@ -1193,6 +1277,44 @@ pub fn software_reset_cpu(cpu: Cpu) {
     crate::rom::software_reset_cpu(cpu as u32)
 }
 
+#[procmacros::doc_replace]
+/// Waits until UART0 has transmitted the data in its TX FIFO.
+///
+/// The ROM, and loggers like `esp-println`, write to UART0 directly, without
+/// a driver that could be flushed. Call this function before
+/// [`software_reset`] to make sure that the last messages are not cut off,
+/// like ESP-IDF does before restarting.
+///
+/// The clock of UART0 must be enabled, which is the case unless it was gated
+/// with [`ClockGate`].
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # {before_snippet}
+/// use esp_hal::system::{flush_uart0, software_reset};
+///
+/// println!("Rebooting to apply the update");
+/// flush_uart0();
+/// software_reset();
+/// # {after_snippet}
+/// ```
+#[instability::unstable]
+pub fn flush_uart0() {
+    let regs = crate::peripherals::UART0::regs();
+    while regs.status().read().txfifo_cnt().bits() > 0 {}
+
+    // The transmitter reports being idle for a short while after the last byte leaves the
+    // FIFO, see `UartTx::flush`.
+    crate::rom::ets_delay_us(10);
+
+    #[cfg(esp32)]
+    let status = regs.status();
+    #[cfg(not(esp32))]
+    let status = regs.fsm_status();
+    while status.read().st_utx_out().bits() != 0 {}
+}
+
 /// Retrieves the reason for the last reset as a SocResetReason enum value.
 /// Returns `None` if the reset reason cannot be determined.
 #[instability::unstable]
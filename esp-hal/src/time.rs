@@ -712,7 +712,7 @@ impl core::ops::Div<Duration> for Duration {
 #[inline]
 fn now() -> Instant {
     #[cfg(esp32)]
-    let (ticks, div) = {
+    {
         // on ESP32 use LACT
         let tg0 = TIMG0::regs();
         tg0.lactupdate().write(|w| unsafe { w.update().bits(1) });
@@ -732,18 +732,12 @@ fn now() -> Instant {
         let hi = tg0.lacthi().read().bits();
 
         let ticks = ((hi as u64) << 32u64) | lo as u64;
-        (ticks, 16)
-    };
+        Instant::from_ticks(ticks / 16)
+    }
 
+    // otherwise use SYSTIMER
     #[cfg(not(esp32))]
-    let (ticks, div) = {
-        use crate::timer::systimer::{SystemTimer, Unit};
-        // otherwise use SYSTIMER
-        let ticks = SystemTimer::unit_value(Unit::Unit0);
-        (ticks, (SystemTimer::ticks_per_second() / 1_000_000))
-    };
-
-    Instant::from_ticks(ticks / div)
+    crate::timer::systimer::SystemTimer::now()
 }
 
 #[cfg(all(esp32, feature = "rt"))]
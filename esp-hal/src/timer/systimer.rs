@@ -94,6 +94,66 @@ impl<'d> SystemTimer<'d> {
         ((xtal_freq_mhz * MULTIPLIER) / DIVIDER) as u64
     }
 
+    #[procmacros::doc_replace(
+        "counter" => {
+            cfg(esp32s2) => "The counter is 64-bit wide and counts at 80 MHz (12.5 ns per tick), so it wraps after more than 7_000 years.",
+            cfg(esp32c2) => "The counter is 52-bit wide and counts at 16 MHz (62.5 ns per tick) with a 40 MHz crystal, or 10.4 MHz with a 26 MHz crystal, so it wraps after more than 8 years.",
+            _ => "The counter is 52-bit wide and counts at 16 MHz (62.5 ns per tick), so it wraps after more than 8 years."
+        }
+    )]
+    /// Returns the current value of `Unit0` as an [`Instant`].
+    ///
+    /// `Unit0` is the time source of [`Instant::now`], so the returned value
+    /// can be compared with other instants. The counter keeps running while
+    /// the driver is not in use, but not in sleep modes.
+    ///
+    /// # {counter}
+    /// As [`Instant`] has a resolution of 1 µs, finer ticks are truncated. Use
+    /// [`Self::unit_value`] and [`Self::ticks_per_second`] to work with the
+    /// raw counter value instead.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::timer::systimer::SystemTimer;
+    /// let start = SystemTimer::now();
+    /// // ...
+    /// let elapsed = SystemTimer::now() - start;
+    /// # {after_snippet}
+    /// ```
+    #[inline]
+    pub fn now() -> Instant {
+        Instant::from_ticks(Self::ticks_to_us(Unit::Unit0.read_count()))
+    }
+
+    /// Converts a number of timer ticks to microseconds, rounding down.
+    #[inline]
+    pub(crate) fn ticks_to_us(ticks: u64) -> u64 {
+        let ticks_per_second = Self::ticks_per_second();
+        if ticks_per_second % 1_000_000 == 0 {
+            return ticks / (ticks_per_second / 1_000_000);
+        }
+
+        // Avoid overflowing on the multiplication for large tick counts.
+        let seconds = ticks / ticks_per_second;
+        let remainder = ticks % ticks_per_second;
+        seconds * 1_000_000 + remainder * 1_000_000 / ticks_per_second
+    }
+
+    /// Converts microseconds to a number of timer ticks, rounding down.
+    #[inline]
+    pub(crate) fn us_to_ticks(us: u64) -> u64 {
+        let ticks_per_second = Self::ticks_per_second();
+        if ticks_per_second % 1_000_000 == 0 {
+            return us * (ticks_per_second / 1_000_000);
+        }
+
+        let seconds = us / 1_000_000;
+        let remainder = us % 1_000_000;
+        seconds * ticks_per_second + remainder * ticks_per_second / 1_000_000
+    }
+
     /// Create a new instance.
     pub fn new(_systimer: SYSTIMER<'d>) -> Self {
         // Don't reset Systimer as it will break `time::Instant::now`, only enable it
@@ -508,16 +568,14 @@ impl super::Timer for Alarm<'_> {
 
         let ticks = self.unit.read_count();
 
-        let us = ticks / (SystemTimer::ticks_per_second() / 1_000_000);
-
-        Instant::from_ticks(us)
+        Instant::from_ticks(SystemTimer::ticks_to_us(ticks))
     }
 
     fn load_value(&self, value: Duration) -> Result<(), Error> {
         let mode = self.mode();
 
         let us = value.as_micros();
-        let ticks = us * (SystemTimer::ticks_per_second() / 1_000_000);
+        let ticks = SystemTimer::us_to_ticks(us);
 
         if matches!(mode, ComparatorMode::Period) {
             // Period mode
@@ -439,14 +439,36 @@ impl Timer<'_> {
         unsafe { &*self.register_block }
     }
 
-    fn timer_group(&self) -> u8 {
+    pub(crate) fn timer_group(&self) -> u8 {
         self.tg
     }
 
-    fn timer_number(&self) -> u8 {
+    pub(crate) fn timer_number(&self) -> u8 {
         self.timer
     }
 
+    /// Creates a handle to timer `timer` of timer group `tg`.
+    ///
+    /// # Safety
+    ///
+    /// The timer must be owned by the caller, e.g. an interrupt handler
+    /// installed by the driver that owns it.
+    pub(crate) unsafe fn steal(tg: u8, timer: u8) -> Timer<'static> {
+        let register_block = match tg {
+            0 => TIMG0::regs(),
+            #[cfg(timergroup_timg1)]
+            1 => TIMG1::regs(),
+            _ => unreachable!(),
+        };
+
+        Timer {
+            register_block,
+            _lifetime: PhantomData,
+            timer,
+            tg,
+        }
+    }
+
     fn t(&self) -> &crate::pac::timg0::T {
         self.register_block().t(self.timer_number().into())
     }
@@ -503,6 +525,13 @@ impl Timer<'_> {
             return Err(Error::InvalidTimeout);
         }
 
+        self.set_alarm_ticks(ticks);
+
+        Ok(())
+    }
+
+    /// Sets the alarm value, in timer ticks.
+    pub(crate) fn set_alarm_ticks(&self, ticks: u64) {
         let high = (ticks >> 32) as u32;
         let low = (ticks & 0xFFFF_FFFF) as u32;
 
@@ -510,8 +539,19 @@ impl Timer<'_> {
 
         t.alarmlo().write(|w| unsafe { w.alarm_lo().bits(low) });
         t.alarmhi().write(|w| unsafe { w.alarm_hi().bits(high) });
+    }
 
-        Ok(())
+    /// Returns the number of timer ticks per second.
+    pub(crate) fn ticks_per_second(&self) -> u32 {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32h2)] {
+                // ESP32-H2 is using PLL_48M_CLK source instead of APB_CLK
+                let clk_src = Clocks::get().pll_48m_clock;
+            } else {
+                let clk_src = Clocks::get().apb_clock;
+            }
+        }
+        clk_src.as_hz() / self.divider()
     }
 
     fn clear_interrupt(&self) {
@@ -24,7 +24,9 @@
 //! Mostly feature complete, missing:
 //!
 //! - Touch sensor slope control
-//! - Deep Sleep support (wakeup from Deep Sleep)
+//!
+//! Wake-up from deep sleep is supported through
+//! [`TouchWakeupSource`](crate::rtc_cntl::sleep::TouchWakeupSource).
 
 use core::marker::PhantomData;
 
@@ -15,7 +15,15 @@
 //!
 //! ## Configuration
 //!
-//! The temperature sensor can be configured with different clock sources.
+//! The temperature sensor can be configured with different clock sources and
+//! measurement ranges. Each range trades span for accuracy, so pick the
+//! narrowest one that covers the expected temperatures.
+//!
+//! ## Calibration
+//!
+//! [`TemperatureSensor::read_celsius`] applies the factory calibration stored
+//! in eFuse, if the chip has one. Chips without calibration data fall back to
+//! the uncalibrated conversion.
 //!
 //! ## Examples
 //!
@@ -31,8 +39,8 @@
 //! let delay = Delay::new();
 //! delay.delay_micros(200);
 //! loop {
-//!     let temp = temperature_sensor.get_temperature();
-//!     println!("Temperature: {:.2}°C", temp.to_celcius());
+//!     let temp = temperature_sensor.read_celsius();
+//!     println!("Temperature: {:.2}°C", temp);
 //!     delay.delay_millis(1_000);
 //! }
 //! # }
@@ -40,11 +48,12 @@
 //!
 //! ## Implementation State
 //!
-//! - Temperature calibration range is not supported
 //! - Interrupts are not supported
 
 use crate::{
+    efuse::Efuse,
     peripherals::{APB_SARADC, TSENS},
+    soc::regi2c,
     system::GenericPeripheralGuard,
 };
 
@@ -60,6 +69,50 @@ pub enum ClockSource {
     Xtal,
 }
 
+/// Measurement range of the temperature sensor
+///
+/// Narrower ranges measure more accurately. The maximum error of each range
+/// is noted on its variant.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Copy, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum MeasurementRange {
+    /// 50 °C to 125 °C, with an error of less than 3 °C
+    Celsius50To125,
+    /// 20 °C to 100 °C, with an error of less than 2 °C
+    #[default]
+    Celsius20To100,
+    /// -10 °C to 80 °C, with an error of less than 1 °C
+    CelsiusMinus10To80,
+    /// -30 °C to 50 °C, with an error of less than 2 °C
+    CelsiusMinus30To50,
+    /// -40 °C to 20 °C, with an error of less than 3 °C
+    CelsiusMinus40To20,
+}
+
+impl MeasurementRange {
+    // see <https://github.com/espressif/esp-idf/blob/903af13e8/components/esp_driver_tsens/src/temperature_sensor.c>
+    fn offset(self) -> i8 {
+        match self {
+            Self::Celsius50To125 => -2,
+            Self::Celsius20To100 => -1,
+            Self::CelsiusMinus10To80 => 0,
+            Self::CelsiusMinus30To50 => 1,
+            Self::CelsiusMinus40To20 => 2,
+        }
+    }
+
+    fn dac_value(self) -> u8 {
+        match self {
+            Self::Celsius50To125 => 5,
+            Self::Celsius20To100 => 7,
+            Self::CelsiusMinus10To80 => 15,
+            Self::CelsiusMinus30To50 => 11,
+            Self::CelsiusMinus40To20 => 10,
+        }
+    }
+}
+
 /// Temperature sensor configuration
 #[derive(Debug, Clone, Default, PartialEq, Eq, Copy, Hash, procmacros::BuilderLite)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -67,6 +120,9 @@ pub enum ClockSource {
 pub struct Config {
     /// Clock source for the temperature sensor
     clock_source: ClockSource,
+
+    /// Measurement range of the temperature sensor
+    range: MeasurementRange,
 }
 
 /// Temperature sensor configuration error
@@ -119,6 +175,8 @@ impl Temperature {
 #[derive(Debug)]
 pub struct TemperatureSensor<'d> {
     _peripheral: TSENS<'d>,
+    offset: i8,
+    calibration: f32,
     _tsens_guard: GenericPeripheralGuard<{ crate::system::Peripheral::Tsens as u8 }>,
     _abp_saradc_guard: GenericPeripheralGuard<{ crate::system::Peripheral::ApbSarAdc as u8 }>,
 }
@@ -133,6 +191,8 @@ impl<'d> TemperatureSensor<'d> {
 
         let mut tsens = Self {
             _peripheral: peripheral,
+            offset: config.range.offset(),
+            calibration: Efuse::rtc_calib_tsens_val().unwrap_or(0) as f32 / 10.0,
             _tsens_guard: tsens_guard,
             _abp_saradc_guard: apb_saradc_guard,
         };
@@ -166,17 +226,28 @@ impl<'d> TemperatureSensor<'d> {
                 .bit(matches!(config.clock_source, ClockSource::Xtal))
         });
 
+        regi2c::ADC_SARADC_TSENS_DAC.write_field(config.range.dac_value());
+        self.offset = config.range.offset();
+
         Ok(())
     }
 
     /// Get the raw temperature value
+    ///
+    /// The returned value does not include the factory calibration, use
+    /// [`Self::read_celsius`] for a calibrated reading.
     #[inline]
     pub fn get_temperature(&self) -> Temperature {
         let raw_value = APB_SARADC::regs().tsens_ctrl().read().out().bits();
 
-        // TODO Address multiple temperature ranges and offsets
-        let offset = -1i8;
+        Temperature::new(raw_value, self.offset)
+    }
 
-        Temperature::new(raw_value, offset)
+    /// Read the temperature in Celsius
+    ///
+    /// The factory calibration stored in eFuse is applied if available.
+    #[inline]
+    pub fn read_celsius(&self) -> f32 {
+        self.get_temperature().to_celsius() - self.calibration
     }
 }
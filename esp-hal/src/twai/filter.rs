@@ -11,7 +11,30 @@
 //! ## Configuration
 //!
 //! The acceptance filters are configured using two 32-bit values known as the
-//! acceptance code and the acceptance mask.
+//! acceptance code and the acceptance mask. The code holds the bit values to
+//! match, and the mask selects which bits of the code are compared. Each
+//! filter type can be created from bit patterns using `new`, or from code and
+//! mask values using `new_from_code_mask`. In both cases, only the bits the
+//! filter cares about have to be set in the mask.
+//!
+//! The filter is applied in hardware, so frames that don't match are dropped
+//! before they are stored in the receive FIFO. This avoids overrunning the
+//! FIFO on busy buses.
+//!
+//! ### Bit layout
+//!
+//! The frame fields are mapped to the 32 bits of the code and mask as
+//! follows, bit 31 being the most significant:
+//!
+//! | Filter                   | Bits 31..16                                                     | Bits 15..0                                                              |
+//! |--------------------------|-----------------------------------------------------------------|-------------------------------------------------------------------------|
+//! | [`SingleStandardFilter`] | ID (31..21), RTR (20), unused (19..16)                          | first data byte (15..8), second data byte (7..0)                        |
+//! | [`SingleExtendedFilter`] | ID (31..3), RTR (2), unused (1..0)                              |                                                                         |
+//! | [`DualStandardFilter`]   | 1st filter: ID (31..21), RTR (20), data byte bits 7..4 (19..16) | 2nd filter: ID (15..5), RTR (4); 1st filter: data byte bits 3..0 (3..0) |
+//! | [`DualExtendedFilter`]   | 1st filter: ID bits 28..13                                      | 2nd filter: ID bits 28..13                                              |
+//!
+//! The single extended filter spans both halves. In dual filter mode, a
+//! frame is accepted if it matches either of the two filters.
 
 use super::{ExtendedId, StandardId};
 
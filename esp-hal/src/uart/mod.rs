@@ -48,6 +48,8 @@
 #[cfg(feature = "unstable")]
 pub mod uhci;
 
+#[cfg(feature = "unstable")]
+use core::convert::Infallible;
 use core::{marker::PhantomData, sync::atomic::Ordering, task::Poll};
 
 #[cfg(feature = "unstable")]
@@ -73,9 +75,13 @@ use crate::{
     interrupt::InterruptHandler,
     pac::uart0::RegisterBlock,
     private::OnDrop,
+    sync::RawMutex,
     system::{PeripheralClockControl, PeripheralGuard},
 };
 
+/// Serializes the read-modify-write accesses of the interrupt enable registers.
+static INT_ENA_LOCK: RawMutex = RawMutex::new();
+
 /// UART RX Error
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -105,6 +111,17 @@ pub enum RxError {
     /// This error occurs when the parity bit in the received data does not
     /// match the expected parity configuration.
     ParityMismatch,
+
+    /// The checksum of a received frame did not match its contents.
+    ///
+    /// This error is returned by [`UartRx::read_exact_with_checksum`].
+    ChecksumMismatch,
+
+    /// The buffer filled up before the line delimiter was received.
+    ///
+    /// This error is returned by [`UartRx::read_line`]. The buffer contains
+    /// the beginning of the line.
+    LineTooLong,
 }
 
 impl core::error::Error for RxError {}
@@ -118,6 +135,8 @@ impl core::fmt::Display for RxError {
                 write!(f, "A framing error was detected on the RX line")
             }
             RxError::ParityMismatch => write!(f, "A parity error was detected on the RX line"),
+            RxError::ChecksumMismatch => write!(f, "The checksum of the received frame is invalid"),
+            RxError::LineTooLong => write!(f, "The received line does not fit into the buffer"),
         }
     }
 }
@@ -129,6 +148,37 @@ impl embedded_io::Error for RxError {
     }
 }
 
+/// Line conditions reported along with a received byte.
+///
+/// See [`UartRx::read_byte_status`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub struct RxStatus {
+    /// A parity error was detected.
+    pub parity_error: bool,
+
+    /// A framing error was detected, i.e. the stop bit was not received where
+    /// it was expected.
+    pub frame_error: bool,
+
+    /// The RX FIFO overflowed, and received bytes were lost.
+    ///
+    /// The driver resets the RX FIFO when an overrun is reported, discarding
+    /// any bytes that were still in it.
+    pub overrun: bool,
+}
+
+#[cfg(feature = "unstable")]
+impl RxStatus {
+    /// Returns `true` if no line condition was reported.
+    pub fn is_ok(&self) -> bool {
+        !(self.parity_error || self.frame_error || self.overrun)
+    }
+}
+
 /// UART TX Error
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -158,6 +208,9 @@ pub enum ClockSource {
     #[cfg_attr(not(any(esp32c6, esp32h2, soc_has_lp_uart)), default)]
     Apb,
     /// RC_FAST_CLK clock source (17.5 MHz)
+    ///
+    /// The frequency of this clock changes with the temperature. Use
+    /// `Clocks::recalibrate_rc` to measure it.
     #[cfg(not(any(esp32, esp32s2)))]
     RcFast,
     /// XTAL_CLK clock source
@@ -283,6 +336,39 @@ pub struct HwFlowControl {
     pub rts: RtsConfig,
 }
 
+/// The active level of the RS-485 driver enable signal.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub enum DriverEnablePolarity {
+    /// The driver enable signal is high while transmitting.
+    #[default]
+    ActiveHigh,
+    /// The driver enable signal is low while transmitting.
+    ActiveLow,
+}
+
+/// RS-485 configuration.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+pub enum Rs485Mode {
+    /// The RTS pin is used for hardware flow control, if enabled.
+    #[default]
+    Disabled,
+    /// Half-duplex RS-485, with the RTS pin driving the driver enable (DE)
+    /// and receiver enable (RE) inputs of the transceiver.
+    ///
+    /// The hardware asserts the RTS pin while data is transmitted, and
+    /// releases it after the last stop bit has been sent. The receiver is
+    /// disabled while transmitting, so the driver does not receive its own
+    /// data.
+    ///
+    /// The RTS pin is set with [`Uart::with_rts`]. This mode can't be used
+    /// together with RTS flow control.
+    HalfDuplex(DriverEnablePolarity),
+}
+
 /// Defines how strictly the requested baud rate must be met.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -298,6 +384,14 @@ pub enum BaudrateTolerance {
     ErrorPercent(u8),
 }
 
+/// The size of the RX and TX FIFOs of every UART, in bytes.
+///
+/// This is the number of bytes that can be written without blocking when the
+/// TX FIFO is empty, and the number of bytes that can be received without
+/// reading before the RX FIFO overflows.
+#[instability::unstable]
+pub const FIFO_SIZE: u16 = Info::UART_FIFO_SIZE;
+
 /// UART Configuration
 #[derive(Debug, Clone, Copy, procmacros::BuilderLite)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -322,9 +416,34 @@ pub struct Config {
     /// Hardware flow control.
     #[builder_lite(unstable)]
     hw_flow_ctrl: HwFlowControl,
+    /// RS-485 direction control.
+    #[builder_lite(unstable)]
+    rs485_mode: Rs485Mode,
     /// Clock source used by the UART peripheral.
     #[builder_lite(unstable)]
     clock_source: ClockSource,
+    /// Invert the level of the TX pin, so that it idles low.
+    ///
+    /// Inverting a signal doesn't change its timing, so this can be combined
+    /// with any baud rate and frame format. In IrDA mode, the encoded pulses
+    /// are inverted.
+    #[builder_lite(unstable)]
+    invert_tx: bool,
+    /// Invert the level of the RX pin, for example for serial sensors on a
+    /// single wire that idles low.
+    ///
+    /// Like [`Self::invert_tx`], this doesn't change the timing of the
+    /// signal. The driver doesn't use the hardware's baud rate detection, so
+    /// the configured baud rate is used regardless of the inversion.
+    #[builder_lite(unstable)]
+    invert_rx: bool,
+    /// Enable the IrDA encoder and decoder.
+    ///
+    /// In IrDA mode, a zero bit is transmitted as a pulse that is 3/16 of
+    /// the bit time long, and a one bit as no pulse. The receiver expects
+    /// the same encoding.
+    #[builder_lite(unstable)]
+    irda_mode: bool,
     /// UART Receive part configuration.
     rx: RxConfig,
     /// UART Transmit part configuration.
@@ -343,23 +462,70 @@ impl Default for Config {
             stop_bits: Default::default(),
             sw_flow_ctrl: Default::default(),
             hw_flow_ctrl: Default::default(),
+            rs485_mode: Default::default(),
             clock_source: Default::default(),
+            invert_tx: false,
+            invert_rx: false,
+            irda_mode: false,
         }
     }
 }
 
 impl Config {
     fn validate(&self) -> Result<(), ConfigError> {
-        if let BaudrateTolerance::ErrorPercent(percentage) = self.baudrate_tolerance {
-            assert!(percentage > 0 && percentage <= 100);
+        #[cfg(feature = "unstable")]
+        if matches!(
+            self.baudrate_tolerance,
+            BaudrateTolerance::ErrorPercent(percentage) if !(1..=100).contains(&percentage)
+        ) {
+            return Err(ConfigError::BaudrateToleranceNotSupported);
         }
 
         // Max supported baud rate is 5Mbaud
         if self.baudrate == 0 || self.baudrate > 5_000_000 {
             return Err(ConfigError::BaudrateNotSupported);
         }
+
+        // Check the settings of the halves up front, so that an invalid
+        // configuration isn't partially applied.
+        if self.rx.fifo_full_threshold > Info::RX_FIFO_MAX_THRHD {
+            return Err(ConfigError::RxFifoThresholdNotSupported);
+        }
+        if self.tx.fifo_empty_threshold > Info::TX_FIFO_MAX_THRHD {
+            return Err(ConfigError::TxFifoThresholdNotSupported);
+        }
+        if let Some(timeout) = self.rx.timeout {
+            Info::rx_timeout_threshold(timeout, self.symbol_length())?;
+        }
+
+        // Both would drive the RTS pin.
+        #[cfg(feature = "unstable")]
+        if matches!(self.rs485_mode, Rs485Mode::HalfDuplex(_))
+            && matches!(self.hw_flow_ctrl.rts, RtsConfig::Enabled(_))
+        {
+            return Err(ConfigError::Rs485WithRtsFlowControl);
+        }
+
         Ok(())
     }
+
+    /// Returns the number of bits in a frame, as counted by the RX timeout.
+    fn symbol_length(&self) -> u8 {
+        symbol_length(
+            self.data_bits as u8 + 5,
+            self.parity != Parity::None,
+            self.stop_bits != StopBits::_1,
+        )
+    }
+}
+
+/// Returns the number of bits in a frame with `data_bits` data bits, as
+/// counted by the RX timeout.
+fn symbol_length(data_bits: u8, parity: bool, more_than_one_stop_bit: bool) -> u8 {
+    // esp-idf also counts 2 bits for settings 1.5 and 2 stop bits
+    let stop_bits = if more_than_one_stop_bit { 2 } else { 1 };
+
+    1 + data_bits + parity as u8 + stop_bits
 }
 
 /// UART Receive part configuration.
@@ -368,8 +534,14 @@ impl Config {
 #[non_exhaustive]
 pub struct RxConfig {
     /// Threshold level at which the RX FIFO is considered full.
+    ///
+    /// Must be less than [`FIFO_SIZE`], otherwise applying the configuration
+    /// fails with [`ConfigError::RxFifoThresholdNotSupported`].
     fifo_full_threshold: u16,
-    /// Optional timeout value for RX operations.
+    /// Optional timeout value for RX operations, in symbols.
+    ///
+    /// Applying the configuration fails with [`ConfigError::TimeoutTooLong`]
+    /// if the timeout exceeds what the hardware can count.
     timeout: Option<u8>,
 }
 
@@ -390,6 +562,11 @@ impl Default for RxConfig {
 #[non_exhaustive]
 pub struct TxConfig {
     /// Threshold level at which the TX FIFO is considered empty.
+    ///
+    /// [`UartInterrupt::TxFifoEmpty`] is raised while the TX FIFO holds
+    /// fewer bytes than this. Must be less than [`FIFO_SIZE`], otherwise
+    /// applying the configuration fails with
+    /// [`ConfigError::TxFifoThresholdNotSupported`].
     fifo_empty_threshold: u16,
 }
 
@@ -435,6 +612,49 @@ impl Default for AtCmdConfig {
     }
 }
 
+/// Checksum algorithms for framed transfers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+#[non_exhaustive]
+pub enum ChecksumKind {
+    /// XOR of all bytes.
+    Xor,
+    /// Sum of all bytes, modulo 256.
+    Sum,
+    /// CRC-8 with polynomial 0x07, initial value 0x00 and no final XOR
+    /// (CRC-8/SMBUS).
+    #[default]
+    Crc8,
+}
+
+/// Configuration of the checksum trailer used by
+/// [`UartTx::write_with_checksum`] and [`UartRx::read_exact_with_checksum`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, procmacros::BuilderLite)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[instability::unstable]
+#[non_exhaustive]
+pub struct ChecksumConfig {
+    /// The checksum algorithm.
+    kind: ChecksumKind,
+    /// The number of leading bytes of the frame (e.g. a header) that are not
+    /// covered by the checksum.
+    skip: usize,
+}
+
+impl ChecksumConfig {
+    fn compute(&self, frame: &[u8]) -> u8 {
+        let span = frame.get(self.skip..).unwrap_or_default();
+
+        match self.kind {
+            ChecksumKind::Xor => span.iter().fold(0, |acc, byte| acc ^ byte),
+            ChecksumKind::Sum => span.iter().fold(0, |acc, byte| acc.wrapping_add(*byte)),
+            // The ROM function complements both the initial value and the result.
+            ChecksumKind::Crc8 => !crate::rom::crc::crc8_be(!0, span),
+        }
+    }
+}
+
 struct UartBuilder<'d, Dm: DriverMode> {
     uart: AnyUart<'d>,
     phantom: PhantomData<Dm>,
@@ -534,9 +754,17 @@ pub enum ConfigError {
     ///    desired value, and the driver cannot reach this speed.
     BaudrateNotSupported,
 
+    /// The requested baud rate tolerance is not between 1 and 100 percent.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    BaudrateToleranceNotSupported,
+
     /// The requested timeout exceeds the maximum value (
-    #[cfg_attr(esp32, doc = "127")]
-    #[cfg_attr(not(esp32), doc = "1023")]
+    #[cfg_attr(esp32, doc = "127 symbols")]
+    #[cfg_attr(
+        not(esp32),
+        doc = "1023 bits, i.e. the timeout times the frame length in bits"
+    )]
     /// ).
     TimeoutTooLong,
 
@@ -545,6 +773,11 @@ pub enum ConfigError {
 
     /// The requested TX FIFO threshold exceeds the maximum value (127 bytes).
     TxFifoThresholdNotSupported,
+
+    /// RS-485 mode and RTS flow control can't be enabled at the same time.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    Rs485WithRtsFlowControl,
 }
 
 impl core::error::Error for ConfigError {}
@@ -559,12 +792,40 @@ impl core::fmt::Display for ConfigError {
             ConfigError::BaudrateNotSupported => {
                 write!(f, "The requested baud rate is not supported")
             }
-            ConfigError::TimeoutTooLong => write!(f, "The requested timeout is not supported"),
-            ConfigError::RxFifoThresholdNotSupported => {
-                write!(f, "The requested RX FIFO threshold is not supported")
+            #[cfg(feature = "unstable")]
+            ConfigError::BaudrateToleranceNotSupported => {
+                write!(
+                    f,
+                    "The baud rate tolerance must be between 1 and 100 percent"
+                )
             }
-            ConfigError::TxFifoThresholdNotSupported => {
-                write!(f, "The requested TX FIFO threshold is not supported")
+            ConfigError::TimeoutTooLong => {
+                #[cfg(esp32)]
+                let limit = "symbols";
+                #[cfg(not(esp32))]
+                let limit = "bits";
+                write!(
+                    f,
+                    "The requested timeout exceeds the maximum of {} {limit}",
+                    Info::RX_TIMEOUT_MAX_THRHD
+                )
+            }
+            ConfigError::RxFifoThresholdNotSupported => write!(
+                f,
+                "The requested RX FIFO threshold exceeds the maximum of {} bytes",
+                Info::RX_FIFO_MAX_THRHD
+            ),
+            ConfigError::TxFifoThresholdNotSupported => write!(
+                f,
+                "The requested TX FIFO threshold exceeds the maximum of {} bytes",
+                Info::TX_FIFO_MAX_THRHD
+            ),
+            #[cfg(feature = "unstable")]
+            ConfigError::Rs485WithRtsFlowControl => {
+                write!(
+                    f,
+                    "RS-485 mode can't be used together with RTS flow control"
+                )
             }
         }
     }
@@ -650,6 +911,44 @@ impl<'d> UartTx<'d, Blocking> {
             tx_pin: self.tx_pin,
         }
     }
+
+    /// Listen for the given interrupts.
+    ///
+    /// Only the transmitter's interrupts, [`UartInterrupt::TxDone`] and
+    /// [`UartInterrupt::TxFifoEmpty`], are affected. The others are left to
+    /// the [`UartRx`] half, so that the two halves of a split UART can manage
+    /// their interrupts independently.
+    ///
+    /// The interrupt handler is shared by both halves, register it with
+    /// [`Uart::set_interrupt_handler`] before splitting the driver.
+    #[instability::unstable]
+    pub fn listen(&mut self, interrupts: impl Into<EnumSet<UartInterrupt>>) {
+        let interrupts = interrupts.into() & UartInterrupt::tx();
+        self.uart.info().enable_listen(interrupts, true)
+    }
+
+    /// Unlisten the given interrupts.
+    ///
+    /// Only the transmitter's interrupts are affected, see [`Self::listen`].
+    #[instability::unstable]
+    pub fn unlisten(&mut self, interrupts: impl Into<EnumSet<UartInterrupt>>) {
+        let interrupts = interrupts.into() & UartInterrupt::tx();
+        self.uart.info().enable_listen(interrupts, false)
+    }
+
+    /// Gets the asserted transmitter interrupts.
+    #[instability::unstable]
+    pub fn interrupts(&mut self) -> EnumSet<UartInterrupt> {
+        self.uart.info().interrupts() & UartInterrupt::tx()
+    }
+
+    /// Resets the given asserted transmitter interrupts.
+    #[instability::unstable]
+    pub fn clear_interrupts(&mut self, interrupts: EnumSet<UartInterrupt>) {
+        self.uart
+            .info()
+            .clear_interrupts(interrupts & UartInterrupt::tx())
+    }
 }
 
 impl<'d> UartTx<'d, Async> {
@@ -796,6 +1095,16 @@ where
         self.uart.info().tx_fifo_count() < Info::UART_FIFO_SIZE
     }
 
+    /// Returns the number of bytes in the TX FIFO that have not been sent
+    /// yet.
+    ///
+    /// Up to [`FIFO_SIZE`] minus this number of bytes can be written without
+    /// blocking.
+    #[instability::unstable]
+    pub fn tx_fifo_count(&self) -> u16 {
+        self.uart.info().tx_fifo_count()
+    }
+
     /// Write bytes.
     ///
     /// This function writes data to the internal TX FIFO of the UART
@@ -822,10 +1131,36 @@ where
         Ok(())
     }
 
+    /// Write a frame followed by a checksum byte.
+    ///
+    /// The checksum is computed over `data`, except for the leading bytes
+    /// excluded by [`ChecksumConfig::with_skip`]. This function blocks until
+    /// the whole frame and the checksum have been written to the TX FIFO.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns a [`TxError`] if an error occurred during the
+    /// write operation.
+    #[instability::unstable]
+    pub fn write_with_checksum(
+        &mut self,
+        data: &[u8],
+        config: ChecksumConfig,
+    ) -> Result<(), TxError> {
+        let checksum = config.compute(data);
+        self.write_all(data)?;
+        self.write_all(&[checksum])
+    }
+
     /// Flush the transmit buffer.
     ///
     /// This function blocks until all data in the TX FIFO has been
-    /// transmitted.
+    /// transmitted, including the last byte's stop bits.
+    ///
+    /// Writing, including through [`core::fmt::Write`], returns as soon as the
+    /// data is in the FIFO. Flush before anything that would cut off the
+    /// transmission, like entering a sleep mode, resetting the chip or
+    /// changing the baud rate.
     #[instability::unstable]
     pub fn flush(&mut self) -> Result<(), TxError> {
         while self.uart.info().tx_fifo_count() > 0 {}
@@ -833,6 +1168,58 @@ where
         Ok(())
     }
 
+    /// Returns whether all written data has been transmitted.
+    ///
+    /// This is the non-blocking counterpart of [`Self::flush`]. For a few
+    /// microseconds after the last byte leaves the FIFO, the transmitter may
+    /// report being idle before it starts sending the byte, so use
+    /// [`Self::flush`] when the line must be quiet, e.g. before sleeping.
+    #[instability::unstable]
+    pub fn is_tx_idle(&self) -> bool {
+        self.uart.info().tx_fifo_count() == 0 && self.is_transmitter_idle()
+    }
+
+    /// Send a break condition.
+    ///
+    /// This function first waits for the data in the TX FIFO to be
+    /// transmitted, then holds the TX line low for `bits` bit times, and
+    /// blocks until the break has been sent. A break needs to be longer than a
+    /// whole frame for the receiver to detect it, e.g. LIN requires at least 13
+    /// bit times.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns a [`TxError`] if an error occurred while waiting
+    /// for the pending data to be transmitted.
+    #[instability::unstable]
+    pub fn send_break(&mut self, bits: u8) -> Result<(), TxError> {
+        // The break would otherwise be sent right after the pending data, or
+        // corrupt the frame that's currently being transmitted.
+        self.flush()?;
+
+        let regs = self.regs();
+        regs.int_clr().write(|w| w.tx_brk_done().clear_bit_by_one());
+
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32, esp32s2))] {
+                let brk_conf = regs.idle_conf();
+            } else {
+                let brk_conf = regs.txbrk_conf();
+            }
+        }
+        brk_conf.modify(|_, w| unsafe { w.tx_brk_num().bits(bits) });
+        regs.conf0().modify(|_, w| w.txd_brk().set_bit());
+        sync_regs(regs);
+
+        while regs.int_raw().read().tx_brk_done().bit_is_clear() {}
+
+        regs.conf0().modify(|_, w| w.txd_brk().clear_bit());
+        sync_regs(regs);
+        regs.int_clr().write(|w| w.tx_brk_done().clear_bit_by_one());
+
+        Ok(())
+    }
+
     fn flush_last_byte(&mut self) {
         // This function handles an edge case that happens when the TX FIFO count
         // changes to 0. The FSM is in the Idle state for a short while after
@@ -840,14 +1227,14 @@ where
         // takes, but 10us seems to be a good enough duration to wait, for both
         // fast and slow baud rates.
         crate::rom::ets_delay_us(10);
-        while !self.is_tx_idle() {}
+        while !self.is_transmitter_idle() {}
     }
 
     /// Checks if the TX line is idle for this UART instance.
     ///
     /// Returns `true` if the transmit line is idle, meaning no data is
     /// currently being transmitted.
-    fn is_tx_idle(&self) -> bool {
+    fn is_transmitter_idle(&self) -> bool {
         #[cfg(esp32)]
         let status = self.regs().status();
         #[cfg(not(esp32))]
@@ -939,6 +1326,45 @@ impl<'d> UartRx<'d, Blocking> {
             guard: self.guard,
         }
     }
+
+    /// Listen for the given interrupts.
+    ///
+    /// Only the receiver's interrupts are affected, the transmitter's
+    /// interrupts, [`UartInterrupt::TxDone`] and
+    /// [`UartInterrupt::TxFifoEmpty`], are left to the [`UartTx`] half. This
+    /// allows the two halves of a split UART to manage their interrupts
+    /// independently.
+    ///
+    /// The interrupt handler is shared by both halves, register it with
+    /// [`Uart::set_interrupt_handler`] before splitting the driver.
+    #[instability::unstable]
+    pub fn listen(&mut self, interrupts: impl Into<EnumSet<UartInterrupt>>) {
+        let interrupts = interrupts.into() - UartInterrupt::tx();
+        self.uart.info().enable_listen(interrupts, true)
+    }
+
+    /// Unlisten the given interrupts.
+    ///
+    /// Only the receiver's interrupts are affected, see [`Self::listen`].
+    #[instability::unstable]
+    pub fn unlisten(&mut self, interrupts: impl Into<EnumSet<UartInterrupt>>) {
+        let interrupts = interrupts.into() - UartInterrupt::tx();
+        self.uart.info().enable_listen(interrupts, false)
+    }
+
+    /// Gets the asserted receiver interrupts.
+    #[instability::unstable]
+    pub fn interrupts(&mut self) -> EnumSet<UartInterrupt> {
+        self.uart.info().interrupts() - UartInterrupt::tx()
+    }
+
+    /// Resets the given asserted receiver interrupts.
+    #[instability::unstable]
+    pub fn clear_interrupts(&mut self, interrupts: EnumSet<UartInterrupt>) {
+        self.uart
+            .info()
+            .clear_interrupts(interrupts - UartInterrupt::tx())
+    }
 }
 
 impl<'d> UartRx<'d, Async> {
@@ -1157,6 +1583,36 @@ where
         self.uart.info().rx_fifo_count() > 0
     }
 
+    /// Returns the number of received bytes in the RX FIFO.
+    #[instability::unstable]
+    pub fn rx_fifo_count(&self) -> u16 {
+        self.uart.info().rx_fifo_count()
+    }
+
+    /// Returns whether the RX line has been idle for the configured timeout.
+    ///
+    /// The idle time is measured from the last received byte, and restarts
+    /// with every byte, so polling code can use this function to detect the
+    /// end of a frame without interrupts. Nothing is reported before data has
+    /// been received, or if the timeout is disabled (see
+    /// [`RxConfig::with_timeout`]).
+    ///
+    /// Returning `true` clears the status, so each idle period is reported
+    /// once. On ESP32 and ESP32-S2, the status can't be cleared while the RX
+    /// FIFO holds data, so it is reported until the received data has been
+    /// read.
+    #[instability::unstable]
+    pub fn line_idle(&mut self) -> bool {
+        let info = self.uart.info();
+
+        let idle = info.rx_events().contains(RxEvent::FifoTout);
+        if idle {
+            info.clear_rx_events(RxEvent::FifoTout);
+        }
+
+        idle
+    }
+
     /// Read bytes.
     ///
     /// The UART hardware continuously receives bytes and stores them in the RX
@@ -1202,6 +1658,92 @@ where
         self.uart.info().read_buffered(buf)
     }
 
+    /// Read a single byte along with the line conditions reported by the
+    /// hardware.
+    ///
+    /// Unlike [`Self::read`], parity errors, framing errors and FIFO overruns
+    /// are not returned as an error, but reported in the returned
+    /// [`RxStatus`], so that the application can decide what to do with the
+    /// byte.
+    ///
+    /// The UART latches line conditions instead of storing them for each byte
+    /// in the FIFO, so the status covers every byte received since the
+    /// previous status was read. Reading byte by byte keeps the reported
+    /// status as close to the affected byte as the hardware allows.
+    ///
+    /// If an overrun is reported, the RX FIFO is reset after reading the
+    /// returned byte, as the silicon does not keep the FIFO contents
+    /// consistent after an overflow. The remaining bytes are lost.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] if the RX FIFO is empty.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    pub fn read_byte_status(&mut self) -> nb::Result<(u8, RxStatus), Infallible> {
+        self.uart.info().read_byte_status()
+    }
+
+    /// Read a frame followed by a checksum byte.
+    ///
+    /// This function blocks until `buf.len()` bytes and the trailing checksum
+    /// byte have been received. On success, `buf` contains the frame without
+    /// the checksum.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns [`RxError::ChecksumMismatch`] if the received
+    /// checksum doesn't match the one computed over the frame, or another
+    /// [`RxError`] if an error occurred while receiving. The contents of `buf`
+    /// are unspecified in both cases.
+    #[instability::unstable]
+    pub fn read_exact_with_checksum(
+        &mut self,
+        buf: &mut [u8],
+        config: ChecksumConfig,
+    ) -> Result<(), RxError> {
+        let mut received = 0;
+        while received < buf.len() {
+            received += self.read(&mut buf[received..])?;
+        }
+
+        let mut checksum = [0u8];
+        while self.read(&mut checksum)? == 0 {}
+
+        if checksum[0] != config.compute(buf) {
+            return Err(RxError::ChecksumMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Read a line terminated by `delimiter`.
+    ///
+    /// This function blocks until `delimiter` has been received, and returns
+    /// the length of the line, excluding the delimiter. The line is stored at
+    /// the beginning of `buf`, `buf` must have room for the delimiter, too.
+    ///
+    /// Bytes received after the delimiter are left in the RX FIFO.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns [`RxError::LineTooLong`] if `buf` filled up before
+    /// the delimiter was received. In this case, `buf` contains the beginning
+    /// of the line and the rest of the line is left in the RX FIFO.
+    ///
+    /// This function returns another [`RxError`] if an error occurred while
+    /// receiving. The contents of `buf` are unspecified in this case.
+    #[instability::unstable]
+    pub fn read_line(&mut self, buf: &mut [u8], delimiter: u8) -> Result<usize, RxError> {
+        // Read byte by byte to leave anything after the delimiter in the FIFO.
+        for len in 0..buf.len() {
+            self.read(&mut buf[len..][..1])?;
+            if buf[len] == delimiter {
+                return Ok(len);
+            }
+        }
+
+        Err(RxError::LineTooLong)
+    }
+
     /// Disables all RX-related interrupts for this UART instance.
     ///
     /// This function clears and disables the `receive FIFO full` interrupt,
@@ -1247,6 +1789,66 @@ impl<'d> Uart<'d, Blocking> {
         UartBuilder::new(uart).init(config)
     }
 
+    #[procmacros::doc_replace]
+    /// Create a new UART instance in [`Blocking`] mode, connected to a
+    /// half-duplex RS-485 transceiver.
+    ///
+    /// `de` is connected to the driver enable (DE) and receiver enable (RE)
+    /// inputs of the transceiver. The hardware asserts it while data is
+    /// transmitted, and releases it once the last stop bit has been shifted
+    /// out, so that the transceiver switches back to receiving without
+    /// truncating the last byte. The driver does not insert idle time after
+    /// transmissions, so `de` is released right after the stop bit. See
+    /// [`Rs485Mode::HalfDuplex`] for details.
+    ///
+    /// If the RS-485 mode of `config` is [`Rs485Mode::Disabled`], `de` is
+    /// active high.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns a [`ConfigError`] if the configuration is not
+    /// supported by the hardware, for example if it enables RTS flow
+    /// control.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::uart::{Config, Uart};
+    /// let mut uart = Uart::new_rs485(
+    ///     peripherals.UART1,
+    ///     Config::default(),
+    ///     peripherals.GPIO2,
+    ///     peripherals.GPIO1,
+    ///     peripherals.GPIO3,
+    /// )?;
+    ///
+    /// uart.write(b"request")?;
+    /// uart.flush()?;
+    /// # {after_snippet}
+    /// ```
+    #[instability::unstable]
+    pub fn new_rs485(
+        uart: impl Instance + 'd,
+        config: Config,
+        tx: impl PeripheralOutput<'d>,
+        rx: impl PeripheralInput<'d>,
+        de: impl PeripheralOutput<'d>,
+    ) -> Result<Self, ConfigError> {
+        let config = match config.rs485_mode {
+            Rs485Mode::Disabled => Config {
+                rs485_mode: Rs485Mode::HalfDuplex(DriverEnablePolarity::ActiveHigh),
+                ..config
+            },
+            Rs485Mode::HalfDuplex(_) => config,
+        };
+
+        Ok(Self::new(uart, config)?
+            .with_tx(tx)
+            .with_rx(rx)
+            .with_rts(de))
+    }
+
     /// Reconfigures the driver to operate in [`Async`] mode.
     ///
     /// See the [`Async`] documentation for an example on how to use this
@@ -1521,6 +2123,14 @@ pub enum UartInterrupt {
     /// The transmitter has finished sending out all data from the FIFO.
     TxDone,
 
+    /// The TX FIFO holds fewer bytes than what
+    /// [`TxConfig::fifo_empty_threshold`] specifies.
+    ///
+    /// The interrupt is raised again right after clearing it, until the FIFO
+    /// is filled above the threshold. Stop listening for it when there is no
+    /// more data to send.
+    TxFifoEmpty,
+
     /// The receiver has received more data than what
     /// [`RxConfig::fifo_full_threshold`] specifies.
     RxFifoFull,
@@ -1528,6 +2138,28 @@ pub enum UartInterrupt {
     /// The receiver has not received any data for the time
     /// [`RxConfig::with_timeout`] specifies.
     RxTimeout,
+
+    /// The receiver has detected a parity error.
+    RxParityError,
+
+    /// The receiver has detected a framing error.
+    RxFrameError,
+
+    /// The RX FIFO has overflowed.
+    RxFifoOverflow,
+
+    /// The receiver has detected a break condition, i.e. the RX line was held
+    /// low for longer than a frame.
+    ///
+    /// See [`Uart::send_break`] for sending one.
+    RxBreakDetected,
+}
+
+impl UartInterrupt {
+    /// The interrupts that belong to the transmitter.
+    fn tx() -> EnumSet<UartInterrupt> {
+        UartInterrupt::TxDone | UartInterrupt::TxFifoEmpty
+    }
 }
 
 impl<'d, Dm> Uart<'d, Dm>
@@ -1629,6 +2261,23 @@ where
         self.tx.write_ready()
     }
 
+    /// Returns the number of bytes in the TX FIFO that have not been sent
+    /// yet.
+    ///
+    /// See [`UartTx::tx_fifo_count`] for details.
+    #[instability::unstable]
+    pub fn tx_fifo_count(&self) -> u16 {
+        self.tx.tx_fifo_count()
+    }
+
+    /// Returns whether all written data has been transmitted.
+    ///
+    /// See [`UartTx::is_tx_idle`] for details.
+    #[instability::unstable]
+    pub fn is_tx_idle(&self) -> bool {
+        self.tx.is_tx_idle()
+    }
+
     #[procmacros::doc_replace]
     /// Writes bytes.
     ///
@@ -1662,6 +2311,9 @@ where
     #[procmacros::doc_replace]
     /// Flush the transmit buffer of the UART
     ///
+    /// Blocks until all written data has been transmitted. See
+    /// [`UartTx::flush`] for when this is needed.
+    ///
     /// ## Example
     ///
     /// ```rust, no_run
@@ -1678,6 +2330,57 @@ where
         self.tx.flush()
     }
 
+    #[procmacros::doc_replace]
+    /// Write a frame followed by a checksum byte.
+    ///
+    /// See [`UartTx::write_with_checksum`] for details.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::uart::{ChecksumConfig, ChecksumKind, Config, Uart};
+    /// let mut uart = Uart::new(peripherals.UART0, Config::default())?;
+    ///
+    /// // The first two bytes are a header that is not covered by the checksum.
+    /// let checksum = ChecksumConfig::default()
+    ///     .with_kind(ChecksumKind::Crc8)
+    ///     .with_skip(2);
+    /// uart.write_with_checksum(&[0xAA, 0x55, 1, 2, 3], checksum)?;
+    /// # {after_snippet}
+    /// ```
+    #[instability::unstable]
+    pub fn write_with_checksum(
+        &mut self,
+        data: &[u8],
+        config: ChecksumConfig,
+    ) -> Result<(), TxError> {
+        self.tx.write_with_checksum(data, config)
+    }
+
+    #[procmacros::doc_replace]
+    /// Send a break condition.
+    ///
+    /// See [`UartTx::send_break`] for details.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::uart::{Config, Uart};
+    /// let mut uart = Uart::new(peripherals.UART0, Config::default())?;
+    ///
+    /// // A LIN header starts with a break of at least 13 bit times, followed by
+    /// // the sync byte and the protected identifier.
+    /// uart.send_break(13)?;
+    /// uart.write(&[0x55, 0x3C])?;
+    /// # {after_snippet}
+    /// ```
+    #[instability::unstable]
+    pub fn send_break(&mut self, bits: u8) -> Result<(), TxError> {
+        self.tx.send_break(bits)
+    }
+
     /// Returns whether the UART buffer has data.
     ///
     /// If this function returns `true`, [`Self::read`] will not block.
@@ -1686,6 +2389,20 @@ where
         self.rx.read_ready()
     }
 
+    /// Returns the number of received bytes in the RX FIFO.
+    #[instability::unstable]
+    pub fn rx_fifo_count(&self) -> u16 {
+        self.rx.rx_fifo_count()
+    }
+
+    /// Returns whether the RX line has been idle for the configured timeout.
+    ///
+    /// See [`UartRx::line_idle`] for details.
+    #[instability::unstable]
+    pub fn line_idle(&mut self) -> bool {
+        self.rx.line_idle()
+    }
+
     #[procmacros::doc_replace]
     /// Read received bytes.
     ///
@@ -1725,6 +2442,26 @@ where
         self.rx.read(buf)
     }
 
+    /// Read a frame followed by a checksum byte.
+    ///
+    /// See [`UartRx::read_exact_with_checksum`] for details.
+    #[instability::unstable]
+    pub fn read_exact_with_checksum(
+        &mut self,
+        buf: &mut [u8],
+        config: ChecksumConfig,
+    ) -> Result<(), RxError> {
+        self.rx.read_exact_with_checksum(buf, config)
+    }
+
+    /// Read a line terminated by `delimiter`.
+    ///
+    /// See [`UartRx::read_line`] for details.
+    #[instability::unstable]
+    pub fn read_line(&mut self, buf: &mut [u8], delimiter: u8) -> Result<usize, RxError> {
+        self.rx.read_line(buf, delimiter)
+    }
+
     #[procmacros::doc_replace]
     /// Change the configuration.
     ///
@@ -1752,12 +2489,51 @@ where
         Ok(())
     }
 
+    #[procmacros::doc_replace]
+    /// Change the baud rate without reconfiguring the rest of the driver.
+    ///
+    /// The transmit FIFO is flushed before the new rate takes effect, so
+    /// data already queued for transmission is sent at the old rate. The
+    /// clock source and all other settings are left as they were.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`ConfigError::BaudrateNotSupported`] if the baud rate is out of
+    /// range, and [`ConfigError::BaudrateNotAchievable`] if the current clock
+    /// source cannot produce it within 1%. The baud rate is left unchanged in
+    /// both cases.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::uart::{Config, Uart};
+    /// let mut uart = Uart::new(peripherals.UART0, Config::default())?;
+    ///
+    /// // Negotiate a faster rate with the peer, then switch over.
+    /// uart.write(b"AT+BAUD=921600\r\n")?;
+    /// uart.set_baudrate(921_600)?;
+    /// # {after_snippet}
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    pub fn set_baudrate(&mut self, baudrate: u32) -> Result<(), ConfigError> {
+        while self.tx.uart.info().tx_fifo_count() > 0 {}
+        self.tx.flush_last_byte();
+
+        self.tx.uart.info().set_baudrate(baudrate)
+    }
+
     #[procmacros::doc_replace]
     /// Split the UART into a transmitter and receiver
     ///
     /// This is particularly useful when having two tasks correlating to
     /// transmitting and receiving.
     ///
+    /// Each half manages its own interrupts with `listen`, `unlisten`,
+    /// `interrupts` and `clear_interrupts`. The interrupt handler is shared,
+    /// register it with [`Self::set_interrupt_handler`] before splitting.
+    ///
     /// ## Example
     ///
     /// ```rust, no_run
@@ -1808,6 +2584,16 @@ where
         self.rx.read_buffered(buf)
     }
 
+    /// Read a single byte along with the line conditions reported by the
+    /// hardware.
+    ///
+    /// See [`UartRx::read_byte_status`] for details.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    pub fn read_byte_status(&mut self) -> nb::Result<(u8, RxStatus), Infallible> {
+        self.rx.read_byte_status()
+    }
+
     /// Configures the AT-CMD detection settings
     #[instability::unstable]
     pub fn set_at_cmd(&mut self, config: AtCmdConfig) {
@@ -2641,6 +3427,10 @@ impl Info {
     const UART_FIFO_SIZE: u16 = property!("uart.ram_size");
     const RX_FIFO_MAX_THRHD: u16 = Self::UART_FIFO_SIZE - 1;
     const TX_FIFO_MAX_THRHD: u16 = Self::RX_FIFO_MAX_THRHD;
+    #[cfg(esp32)]
+    const RX_TIMEOUT_MAX_THRHD: u16 = 0x7F; // 7 bits
+    #[cfg(not(esp32))]
+    const RX_TIMEOUT_MAX_THRHD: u16 = 0x3FF; // 10 bits
 
     /// Returns the register block for this UART instance.
     pub fn regs(&self) -> &RegisterBlock {
@@ -2651,16 +3441,24 @@ impl Info {
     fn enable_listen(&self, interrupts: EnumSet<UartInterrupt>, enable: bool) {
         let reg_block = self.regs();
 
-        reg_block.int_ena().modify(|_, w| {
-            for interrupt in interrupts {
-                match interrupt {
-                    UartInterrupt::AtCmd => w.at_cmd_char_det().bit(enable),
-                    UartInterrupt::TxDone => w.tx_done().bit(enable),
-                    UartInterrupt::RxFifoFull => w.rxfifo_full().bit(enable),
-                    UartInterrupt::RxTimeout => w.rxfifo_tout().bit(enable),
-                };
-            }
-            w
+        // The halves of a split driver may modify the register from different contexts.
+        INT_ENA_LOCK.lock(|| {
+            reg_block.int_ena().modify(|_, w| {
+                for interrupt in interrupts {
+                    match interrupt {
+                        UartInterrupt::AtCmd => w.at_cmd_char_det().bit(enable),
+                        UartInterrupt::TxDone => w.tx_done().bit(enable),
+                        UartInterrupt::TxFifoEmpty => w.txfifo_empty().bit(enable),
+                        UartInterrupt::RxFifoFull => w.rxfifo_full().bit(enable),
+                        UartInterrupt::RxTimeout => w.rxfifo_tout().bit(enable),
+                        UartInterrupt::RxParityError => w.parity_err().bit(enable),
+                        UartInterrupt::RxFrameError => w.frm_err().bit(enable),
+                        UartInterrupt::RxFifoOverflow => w.rxfifo_ovf().bit(enable),
+                        UartInterrupt::RxBreakDetected => w.brk_det().bit(enable),
+                    };
+                }
+                w
+            })
         });
     }
 
@@ -2676,12 +3474,27 @@ impl Info {
         if ints.tx_done().bit_is_set() {
             res.insert(UartInterrupt::TxDone);
         }
+        if ints.txfifo_empty().bit_is_set() {
+            res.insert(UartInterrupt::TxFifoEmpty);
+        }
         if ints.rxfifo_full().bit_is_set() {
             res.insert(UartInterrupt::RxFifoFull);
         }
         if ints.rxfifo_tout().bit_is_set() {
             res.insert(UartInterrupt::RxTimeout);
         }
+        if ints.parity_err().bit_is_set() {
+            res.insert(UartInterrupt::RxParityError);
+        }
+        if ints.frm_err().bit_is_set() {
+            res.insert(UartInterrupt::RxFrameError);
+        }
+        if ints.rxfifo_ovf().bit_is_set() {
+            res.insert(UartInterrupt::RxFifoOverflow);
+        }
+        if ints.brk_det().bit_is_set() {
+            res.insert(UartInterrupt::RxBreakDetected);
+        }
 
         res
     }
@@ -2694,8 +3507,13 @@ impl Info {
                 match interrupt {
                     UartInterrupt::AtCmd => w.at_cmd_char_det().clear_bit_by_one(),
                     UartInterrupt::TxDone => w.tx_done().clear_bit_by_one(),
+                    UartInterrupt::TxFifoEmpty => w.txfifo_empty().clear_bit_by_one(),
                     UartInterrupt::RxFifoFull => w.rxfifo_full().clear_bit_by_one(),
                     UartInterrupt::RxTimeout => w.rxfifo_tout().clear_bit_by_one(),
+                    UartInterrupt::RxParityError => w.parity_err().clear_bit_by_one(),
+                    UartInterrupt::RxFrameError => w.frm_err().clear_bit_by_one(),
+                    UartInterrupt::RxFifoOverflow => w.rxfifo_ovf().clear_bit_by_one(),
+                    UartInterrupt::RxBreakDetected => w.brk_det().clear_bit_by_one(),
                 };
             }
             w
@@ -2708,20 +3526,24 @@ impl Info {
         self.change_data_bits(config.data_bits);
         self.change_parity(config.parity);
         self.change_stop_bits(config.stop_bits);
+        self.change_rs485_mode(config.rs485_mode);
+        self.change_signal_mode(config);
         self.change_flow_control(config.sw_flow_ctrl, config.hw_flow_ctrl);
 
         Ok(())
     }
 
     fn enable_listen_tx(&self, events: EnumSet<TxEvent>, enable: bool) {
-        self.regs().int_ena().modify(|_, w| {
-            for event in events {
-                match event {
-                    TxEvent::Done => w.tx_done().bit(enable),
-                    TxEvent::FiFoEmpty => w.txfifo_empty().bit(enable),
-                };
-            }
-            w
+        INT_ENA_LOCK.lock(|| {
+            self.regs().int_ena().modify(|_, w| {
+                for event in events {
+                    match event {
+                        TxEvent::Done => w.tx_done().bit(enable),
+                        TxEvent::FiFoEmpty => w.txfifo_empty().bit(enable),
+                    };
+                }
+                w
+            })
         });
     }
 
@@ -2753,20 +3575,22 @@ impl Info {
     }
 
     fn enable_listen_rx(&self, events: EnumSet<RxEvent>, enable: bool) {
-        self.regs().int_ena().modify(|_, w| {
-            for event in events {
-                match event {
-                    RxEvent::FifoFull => w.rxfifo_full().bit(enable),
-                    RxEvent::CmdCharDetected => w.at_cmd_char_det().bit(enable),
-
-                    RxEvent::FifoOvf => w.rxfifo_ovf().bit(enable),
-                    RxEvent::FifoTout => w.rxfifo_tout().bit(enable),
-                    RxEvent::GlitchDetected => w.glitch_det().bit(enable),
-                    RxEvent::FrameError => w.frm_err().bit(enable),
-                    RxEvent::ParityError => w.parity_err().bit(enable),
-                };
-            }
-            w
+        INT_ENA_LOCK.lock(|| {
+            self.regs().int_ena().modify(|_, w| {
+                for event in events {
+                    match event {
+                        RxEvent::FifoFull => w.rxfifo_full().bit(enable),
+                        RxEvent::CmdCharDetected => w.at_cmd_char_det().bit(enable),
+
+                        RxEvent::FifoOvf => w.rxfifo_ovf().bit(enable),
+                        RxEvent::FifoTout => w.rxfifo_tout().bit(enable),
+                        RxEvent::GlitchDetected => w.glitch_det().bit(enable),
+                        RxEvent::FrameError => w.frm_err().bit(enable),
+                        RxEvent::ParityError => w.parity_err().bit(enable),
+                    };
+                }
+                w
+            })
         });
     }
 
@@ -2874,28 +3698,11 @@ impl Info {
     /// - `esp32`: Symbol size is fixed to 8, do not pass a value > **0x7F**.
     /// - `esp32c2`, `esp32c3`, `esp32c6`, `esp32h2`, esp32s2`, esp32s3`: The value you pass times
     ///   the symbol size must be <= **0x3FF**
-    fn set_rx_timeout(&self, timeout: Option<u8>, _symbol_len: u8) -> Result<(), ConfigError> {
-        cfg_if::cfg_if! {
-            if #[cfg(esp32)] {
-                const MAX_THRHD: u8 = 0x7F; // 7 bits
-            } else {
-                const MAX_THRHD: u16 = 0x3FF; // 10 bits
-            }
-        }
-
+    fn set_rx_timeout(&self, timeout: Option<u8>, symbol_len: u8) -> Result<(), ConfigError> {
         let register_block = self.regs();
 
         if let Some(timeout) = timeout {
-            // the esp32 counts directly in number of symbols (symbol len fixed to 8)
-            #[cfg(esp32)]
-            let timeout_reg = timeout;
-            // all other count in bits, so we need to multiply by the symbol len.
-            #[cfg(not(esp32))]
-            let timeout_reg = timeout as u16 * _symbol_len as u16;
-
-            if timeout_reg > MAX_THRHD {
-                return Err(ConfigError::TimeoutTooLong);
-            }
+            let timeout_reg = Self::rx_timeout_threshold(timeout, symbol_len)?;
 
             cfg_if::cfg_if! {
                 if #[cfg(esp32)] {
@@ -2906,7 +3713,7 @@ impl Info {
                     let reg_thrhd = register_block.mem_conf();
                 }
             }
-            reg_thrhd.modify(|_, w| unsafe { w.rx_tout_thrhd().bits(timeout_reg) });
+            reg_thrhd.modify(|_, w| unsafe { w.rx_tout_thrhd().bits(timeout_reg as _) });
         }
 
         cfg_if::cfg_if! {
@@ -2923,6 +3730,23 @@ impl Info {
         Ok(())
     }
 
+    /// Returns the value of the RX timeout threshold register for a timeout of
+    /// `timeout` symbols.
+    fn rx_timeout_threshold(timeout: u8, _symbol_len: u8) -> Result<u16, ConfigError> {
+        // the esp32 counts directly in number of symbols (symbol len fixed to 8)
+        #[cfg(esp32)]
+        let timeout_reg = timeout as u16;
+        // all other count in bits, so we need to multiply by the symbol len.
+        #[cfg(not(esp32))]
+        let timeout_reg = timeout as u16 * _symbol_len as u16;
+
+        if timeout_reg > Self::RX_TIMEOUT_MAX_THRHD {
+            return Err(ConfigError::TimeoutTooLong);
+        }
+
+        Ok(timeout_reg)
+    }
+
     fn is_instance(&self, other: impl Instance) -> bool {
         self == other.info()
     }
@@ -2931,23 +3755,98 @@ impl Info {
         sync_regs(self.regs());
     }
 
-    fn change_baud(&self, config: &Config) -> Result<(), ConfigError> {
+    fn clock_source_frequency(clock_source: ClockSource) -> u32 {
         let clocks = Clocks::get();
-        let clk = match config.clock_source {
+        match clock_source {
             ClockSource::Apb => clocks.apb_clock.as_hz(),
             #[cfg(not(any(esp32, esp32s2)))]
             ClockSource::Xtal => clocks.xtal_clock.as_hz(),
             #[cfg(not(any(esp32, esp32s2)))]
-            ClockSource::RcFast => property!("soc.rc_fast_clk_default"), // TODO: adjustable
+            ClockSource::RcFast => Clocks::rc_fast_clock().as_hz(),
             #[cfg(soc_ref_tick_hz_is_set)]
             ClockSource::RefTick => property!("soc.ref_tick_hz"),
-        };
+        }
+    }
 
+    /// Returns the clock source selected by the last applied configuration.
+    #[cfg(feature = "unstable")]
+    fn current_clock_source(&self) -> ClockSource {
         cfg_if::cfg_if! {
-            if #[cfg(any(esp32c2, esp32c3, esp32s3, esp32c6, esp32h2))] {
+            if #[cfg(any(esp32, esp32s2))] {
+                if self.regs().conf0().read().tick_ref_always_on().bit_is_set() {
+                    ClockSource::Apb
+                } else {
+                    ClockSource::RefTick
+                }
+            } else {
+                cfg_if::cfg_if! {
+                    if #[cfg(any(esp32c2, esp32c3, esp32s3))] {
+                        let sclk_sel = self.regs().clk_conf().read().sclk_sel().bits();
+                    } else {
+                        let pcr = crate::peripherals::PCR::regs();
+                        let conf = if self.is_instance(unsafe { crate::peripherals::UART0::steal() }) {
+                            pcr.uart(0).clk_conf()
+                        } else {
+                            pcr.uart(1).clk_conf()
+                        };
+                        let sclk_sel = conf.read().sclk_sel().bits();
+                    }
+                }
+
+                match sclk_sel {
+                    2 => ClockSource::RcFast,
+                    3 => ClockSource::Xtal,
+                    _ => ClockSource::Apb,
+                }
+            }
+        }
+    }
 
+    /// Calculates the clock dividers for the given baud rate.
+    ///
+    /// Returns the divider of the UART's source clock, and the baud rate
+    /// divider with 4 fractional bits.
+    fn baud_dividers(clk: u32, baudrate: u32) -> (u32, u32) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32c2, esp32c3, esp32s3, esp32c6, esp32h2))] {
                 const MAX_DIV: u32 = 0b1111_1111_1111 - 1;
-                let clk_div = (clk.div_ceil(MAX_DIV)).div_ceil(config.baudrate);
+                let clk_div = (clk.div_ceil(MAX_DIV)).div_ceil(baudrate);
+            } else {
+                let clk_div = 1;
+            }
+        }
+
+        (clk_div, (clk << 4) / (baudrate * clk_div))
+    }
+
+    #[cfg(feature = "unstable")]
+    fn set_baudrate(&self, baudrate: u32) -> Result<(), ConfigError> {
+        let config = Config::default()
+            .with_baudrate(baudrate)
+            .with_baudrate_tolerance(BaudrateTolerance::Exact)
+            .with_clock_source(self.current_clock_source());
+        config.validate()?;
+
+        // Check that the rate is achievable before touching the running peripheral.
+        let clk = Self::clock_source_frequency(config.clock_source);
+        let (clk_div, divider) = Self::baud_dividers(clk, baudrate);
+        if clk_div > 256 || divider >> 4 == 0 {
+            return Err(ConfigError::BaudrateNotAchievable);
+        }
+        let actual_baud = (clk << 4) / (divider * clk_div);
+        if baudrate.abs_diff(actual_baud) * 100 / actual_baud > 1 {
+            return Err(ConfigError::BaudrateNotAchievable);
+        }
+
+        self.change_baud(&config)
+    }
+
+    fn change_baud(&self, config: &Config) -> Result<(), ConfigError> {
+        let clk = Self::clock_source_frequency(config.clock_source);
+        let (clk_div, divider) = Self::baud_dividers(clk, config.baudrate);
+
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32c2, esp32c3, esp32s3, esp32c6, esp32h2))] {
 
                 // define `conf` in scope for modification below
                 cfg_if::cfg_if! {
@@ -2980,17 +3879,14 @@ impl Info {
                     });
                     w.sclk_div_a().bits(0);
                     w.sclk_div_b().bits(0);
-                    w.sclk_div_num().bits(clk_div as u8 - 1)
+                    w.sclk_div_num().bits((clk_div - 1) as u8)
                 });
-
-                let divider = (clk << 4) / (config.baudrate * clk_div);
             } else {
+                let _ = clk_div;
                 self.regs().conf0().modify(|_, w| {
                     w.tick_ref_always_on()
                         .bit(config.clock_source == ClockSource::Apb)
                 });
-
-                let divider = (clk << 4) / config.baudrate;
             }
         }
 
@@ -3047,6 +3943,45 @@ impl Info {
             .modify(|_, w| unsafe { w.stop_bit_num().bits(stop_bits as u8 + 1) });
     }
 
+    fn change_rs485_mode(&self, mode: Rs485Mode) {
+        let (enable, polarity) = match mode {
+            Rs485Mode::HalfDuplex(polarity) => (true, polarity),
+            Rs485Mode::Disabled => (false, DriverEnablePolarity::ActiveHigh),
+        };
+
+        // With `sw_rts` set, RTS is low unless the transmitter is busy.
+        self.regs().conf0().modify(|_, w| w.sw_rts().bit(enable));
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32c6, esp32h2))] {
+                self.regs().conf1().modify(|_, w| {
+                    w.rts_inv().bit(polarity == DriverEnablePolarity::ActiveLow)
+                });
+            } else {
+                self.regs().conf0().modify(|_, w| {
+                    w.rts_inv().bit(polarity == DriverEnablePolarity::ActiveLow)
+                });
+            }
+        }
+
+        // Leave `dl1_en` alone, it may be used by the ESP32 stop bit workaround.
+        self.regs().rs485_conf().modify(|_, w| {
+            // Don't receive the transmitted data, and don't transmit while receiving.
+            w.rs485tx_rx_en().clear_bit();
+            w.rs485rxby_tx_en().clear_bit();
+            w.rs485_en().bit(enable)
+        });
+    }
+
+    fn change_signal_mode(&self, config: &Config) {
+        // The registers are synchronized by `change_flow_control`.
+        self.regs().conf0().modify(|_, w| {
+            w.txd_inv().bit(config.invert_tx);
+            w.rxd_inv().bit(config.invert_rx);
+            w.irda_tx_en().bit(config.irda_mode);
+            w.irda_en().bit(config.irda_mode)
+        });
+    }
+
     fn change_flow_control(&self, sw_flow_ctrl: SwFlowControl, hw_flow_ctrl: HwFlowControl) {
         // set SW flow control
         match sw_flow_ctrl {
@@ -3202,22 +4137,15 @@ impl Info {
     fn current_symbol_length(&self) -> u8 {
         let conf0 = self.regs().conf0().read();
         let data_bits = conf0.bit_num().bits() + 5; // 5 data bits are encoded as variant 0
-        let parity = conf0.parity_en().bit() as u8;
-        let mut stop_bits = conf0.stop_bit_num().bits();
+        let parity = conf0.parity_en().bit();
+        let more_than_one_stop_bit = conf0.stop_bit_num().bits() != 1;
 
-        match stop_bits {
-            1 => {
-                // workaround for hardware issue, when UART stop bit set as 2-bit mode.
-                #[cfg(esp32)]
-                if self.regs().rs485_conf().read().dl1_en().bit_is_set() {
-                    stop_bits = 2;
-                }
-            }
-            // esp-idf also counts 2 bits for settings 1.5 and 2 stop bits
-            _ => stop_bits = 2,
-        }
+        // workaround for hardware issue, when UART stop bit set as 2-bit mode.
+        #[cfg(esp32)]
+        let more_than_one_stop_bit =
+            more_than_one_stop_bit || self.regs().rs485_conf().read().dl1_en().bit_is_set();
 
-        1 + data_bits + parity + stop_bits
+        symbol_length(data_bits, parity, more_than_one_stop_bit)
     }
 
     /// Reads one byte from the RX FIFO.
@@ -3332,6 +4260,33 @@ impl Info {
         self.read_buffered(buf)
     }
 
+    #[cfg(feature = "unstable")]
+    fn read_byte_status(&self) -> nb::Result<(u8, RxStatus), Infallible> {
+        if self.rx_fifo_count() == 0 {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        let conditions = RxEvent::FifoOvf | RxEvent::FrameError | RxEvent::ParityError;
+        let events = self.rx_events().intersection(conditions);
+
+        let byte = self.read_next_from_fifo();
+
+        if !events.is_empty() {
+            self.clear_rx_events(events);
+            if events.contains(RxEvent::FifoOvf) {
+                self.rxfifo_reset();
+            }
+        }
+
+        let status = RxStatus {
+            parity_error: events.contains(RxEvent::ParityError),
+            frame_error: events.contains(RxEvent::FrameError),
+            overrun: events.contains(RxEvent::FifoOvf),
+        };
+
+        Ok((byte, status))
+    }
+
     fn read_buffered(&self, buf: &mut [u8]) -> Result<usize, RxError> {
         // Get the count first, to avoid accidentally reading a corrupted byte received
         // after the error check.
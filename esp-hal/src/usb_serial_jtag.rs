@@ -119,7 +119,7 @@
 
 #[instability::unstable]
 use core::task::Poll;
-use core::{convert::Infallible, marker::PhantomData};
+use core::marker::PhantomData;
 
 use procmacros::handler;
 
@@ -131,10 +131,35 @@ use crate::{
     pac::usb_device::RegisterBlock,
     peripherals::USB_DEVICE,
     system::PeripheralClockControl,
+    time::{Duration, Instant},
 };
 
-/// Custom USB serial error type
-type Error = Infallible;
+/// USB Serial/JTAG error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Error {
+    /// The host didn't read the TX FIFO in time, most likely because no host
+    /// is connected. The data that didn't fit in the FIFO was discarded.
+    HostNotConnected,
+}
+
+impl core::error::Error for Error {}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::HostNotConnected => write!(f, "The host didn't read the transmitted data"),
+        }
+    }
+}
+
+#[instability::unstable]
+impl embedded_io::Error for Error {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::TimedOut
+    }
+}
 
 /// USB Serial/JTAG (Full-duplex)
 pub struct UsbSerialJtag<'d, Dm: DriverMode> {
@@ -146,6 +171,9 @@ pub struct UsbSerialJtag<'d, Dm: DriverMode> {
 pub struct UsbSerialJtagTx<'d, Dm: DriverMode> {
     peripheral: USB_DEVICE<'d>,
     phantom: PhantomData<Dm>,
+    /// A previous blocking write has given up waiting for the host. We use
+    /// this flag to avoid blocking on every write if no host is attached.
+    host_timed_out: bool,
 }
 
 /// USB Serial/JTAG (Receive)
@@ -158,10 +186,15 @@ impl<'d, Dm> UsbSerialJtagTx<'d, Dm>
 where
     Dm: DriverMode,
 {
+    /// How long a blocking write waits for the host to read the TX FIFO
+    /// before assuming that no host is attached.
+    const HOST_TIMEOUT: Duration = Duration::from_millis(50);
+
     fn new_inner(peripheral: USB_DEVICE<'d>) -> Self {
         Self {
             peripheral,
             phantom: PhantomData,
+            host_timed_out: false,
         }
     }
 
@@ -169,23 +202,68 @@ where
         self.peripheral.register_block()
     }
 
+    fn tx_fifo_ready(&self) -> bool {
+        // FIXME: raw register access
+        self.regs().ep1_conf().read().bits() & 0b011 != 0b000
+    }
+
+    /// Waits for the host to read the TX FIFO.
+    ///
+    /// Returns `false` if the host didn't read the FIFO in time. In that case,
+    /// subsequent calls return immediately until the host has caught up.
+    fn wait_for_host(&mut self) -> bool {
+        if self.tx_fifo_ready() {
+            self.host_timed_out = false;
+            return true;
+        }
+
+        if self.host_timed_out {
+            return false;
+        }
+
+        let start = Instant::now();
+        while !self.tx_fifo_ready() {
+            if start.elapsed() > Self::HOST_TIMEOUT {
+                self.host_timed_out = true;
+                return false;
+            }
+        }
+
+        true
+    }
+
     /// Write data to the serial output in chunks of up to 64 bytes
+    ///
+    /// If no host is attached, or the host doesn't read the data, the
+    /// remaining data is discarded after a short timeout instead of blocking
+    /// forever, and [`Error::HostNotConnected`] is returned.
     pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+        if self.write_chunks(data) < data.len() {
+            return Err(Error::HostNotConnected);
+        }
+
+        Ok(())
+    }
+
+    /// Writes `data` in chunks of up to 64 bytes until the host stops
+    /// reading, and returns the number of bytes that were queued.
+    fn write_chunks(&mut self, data: &[u8]) -> usize {
+        let mut written = 0;
         for chunk in data.chunks(64) {
+            if !self.wait_for_host() {
+                break;
+            }
+
             for byte in chunk {
                 self.regs()
                     .ep1()
                     .write(|w| unsafe { w.rdwr_byte().bits(*byte) });
             }
             self.regs().ep1_conf().modify(|_, w| w.wr_done().set_bit());
-
-            // FIXME: raw register access
-            while self.regs().ep1_conf().read().bits() & 0b011 == 0b000 {
-                // wait
-            }
+            written += chunk.len();
         }
 
-        Ok(())
+        written
     }
 
     /// Write data to the serial output in a non-blocking manner
@@ -208,12 +286,14 @@ where
     }
 
     /// Flush the output FIFO and block until it has been sent
+    ///
+    /// Like [`Self::write`], this gives up after a short timeout and returns
+    /// [`Error::HostNotConnected`] if the host doesn't read the data.
     pub fn flush_tx(&mut self) -> Result<(), Error> {
         self.regs().ep1_conf().modify(|_, w| w.wr_done().set_bit());
 
-        // FIXME: raw register access
-        while self.regs().ep1_conf().read().bits() & 0b011 == 0b000 {
-            // wait
+        if !self.wait_for_host() {
+            return Err(Error::HostNotConnected);
         }
 
         Ok(())
@@ -330,6 +410,7 @@ impl<'d> UsbSerialJtag<'d, Blocking> {
             tx: UsbSerialJtagTx {
                 peripheral: self.tx.peripheral,
                 phantom: PhantomData,
+                host_timed_out: self.tx.host_timed_out,
             },
         }
     }
@@ -384,6 +465,8 @@ where
     }
 
     /// Write data to the serial output in chunks of up to 64 bytes
+    ///
+    /// See [`UsbSerialJtagTx::write`].
     pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
         self.tx.write(data)
     }
@@ -605,9 +688,14 @@ where
     Dm: DriverMode,
 {
     fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
-        self.write(buf)?;
+        if buf.is_empty() {
+            return Ok(0);
+        }
 
-        Ok(buf.len())
+        match self.write_chunks(buf) {
+            0 => Err(Error::HostNotConnected),
+            written => Ok(written),
+        }
     }
 
     fn flush(&mut self) -> Result<(), Self::Error> {
@@ -718,6 +806,7 @@ impl<'d> UsbSerialJtag<'d, Async> {
             tx: UsbSerialJtagTx {
                 peripheral: self.tx.peripheral,
                 phantom: PhantomData,
+                host_timed_out: self.tx.host_timed_out,
             },
         }
     }
@@ -490,6 +490,7 @@ impl Chip {
                     "soc_has_dma_ch0",
                     "soc_has_adc1",
                     "soc_has_bt",
+                    "soc_has_dedicated_gpio",
                     "soc_has_sw_interrupt",
                     "soc_has_wifi",
                     "soc_has_mem2mem1",
@@ -607,6 +608,7 @@ impl Chip {
                     "cargo:rustc-cfg=soc_has_dma_ch0",
                     "cargo:rustc-cfg=soc_has_adc1",
                     "cargo:rustc-cfg=soc_has_bt",
+                    "cargo:rustc-cfg=soc_has_dedicated_gpio",
                     "cargo:rustc-cfg=soc_has_sw_interrupt",
                     "cargo:rustc-cfg=soc_has_wifi",
                     "cargo:rustc-cfg=soc_has_mem2mem1",
@@ -743,6 +745,7 @@ impl Chip {
                     "soc_has_adc1",
                     "soc_has_adc2",
                     "soc_has_bt",
+                    "soc_has_dedicated_gpio",
                     "soc_has_sw_interrupt",
                     "soc_has_tsens",
                     "soc_has_wifi",
@@ -889,6 +892,7 @@ impl Chip {
                     "cargo:rustc-cfg=soc_has_adc1",
                     "cargo:rustc-cfg=soc_has_adc2",
                     "cargo:rustc-cfg=soc_has_bt",
+                    "cargo:rustc-cfg=soc_has_dedicated_gpio",
                     "cargo:rustc-cfg=soc_has_sw_interrupt",
                     "cargo:rustc-cfg=soc_has_tsens",
                     "cargo:rustc-cfg=soc_has_wifi",
@@ -1067,6 +1071,7 @@ impl Chip {
                     "soc_has_dma_ch2",
                     "soc_has_adc1",
                     "soc_has_bt",
+                    "soc_has_dedicated_gpio",
                     "soc_has_lp_core",
                     "soc_has_sw_interrupt",
                     "soc_has_tsens",
@@ -1270,6 +1275,7 @@ impl Chip {
                     "cargo:rustc-cfg=soc_has_dma_ch2",
                     "cargo:rustc-cfg=soc_has_adc1",
                     "cargo:rustc-cfg=soc_has_bt",
+                    "cargo:rustc-cfg=soc_has_dedicated_gpio",
                     "cargo:rustc-cfg=soc_has_lp_core",
                     "cargo:rustc-cfg=soc_has_sw_interrupt",
                     "cargo:rustc-cfg=soc_has_tsens",
@@ -1468,6 +1474,7 @@ impl Chip {
                     "soc_has_dma_ch2",
                     "soc_has_adc1",
                     "soc_has_bt",
+                    "soc_has_dedicated_gpio",
                     "soc_has_sw_interrupt",
                     "soc_has_mem2mem1",
                     "soc_has_mem2mem4",
@@ -1645,6 +1652,7 @@ impl Chip {
                     "cargo:rustc-cfg=soc_has_dma_ch2",
                     "cargo:rustc-cfg=soc_has_adc1",
                     "cargo:rustc-cfg=soc_has_bt",
+                    "cargo:rustc-cfg=soc_has_dedicated_gpio",
                     "cargo:rustc-cfg=soc_has_sw_interrupt",
                     "cargo:rustc-cfg=soc_has_mem2mem1",
                     "cargo:rustc-cfg=soc_has_mem2mem4",
@@ -2621,6 +2629,7 @@ impl Config {
         println!("cargo:rustc-check-cfg=cfg(soc_has_systimer)");
         println!("cargo:rustc-check-cfg=cfg(soc_has_xts_aes)");
         println!("cargo:rustc-check-cfg=cfg(soc_has_dma_ch0)");
+        println!("cargo:rustc-check-cfg=cfg(soc_has_dedicated_gpio)");
         println!("cargo:rustc-check-cfg=cfg(soc_has_mem2mem1)");
         println!("cargo:rustc-check-cfg=cfg(soc_has_mem2mem2)");
         println!("cargo:rustc-check-cfg=cfg(soc_has_mem2mem3)");
@@ -2726,7 +2735,6 @@ impl Config {
         println!("cargo:rustc-check-cfg=cfg(wifi_has_wifi6)");
         println!("cargo:rustc-check-cfg=cfg(esp32h2)");
         println!("cargo:rustc-check-cfg=cfg(esp32s2)");
-        println!("cargo:rustc-check-cfg=cfg(soc_has_dedicated_gpio)");
         println!("cargo:rustc-check-cfg=cfg(soc_has_pms)");
         println!("cargo:rustc-check-cfg=cfg(soc_has_syscon)");
         println!("cargo:rustc-check-cfg=cfg(soc_has_usb0)");
@@ -837,7 +837,9 @@ macro_rules! define_io_mux_signals {
         #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[derive(Debug, PartialEq, Copy, Clone)]
         #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-        #[doc(hidden)]
+        #[cfg_attr(not(feature = "unstable"), doc(hidden))]
+        #[allow(missing_docs)]
+        #[doc = "The peripheral input signals of the GPIO matrix."]
         pub enum InputSignal {
             SPICLK                = 0,
             SPIQ                  = 1,
@@ -1025,7 +1027,9 @@ macro_rules! define_io_mux_signals {
         #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[derive(Debug, PartialEq, Copy, Clone)]
         #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-        #[doc(hidden)]
+        #[cfg_attr(not(feature = "unstable"), doc(hidden))]
+        #[allow(missing_docs)]
+        #[doc = "The peripheral output signals of the GPIO matrix."]
         pub enum OutputSignal {
             SPICLK                   = 0,
             SPIQ                     = 1,
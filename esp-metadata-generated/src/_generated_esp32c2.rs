@@ -382,8 +382,9 @@ macro_rules! for_each_peripheral {
         bind_peri_interrupt, enable_peri_interrupt, disable_peri_interrupt })));
         _for_each_inner!((XTS_AES <= XTS_AES() (unstable))); _for_each_inner!((DMA_CH0 <=
         virtual() (unstable))); _for_each_inner!((ADC1 <= virtual() (unstable)));
-        _for_each_inner!((BT <= virtual() (unstable))); _for_each_inner!((SW_INTERRUPT <=
-        virtual() (unstable))); _for_each_inner!((WIFI <= virtual() (unstable)));
+        _for_each_inner!((BT <= virtual() (unstable))); _for_each_inner!((DEDICATED_GPIO
+        <= virtual() (unstable))); _for_each_inner!((SW_INTERRUPT <= virtual()
+        (unstable))); _for_each_inner!((WIFI <= virtual() (unstable)));
         _for_each_inner!((MEM2MEM1 <= virtual() (unstable))); _for_each_inner!((MEM2MEM2
         <= virtual() (unstable))); _for_each_inner!((MEM2MEM3 <= virtual() (unstable)));
         _for_each_inner!((MEM2MEM4 <= virtual() (unstable))); _for_each_inner!((MEM2MEM5
@@ -414,12 +415,12 @@ macro_rules! for_each_peripheral {
         disable_peri_interrupt })), (UART1 <= UART1(UART1 : { bind_peri_interrupt,
         enable_peri_interrupt, disable_peri_interrupt })), (XTS_AES <= XTS_AES()
         (unstable)), (DMA_CH0 <= virtual() (unstable)), (ADC1 <= virtual() (unstable)),
-        (BT <= virtual() (unstable)), (SW_INTERRUPT <= virtual() (unstable)), (WIFI <=
-        virtual() (unstable)), (MEM2MEM1 <= virtual() (unstable)), (MEM2MEM2 <= virtual()
-        (unstable)), (MEM2MEM3 <= virtual() (unstable)), (MEM2MEM4 <= virtual()
-        (unstable)), (MEM2MEM5 <= virtual() (unstable)), (MEM2MEM6 <= virtual()
-        (unstable)), (MEM2MEM7 <= virtual() (unstable)), (MEM2MEM8 <= virtual()
-        (unstable))));
+        (BT <= virtual() (unstable)), (DEDICATED_GPIO <= virtual() (unstable)),
+        (SW_INTERRUPT <= virtual() (unstable)), (WIFI <= virtual() (unstable)), (MEM2MEM1
+        <= virtual() (unstable)), (MEM2MEM2 <= virtual() (unstable)), (MEM2MEM3 <=
+        virtual() (unstable)), (MEM2MEM4 <= virtual() (unstable)), (MEM2MEM5 <= virtual()
+        (unstable)), (MEM2MEM6 <= virtual() (unstable)), (MEM2MEM7 <= virtual()
+        (unstable)), (MEM2MEM8 <= virtual() (unstable))));
     };
 }
 /// This macro can be used to generate code for each `GPIOn` instance.
@@ -592,7 +593,9 @@ macro_rules! define_io_mux_signals {
         #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[derive(Debug, PartialEq, Copy, Clone)]
         #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-        #[doc(hidden)]
+        #[cfg_attr(not(feature = "unstable"), doc(hidden))]
+        #[allow(missing_docs)]
+        #[doc = "The peripheral input signals of the GPIO matrix."]
         pub enum InputSignal {
             SPIQ          = 0,
             SPID          = 1,
@@ -634,7 +637,9 @@ macro_rules! define_io_mux_signals {
         #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[derive(Debug, PartialEq, Copy, Clone)]
         #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-        #[doc(hidden)]
+        #[cfg_attr(not(feature = "unstable"), doc(hidden))]
+        #[allow(missing_docs)]
+        #[doc = "The peripheral output signals of the GPIO matrix."]
         pub enum OutputSignal {
             SPIQ          = 0,
             SPID          = 1,
@@ -519,7 +519,8 @@ macro_rules! for_each_peripheral {
         _for_each_inner!((DMA_CH1 <= virtual() (unstable))); _for_each_inner!((DMA_CH2 <=
         virtual() (unstable))); _for_each_inner!((ADC1 <= virtual() (unstable)));
         _for_each_inner!((ADC2 <= virtual() (unstable))); _for_each_inner!((BT <=
-        virtual() (unstable))); _for_each_inner!((SW_INTERRUPT <= virtual() (unstable)));
+        virtual() (unstable))); _for_each_inner!((DEDICATED_GPIO <= virtual()
+        (unstable))); _for_each_inner!((SW_INTERRUPT <= virtual() (unstable)));
         _for_each_inner!((TSENS <= virtual() (unstable))); _for_each_inner!((WIFI <=
         virtual() (unstable))); _for_each_inner!((all(GPIO0 <= virtual()), (GPIO1 <=
         virtual()), (GPIO2 <= virtual()), (GPIO3 <= virtual()), (GPIO4 <= virtual()),
@@ -556,9 +557,9 @@ macro_rules! for_each_peripheral {
         enable_peri_interrupt, disable_peri_interrupt }) (unstable)), (XTS_AES <=
         XTS_AES() (unstable)), (DMA_CH0 <= virtual() (unstable)), (DMA_CH1 <= virtual()
         (unstable)), (DMA_CH2 <= virtual() (unstable)), (ADC1 <= virtual() (unstable)),
-        (ADC2 <= virtual() (unstable)), (BT <= virtual() (unstable)), (SW_INTERRUPT <=
-        virtual() (unstable)), (TSENS <= virtual() (unstable)), (WIFI <= virtual()
-        (unstable))));
+        (ADC2 <= virtual() (unstable)), (BT <= virtual() (unstable)), (DEDICATED_GPIO <=
+        virtual() (unstable)), (SW_INTERRUPT <= virtual() (unstable)), (TSENS <=
+        virtual() (unstable)), (WIFI <= virtual() (unstable))));
     };
 }
 /// This macro can be used to generate code for each `GPIOn` instance.
@@ -736,7 +737,9 @@ macro_rules! define_io_mux_signals {
         #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[derive(Debug, PartialEq, Copy, Clone)]
         #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-        #[doc(hidden)]
+        #[cfg_attr(not(feature = "unstable"), doc(hidden))]
+        #[allow(missing_docs)]
+        #[doc = "The peripheral input signals of the GPIO matrix."]
         pub enum InputSignal {
             SPIQ             = 0,
             SPID             = 1,
@@ -787,7 +790,9 @@ macro_rules! define_io_mux_signals {
         #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[derive(Debug, PartialEq, Copy, Clone)]
         #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-        #[doc(hidden)]
+        #[cfg_attr(not(feature = "unstable"), doc(hidden))]
+        #[allow(missing_docs)]
+        #[doc = "The peripheral output signals of the GPIO matrix."]
         pub enum OutputSignal {
             SPIQ             = 0,
             SPID             = 1,
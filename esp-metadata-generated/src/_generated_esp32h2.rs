@@ -535,6 +535,7 @@ macro_rules! for_each_peripheral {
         (unstable))); _for_each_inner!((DMA_CH1 <= virtual() (unstable)));
         _for_each_inner!((DMA_CH2 <= virtual() (unstable))); _for_each_inner!((ADC1 <=
         virtual() (unstable))); _for_each_inner!((BT <= virtual() (unstable)));
+        _for_each_inner!((DEDICATED_GPIO <= virtual() (unstable)));
         _for_each_inner!((SW_INTERRUPT <= virtual() (unstable)));
         _for_each_inner!((MEM2MEM1 <= virtual() (unstable))); _for_each_inner!((MEM2MEM4
         <= virtual() (unstable))); _for_each_inner!((MEM2MEM5 <= virtual() (unstable)));
@@ -590,12 +591,12 @@ macro_rules! for_each_peripheral {
         bind_peri_interrupt, enable_peri_interrupt, disable_peri_interrupt })
         (unstable)), (DMA_CH0 <= virtual() (unstable)), (DMA_CH1 <= virtual()
         (unstable)), (DMA_CH2 <= virtual() (unstable)), (ADC1 <= virtual() (unstable)),
-        (BT <= virtual() (unstable)), (SW_INTERRUPT <= virtual() (unstable)), (MEM2MEM1
-        <= virtual() (unstable)), (MEM2MEM4 <= virtual() (unstable)), (MEM2MEM5 <=
-        virtual() (unstable)), (MEM2MEM10 <= virtual() (unstable)), (MEM2MEM11 <=
-        virtual() (unstable)), (MEM2MEM12 <= virtual() (unstable)), (MEM2MEM13 <=
-        virtual() (unstable)), (MEM2MEM14 <= virtual() (unstable)), (MEM2MEM15 <=
-        virtual() (unstable))));
+        (BT <= virtual() (unstable)), (DEDICATED_GPIO <= virtual() (unstable)),
+        (SW_INTERRUPT <= virtual() (unstable)), (MEM2MEM1 <= virtual() (unstable)),
+        (MEM2MEM4 <= virtual() (unstable)), (MEM2MEM5 <= virtual() (unstable)),
+        (MEM2MEM10 <= virtual() (unstable)), (MEM2MEM11 <= virtual() (unstable)),
+        (MEM2MEM12 <= virtual() (unstable)), (MEM2MEM13 <= virtual() (unstable)),
+        (MEM2MEM14 <= virtual() (unstable)), (MEM2MEM15 <= virtual() (unstable))));
     };
 }
 /// This macro can be used to generate code for each `GPIOn` instance.
@@ -759,7 +760,9 @@ macro_rules! define_io_mux_signals {
         #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[derive(Debug, PartialEq, Copy, Clone)]
         #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-        #[doc(hidden)]
+        #[cfg_attr(not(feature = "unstable"), doc(hidden))]
+        #[allow(missing_docs)]
+        #[doc = "The peripheral input signals of the GPIO matrix."]
         pub enum InputSignal {
             EXT_ADC_START       = 0,
             U0RXD               = 6,
@@ -846,7 +849,9 @@ macro_rules! define_io_mux_signals {
         #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[derive(Debug, PartialEq, Copy, Clone)]
         #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-        #[doc(hidden)]
+        #[cfg_attr(not(feature = "unstable"), doc(hidden))]
+        #[allow(missing_docs)]
+        #[doc = "The peripheral output signals of the GPIO matrix."]
         pub enum OutputSignal {
             LEDC_LS_SIG0     = 0,
             LEDC_LS_SIG1     = 1,
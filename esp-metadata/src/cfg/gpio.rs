@@ -573,13 +573,19 @@ fn render_signals(enum_name: &str, signals: &[IoMuxSignal]) -> TokenStream {
         });
     }
 
+    let doc = format!(
+        "The peripheral {} signals of the GPIO matrix.",
+        if enum_name == "InputSignal" { "input" } else { "output" }
+    );
     let enum_name = format_ident!("{enum_name}");
 
     quote! {
         #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[derive(Debug, PartialEq, Copy, Clone)]
         #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-        #[doc(hidden)]
+        #[cfg_attr(not(feature = "unstable"), doc(hidden))]
+        #[allow(missing_docs)]
+        #[doc = #doc]
         pub enum #enum_name {
             #(#variants)*
         }
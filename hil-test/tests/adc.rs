@@ -0,0 +1,190 @@
+//! ADC Tests
+
+//% CHIPS: esp32c2 esp32c3 esp32c6 esp32h2
+//% FEATURES: unstable
+
+#![no_std]
+#![no_main]
+
+use esp_hal::{
+    Blocking,
+    analog::adc::{Adc, AdcConfig, AdcPin, Attenuation},
+    delay::Delay,
+    gpio::{Level, Output, OutputConfig},
+    peripherals::{ADC1, GPIO2},
+};
+use hil_test as _;
+use portable_atomic::{AtomicU32, Ordering};
+
+struct Context {
+    adc: Adc<'static, ADC1<'static>, Blocking>,
+    pin: AdcPin<GPIO2<'static>, ADC1<'static>>,
+    // Connected to `pin`
+    output: Output<'static>,
+    delay: Delay,
+}
+
+static OUT_OF_WINDOW: AtomicU32 = AtomicU32::new(0);
+
+fn out_of_window() {
+    OUT_OF_WINDOW.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3, executor = hil_test::Executor::new())]
+mod tests {
+    use esp_hal::analog::adc::QUEUE_CAPACITY;
+
+    use super::*;
+
+    #[init]
+    fn init() -> Context {
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+
+        let (analog_pin, output_pin) = hil_test::common_test_pins!(peripherals);
+        let output = Output::new(output_pin, Level::Low, OutputConfig::default());
+
+        let mut config = AdcConfig::new();
+        let pin = config.enable_pin(analog_pin, Attenuation::_11dB);
+        let adc = Adc::new(peripherals.ADC1, config);
+
+        Context {
+            adc,
+            pin,
+            output,
+            delay: Delay::new(),
+        }
+    }
+
+    #[test]
+    fn test_queued_sampling_at_fixed_rate(mut ctx: Context) {
+        const SAMPLES: usize = 1000;
+
+        let mut received = 0;
+        for i in 0..SAMPLES {
+            ctx.delay.delay_micros(100);
+            nb::block!(ctx.adc.start_queued(&mut ctx.pin)).unwrap();
+
+            // Drain in batches to make sure the queue buffers in between
+            if i % (QUEUE_CAPACITY / 2) == 0 {
+                while ctx.adc.read_queued(&mut ctx.pin).is_ok() {
+                    received += 1;
+                }
+            }
+        }
+
+        // Let the last conversion finish
+        ctx.delay.delay_micros(100);
+        loop {
+            match ctx.adc.read_queued(&mut ctx.pin) {
+                Ok(_) => received += 1,
+                Err(nb::Error::WouldBlock) => break,
+                Err(nb::Error::Other(e)) => panic!("{:?}", e),
+            }
+        }
+
+        assert_eq!(received, SAMPLES);
+    }
+
+    #[test]
+    fn test_queue_overflow_is_reported(mut ctx: Context) {
+        const EXTRA: usize = 5;
+
+        for _ in 0..QUEUE_CAPACITY + EXTRA {
+            nb::block!(ctx.adc.start_queued(&mut ctx.pin)).unwrap();
+        }
+
+        // Let the last conversion finish
+        ctx.delay.delay_micros(100);
+
+        let error = ctx.adc.read_queued(&mut ctx.pin).unwrap_err();
+        let nb::Error::Other(overflow) = error else {
+            panic!("Expected an overflow");
+        };
+        assert_eq!(overflow.dropped(), EXTRA as u32);
+
+        // The queued results are still there
+        for _ in 0..QUEUE_CAPACITY {
+            assert!(ctx.adc.read_queued(&mut ctx.pin).is_ok());
+        }
+        assert!(matches!(
+            ctx.adc.read_queued(&mut ctx.pin),
+            Err(nb::Error::WouldBlock)
+        ));
+    }
+
+    #[test]
+    fn test_monitor_fires_outside_of_window(mut ctx: Context) {
+        // Driven low, the conversions end up well below the window.
+        ctx.adc.enable_monitor(&ctx.pin, 1000, 4095, out_of_window);
+
+        for _ in 0..10 {
+            nb::block!(ctx.adc.start_queued(&mut ctx.pin)).unwrap();
+        }
+        ctx.delay.delay_micros(100);
+        assert_eq!(OUT_OF_WINDOW.load(Ordering::Relaxed), 10);
+
+        // Driven high, every conversion is inside the window.
+        ctx.output.set_high();
+        for _ in 0..10 {
+            nb::block!(ctx.adc.start_queued(&mut ctx.pin)).unwrap();
+        }
+        ctx.delay.delay_micros(100);
+        assert_eq!(OUT_OF_WINDOW.load(Ordering::Relaxed), 10);
+
+        // A disabled monitor doesn't fire.
+        ctx.output.set_low();
+        ctx.adc.disable_monitor();
+        for _ in 0..10 {
+            nb::block!(ctx.adc.start_queued(&mut ctx.pin)).unwrap();
+        }
+        ctx.delay.delay_micros(100);
+        assert_eq!(OUT_OF_WINDOW.load(Ordering::Relaxed), 10);
+    }
+
+    #[test]
+    async fn test_async_reads_in_sequence(mut ctx: Context) {
+        let mut adc = ctx.adc.into_async();
+
+        // Each read must wait for its own conversion, not return a stale result.
+        for _ in 0..10 {
+            ctx.output.set_low();
+            let low = adc.read_oneshot(&mut ctx.pin).await;
+            ctx.output.set_high();
+            let high = adc.read_oneshot(&mut ctx.pin).await;
+
+            assert!(low < 1000, "{}", low);
+            assert!(high >= 1000, "{}", high);
+        }
+    }
+
+    #[test]
+    #[cfg(not(esp32c2))]
+    fn test_calibration_cache_skips_efuse() {
+        use esp_hal::analog::adc::{AdcCalCache, AdcCalCurve, AdcCalScheme};
+
+        type Cal = AdcCalCurve<ADC1<'static>>;
+
+        let uncached = Cal::new_cal(Attenuation::_11dB);
+
+        AdcCalCache::enable();
+
+        // The device was not woken up from deep sleep, so the cache starts out empty.
+        let reads = AdcCalCache::efuse_reads();
+        let first = Cal::new_cal(Attenuation::_11dB);
+        assert!(AdcCalCache::efuse_reads() > reads);
+
+        // This is what re-initializing the ADC after waking up does.
+        let reads = AdcCalCache::efuse_reads();
+        let cached = Cal::new_cal(Attenuation::_11dB);
+        assert_eq!(AdcCalCache::efuse_reads(), reads);
+
+        for raw in (0..4096).step_by(256) {
+            assert_eq!(cached.adc_val(raw), uncached.adc_val(raw));
+            assert_eq!(cached.adc_val(raw), first.adc_val(raw));
+        }
+        assert_eq!(cached.adc_cal(), uncached.adc_cal());
+
+        AdcCalCache::disable();
+    }
+}
@@ -445,6 +445,101 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(not(esp32))]
+    fn test_aes_dma_cbc_ctr() {
+        use esp_hal::{
+            aes::dma::{AesDma, DmaCipherState},
+            dma::{DmaRxBuf, DmaTxBuf},
+            dma_buffers,
+        };
+
+        fn process<'d>(
+            aes: AesDma<'d>,
+            (output, mut input): (DmaRxBuf, DmaTxBuf),
+            operation: Operation,
+            cipher_state: DmaCipherState,
+            data: &[u8],
+            expected: &[u8],
+            tag: &str,
+        ) -> (AesDma<'d>, (DmaRxBuf, DmaTxBuf)) {
+            input.as_mut_slice().copy_from_slice(data);
+            let transfer = aes
+                .process(
+                    data.len() / 16,
+                    output,
+                    input,
+                    operation,
+                    &cipher_state,
+                    KEY_128,
+                )
+                .map_err(|e| e.0)
+                .unwrap();
+            let (aes, output, input) = transfer.wait();
+            hil_test::assert_eq!(output.as_slice(), expected, "{} failed", tag);
+
+            (aes, (output, input))
+        }
+
+        let peripherals = esp_hal::init(Config::default().with_cpu_clock(CpuClock::max()));
+
+        cfg_if::cfg_if! {
+            if #[cfg(esp32s2)] {
+                let dma_channel = peripherals.DMA_CRYPTO;
+            } else {
+                let dma_channel = peripherals.DMA_CH0;
+            }
+        }
+
+        let aes = Aes::new(peripherals.AES).with_dma(dma_channel);
+
+        let (output, rx_descriptors, input, tx_descriptors) = dma_buffers!(PLAINTEXT_BUF_SIZE);
+        let buffers = (
+            DmaRxBuf::new(rx_descriptors, output).unwrap(),
+            DmaTxBuf::new(tx_descriptors, input).unwrap(),
+        );
+
+        let mut plaintext = [0; PLAINTEXT_BUF_SIZE];
+        fill_with_plaintext(&mut plaintext);
+
+        let (aes, buffers) = process(
+            aes,
+            buffers,
+            Operation::Encrypt,
+            Cbc::new(IV).into(),
+            &plaintext,
+            &CIPHERTEXT_CBC_128,
+            "CBC encryption",
+        );
+        let (aes, buffers) = process(
+            aes,
+            buffers,
+            Operation::Decrypt,
+            Cbc::new(IV).into(),
+            &CIPHERTEXT_CBC_128,
+            &plaintext,
+            "CBC decryption",
+        );
+        let (aes, buffers) = process(
+            aes,
+            buffers,
+            Operation::Encrypt,
+            Ctr::new(IV).into(),
+            &plaintext,
+            &CIPHERTEXT_CTR,
+            "CTR encryption",
+        );
+        let _ = process(
+            aes,
+            buffers,
+            Operation::Decrypt,
+            Ctr::new(IV).into(),
+            &CIPHERTEXT_CTR,
+            &plaintext,
+            "CTR decryption",
+        );
+    }
+
     #[test]
     fn test_aes_work_queue_cpu() {
         let p = esp_hal::init(Config::default().with_cpu_clock(CpuClock::max()));
@@ -42,6 +42,43 @@ mod tests {
         assert_eq!(crc_smbus, 0xf4);
     }
 
+    #[test]
+    fn test_crc_incremental() {
+        let data: &[u8] = b"123456789";
+        let (head, tail) = data.split_at(4);
+
+        // Feeding the data in chunks must give the same result as a single call,
+        // whether the ROM or the table-based implementation is used.
+        assert_eq!(
+            crc::crc32_le(crc::crc32_le(!0xffffffff, head), tail),
+            crc::crc32_le(!0xffffffff, data)
+        );
+        assert_eq!(
+            crc::crc32_be(crc::crc32_be(!0xffffffff, head), tail),
+            crc::crc32_be(!0xffffffff, data)
+        );
+        assert_eq!(
+            crc::crc16_le(crc::crc16_le(!0, head), tail),
+            crc::crc16_le(!0, data)
+        );
+        assert_eq!(
+            crc::crc16_be(crc::crc16_be(!0, head), tail),
+            crc::crc16_be(!0, data)
+        );
+        assert_eq!(
+            crc::crc8_le(crc::crc8_le(!0, head), tail),
+            crc::crc8_le(!0, data)
+        );
+        assert_eq!(
+            crc::crc8_be(crc::crc8_be(!0, head), tail),
+            crc::crc8_be(!0, data)
+        );
+
+        // CRC-16/XMODEM
+        let crc_xmodem = !crc::crc16_be(!0, data);
+        assert_eq!(crc_xmodem, 0x31c3);
+    }
+
     #[test]
     fn test_md5() {
         let sentence = "The quick brown fox jumps over a lazy dog";
@@ -0,0 +1,128 @@
+//! DAC Test
+//!
+//! The DAC output is measured by ADC2, which is connected to the same pad.
+
+//% CHIPS: esp32 esp32s2
+//% FEATURES: unstable
+
+#![no_std]
+#![no_main]
+
+use esp_hal::{
+    Blocking,
+    analog::{
+        adc::{Adc, AdcConfig, AdcPin, Attenuation},
+        dac::{CosineConfig, CosineError, CosineScale, Dac},
+    },
+    delay::Delay,
+    peripherals::{ADC2, DAC1},
+    time::Rate,
+};
+use hil_test as _;
+
+cfg_if::cfg_if! {
+    if #[cfg(esp32)] {
+        type DacPin = esp_hal::peripherals::GPIO25<'static>;
+    } else {
+        type DacPin = esp_hal::peripherals::GPIO17<'static>;
+    }
+}
+
+struct Context {
+    dac: Dac<'static, DAC1<'static>>,
+    adc: Adc<'static, ADC2<'static>, Blocking>,
+    pin: AdcPin<DacPin, ADC2<'static>>,
+    delay: Delay,
+}
+
+impl Context {
+    fn read(&mut self) -> u16 {
+        nb::block!(self.adc.read_oneshot(&mut self.pin)).unwrap()
+    }
+
+    /// Returns the lowest and highest of `count` samples taken 10 µs apart.
+    fn read_range(&mut self, count: usize) -> (u16, u16) {
+        let mut range = (u16::MAX, 0);
+        for _ in 0..count {
+            let value = self.read();
+            range = (range.0.min(value), range.1.max(value));
+            self.delay.delay_micros(10);
+        }
+        range
+    }
+}
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3)]
+mod tests {
+    use super::*;
+
+    #[init]
+    fn init() -> Context {
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                let adc_pin = peripherals.GPIO25;
+                let dac_pin = unsafe { esp_hal::peripherals::GPIO25::steal() };
+            } else {
+                let adc_pin = peripherals.GPIO17;
+                let dac_pin = unsafe { esp_hal::peripherals::GPIO17::steal() };
+            }
+        }
+
+        let mut config = AdcConfig::new();
+        let pin = config.enable_pin(adc_pin, Attenuation::_11dB);
+        let adc = Adc::new(peripherals.ADC2, config);
+
+        // The pad is shared with the ADC, which only ever reads it.
+        let dac = Dac::new(peripherals.DAC1, dac_pin);
+
+        Context {
+            dac,
+            adc,
+            pin,
+            delay: Delay::new(),
+        }
+    }
+
+    #[test]
+    fn written_value_is_read_back(mut ctx: Context) {
+        let mut previous = None;
+        for value in [20, 60, 100] {
+            ctx.dac.write(value);
+            ctx.delay.delay_micros(100);
+
+            let reading = ctx.read();
+            if let Some(previous) = previous {
+                assert!(reading > previous + 100, "{} after {}", reading, previous);
+            }
+            previous = Some(reading);
+        }
+    }
+
+    #[test]
+    fn cosine_wave_is_stopped_by_write(mut ctx: Context) {
+        ctx.dac
+            .enable_cosine(&CosineConfig::default().with_scale(CosineScale::Half))
+            .unwrap();
+
+        // 10 periods of the 1 kHz wave.
+        let (min, max) = ctx.read_range(1000);
+        assert!(max - min > 500, "cosine wave from {} to {}", min, max);
+
+        ctx.dac.write(128);
+        ctx.delay.delay_micros(100);
+
+        let (min, max) = ctx.read_range(1000);
+        assert!(max - min < 300, "constant output from {} to {}", min, max);
+    }
+
+    #[test]
+    fn cosine_frequency_is_checked(mut ctx: Context) {
+        let result = ctx
+            .dac
+            .enable_cosine(&CosineConfig::default().with_frequency(Rate::from_hz(10)));
+        assert_eq!(result, Err(CosineError::UnsupportedFrequency));
+    }
+}
@@ -0,0 +1,77 @@
+//! Dedicated GPIO Test
+
+//% CHIPS: esp32c2 esp32c3 esp32c6 esp32h2
+//% FEATURES: unstable
+
+#![no_std]
+#![no_main]
+
+use esp_hal::{
+    delay::Delay,
+    gpio::{AnyPin, Pin, dedicated::DedicatedPin},
+    peripherals::DEDICATED_GPIO,
+};
+use hil_test as _;
+
+struct Context {
+    dedicated_gpio: DEDICATED_GPIO<'static>,
+    test_gpio1: AnyPin<'static>,
+    test_gpio2: AnyPin<'static>,
+    delay: Delay,
+}
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3)]
+mod tests {
+    use super::*;
+
+    #[init]
+    fn init() -> Context {
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+
+        let (gpio1, gpio2) = hil_test::common_test_pins!(peripherals);
+
+        Context {
+            dedicated_gpio: peripherals.DEDICATED_GPIO,
+            test_gpio1: gpio1.degrade(),
+            test_gpio2: gpio2.degrade(),
+            delay: Delay::new(),
+        }
+    }
+
+    #[test]
+    fn output_is_read_back_through_input(ctx: Context) {
+        // The test pins are connected, so the output on channel 3 can be read on
+        // input channel 5.
+        let mut gpio = DedicatedPin::new(ctx.dedicated_gpio)
+            .with_output(3, ctx.test_gpio1)
+            .with_input(5, ctx.test_gpio2);
+
+        for value in [0b1000, 0b0000, 0b1000] {
+            gpio.write_all(value);
+            ctx.delay.delay_micros(1);
+
+            assert_eq!(gpio.output_levels(), value);
+            assert_eq!(gpio.read_all() & (1 << 5), (value >> 3) << 5);
+        }
+    }
+
+    #[test]
+    fn set_high_and_set_low_only_change_masked_channels(ctx: Context) {
+        let mut gpio = DedicatedPin::new(ctx.dedicated_gpio)
+            .with_output(0, ctx.test_gpio1)
+            .with_input(0, ctx.test_gpio2);
+
+        gpio.write_all(0b1010_0000);
+
+        gpio.set_high(0b0000_0001);
+        ctx.delay.delay_micros(1);
+        assert_eq!(gpio.output_levels(), 0b1010_0001);
+        assert_eq!(gpio.read_all() & 1, 1);
+
+        gpio.set_low(0b1000_0001);
+        ctx.delay.delay_micros(1);
+        assert_eq!(gpio.output_levels(), 0b0010_0000);
+        assert_eq!(gpio.read_all() & 1, 0);
+    }
+}
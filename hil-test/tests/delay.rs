@@ -93,4 +93,29 @@ mod tests {
             (t2 - t1).as_millis()
         );
     }
+
+    #[test]
+    fn delay_until_waits_for_deadline(ctx: Context) {
+        let deadline = Instant::now() + Duration::from_millis(50);
+        ctx.delay.delay_until(deadline);
+        let t2 = Instant::now();
+
+        assert!(t2 >= deadline, "overshoot: {:?}", deadline - t2);
+    }
+
+    #[test]
+    fn delay_until_past_deadline_returns_immediately(ctx: Context) {
+        let deadline = Instant::now();
+        ctx.delay.delay_millis(10);
+
+        let t1 = Instant::now();
+        ctx.delay.delay_until(deadline);
+        let t2 = Instant::now();
+
+        assert!(
+            (t2 - t1).as_millis() < 1u64,
+            "diff: {:?}",
+            (t2 - t1).as_micros()
+        );
+    }
 }
@@ -223,4 +223,20 @@ mod tests {
         check(esp_hal::dma_tx_buffer!(TX_SIZE + 2), TX_SIZE + 2);
         check(esp_hal::dma_tx_buffer!(TX_SIZE + 3), TX_SIZE + 3);
     }
+
+    #[test]
+    fn test_dma_descriptor_set() {
+        use esp_hal::dma::DmaDescriptorSet;
+
+        let descriptors = DmaDescriptorSet::<3>::new();
+        core::assert_eq!(descriptors.len(), 3);
+        core::assert_eq!(
+            DmaDescriptorSet::<3>::max_transfer_size(64),
+            3 * (4096 - 64)
+        );
+        core::assert_eq!(
+            compute_size(DmaDescriptorSet::<3>::max_transfer_size(64), 4096 - 64),
+            descriptors.len()
+        );
+    }
 }
@@ -0,0 +1,82 @@
+//! eFuse tests
+//!
+//! The test that programs the user data block only runs with the `burn-efuse`
+//! feature enabled. It permanently programs `BLOCK3` of the device under test,
+//! so it is not enabled by default.
+
+//% CHIPS: esp32c3 esp32c6 esp32h2 esp32s3
+//% FEATURES: unstable
+
+#![no_std]
+#![no_main]
+
+use esp_hal::efuse::{CUSTOM_MAC, Efuse, KEY_BLOCK_COUNT, MAC0, WriteError};
+use hil_test as _;
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3)]
+mod tests {
+    use super::*;
+
+    #[init]
+    fn init() {
+        let _ = esp_hal::init(esp_hal::Config::default());
+    }
+
+    #[test]
+    fn write_rejects_fields_outside_user_data_block() {
+        let result = unsafe { Efuse::write_field(MAC0, &[0xFF; 4]) };
+        assert_eq!(result, Err(WriteError::UnsupportedBlock));
+    }
+
+    #[test]
+    fn write_rejects_data_longer_than_field() {
+        // CUSTOM_MAC is 48 bits long.
+        let result = unsafe { Efuse::write_field(CUSTOM_MAC, &[0, 0, 0, 0, 0, 0, 1]) };
+        assert_eq!(result, Err(WriteError::DataTooLong));
+    }
+
+    #[test]
+    fn security_info_matches_individual_getters() {
+        let info = Efuse::security_info();
+
+        assert_eq!(info.flash_encryption, Efuse::flash_encryption());
+        assert_eq!(info.secure_boot, Efuse::secure_boot_enabled());
+        assert_eq!(info.jtag_disabled, Efuse::jtag_disabled());
+        assert_eq!(info.download_mode_disabled, Efuse::download_mode_disabled());
+        for (key, purpose) in info.key_purposes.iter().enumerate() {
+            assert_eq!(Some(*purpose), Efuse::key_purpose(key));
+        }
+        assert_eq!(Efuse::key_purpose(KEY_BLOCK_COUNT), None);
+    }
+
+    #[test]
+    fn chip_info_matches_individual_getters() {
+        let info = Efuse::chip_info();
+
+        assert_eq!(info.revision, Efuse::chip_revision());
+        assert_eq!(info.mac_address, Efuse::read_base_mac_address());
+        assert_eq!(info.package, Efuse::package());
+        assert_eq!(info.embedded_flash, Efuse::flash_size());
+        // The radios are present on every supported chip, except for Wi-Fi on the ESP32-H2.
+        assert!(info.bluetooth);
+        assert_eq!(info.wifi, cfg!(not(esp32h2)));
+    }
+
+    #[test]
+    #[cfg(feature = "burn-efuse")]
+    fn write_user_data_reads_back() {
+        use esp_hal::efuse::BLOCK_USR_DATA;
+
+        const DATA: [u8; 24] = *b"esp-hal eFuse write test";
+
+        match unsafe { Efuse::write_field(BLOCK_USR_DATA, &DATA) } {
+            Ok(()) => {}
+            // The block can only be programmed once, a previous run may have done it.
+            Err(WriteError::AlreadyProgrammed) => {}
+            Err(e) => panic!("Failed to program the eFuse: {:?}", e),
+        }
+
+        assert_eq!(Efuse::read_field_le::<[u8; 24]>(BLOCK_USR_DATA), DATA);
+    }
+}
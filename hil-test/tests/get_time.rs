@@ -56,6 +56,21 @@ mod tests {
         })
     }
 
+    #[cfg(systimer)]
+    #[test]
+    fn test_systimer_now_shares_time_base(ctx: Context) {
+        use esp_hal::{time::Instant, timer::systimer::SystemTimer};
+
+        let t1 = Instant::now();
+        let s1 = SystemTimer::now();
+        ctx.delay.delay_millis(100);
+        let s2 = SystemTimer::now();
+        let t2 = Instant::now();
+
+        assert!(t1 <= s1 && s2 <= t2);
+        assert!((s2 - s1).as_millis() >= 100u64);
+    }
+
     #[cfg(esp32)]
     #[test]
     fn test_current_time_construct_timg0(ctx: Context) {
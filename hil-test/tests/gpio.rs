@@ -18,7 +18,7 @@ cfg_if::cfg_if! {
         use esp_hal::{
             // OutputOpenDrain is here because will be unused otherwise
             delay::Delay,
-            gpio::{DriveMode, Event, Flex, Io},
+            gpio::{DriveMode, Edge, Event, Flex, Io, WakeEvent},
             handler,
             timer::timg::TimerGroup,
         };
@@ -26,6 +26,7 @@ cfg_if::cfg_if! {
 
         static COUNTER: Mutex<RefCell<u32>> = Mutex::new(RefCell::new(0));
         static INPUT_PIN: Mutex<RefCell<Option<Input>>> = Mutex::new(RefCell::new(None));
+        static FLEX_PIN: Mutex<RefCell<Option<Flex>>> = Mutex::new(RefCell::new(None));
     }
 }
 
@@ -42,6 +43,12 @@ struct Context {
     delay: Delay,
     #[cfg(feature = "unstable")]
     io: Io<'static>,
+    #[cfg(feature = "unstable")]
+    unconnected_pin: AnyPin<'static>,
+    #[cfg(feature = "unstable")]
+    uart1: esp_hal::peripherals::UART1<'static>,
+    #[cfg(feature = "unstable")]
+    lpwr: esp_hal::peripherals::LPWR<'static>,
 }
 
 #[cfg_attr(feature = "unstable", handler)]
@@ -68,6 +75,18 @@ pub fn interrupt_handler_unlisten() {
     });
 }
 
+#[cfg_attr(feature = "unstable", handler)]
+#[cfg(feature = "unstable")]
+pub fn pin_interrupt_handler() {
+    critical_section::with(|cs| {
+        *COUNTER.borrow_ref_mut(cs) += 1;
+        FLEX_PIN
+            .borrow_ref_mut(cs)
+            .as_mut()
+            .map(|pin| pin.clear_interrupt());
+    });
+}
+
 // Compile-time test to check that GPIOs can be passed by reference.
 fn _gpios_can_be_reused() {
     let p = esp_hal::init(esp_hal::Config::default());
@@ -132,6 +151,9 @@ mod tests {
 
         let (gpio1, gpio2) = hil_test::common_test_pins!(peripherals);
 
+        #[cfg(feature = "unstable")]
+        let unconnected_pin = hil_test::unconnected_pin!(peripherals);
+
         // Interrupts are unstable
         #[cfg(feature = "unstable")]
         let io = Io::new(peripherals.IO_MUX);
@@ -150,6 +172,12 @@ mod tests {
             delay,
             #[cfg(feature = "unstable")]
             io,
+            #[cfg(feature = "unstable")]
+            unconnected_pin: unconnected_pin.degrade(),
+            #[cfg(feature = "unstable")]
+            uart1: peripherals.UART1,
+            #[cfg(feature = "unstable")]
+            lpwr: peripherals.LPWR,
         }
     }
 
@@ -184,6 +212,103 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 5);
     }
 
+    #[test]
+    #[cfg(feature = "unstable")] // Timers are unstable
+    async fn async_edge_embedded_hal_1_0(ctx: Context) {
+        // Only uses the pins through the embedded-hal traits, like a driver crate would.
+        async fn count_rising_edges<P>(pin: &mut P, edges: usize) -> usize
+        where
+            P: embedded_hal_async::digital::Wait + embedded_hal::digital::InputPin,
+        {
+            let mut count = 0;
+            while count < edges {
+                pin.wait_for_rising_edge().await.unwrap();
+                assert!(pin.is_high().unwrap());
+                count += 1;
+            }
+            count
+        }
+
+        async fn pulse<P>(pin: &mut P, pulses: usize)
+        where
+            P: embedded_hal::digital::StatefulOutputPin,
+        {
+            for _ in 0..pulses {
+                pin.toggle().unwrap();
+                Timer::after(Duration::from_millis(25)).await;
+                pin.toggle().unwrap();
+                Timer::after(Duration::from_millis(25)).await;
+            }
+        }
+
+        let mut input = Input::new(ctx.test_gpio1, InputConfig::default().with_pull(Pull::Down));
+        let mut output = Output::new(ctx.test_gpio2, Level::Low, OutputConfig::default());
+
+        let (count, _) =
+            embassy_futures::join::join(count_rising_edges(&mut input, 5), pulse(&mut output, 5))
+                .await;
+        assert_eq!(count, 5);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")] // Interrupts are unstable
+    async fn pin_handler_runs_alongside_async_pin(ctx: Context) {
+        let counter = AtomicUsize::new(0);
+        let Context {
+            test_gpio1,
+            test_gpio2,
+            unconnected_pin,
+            ..
+        } = ctx;
+        let mut test_gpio1 = Input::new(test_gpio1, InputConfig::default().with_pull(Pull::Down));
+        let mut test_gpio2 = Output::new(test_gpio2, Level::Low, OutputConfig::default());
+
+        // The pin's output drives its own input.
+        let mut flex = Flex::new(unconnected_pin);
+        flex.set_low();
+        flex.set_output_enable(true);
+        flex.set_input_enable(true);
+        flex.set_interrupt_handler(pin_interrupt_handler);
+
+        critical_section::with(|cs| {
+            *COUNTER.borrow_ref_mut(cs) = 0;
+            flex.listen(Event::RisingEdge);
+            FLEX_PIN.borrow_ref_mut(cs).replace(flex);
+        });
+
+        embassy_futures::select::select(
+            async {
+                loop {
+                    test_gpio1.wait_for_rising_edge().await;
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+            async {
+                for _ in 0..5 {
+                    test_gpio2.set_high();
+                    critical_section::with(|cs| {
+                        FLEX_PIN.borrow_ref_mut(cs).as_mut().unwrap().set_high()
+                    });
+                    Timer::after(Duration::from_millis(25)).await;
+                    test_gpio2.set_low();
+                    critical_section::with(|cs| {
+                        FLEX_PIN.borrow_ref_mut(cs).as_mut().unwrap().set_low()
+                    });
+                    Timer::after(Duration::from_millis(25)).await;
+                }
+            },
+        )
+        .await;
+
+        let mut flex = critical_section::with(|cs| FLEX_PIN.borrow_ref_mut(cs).take().unwrap());
+        flex.unlisten();
+        // Unregisters the handler.
+        drop(flex);
+
+        assert_eq!(counter.load(Ordering::SeqCst), 5);
+        assert_eq!(critical_section::with(|cs| *COUNTER.borrow_ref(cs)), 5);
+    }
+
     #[test]
     async fn a_pin_can_wait(ctx: Context) {
         let mut first = Input::new(ctx.test_gpio1, InputConfig::default().with_pull(Pull::Down));
@@ -221,6 +346,22 @@ mod tests {
         assert_eq!(test_gpio1.is_high(), false);
     }
 
+    #[test]
+    #[cfg(all(esp32h2, feature = "unstable"))]
+    fn gpio_input_with_hysteresis(ctx: Context) {
+        let mut test_gpio1 = Input::new(ctx.test_gpio1, InputConfig::default());
+        let mut test_gpio2 = Output::new(ctx.test_gpio2, Level::Low, OutputConfig::default());
+
+        test_gpio1.enable_hysteresis(true);
+        assert_eq!(test_gpio1.level(), Level::Low);
+        test_gpio2.set_high();
+        assert_eq!(test_gpio1.level(), Level::High);
+
+        test_gpio1.enable_hysteresis(false);
+        test_gpio2.set_low();
+        assert_eq!(test_gpio1.level(), Level::Low);
+    }
+
     #[test]
     async fn waiting_for_level_does_not_hang(ctx: Context) {
         let mut test_gpio1 =
@@ -338,6 +479,37 @@ mod tests {
         test_gpio1.unlisten();
     }
 
+    #[test]
+    #[cfg(feature = "unstable")] // Interrupts are unstable
+    fn gpio_interrupt_reports_edge(ctx: Context) {
+        let mut test_gpio1 =
+            Input::new(ctx.test_gpio1, InputConfig::default().with_pull(Pull::Down));
+        let mut test_gpio2 = Output::new(ctx.test_gpio2, Level::Low, OutputConfig::default());
+
+        // The default handler disables the interrupt after it fires, so we
+        // listen again for each edge.
+        test_gpio1.listen(Event::AnyEdge);
+        test_gpio2.set_high();
+        ctx.delay.delay_millis(1);
+        assert!(test_gpio1.is_interrupt_set());
+        assert_eq!(test_gpio1.last_edge(), Edge::Rising);
+
+        test_gpio1.clear_interrupt();
+        test_gpio1.listen(Event::AnyEdge);
+        test_gpio2.set_low();
+        ctx.delay.delay_millis(1);
+        assert!(test_gpio1.is_interrupt_set());
+        assert_eq!(test_gpio1.last_edge(), Edge::Falling);
+
+        test_gpio1.clear_interrupt();
+        test_gpio1.listen(Event::AnyEdge);
+        test_gpio2.set_high();
+        ctx.delay.delay_millis(1);
+        assert_eq!(test_gpio1.last_edge(), Edge::Rising);
+
+        test_gpio1.unlisten();
+    }
+
     #[test]
     #[cfg(feature = "unstable")] // Interrupts are unstable
     async fn unlisten_in_interrupt_handler_does_not_panic(mut ctx: Context) {
@@ -357,6 +529,195 @@ mod tests {
         while critical_section::with(|cs| *COUNTER.borrow_ref(cs)) == 0 {}
     }
 
+    #[test]
+    #[cfg(feature = "unstable")] // Interrupts are unstable
+    fn wakeup_source_coexists_with_interrupt(mut ctx: Context) {
+        ctx.io.set_interrupt_handler(interrupt_handler_unlisten);
+
+        let mut test_gpio1 =
+            Input::new(ctx.test_gpio1, InputConfig::default().with_pull(Pull::Down));
+        let mut test_gpio2 = Output::new(ctx.test_gpio2, Level::Low, OutputConfig::default());
+
+        critical_section::with(|cs| {
+            *COUNTER.borrow_ref_mut(cs) = 0;
+            test_gpio1.listen(Event::HighLevel);
+            test_gpio1.wakeup_enable(true, WakeEvent::HighLevel);
+            INPUT_PIN.borrow_ref_mut(cs).replace(test_gpio1);
+        });
+        test_gpio2.set_high();
+        ctx.delay.delay_millis(1);
+
+        let count = critical_section::with(|cs| *COUNTER.borrow_ref(cs));
+        assert_eq!(count, 1);
+
+        let mut test_gpio1 =
+            critical_section::with(|cs| INPUT_PIN.borrow_ref_mut(cs).take().unwrap());
+        test_gpio1.wakeup_enable(false, WakeEvent::HighLevel);
+    }
+
+    #[test]
+    #[cfg(all(feature = "unstable", not(esp32h2)))] // Sleep is not supported on the ESP32-H2
+    fn wakeup_source_wakes_from_light_sleep(mut ctx: Context) {
+        use esp_hal::rtc_cntl::{
+            Rtc,
+            sleep::{GpioWakeupSource, TimerWakeupSource},
+        };
+
+        ctx.io.set_interrupt_handler(interrupt_handler_unlisten);
+
+        let mut rtc = Rtc::new(ctx.lpwr);
+        let mut test_gpio1 =
+            Input::new(ctx.test_gpio1, InputConfig::default().with_pull(Pull::Down));
+        let mut test_gpio2 = Output::new(ctx.test_gpio2, Level::Low, OutputConfig::default());
+
+        // The timer only ends the test if the GPIO fails to wake up the chip.
+        let gpio = GpioWakeupSource::new();
+        let timer = TimerWakeupSource::new(core::time::Duration::from_secs(2));
+
+        let start = rtc.current_time_us();
+        critical_section::with(|cs| {
+            *COUNTER.borrow_ref_mut(cs) = 0;
+            test_gpio1.listen(Event::HighLevel);
+            test_gpio1.wakeup_enable(true, WakeEvent::HighLevel);
+            INPUT_PIN.borrow_ref_mut(cs).replace(test_gpio1);
+
+            // The level is already high when the chip goes to sleep, so it wakes
+            // up right away. The interrupt is handled after the critical section.
+            test_gpio2.set_high();
+            rtc.sleep_light(&[&gpio, &timer]);
+        });
+        let slept_us = rtc.current_time_us() - start;
+        assert!(slept_us < 500_000, "{}", slept_us);
+
+        ctx.delay.delay_millis(1);
+        let count = critical_section::with(|cs| *COUNTER.borrow_ref(cs));
+        assert_eq!(count, 1);
+
+        let mut test_gpio1 =
+            critical_section::with(|cs| INPUT_PIN.borrow_ref_mut(cs).take().unwrap());
+        test_gpio1.wakeup_enable(false, WakeEvent::HighLevel);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")] // Interrupts are unstable
+    fn interrupt_status_reports_all_pending_pins(mut ctx: Context) {
+        let expected = (1 << ctx.test_gpio1.number()) | (1 << ctx.unconnected_pin.number());
+
+        let mut test_gpio1 =
+            Input::new(ctx.test_gpio1, InputConfig::default().with_pull(Pull::Down));
+        let mut test_gpio2 = Output::new(ctx.test_gpio2, Level::Low, OutputConfig::default());
+
+        // The pin's output drives its own input.
+        let mut flex = Flex::new(ctx.unconnected_pin);
+        flex.set_low();
+        flex.set_output_enable(true);
+        flex.set_input_enable(true);
+
+        // Keep the interrupt handler from running while we inspect the status.
+        critical_section::with(|_| {
+            test_gpio1.listen(Event::RisingEdge);
+            flex.listen(Event::RisingEdge);
+            assert_eq!(ctx.io.interrupt_status() & expected, 0);
+
+            test_gpio2.set_high();
+            flex.set_high();
+            ctx.delay.delay_micros(10);
+
+            assert_eq!(ctx.io.interrupt_status(), expected);
+
+            ctx.io.clear_interrupts(expected);
+            assert_eq!(ctx.io.interrupt_status(), 0);
+            assert!(!test_gpio1.is_interrupt_set());
+            assert!(!flex.is_interrupt_set());
+
+            test_gpio1.unlisten();
+            flex.unlisten();
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")] // Interrupts are unstable
+    fn pin_event_handler_is_called_for_each_event(ctx: Context) {
+        use portable_atomic::AtomicU64;
+
+        static RISING: AtomicUsize = AtomicUsize::new(0);
+        static FALLING: AtomicUsize = AtomicUsize::new(0);
+        static PINS: AtomicU64 = AtomicU64::new(0);
+
+        let expected = (1 << ctx.test_gpio1.number()) | (1 << ctx.unconnected_pin.number());
+
+        fn on_event(pin: u8, edge: Edge) {
+            match edge {
+                Edge::Rising => RISING.fetch_add(1, Ordering::Relaxed),
+                Edge::Falling => FALLING.fetch_add(1, Ordering::Relaxed),
+            };
+            PINS.fetch_or(1 << pin, Ordering::Relaxed);
+        }
+
+        let mut test_gpio1 =
+            Input::new(ctx.test_gpio1, InputConfig::default().with_pull(Pull::Down));
+        let mut test_gpio2 = Output::new(ctx.test_gpio2, Level::Low, OutputConfig::default());
+
+        // The pin's output drives its own input.
+        let mut flex = Flex::new(ctx.unconnected_pin);
+        flex.set_low();
+        flex.set_output_enable(true);
+        flex.set_input_enable(true);
+
+        test_gpio1.set_event_handler(on_event);
+        flex.set_event_handler(on_event);
+
+        test_gpio1.listen(Event::RisingEdge);
+        flex.listen(Event::RisingEdge);
+
+        // The handler clears the status, so the interrupts fire every time.
+        for _ in 0..3 {
+            test_gpio2.set_high();
+            flex.set_high();
+            ctx.delay.delay_millis(1);
+            test_gpio2.set_low();
+            flex.set_low();
+            ctx.delay.delay_millis(1);
+        }
+
+        test_gpio1.unlisten();
+        flex.unlisten();
+
+        assert_eq!(RISING.load(Ordering::Relaxed), 6);
+        assert_eq!(FALLING.load(Ordering::Relaxed), 0);
+        assert_eq!(PINS.load(Ordering::Relaxed), expected);
+        assert_eq!(ctx.io.interrupt_status() & expected, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")] // Interrupts are unstable
+    fn dropping_a_split_half_keeps_the_other_halfs_handler(ctx: Context) {
+        static RISING: AtomicUsize = AtomicUsize::new(0);
+
+        fn on_event(_pin: u8, _edge: Edge) {
+            RISING.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let (mut input, output) = unsafe { Flex::new(ctx.test_gpio1).split_into_drivers() };
+        input.set_event_handler(on_event);
+        input.listen(Event::RisingEdge);
+
+        // Dropping the output half must not unregister the input half's handler.
+        drop(output);
+
+        let mut test_gpio2 = Output::new(ctx.test_gpio2, Level::Low, OutputConfig::default());
+        for _ in 0..3 {
+            test_gpio2.set_high();
+            ctx.delay.delay_millis(1);
+            test_gpio2.set_low();
+            ctx.delay.delay_millis(1);
+        }
+
+        input.unlisten();
+
+        assert_eq!(RISING.load(Ordering::Relaxed), 3);
+    }
+
     #[test]
     #[cfg(feature = "unstable")] // delay is unstable
     fn gpio_od(ctx: Context) {
@@ -410,6 +771,50 @@ mod tests {
         assert_eq!(input.level(), Level::High);
     }
 
+    #[test]
+    #[cfg(feature = "unstable")] // delay is unstable
+    fn gpio_od_senses_line_while_released(ctx: Context) {
+        let line = Output::new(ctx.test_gpio1, Level::High, OutputConfig::default())
+            .into_open_drain(Pull::None);
+        let mut other = Output::new(ctx.test_gpio2, Level::Low, OutputConfig::default());
+
+        // The released line follows the other driver
+        ctx.delay.delay_millis(1);
+        assert_eq!(line.level(), Level::Low);
+
+        other.set_high();
+        ctx.delay.delay_millis(1);
+        assert_eq!(line.level(), Level::High);
+
+        other.set_low();
+        ctx.delay.delay_millis(1);
+        assert_eq!(line.level(), Level::Low);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")] // delay is unstable
+    fn gpio_od_with_internal_pull_up(ctx: Context) {
+        let mut line = Output::new(ctx.test_gpio1, Level::High, OutputConfig::default())
+            .into_open_drain(Pull::Up);
+        let input = Input::new(ctx.test_gpio2, InputConfig::default().with_pull(Pull::None));
+
+        ctx.delay.delay_millis(1);
+
+        // The released line is pulled high by the internal pull-up resistor
+        assert_eq!(line.level(), Level::High);
+        assert_eq!(input.level(), Level::High);
+
+        line.set_low();
+        ctx.delay.delay_millis(1);
+        assert_eq!(line.level(), Level::Low);
+        assert_eq!(input.level(), Level::Low);
+
+        line.set_high();
+        ctx.delay.delay_millis(1);
+        assert_eq!(line.level(), Level::High);
+        assert_eq!(input.level(), Level::High);
+    }
+
     #[test]
     #[cfg(feature = "unstable")]
     fn gpio_flex(ctx: Context) {
@@ -451,6 +856,100 @@ mod tests {
         assert_eq!(test_gpio2.is_set_low(), true);
     }
 
+    #[test]
+    #[cfg(all(feature = "unstable", any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+    fn gpio_hold_keeps_level_across_drivers(ctx: Context) {
+        let mut pin = ctx.test_gpio1;
+        let input = Input::new(ctx.test_gpio2, InputConfig::default());
+
+        let mut output = Output::new(pin.reborrow(), Level::High, OutputConfig::default());
+        output.hold(true);
+
+        // Neither changing the level nor dropping the driver reaches the pad.
+        output.set_low();
+        ctx.delay.delay_millis(1);
+        assert_eq!(input.level(), Level::High);
+        core::mem::drop(output);
+        ctx.delay.delay_millis(1);
+        assert_eq!(input.level(), Level::High);
+
+        // A new driver takes over when the hold is released.
+        let mut output = Output::new(pin, Level::Low, OutputConfig::default());
+        ctx.delay.delay_millis(1);
+        assert_eq!(input.level(), Level::High);
+
+        output.hold(false);
+        ctx.delay.delay_millis(1);
+        assert_eq!(input.level(), Level::Low);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn gpio_matrix_routes_peripheral_signals(ctx: Context) {
+        use esp_hal::{
+            gpio::{InputSignal, OutputSignal},
+            uart::{self, Uart},
+        };
+
+        let mut uart = Uart::new(ctx.uart1, uart::Config::default()).unwrap();
+
+        let mut tx = Flex::new(ctx.test_gpio1);
+        let mut rx = Flex::new(ctx.test_gpio2);
+
+        tx.connect_peripheral_to_output(OutputSignal::U1TXD)
+            .unwrap();
+        tx.set_output_enable(true);
+        rx.connect_input_to_peripheral(InputSignal::U1RXD).unwrap();
+
+        uart.write(&[0x42]).unwrap();
+        let mut byte = [0u8; 1];
+        uart.read(&mut byte).unwrap();
+        assert_eq!(byte[0], 0x42);
+
+        InputSignal::U1RXD.connect_to(&Level::High);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn gpio_matrix_rejects_double_routing(ctx: Context) {
+        use esp_hal::gpio::{InputSignal, OutputSignal, RoutingError};
+
+        let mut tx = Flex::new(ctx.test_gpio1);
+        let mut rx = Flex::new(ctx.test_gpio2);
+        let mut other = Flex::new(ctx.unconnected_pin);
+
+        tx.connect_peripheral_to_output(OutputSignal::U1TXD)
+            .unwrap();
+        // Connecting the same signal again is allowed.
+        tx.connect_peripheral_to_output(OutputSignal::U1TXD)
+            .unwrap();
+        assert_eq!(
+            tx.connect_peripheral_to_output(OutputSignal::U1RTS),
+            Err(RoutingError::OutputAlreadyConnected)
+        );
+        // An output signal can drive multiple pins.
+        other
+            .connect_peripheral_to_output(OutputSignal::U1TXD)
+            .unwrap();
+
+        OutputSignal::U1TXD.disconnect_from(&tx);
+        tx.connect_peripheral_to_output(OutputSignal::U1RTS)
+            .unwrap();
+
+        rx.connect_input_to_peripheral(InputSignal::U1RXD).unwrap();
+        assert_eq!(
+            other.connect_input_to_peripheral(InputSignal::U1RXD),
+            Err(RoutingError::InputAlreadyConnected)
+        );
+
+        InputSignal::U1RXD.connect_to(&Level::High);
+        other
+            .connect_input_to_peripheral(InputSignal::U1RXD)
+            .unwrap();
+
+        InputSignal::U1RXD.connect_to(&Level::High);
+    }
+
     // Tests touch pin (GPIO2) as AnyPin and Output
     // https://github.com/esp-rs/esp-hal/issues/1943
     #[test]
@@ -517,6 +1016,50 @@ mod tests {
         loop {}
     }
 
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn pins_can_be_driven_concurrently_from_different_contexts(ctx: Context) {
+        use esp_hal::interrupt::{Priority, software::SoftwareInterrupt};
+        use esp_hal_embassy::InterruptExecutor;
+        use static_cell::StaticCell;
+
+        static TOGGLES: AtomicUsize = AtomicUsize::new(0);
+
+        static INTERRUPT_EXECUTOR: StaticCell<InterruptExecutor<1>> = StaticCell::new();
+        let interrupt_executor = INTERRUPT_EXECUTOR.init(InterruptExecutor::new(unsafe {
+            SoftwareInterrupt::<1>::steal()
+        }));
+
+        let spawner = interrupt_executor.start(Priority::max());
+
+        spawner.must_spawn(toggle_task(ctx.unconnected_pin));
+
+        #[embassy_executor::task]
+        async fn toggle_task(pin: AnyPin<'static>) {
+            let mut pin = Output::new(pin, Level::Low, OutputConfig::default());
+            let mut level = Level::Low;
+
+            loop {
+                level = !level;
+                pin.set_level(level);
+                assert_eq!(pin.output_level(), level);
+                TOGGLES.fetch_add(1, Ordering::Relaxed);
+
+                Timer::after(Duration::from_micros(100)).await;
+            }
+        }
+
+        // The task preempts this loop at any point. Neither side may observe the
+        // other side's writes on its own pin.
+        let mut pin = Output::new(ctx.test_gpio1, Level::Low, OutputConfig::default());
+        while TOGGLES.load(Ordering::Relaxed) < 100 {
+            pin.set_high();
+            assert!(pin.is_set_high());
+            pin.set_low();
+            assert!(pin.is_set_low());
+        }
+    }
+
     #[test]
     #[cfg(feature = "unstable")]
     async fn pending_interrupt_does_not_cause_future_to_resolve_immediately(ctx: Context) {
@@ -13,6 +13,7 @@ use esp_hal::{
     Blocking,
     i2c::master::{
         AcknowledgeCheckFailedReason,
+        BusTimeout,
         Config,
         Error,
         I2c,
@@ -118,6 +119,30 @@ mod tests {
         assert_eq!(ctx.i2c.write(DUT_ADDRESS, &[]), Ok(()));
     }
 
+    #[test]
+    fn scan_finds_test_device(mut ctx: Context) {
+        let mut found = [0u8; 8];
+        let mut count = 0;
+        for address in ctx.i2c.scan(0x08..=0x77) {
+            found[count] = address;
+            count += 1;
+        }
+        assert!(found[..count].contains(&DUT_ADDRESS));
+        assert!(!found[..count].contains(&NON_EXISTENT_ADDRESS));
+
+        // Ranges are clamped to 7-bit addresses.
+        assert_eq!(ctx.i2c.scan(DUT_ADDRESS..).next(), Some(DUT_ADDRESS));
+        assert_eq!(ctx.i2c.scan(0x78..=0xFF).count(), 0);
+        assert_eq!(ctx.i2c.scan(DUT_ADDRESS..DUT_ADDRESS).count(), 0);
+
+        // The bus is idle after scanning.
+        let mut read_data = [0u8; 22];
+        ctx.i2c
+            .write_read(DUT_ADDRESS, READ_DATA_COMMAND, &mut read_data)
+            .unwrap();
+        assert_ne!(read_data, [0u8; 22]);
+    }
+
     #[test]
     fn test_read_cali(mut ctx: Context) {
         let mut read_data = [0u8; 22];
@@ -286,6 +311,35 @@ mod tests {
             .expect_err("Expected timeout error");
     }
 
+    #[test]
+    fn bus_timeout_when_scl_kept_low_and_recover_bus(mut ctx: Context) {
+        ctx.i2c
+            .apply_config(
+                &Config::default()
+                    .with_timeout(BusTimeout::Duration(time::Duration::from_millis(1))),
+            )
+            .unwrap();
+
+        esp_hal::gpio::InputSignal::I2CEXT0_SCL.connect_to(&esp_hal::gpio::Level::Low);
+
+        let mut read_data = [0u8; 22];
+        assert_eq!(
+            ctx.i2c
+                .write_read(DUT_ADDRESS, READ_DATA_COMMAND, &mut read_data),
+            Err(Error::Timeout)
+        );
+
+        // Release SCL and free the bus.
+        let (_, scl) = hil_test::i2c_pins!(unsafe { esp_hal::peripherals::Peripherals::steal() });
+        esp_hal::gpio::InputSignal::I2CEXT0_SCL.connect_to(&scl);
+        ctx.i2c.recover_bus();
+
+        ctx.i2c
+            .write_read(DUT_ADDRESS, READ_DATA_COMMAND, &mut read_data)
+            .unwrap();
+        assert_ne!(read_data, [0u8; 22]);
+    }
+
     #[test]
     #[cfg(i2c_master_has_fsm_timeouts)]
     async fn test_timeout_when_scl_kept_low_with_fsm_timeout(ctx: Context) {
@@ -316,6 +316,23 @@ mod tests {
         assert!(matches!(tx_transfer.push(&[0; 128]), Err(_)));
     }
 
+    #[test]
+    fn test_i2s_reports_achieved_sample_rate(ctx: Context) {
+        let requested = Rate::from_hz(44100);
+
+        let i2s = I2s::new(
+            ctx.i2s,
+            Standard::Philips,
+            DataFormat::Data16Channel16,
+            requested,
+            ctx.dma_channel,
+        );
+
+        // The dividers should get us within 0.1% of the requested rate.
+        let achieved = i2s.sample_rate().as_hz();
+        assert!(achieved.abs_diff(requested.as_hz()) * 1000 <= requested.as_hz());
+    }
+
     #[test]
     #[timeout(1)]
     fn test_i2s_read_too_late(ctx: Context) {
@@ -0,0 +1,103 @@
+//! LEDC tests
+
+//% CHIPS: esp32 esp32c2 esp32c3 esp32c6 esp32h2 esp32s2 esp32s3
+//% FEATURES: unstable
+
+#![no_std]
+#![no_main]
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+use esp_hal::{
+    handler,
+    ledc::{
+        LSGlobalClkSource,
+        Ledc,
+        LowSpeed,
+        channel::{self, ChannelIFace, FadeEndInterrupt},
+        timer::{self, Timer, TimerIFace},
+    },
+    time::{Duration, Instant, Rate},
+};
+use hil_test::mk_static;
+use portable_atomic::{AtomicU32, Ordering};
+
+static FADE_END: Mutex<Cell<Option<FadeEndInterrupt>>> = Mutex::new(Cell::new(None));
+static FADE_ENDS: AtomicU32 = AtomicU32::new(0);
+
+#[handler]
+fn fade_end() {
+    critical_section::with(|cs| {
+        if let Some(interrupt) = FADE_END.borrow(cs).get()
+            && interrupt.is_set()
+        {
+            FADE_ENDS.fetch_add(1, Ordering::Relaxed);
+            interrupt.clear();
+        }
+    });
+}
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fade_end_interrupt_fires_once_per_fade() {
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+        let (_, pin) = hil_test::common_test_pins!(peripherals);
+
+        let mut ledc = Ledc::new(peripherals.LEDC);
+        ledc.set_global_slow_clock(LSGlobalClkSource::APBClk);
+        ledc.set_interrupt_handler(fade_end);
+
+        let timer = mk_static!(
+            Timer<'static, LowSpeed>,
+            ledc.timer::<LowSpeed>(timer::Number::Timer0)
+        );
+        timer
+            .configure(timer::config::Config {
+                duty: timer::config::Duty::Duty5Bit,
+                clock_source: timer::LSClockSource::APBClk,
+                frequency: Rate::from_khz(24),
+            })
+            .unwrap();
+
+        let mut channel = ledc.channel(channel::Number::Channel0, pin);
+        channel
+            .configure(channel::config::Config {
+                timer,
+                duty_pct: 0,
+                pin_config: channel::config::PinConfig::PushPull,
+            })
+            .unwrap();
+
+        critical_section::with(|cs| FADE_END.borrow(cs).set(Some(channel.fade_end_interrupt())));
+
+        for expected in 1..=2 {
+            channel.start_duty_fade(0, 100, 50).unwrap();
+            assert!(channel.is_duty_fade_running());
+            // Configuring the channel has set a duty, which also raised the interrupt.
+            channel.listen_fade_end();
+
+            let start = Instant::now();
+            while FADE_ENDS.load(Ordering::Relaxed) < expected {
+                assert!(start.elapsed() < Duration::from_millis(500));
+            }
+
+            // Clearing the interrupt doesn't make the fade look like it's still running.
+            assert!(!channel.is_duty_fade_running());
+        }
+
+        channel.unlisten_fade_end();
+
+        // Without the interrupt enabled, the end of the fade is still reported.
+        channel.start_duty_fade(0, 100, 50).unwrap();
+        let start = Instant::now();
+        while channel.is_duty_fade_running() {
+            assert!(start.elapsed() < Duration::from_millis(500));
+        }
+        assert_eq!(FADE_ENDS.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,95 @@
+//! MCPWM tests
+
+//% CHIPS: esp32 esp32c6 esp32h2 esp32s3
+//% FEATURES: unstable
+
+#![no_std]
+#![no_main]
+
+use esp_hal::{
+    delay::Delay,
+    gpio::{Input, InputConfig, Level},
+    mcpwm::{
+        McPwm,
+        PeripheralClockConfig,
+        fault::{FaultAction, FaultMode, FaultResponse, FaultSource},
+        operator::{PWMStream, PwmPinConfig},
+        timer::PwmWorkingMode,
+    },
+    time::Rate,
+};
+use hil_test as _;
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fault_forces_output_low() {
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+        let (input_pin, output_pin) = hil_test::common_test_pins!(peripherals);
+        let input = Input::new(input_pin, InputConfig::default());
+        let delay = Delay::new();
+
+        #[cfg(not(esp32h2))]
+        let clock_cfg = PeripheralClockConfig::with_frequency(Rate::from_mhz(40)).unwrap();
+        #[cfg(esp32h2)]
+        let clock_cfg = PeripheralClockConfig::with_frequency(Rate::from_mhz(32)).unwrap();
+
+        let mut mcpwm = McPwm::new(peripherals.MCPWM0, clock_cfg);
+
+        mcpwm.operator0.set_timer(&mcpwm.timer0);
+        mcpwm.operator0.set_fault_response(
+            FaultResponse::new()
+                .with_mode(FaultSource::Fault0, FaultMode::OneShot)
+                .with_mode(FaultSource::Software, FaultMode::OneShot)
+                .with_action(PWMStream::PWMA, FaultAction::Low),
+        );
+        // The fault input is inactive.
+        mcpwm.fault0.enable(Level::Low, Level::High);
+
+        let mut pwm_pin = mcpwm
+            .operator0
+            .with_pin_a(output_pin, PwmPinConfig::UP_ACTIVE_HIGH);
+
+        let timer_clock_cfg = clock_cfg
+            .timer_clock_with_frequency(99, PwmWorkingMode::Increase, Rate::from_khz(20))
+            .unwrap();
+        mcpwm.timer0.start(timer_clock_cfg);
+
+        // The timestamp is never reached, so the output stays high.
+        pwm_pin.set_timestamp(100);
+        delay.delay_micros(200);
+        assert!(input.is_high());
+        assert!(!mcpwm.fault0.is_active());
+        assert!(!pwm_pin.is_one_shot_fault_active());
+
+        pwm_pin.trigger_one_shot_fault();
+        delay.delay_micros(200);
+        assert!(pwm_pin.is_one_shot_fault_active());
+        assert!(input.is_low());
+
+        pwm_pin.clear_one_shot_fault();
+        delay.delay_micros(200);
+        assert!(!pwm_pin.is_one_shot_fault_active());
+        assert!(input.is_high());
+
+        // The fault input becomes active.
+        mcpwm.fault0.enable(Level::High, Level::High);
+        delay.delay_micros(200);
+        assert!(mcpwm.fault0.is_active());
+        assert!(pwm_pin.is_one_shot_fault_active());
+        assert!(input.is_low());
+
+        // The fault stays latched after the input becomes inactive.
+        mcpwm.fault0.enable(Level::Low, Level::High);
+        delay.delay_micros(200);
+        assert!(!mcpwm.fault0.is_active());
+        assert!(input.is_low());
+
+        pwm_pin.clear_one_shot_fault();
+        delay.delay_micros(200);
+        assert!(input.is_high());
+    }
+}
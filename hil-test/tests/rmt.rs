@@ -281,4 +281,38 @@ mod tests {
             .configure_tx(NoPin, TxChannelConfig::default())
             .unwrap();
     }
+
+    #[test]
+    fn onewire_without_device() {
+        use esp_hal::rmt::onewire::{Error as OneWireError, OneWire};
+
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+        let pin = hil_test::unconnected_pin!(peripherals);
+
+        let rmt = Rmt::new(peripherals.RMT, FREQ).unwrap();
+
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32, esp32s2))] {
+                let rx_channel_creator = rmt.channel1;
+            } else if #[cfg(esp32s3)] {
+                let rx_channel_creator = rmt.channel4;
+            } else {
+                let rx_channel_creator = rmt.channel2;
+            }
+        };
+
+        // One tick per microsecond.
+        let mut bus = OneWire::new(rmt.channel0, rx_channel_creator, pin, DIV / 2).unwrap();
+
+        assert!(!bus.reset().unwrap());
+
+        // Nothing pulls the bus low, so every read slot returns a 1.
+        bus.write_bytes(&[0xCC, 0xBE]).unwrap();
+        let mut data = [0u8; 2];
+        bus.read_bytes(&mut data).unwrap();
+        assert_eq!(data, [0xFF, 0xFF]);
+
+        assert_eq!(bus.read_rom(), Err(OneWireError::NoDevice));
+        assert!(bus.search().next().is_none());
+    }
 }
@@ -0,0 +1,181 @@
+//! RTC Tests
+
+//% CHIPS: esp32 esp32c2 esp32c3 esp32c6 esp32h2 esp32s2 esp32s3
+//% FEATURES: unstable
+
+#![no_std]
+#![no_main]
+
+use hil_test as _;
+
+#[cfg(not(esp32h2))]
+#[esp_hal::handler]
+fn brownout_handler() {
+    panic!("Unexpected brownout interrupt");
+}
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3)]
+mod tests {
+    #[cfg(any(esp32c3, esp32s3))]
+    use esp_hal::peripherals::LPWR;
+    use esp_hal::{
+        clock::Clocks,
+        rtc_cntl::{ResetReason, Rtc, RtcSlowClock, SocResetReason, reset_reason},
+        system::Cpu,
+    };
+    #[cfg(not(any(esp32c2, esp32h2)))]
+    use esp_hal::{
+        rtc_cntl::{PeripheralDisabled, RetainedPeripheral},
+        timer::timg::TimerGroup,
+    };
+
+    #[test]
+    #[cfg(any(esp32c3, esp32s3))]
+    fn test_glitch_detection_enable_and_clear() {
+        let p = esp_hal::init(Default::default());
+        let mut rtc = Rtc::new(p.LPWR);
+
+        rtc.enable_glitch_detection();
+        assert!(LPWR::regs().pg_ctrl().read().power_glitch_en().bit_is_set());
+
+        // A stable supply must not trigger the detector.
+        rtc.clear_glitch_detected();
+        assert!(!rtc.glitch_detected());
+    }
+
+    #[test]
+    #[cfg(not(esp32h2))]
+    fn test_brownout_does_not_trigger_on_stable_supply() {
+        use esp_hal::rtc_cntl::{BrownoutAction, BrownoutLevel};
+
+        let p = esp_hal::init(Default::default());
+        let mut rtc = Rtc::new(p.LPWR);
+
+        rtc.configure_brownout(
+            BrownoutLevel::Level7,
+            BrownoutAction::Interrupt(super::brownout_handler),
+        );
+        esp_hal::delay::Delay::new().delay_millis(10);
+        assert!(!rtc.is_brownout_interrupt_set());
+
+        rtc.disable_brownout();
+    }
+
+    #[test]
+    fn test_current_time_survives_recalibration() {
+        let p = esp_hal::init(Default::default());
+        let mut rtc = Rtc::new(p.LPWR);
+        let delay = esp_hal::delay::Delay::new();
+
+        // 2025-01-01T00:00:00Z
+        rtc.set_current_time_secs(1_735_689_600);
+        assert_eq!(rtc.current_time_secs(), 1_735_689_600);
+
+        delay.delay_millis(100);
+        let before = rtc.current_time_us();
+        rtc.recalibrate_slow_clock().unwrap();
+        let after = rtc.current_time_us();
+        assert!(after >= before, "{} < {}", after, before);
+        assert!(after - before < 50_000, "{}", after - before);
+
+        // The calibrated RTC timer keeps up with the CPU clock.
+        let start = rtc.current_time_us();
+        delay.delay_millis(500);
+        let elapsed = rtc.current_time_us() - start;
+        assert!((495_000..520_000).contains(&elapsed), "{}", elapsed);
+    }
+
+    #[test]
+    fn test_slow_clock_source_is_calibrated() {
+        let p = esp_hal::init(Default::default());
+        let mut rtc = Rtc::new(p.LPWR);
+
+        // The default source is the internal RC_SLOW oscillator.
+        let rc_slow = rtc.rtc_slow_frequency();
+        assert!((50_000..200_000).contains(&rc_slow), "{}", rc_slow);
+
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32c6, esp32h2))] {
+                let (source, range) = (RtcSlowClock::RtcSlowClock32kRc, 20_000..45_000);
+            } else if #[cfg(any(esp32, esp32s2))] {
+                let (source, range) = (RtcSlowClock::RtcSlowClock8mD256, 20_000..45_000);
+            } else {
+                let (source, range) = (RtcSlowClock::RtcSlowClock8mD256, 50_000..90_000);
+            }
+        }
+
+        rtc.set_slow_clock_source(source).unwrap();
+        let frequency = rtc.rtc_slow_frequency();
+        assert!(range.contains(&frequency), "{}", frequency);
+
+        // Switching back restores the calibration.
+        rtc.set_slow_clock_source(RtcSlowClock::RtcSlowClockRcSlow)
+            .unwrap();
+        let frequency = rtc.rtc_slow_frequency();
+        assert!(frequency.abs_diff(rc_slow) < rc_slow / 20, "{}", frequency);
+    }
+
+    #[test]
+    fn test_reset_reason_is_known() {
+        let p = esp_hal::init(Default::default());
+        let rtc = Rtc::new(p.LPWR);
+
+        // Whatever the test runner used to reset the chip, the cause is one that esp-hal knows
+        // about, and the portable reason agrees with the chip-specific one.
+        let reason = rtc.reset_reason(Cpu::ProCpu);
+        assert!(!matches!(reason, ResetReason::Unknown(_)), "{:?}", reason);
+        assert_eq!(
+            reset_reason(Cpu::ProCpu) == Some(SocResetReason::CoreDeepSleep),
+            reason == ResetReason::DeepSleep
+        );
+    }
+
+    #[test]
+    fn test_rc_fast_clock_is_recalibrated() {
+        let _p = esp_hal::init(Default::default());
+
+        assert_eq!(Clocks::rc_fast_deviation_ppm(), 0);
+        let nominal = Clocks::rc_fast_clock().as_hz();
+
+        // The oscillator is trimmed to be within a few percent of the nominal
+        // frequency.
+        let deviation = Clocks::recalibrate_rc().unwrap();
+        assert!(deviation.abs() < 100_000, "{}", deviation);
+        assert_eq!(Clocks::rc_fast_deviation_ppm(), deviation);
+
+        let measured = Clocks::rc_fast_clock().as_hz();
+        assert!(measured.abs_diff(nominal) < nominal / 10, "{}", measured);
+
+        // The temperature doesn't change between two back-to-back measurements.
+        let deviation = Clocks::recalibrate_rc().unwrap();
+        assert!(deviation.abs() < 2_000, "{}", deviation);
+    }
+
+    #[test]
+    #[cfg(not(any(esp32c2, esp32h2)))]
+    fn test_retain_peripheral_config() {
+        let p = esp_hal::init(Default::default());
+        let mut rtc = Rtc::new(p.LPWR);
+
+        // TIMG1 is disabled until its driver is created.
+        assert_eq!(
+            rtc.retain_peripheral_config(RetainedPeripheral::Timg1),
+            Err(PeripheralDisabled)
+        );
+        assert_eq!(
+            rtc.restore_peripheral_config(RetainedPeripheral::Timg1),
+            Err(PeripheralDisabled)
+        );
+
+        let _timg1 = TimerGroup::new(p.TIMG1);
+        rtc.retain_peripheral_config(RetainedPeripheral::Timg1)
+            .unwrap();
+
+        // The chip did not wake up from deep sleep, there is nothing to restore.
+        assert_eq!(
+            rtc.restore_peripheral_config(RetainedPeripheral::Timg1),
+            Ok(false)
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Sigma-delta modulator test
+
+//% CHIPS: esp32 esp32c3 esp32c6 esp32h2 esp32s2 esp32s3
+//% FEATURES: unstable
+
+#![no_std]
+#![no_main]
+
+use esp_hal::{
+    gpio::{AnyPin, Input, InputConfig, Pin, sdm::SigmaDelta},
+    peripherals::GPIO_SD,
+};
+use hil_test as _;
+
+struct Context {
+    gpio_sd: GPIO_SD<'static>,
+    test_gpio1: AnyPin<'static>,
+    test_gpio2: AnyPin<'static>,
+}
+
+/// Samples the input and returns how many of the samples were high.
+fn count_high(input: &Input<'_>, samples: u32) -> u32 {
+    (0..samples).filter(|_| input.is_high()).count() as u32
+}
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: u32 = 10_000;
+
+    #[init]
+    fn init() -> Context {
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+
+        let (gpio1, gpio2) = hil_test::common_test_pins!(peripherals);
+
+        Context {
+            gpio_sd: peripherals.GPIO_SD,
+            test_gpio1: gpio1.degrade(),
+            test_gpio2: gpio2.degrade(),
+        }
+    }
+
+    #[test]
+    fn duty_is_read_back(ctx: Context) {
+        let sdm = SigmaDelta::new(ctx.gpio_sd);
+        let mut channel = sdm.channel1;
+
+        for duty in [i8::MIN, -1, 0, 42, i8::MAX] {
+            channel.set_duty(duty);
+            assert_eq!(channel.duty(), duty);
+        }
+    }
+
+    #[test]
+    fn pulse_density_follows_duty(ctx: Context) {
+        let sdm = SigmaDelta::new(ctx.gpio_sd);
+        let mut channel = sdm.channel0.with_pin(ctx.test_gpio1);
+        channel.set_prescale(u8::MAX);
+
+        let input = Input::new(ctx.test_gpio2, InputConfig::default());
+
+        channel.set_duty(i8::MIN);
+        assert_eq!(count_high(&input, SAMPLES), 0);
+
+        // The samples aren't synchronized to the modulator clock, so only
+        // check the density roughly.
+        channel.set_duty(0);
+        let high = count_high(&input, SAMPLES);
+        assert!(
+            (SAMPLES * 3 / 10..SAMPLES * 7 / 10).contains(&high),
+            "{} high samples",
+            high
+        );
+
+        channel.set_duty(i8::MAX);
+        let high = count_high(&input, SAMPLES);
+        assert!(high > SAMPLES * 9 / 10, "{} high samples", high);
+    }
+}
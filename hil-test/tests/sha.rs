@@ -1,11 +1,13 @@
 //! SHA Test
 
 //% CHIPS: esp32 esp32c2 esp32c3 esp32c6 esp32h2 esp32s2 esp32s3
-//% FEATURES: unstable
+//% FEATURES: unstable esp-hal/sha-kdf
 
 #![no_std]
 #![no_main]
 
+use core::num::NonZeroU32;
+
 use digest::{Digest, Update};
 #[cfg(not(feature = "esp32"))]
 use esp_hal::sha::Sha224;
@@ -16,14 +18,39 @@ use esp_hal::sha::{Sha512_224, Sha512_256};
 use esp_hal::{
     clock::CpuClock,
     rng::{Rng, TrngSource},
-    sha::{Sha, Sha1, Sha256, ShaAlgorithm, ShaDigest},
+    sha::{
+        FinishError,
+        KdfError,
+        MerkleError,
+        MerkleHasher,
+        Sha,
+        Sha1,
+        Sha256,
+        ShaAlgorithm,
+        ShaDigest,
+        ShaHmac,
+        VerifyError,
+    },
 };
+use hex_literal::hex;
 use hil_test as _;
 use nb::block;
 
 /// Dummy data used to feed the hasher.
 const SOURCE_DATA: &[u8] = &[b'a'; 258];
 
+/// [`SOURCE_DATA`], followed by its SHA-256 digest. Being a `static`, it is stored in flash.
+static SIGNED_IMAGE: [u8; 258 + 32] = {
+    let digest = hex!("1ebbdab335e054015f0fc17f62770609723d92c640b65ba9974d666c364a3a63");
+    let mut image = [b'a'; 258 + 32];
+    let mut i = 0;
+    while i < digest.len() {
+        image[258 + i] = digest[i];
+        i += 1;
+    }
+    image
+};
+
 #[track_caller]
 fn assert_sw_hash<D: Digest>(input: &[u8], expected_output: &[u8]) {
     let mut hasher = D::new();
@@ -33,12 +60,55 @@ fn assert_sw_hash<D: Digest>(input: &[u8], expected_output: &[u8]) {
     hil_test::assert_eq!(expected_output, &soft_result[..]);
 }
 
+/// Wraps the DMA driver so that its first `failures` transfers stop early.
+#[cfg(sha_dma)]
+struct Flaky<'a, 'd> {
+    sha: &'a mut esp_hal::sha::dma::ShaDma<'d>,
+    failures: usize,
+    recoveries: usize,
+}
+
+#[cfg(sha_dma)]
+impl esp_hal::sha::dma::ShaDmaOps for Flaky<'_, '_> {
+    fn hash_once<A: ShaAlgorithm>(
+        &mut self,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), esp_hal::sha::Error> {
+        if self.failures > 0 {
+            self.failures -= 1;
+            self.abort_transfer();
+            return Err(esp_hal::sha::Error::DmaTransferIncomplete);
+        }
+        self.sha.hash_once::<A>(input, output)
+    }
+
+    fn abort_transfer(&mut self) {
+        self.sha.abort_transfer();
+    }
+
+    fn recover(&mut self) {
+        self.recoveries += 1;
+        self.sha.recover();
+    }
+}
+
 fn hash_sha<S: ShaAlgorithm>(sha: &mut Sha<'static>, mut input: &[u8], output: &mut [u8]) {
     let mut digest = sha.start::<S>();
     while !input.is_empty() {
         input = block!(digest.update(input)).unwrap();
     }
-    block!(digest.finish(output)).unwrap();
+    digest.finish(output).unwrap();
+}
+
+/// Compares the hardware result against a known-answer vector, which is
+/// independent of the software implementation's byte order.
+#[track_caller]
+fn assert_known_answer<S: ShaAlgorithm>(sha: &mut Sha<'static>, input: &[u8], expected: &[u8]) {
+    let mut output = [0u8; 64];
+    let output = &mut output[..expected.len()];
+    hash_sha::<S>(sha, input, output);
+    hil_test::assert_eq!(expected, output);
 }
 
 fn hash_digest<'a, S: ShaAlgorithm>(sha: &'a mut Sha<'static>, input: &[u8], output: &mut [u8]) {
@@ -47,6 +117,28 @@ fn hash_digest<'a, S: ShaAlgorithm>(sha: &'a mut Sha<'static>, input: &[u8], out
     output.copy_from_slice(&digest::FixedOutput::finalize_fixed(hasher));
 }
 
+/// Software reference of the RFC 6962 Merkle Tree Hash over SHA-256.
+fn merkle_root_sw(leaves: &[&[u8]]) -> [u8; 32] {
+    match leaves {
+        [] => sha2::Sha256::digest([]).into(),
+        [leaf] => sha2::Sha256::new()
+            .chain_update([0x00])
+            .chain_update(leaf)
+            .finalize()
+            .into(),
+        _ => {
+            // Split at the largest power of two smaller than the number of leaves.
+            let split = 1 << (usize::BITS - 1 - (leaves.len() - 1).leading_zeros());
+            sha2::Sha256::new()
+                .chain_update([0x01])
+                .chain_update(merkle_root_sw(&leaves[..split]))
+                .chain_update(merkle_root_sw(&leaves[split..]))
+                .finalize()
+                .into()
+        }
+    }
+}
+
 /// A simple test using the Sha trait. This will compare the result with a
 /// software implementation.
 #[track_caller]
@@ -154,9 +246,20 @@ fn with_random_data(
     }
 }
 
+#[cfg(sha_dma)]
+cfg_if::cfg_if! {
+    if #[cfg(esp32s2)] {
+        type DmaChannel<'d> = esp_hal::peripherals::DMA_CRYPTO<'d>;
+    } else {
+        type DmaChannel<'d> = esp_hal::peripherals::DMA_CH0<'d>;
+    }
+}
+
 pub struct Context {
     _rng_source: TrngSource<'static>,
     sha: Sha<'static>,
+    #[cfg(sha_dma)]
+    dma_channel: DmaChannel<'static>,
 }
 
 #[cfg(test)]
@@ -169,10 +272,871 @@ mod tests {
         let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
         let peripherals = esp_hal::init(config);
 
+        cfg_if::cfg_if! {
+            if #[cfg(all(sha_dma, esp32s2))] {
+                let dma_channel = peripherals.DMA_CRYPTO;
+            } else if #[cfg(sha_dma)] {
+                let dma_channel = peripherals.DMA_CH0;
+            }
+        }
+
         Context {
             _rng_source: TrngSource::new(peripherals.RNG, peripherals.ADC1),
             sha: Sha::new(peripherals.SHA),
+            #[cfg(sha_dma)]
+            dma_channel,
+        }
+    }
+
+    #[test]
+    fn test_fips_180_vectors(mut ctx: Context) {
+        // FIPS 180-4 examples: a one-block and a two-block message. The
+        // digests are in the canonical big-endian byte order.
+        const ONE_BLOCK: &[u8] = b"abc";
+        const TWO_BLOCK: &[u8] = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+
+        assert_known_answer::<Sha1>(
+            &mut ctx.sha,
+            ONE_BLOCK,
+            &hex!("a9993e364706816aba3e25717850c26c9cd0d89d"),
+        );
+        assert_known_answer::<Sha1>(
+            &mut ctx.sha,
+            TWO_BLOCK,
+            &hex!("84983e441c3bd26ebaae4aa1f95129e5e54670f1"),
+        );
+
+        #[cfg(not(feature = "esp32"))]
+        {
+            assert_known_answer::<Sha224>(
+                &mut ctx.sha,
+                ONE_BLOCK,
+                &hex!("23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"),
+            );
+            assert_known_answer::<Sha224>(
+                &mut ctx.sha,
+                TWO_BLOCK,
+                &hex!("75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"),
+            );
         }
+
+        assert_known_answer::<Sha256>(
+            &mut ctx.sha,
+            ONE_BLOCK,
+            &hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        );
+        assert_known_answer::<Sha256>(
+            &mut ctx.sha,
+            TWO_BLOCK,
+            &hex!("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+        );
+
+        #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+        {
+            assert_known_answer::<Sha384>(
+                &mut ctx.sha,
+                ONE_BLOCK,
+                &hex!(
+                    "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed"
+                    "8086072ba1e7cc2358baeca134c825a7"
+                ),
+            );
+            assert_known_answer::<Sha384>(
+                &mut ctx.sha,
+                TWO_BLOCK,
+                &hex!(
+                    "3391fdddfc8dc7393707a65b1b4709397cf8b1d162af05abfe8f450de5f36bc6"
+                    "b0455a8520bc4e6f5fe95b1fe3c8452b"
+                ),
+            );
+            assert_known_answer::<Sha512>(
+                &mut ctx.sha,
+                ONE_BLOCK,
+                &hex!(
+                    "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a"
+                    "2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+                ),
+            );
+            assert_known_answer::<Sha512>(
+                &mut ctx.sha,
+                TWO_BLOCK,
+                &hex!(
+                    "204a8fc6dda82f0a0ced7beb8e08a41657c16ef468b228a8279be331a703c335"
+                    "96fd15c13b1b07f9aa1d3bea57789ca031ad85c7a71dd70354ec631238ca3445"
+                ),
+            );
+        }
+
+        #[cfg(any(feature = "esp32s2", feature = "esp32s3"))]
+        {
+            assert_known_answer::<Sha512_224>(
+                &mut ctx.sha,
+                ONE_BLOCK,
+                &hex!("4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa"),
+            );
+            assert_known_answer::<Sha512_224>(
+                &mut ctx.sha,
+                TWO_BLOCK,
+                &hex!("e5302d6d54bb242275d1e7622d68df6eb02dedd13f564c13dbda2174"),
+            );
+            assert_known_answer::<Sha512_256>(
+                &mut ctx.sha,
+                ONE_BLOCK,
+                &hex!("53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"),
+            );
+            assert_known_answer::<Sha512_256>(
+                &mut ctx.sha,
+                TWO_BLOCK,
+                &hex!("bde8e1f9f19bb9fd3406c90ec6bc47bd36d8ada9f11880dbc8a22a7078b6a461"),
+            );
+        }
+    }
+
+    #[test]
+    fn test_hmac(mut ctx: Context) {
+        fn sign<A: ShaAlgorithm, const N: usize>(
+            sha: &mut Sha<'static>,
+            key: &[u8],
+            message: &[u8],
+        ) -> [u8; N] {
+            ShaHmac::<A>::new(sha, key).unwrap().sign(message).unwrap()
+        }
+
+        // RFC 2202, test case 2
+        let mac: [u8; 20] = sign::<Sha1, _>(&mut ctx.sha, b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(mac, hex!("effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"));
+
+        // RFC 4231, test case 2
+        let mac: [u8; 32] =
+            sign::<Sha256, _>(&mut ctx.sha, b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            mac,
+            hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+
+        // RFC 4231, test case 6 - the key is longer than the block size
+        let mac: [u8; 32] = sign::<Sha256, _>(
+            &mut ctx.sha,
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        assert_eq!(
+            mac,
+            hex!("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+        );
+
+        // RFC 4231, test case 5 - truncated output
+        let mac: [u8; 16] = sign::<Sha256, _>(&mut ctx.sha, &[0x0c; 20], b"Test With Truncation");
+        assert_eq!(mac, hex!("a3b6167473100ee06e0c796c2955552b"));
+    }
+
+    #[test]
+    fn test_hmac_reused_for_several_messages(mut ctx: Context) {
+        let mut hmac = ShaHmac::<Sha256>::new(&mut ctx.sha, b"Jefe").unwrap();
+
+        // The saved state after the key blocks must not be modified by signing.
+        for _ in 0..3 {
+            // RFC 4231, test case 2
+            let mac: [u8; 32] = hmac.sign(b"what do ya want for nothing?").unwrap();
+            assert_eq!(
+                mac,
+                hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+            );
+
+            // A message that spans more than one block.
+            let mac: [u8; 32] = hmac.sign(&[0xdd; 100]).unwrap();
+            assert_eq!(
+                mac,
+                hex!("a2fc72c862cb1d0142440a2f4dfdd0e605f5b90737b697cc060b0a36ba11bcd0")
+            );
+        }
+    }
+
+    #[test]
+    fn test_derive_key_rfc5869(mut ctx: Context) {
+        // RFC 5869, Appendix A, test cases 1 to 3
+        let mut okm = [0u8; 42];
+        ctx.sha
+            .derive_key(
+                &hex!("000102030405060708090a0b0c"),
+                &hex!("f0f1f2f3f4f5f6f7f8f9"),
+                &[0x0b; 22],
+                &mut okm,
+            )
+            .unwrap();
+        assert_eq!(
+            okm,
+            hex!(
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf"
+                "34007208d5b887185865"
+            )
+        );
+
+        let ikm: [u8; 80] = core::array::from_fn(|i| i as u8);
+        let salt: [u8; 80] = core::array::from_fn(|i| 0x60 + i as u8);
+        let info: [u8; 80] = core::array::from_fn(|i| 0xb0 + i as u8);
+        let mut okm = [0u8; 82];
+        ctx.sha.derive_key(&salt, &info, &ikm, &mut okm).unwrap();
+        assert_eq!(
+            okm,
+            hex!(
+                "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c"
+                "59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71"
+                "cc30c58179ec3e87c14c01d5c1f3434f1d87"
+            )
+        );
+
+        let mut okm = [0u8; 42];
+        ctx.sha.derive_key(&[], &[], &[0x0b; 22], &mut okm).unwrap();
+        assert_eq!(
+            okm,
+            hex!(
+                "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d"
+                "9d201395faa4b61a96c8"
+            )
+        );
+
+        // HKDF can derive at most 255 blocks.
+        let mut okm = [0u8; 255 * 32 + 1];
+        assert_eq!(
+            ctx.sha.derive_key(&[], &[], &[0x0b; 22], &mut okm),
+            Err(KdfError::OutputTooLong)
+        );
+    }
+
+    #[test]
+    fn test_hkdf_extract_and_expand(mut ctx: Context) {
+        // RFC 5869, Appendix A, test case 1
+        let prk: [u8; 32] = ctx
+            .sha
+            .hkdf_extract::<Sha256, _>(&hex!("000102030405060708090a0b0c"), &[0x0b; 22])
+            .unwrap();
+        assert_eq!(
+            prk,
+            hex!("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")
+        );
+
+        let mut okm = [0u8; 42];
+        ctx.sha
+            .hkdf_expand::<Sha256>(&prk, &hex!("f0f1f2f3f4f5f6f7f8f9"), &mut okm)
+            .unwrap();
+        assert_eq!(
+            okm,
+            hex!(
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf"
+                "34007208d5b887185865"
+            )
+        );
+
+        // RFC 5869, Appendix A, test case 4
+        let prk: [u8; 20] = ctx
+            .sha
+            .hkdf_extract::<Sha1, _>(&hex!("000102030405060708090a0b0c"), &[0x0b; 11])
+            .unwrap();
+        assert_eq!(prk, hex!("9b6c18c432a7bf8f0e71c8eb88f4b30baa2ba243"));
+
+        let mut okm = [0u8; 42];
+        ctx.sha
+            .hkdf_expand::<Sha1>(&prk, &hex!("f0f1f2f3f4f5f6f7f8f9"), &mut okm)
+            .unwrap();
+        assert_eq!(
+            okm,
+            hex!(
+                "085a01ea1b10f36933068b56efa5ad81a4f14b822f5b091568a9cdd4f155fda2"
+                "c22e422478d305f3f896"
+            )
+        );
+
+        // The limit depends on the digest length.
+        let mut okm = [0u8; 255 * 20 + 1];
+        assert_eq!(
+            ctx.sha.hkdf_expand::<Sha1>(&prk, &[], &mut okm),
+            Err(KdfError::OutputTooLong)
+        );
+
+        // The pseudorandom key must be at least as long as the digest.
+        let mut okm = [0u8; 42];
+        assert_eq!(
+            ctx.sha.hkdf_expand::<Sha1>(&prk[..19], &[], &mut okm),
+            Err(KdfError::PrkTooShort)
+        );
+    }
+
+    #[test]
+    fn test_pbkdf2_rfc6070(mut ctx: Context) {
+        fn pbkdf2<A: ShaAlgorithm, const N: usize>(
+            sha: &mut Sha<'static>,
+            password: &[u8],
+            salt: &[u8],
+            iterations: u32,
+        ) -> [u8; N] {
+            let mut output = [0; N];
+            sha.pbkdf2_hmac::<A>(
+                password,
+                salt,
+                NonZeroU32::new(iterations).unwrap(),
+                &mut output,
+            )
+            .unwrap();
+            output
+        }
+
+        let dk: [u8; 20] = pbkdf2::<Sha1, _>(&mut ctx.sha, b"password", b"salt", 1);
+        assert_eq!(dk, hex!("0c60c80f961f0e71f3a9b524af6012062fe037a6"));
+
+        let dk: [u8; 20] = pbkdf2::<Sha1, _>(&mut ctx.sha, b"password", b"salt", 2);
+        assert_eq!(dk, hex!("ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957"));
+
+        let dk: [u8; 20] = pbkdf2::<Sha1, _>(&mut ctx.sha, b"password", b"salt", 4096);
+        assert_eq!(dk, hex!("4b007901b765489abead49d926f721d065a429c1"));
+
+        // The output spans more than one block.
+        let dk: [u8; 25] = pbkdf2::<Sha1, _>(
+            &mut ctx.sha,
+            b"passwordPASSWORDpassword",
+            b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+            4096,
+        );
+        assert_eq!(
+            dk,
+            hex!("3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038")
+        );
+
+        let dk: [u8; 16] = pbkdf2::<Sha1, _>(&mut ctx.sha, b"pass\0word", b"sa\0lt", 4096);
+        assert_eq!(dk, hex!("56fa6aa75548099dcc37d7f03425e0c3"));
+
+        // Not from RFC 6070, checked against a software implementation.
+        let dk: [u8; 40] = pbkdf2::<Sha256, _>(&mut ctx.sha, b"password", b"salt", 2);
+        assert_eq!(
+            dk,
+            hex!(
+                "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"
+                "830651afcb5c862f"
+            )
+        );
+    }
+
+    #[test]
+    fn test_verify_signed_region(mut ctx: Context) {
+        let (region, signature_block) = SIGNED_IMAGE.split_at(SOURCE_DATA.len());
+        assert_eq!(
+            ctx.sha
+                .verify_signed_region::<Sha256>(region, signature_block),
+            Ok(true)
+        );
+
+        // Tamper with a copy of the region.
+        let mut tampered = [0u8; 258];
+        tampered.copy_from_slice(region);
+        tampered[100] ^= 1;
+        assert_eq!(
+            ctx.sha
+                .verify_signed_region::<Sha256>(&tampered, signature_block),
+            Ok(false)
+        );
+
+        // The signature block must not be part of the signed region.
+        assert_eq!(
+            ctx.sha
+                .verify_signed_region::<Sha256>(&SIGNED_IMAGE, signature_block),
+            Err(VerifyError::SignatureInRegion)
+        );
+        assert_eq!(
+            ctx.sha
+                .verify_signed_region::<Sha256>(region, &signature_block[..31]),
+            Err(VerifyError::SignatureBlockTooShort)
+        );
+    }
+
+    #[test]
+    fn test_finish_verify(mut ctx: Context) {
+        let expected = &SIGNED_IMAGE[SOURCE_DATA.len()..];
+
+        let mut digest = ctx.sha.start::<Sha256>();
+        digest.update_iter(SOURCE_DATA.iter().copied()).unwrap();
+        assert_eq!(digest.finish_verify(expected), Ok(true));
+
+        let mut tampered = [0u8; 32];
+        tampered.copy_from_slice(expected);
+        tampered[31] ^= 1;
+        let mut digest = ctx.sha.start::<Sha256>();
+        digest.update_iter(SOURCE_DATA.iter().copied()).unwrap();
+        assert_eq!(digest.finish_verify(&tampered), Ok(false));
+
+        // The expected digest must be as long as the algorithm's digest.
+        let mut digest = ctx.sha.start::<Sha256>();
+        digest.update_iter(SOURCE_DATA.iter().copied()).unwrap();
+        assert_eq!(
+            digest.finish_verify(&expected[..20]),
+            Err(VerifyError::WrongDigestLength)
+        );
+    }
+
+    #[test]
+    fn test_finish_with_total_len(mut ctx: Context) {
+        let mut expected = [0u8; 32];
+        let mut digest = ctx.sha.start::<Sha256>();
+        digest.update_iter(SOURCE_DATA.iter().copied()).unwrap();
+        let digest = digest.finish(&mut expected).unwrap();
+
+        // Feed the data in chunks, like a streaming pipeline would.
+        let mut output = [0u8; 32];
+        let mut digest = digest.reset();
+        let mut processed = 0;
+        for chunk in SOURCE_DATA.chunks(64) {
+            digest.update_iter(chunk.iter().copied()).unwrap();
+            processed += chunk.len() as u64;
+            assert_eq!(digest.bytes_processed(), processed);
+        }
+        let digest = digest
+            .finish_with_total_len(&mut output, SOURCE_DATA.len())
+            .unwrap();
+        assert_eq!(output, expected);
+
+        // A lost chunk is reported.
+        let mut digest = digest.reset();
+        for chunk in SOURCE_DATA.chunks(64).skip(1) {
+            digest.update_iter(chunk.iter().copied()).unwrap();
+        }
+        let result = digest.finish_with_total_len(&mut output, SOURCE_DATA.len());
+        assert!(matches!(
+            result,
+            Err(FinishError::LengthMismatch {
+                expected,
+                actual,
+            }) if expected == SOURCE_DATA.len() && actual == SOURCE_DATA.len() - 64
+        ));
+    }
+
+    #[test]
+    #[cfg(not(esp32))]
+    fn test_peek_state(mut ctx: Context) {
+        let mut expected = [0u8; 32];
+        let mut digest = ctx.sha.start::<Sha256>();
+        digest.update_iter(SOURCE_DATA.iter().copied()).unwrap();
+        let digest = digest.finish(&mut expected).unwrap();
+
+        // The chaining value after compressing a single block of `a`s.
+        let mut digest = digest.reset();
+        digest
+            .update_iter(SOURCE_DATA[..64].iter().copied())
+            .unwrap();
+        let mut state = [0u8; 64];
+        let len = block!(digest.peek_state(&mut state)).unwrap();
+        assert_eq!(len, 32);
+        assert_eq!(
+            state[..32],
+            hex!("df5bb81ce81e0626fb45a8944fd40f31b25e6816d6d499c1ab90492900635e66")
+        );
+
+        // Peeking doesn't disturb the digest.
+        digest
+            .update_iter(SOURCE_DATA[64..].iter().copied())
+            .unwrap();
+        let mut output = [0u8; 32];
+        digest.finish(&mut output).unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_merkle_root_matches_reference(mut ctx: Context) {
+        const LEAF_SIZE: usize = 64;
+
+        let mut data = [0u8; 7 * LEAF_SIZE];
+        Rng::new().read(&mut data);
+
+        // Power of two leaf counts, and odd nodes on different levels.
+        for leaf_count in [0, 1, 2, 3, 4, 5, 7] {
+            let mut leaves = [&[][..]; 7];
+            for (leaf, chunk) in leaves.iter_mut().zip(data.chunks(LEAF_SIZE)) {
+                *leaf = chunk;
+            }
+            let leaves = &leaves[..leaf_count];
+
+            let mut hasher = MerkleHasher::<Sha256>::new(&mut ctx.sha, LEAF_SIZE);
+            for leaf in leaves {
+                hasher.push_leaf(leaf).unwrap();
+            }
+            assert_eq!(hasher.leaf_count(), leaf_count);
+            let root: [u8; 32] = hasher.root().unwrap();
+
+            assert_eq!(root, merkle_root_sw(leaves), "{} leaves", leaf_count);
+        }
+
+        // Only the last leaf may be shorter.
+        let leaves = [&data[..LEAF_SIZE], &data[LEAF_SIZE..][..10]];
+        let mut hasher = MerkleHasher::<Sha256>::new(&mut ctx.sha, LEAF_SIZE);
+        for leaf in leaves {
+            hasher.push_leaf(leaf).unwrap();
+        }
+        assert_eq!(
+            hasher.push_leaf(&data[..LEAF_SIZE]),
+            Err(MerkleError::LeafSizeMismatch)
+        );
+        let root: [u8; 32] = hasher.root().unwrap();
+        assert_eq!(root, merkle_root_sw(&leaves));
+
+        let mut hasher = MerkleHasher::<Sha256>::new(&mut ctx.sha, LEAF_SIZE);
+        assert_eq!(
+            hasher.push_leaf(&data[..LEAF_SIZE + 1]),
+            Err(MerkleError::LeafSizeMismatch)
+        );
+    }
+
+    #[test]
+    fn test_reset_after_finish(mut ctx: Context) {
+        let mut first = [0u8; 32];
+        let mut second = [0u8; 32];
+
+        let mut digest = ctx.sha.start::<Sha256>();
+        block!(digest.update(b"HELLO, ")).unwrap();
+        let finished = digest.finish(&mut first).unwrap();
+
+        // A reset digest must not carry anything over from the finished hash.
+        let mut digest = finished.reset();
+        let mut remaining = SOURCE_DATA;
+        while !remaining.is_empty() {
+            remaining = block!(digest.update(remaining)).unwrap();
+        }
+        digest.finish(&mut second).unwrap();
+
+        assert_sw_hash::<sha2::Sha256>(b"HELLO, ", &first);
+        assert_sw_hash::<sha2::Sha256>(SOURCE_DATA, &second);
+    }
+
+    #[test]
+    fn test_drop_clears_registers(ctx: Context) {
+        fn registers_are_clear() -> bool {
+            let regs = esp_hal::peripherals::SHA::regs();
+            cfg_if::cfg_if! {
+                if #[cfg(esp32)] {
+                    regs.text_iter().all(|reg| reg.read().bits() == 0)
+                } else {
+                    regs.m_mem_iter().all(|reg| reg.read().bits() == 0)
+                        && regs.h_mem_iter().all(|reg| reg.read().bits() == 0)
+                }
+            }
+        }
+
+        // A second driver keeps the peripheral clocked, so that the registers can be read
+        // after the first one is dropped.
+        let _sha = Sha::new(unsafe { esp_hal::peripherals::SHA::steal() });
+
+        let mut sha = ctx.sha;
+        let mut output = [0u8; 32];
+        hash_sha::<Sha256>(&mut sha, SOURCE_DATA, &mut output);
+        assert!(!registers_are_clear());
+
+        core::mem::drop(sha);
+        assert!(registers_are_clear());
+    }
+
+    #[test]
+    fn test_update_iter_matches_update(mut ctx: Context) {
+        // Cover lengths that are not multiples of the word size, and that span more
+        // than one message block.
+        for len in [0, 1, 3, 5, 63, 64, 65, 130, SOURCE_DATA.len()] {
+            let input = &SOURCE_DATA[..len];
+
+            let mut expected = [0u8; 32];
+            hash_sha::<Sha256>(&mut ctx.sha, input, &mut expected);
+
+            let mut output = [0u8; 32];
+            let mut digest = ctx.sha.start::<Sha256>();
+            digest.update_iter(input.iter().copied()).unwrap();
+            digest.finish(&mut output).unwrap();
+
+            assert_eq!(output, expected);
+        }
+
+        // Mix slices and iterators in a single hash.
+        let mut output = [0u8; 32];
+        let mut digest = ctx.sha.start::<Sha256>();
+        block!(digest.update(&SOURCE_DATA[..7])).unwrap();
+        digest
+            .update_iter(SOURCE_DATA[7..100].iter().copied())
+            .unwrap();
+        let mut remaining = &SOURCE_DATA[100..];
+        while !remaining.is_empty() {
+            remaining = block!(digest.update(remaining)).unwrap();
+        }
+        digest.finish(&mut output).unwrap();
+
+        assert_sw_hash::<sha2::Sha256>(SOURCE_DATA, &output);
+    }
+
+    #[test]
+    fn test_update_words_matches_update(mut ctx: Context) {
+        let mut words = [0u32; 64];
+        let mut bytes = [0u8; 256];
+        for (i, word) in words.iter_mut().enumerate() {
+            *word = (i as u32).wrapping_mul(0x9E37_79B9);
+            bytes[i * 4..][..4].copy_from_slice(&word.to_ne_bytes());
+        }
+
+        // Cover partial, single and multiple message blocks.
+        for len in [0, 1, 15, 16, 17, 64] {
+            let mut output = [0u8; 32];
+            let mut digest = ctx.sha.start::<Sha256>();
+            let mut remaining = &words[..len];
+            while !remaining.is_empty() {
+                remaining = block!(digest.update_words(remaining)).unwrap();
+            }
+            digest.finish(&mut output).unwrap();
+
+            assert_sw_hash::<sha2::Sha256>(&bytes[..len * 4], &output);
+        }
+
+        // Words following a partial word go through the byte path.
+        let mut output = [0u8; 32];
+        let mut digest = ctx.sha.start::<Sha256>();
+        block!(digest.update(&bytes[..3])).unwrap();
+        assert!(block!(digest.update_words(&words[1..])).unwrap().is_empty());
+        digest.finish(&mut output).unwrap();
+
+        let mut input = [0u8; 255];
+        input[..3].copy_from_slice(&bytes[..3]);
+        input[3..].copy_from_slice(&bytes[4..]);
+        assert_sw_hash::<sha2::Sha256>(&input, &output);
+    }
+
+    #[test]
+    #[cfg(sha_dma)]
+    fn test_sha_dma_oneshot(ctx: Context) {
+        // DMA source data must be in RAM. The length is deliberately not a multiple of the
+        // block size, so that the tail is processed by the CPU.
+        let mut input = [0u8; 4096 + 13];
+        Rng::new().read(&mut input);
+
+        let mut sha = ctx.sha.with_dma(ctx.dma_channel);
+
+        let output: [u8; 20] = sha.hash_oneshot::<Sha1, _>(&input).unwrap();
+        assert_sw_hash::<sha1::Sha1>(&input, &output);
+
+        let output: [u8; 32] = sha.hash_oneshot::<Sha256, _>(&input).unwrap();
+        assert_sw_hash::<sha2::Sha256>(&input, &output);
+
+        let output: [u8; 32] = sha.hash_oneshot::<Sha256, _>(&input[..4096]).unwrap();
+        assert_sw_hash::<sha2::Sha256>(&input[..4096], &output);
+
+        #[cfg(any(esp32s2, esp32s3))]
+        {
+            let output: [u8; 64] = sha.hash_oneshot::<Sha512, _>(&input).unwrap();
+            assert_sw_hash::<sha2::Sha512>(&input, &output);
+        }
+    }
+
+    #[test]
+    #[cfg(sha_dma)]
+    fn test_sha_dma_digest_in_parts(ctx: Context) {
+        let mut input = [0u8; 3 * 4096 + 13];
+        Rng::new().read(&mut input);
+
+        let mut sha = ctx.sha.with_dma(ctx.dma_channel);
+
+        // Parts that are long enough for DMA, short parts that are written by the CPU, and parts
+        // that start in the middle of a block.
+        for part_len in [4096, 13, 1000, 4096 + 7] {
+            let mut digest = sha.start::<Sha256>();
+            for part in input.chunks(part_len) {
+                digest.update(part).unwrap();
+            }
+            assert_eq!(digest.bytes_processed(), input.len() as u64);
+            let mut output = [0u8; 32];
+            digest.finish(&mut output).unwrap();
+            assert_sw_hash::<sha2::Sha256>(&input, &output);
+        }
+    }
+
+    #[test]
+    #[cfg(sha_dma)]
+    fn test_sha_dma_process_with_retry(ctx: Context) {
+        // A message that is not in RAM can't be accessed by the DMA.
+        static FLASH_INPUT: [u8; 256] = [0xA5; 256];
+
+        let mut input = [0u8; 4096 + 13];
+        Rng::new().read(&mut input);
+
+        let mut sha = ctx.sha.with_dma(ctx.dma_channel);
+
+        let mut output = [0u8; 32];
+        sha.process_with_retry::<Sha256>(&input, &mut output, 3)
+            .unwrap();
+        assert_sw_hash::<sha2::Sha256>(&input, &output);
+
+        // Configuration errors are not retried, and leave the driver usable.
+        assert_eq!(
+            sha.process_with_retry::<Sha256>(&FLASH_INPUT, &mut output, 3),
+            Err(esp_hal::sha::Error::Dma(
+                esp_hal::dma::DmaError::UnsupportedMemoryRegion
+            ))
+        );
+
+        let mut output = [0u8; 20];
+        sha.process_with_retry::<Sha1>(&input, &mut output, 0)
+            .unwrap();
+        assert_sw_hash::<sha1::Sha1>(&input, &output);
+    }
+
+    #[test]
+    #[cfg(sha_dma)]
+    fn test_sha_dma_retry_recovers_from_transient_errors(ctx: Context) {
+        use esp_hal::sha::dma::hash_with_retry;
+
+        let mut input = [0u8; 4096 + 13];
+        Rng::new().read(&mut input);
+
+        let mut sha = ctx.sha.with_dma(ctx.dma_channel);
+
+        // The first two attempts fail, the third one succeeds.
+        let mut flaky = Flaky {
+            sha: &mut sha,
+            failures: 2,
+            recoveries: 0,
+        };
+        let mut output = [0u8; 32];
+        hash_with_retry::<Sha256>(&mut flaky, &input, &mut output, 3).unwrap();
+        assert_eq!(flaky.recoveries, 2);
+        assert_sw_hash::<sha2::Sha256>(&input, &output);
+    }
+
+    #[test]
+    #[cfg(sha_dma)]
+    fn test_sha_dma_retry_gives_up_after_max_retries(ctx: Context) {
+        use esp_hal::sha::dma::hash_with_retry;
+
+        let mut input = [0u8; 4096 + 13];
+        Rng::new().read(&mut input);
+
+        let mut sha = ctx.sha.with_dma(ctx.dma_channel);
+
+        // The first attempt and all three retries fail.
+        let mut flaky = Flaky {
+            sha: &mut sha,
+            failures: 5,
+            recoveries: 0,
+        };
+        let mut output = [0u8; 32];
+        assert_eq!(
+            hash_with_retry::<Sha256>(&mut flaky, &input, &mut output, 3),
+            Err(esp_hal::sha::Error::DmaTransferIncomplete)
+        );
+        assert_eq!(flaky.failures, 1);
+
+        // The driver has been recovered after the last attempt.
+        assert_eq!(flaky.recoveries, 4);
+        let output: [u8; 32] = sha.hash_oneshot::<Sha256, _>(&input).unwrap();
+        assert_sw_hash::<sha2::Sha256>(&input, &output);
+    }
+
+    #[test]
+    #[cfg(sha_dma)]
+    fn test_sha_dma_incomplete_transfer_needs_recover(ctx: Context) {
+        use esp_hal::sha::{Error, dma::ShaDmaOps};
+
+        let mut input = [0u8; 4096 + 13];
+        Rng::new().read(&mut input);
+
+        let mut sha = ctx.sha.with_dma(ctx.dma_channel);
+
+        // A transfer that stops early loses the digest, and the peripheral can't
+        // be used until it is recovered.
+        ShaDmaOps::abort_transfer(&mut sha);
+        let mut digest = sha.start::<Sha256>();
+        assert_eq!(digest.update(&input), Err(Error::BadState));
+        let mut output = [0u8; 32];
+        assert_eq!(digest.finish(&mut output), Err(Error::BadState));
+        assert_eq!(sha.hash_oneshot::<Sha256, 32>(&input), Err(Error::BadState));
+
+        sha.recover();
+        let output: [u8; 32] = sha.hash_oneshot::<Sha256, _>(&input).unwrap();
+        assert_sw_hash::<sha2::Sha256>(&input, &output);
+    }
+
+    #[test]
+    fn test_hash_selects_path_by_size_and_placement(mut ctx: Context) {
+        // A message that is not in RAM can't be accessed by the DMA.
+        static FLASH_INPUT: [u8; 4096 + 13] = [0xA5; 4096 + 13];
+        const SIZES: [usize; 9] = [0, 1, 63, 64, 65, 255, 256, 1000, 4096 + 13];
+
+        let mut input = [0u8; 4096 + 13];
+        Rng::new().read(&mut input);
+
+        for size in SIZES {
+            let digest: [u8; 32] = ctx.sha.hash::<Sha256, _>(&input[..size]).unwrap();
+            assert_sw_hash::<sha2::Sha256>(&input[..size], &digest);
+
+            let digest: [u8; 32] = ctx.sha.hash::<Sha256, _>(&FLASH_INPUT[..size]).unwrap();
+            assert_sw_hash::<sha2::Sha256>(&FLASH_INPUT[..size], &digest);
+        }
+
+        #[cfg(sha_dma)]
+        {
+            let mut sha = ctx.sha.with_dma(ctx.dma_channel);
+
+            for size in SIZES {
+                let digest: [u8; 32] = sha.hash::<Sha256, _>(&input[..size]).unwrap();
+                assert_sw_hash::<sha2::Sha256>(&input[..size], &digest);
+
+                // Falls back to the CPU.
+                let digest: [u8; 32] = sha.hash::<Sha256, _>(&FLASH_INPUT[..size]).unwrap();
+                assert_sw_hash::<sha2::Sha256>(&FLASH_INPUT[..size], &digest);
+
+                let digest: [u8; 20] = sha.hash::<Sha1, _>(&input[..size]).unwrap();
+                assert_sw_hash::<sha1::Sha1>(&input[..size], &digest);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+    fn test_sha_384_512_known_vectors(mut ctx: Context) {
+        // FIPS 180-2, appendices C.1, C.2 (SHA-512), D.1 and D.2 (SHA-384)
+        const ONE_BLOCK: &[u8] = b"abc";
+        // 896 bits, so that the padding doesn't fit into the first block.
+        const MULTI_BLOCK: &[u8] = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+            hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+
+        let mut output = [0u8; 48];
+        hash_sha::<Sha384>(&mut ctx.sha, ONE_BLOCK, &mut output);
+        assert_eq!(
+            output,
+            hex!(
+                "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed"
+                "8086072ba1e7cc2358baeca134c825a7"
+            )
+        );
+        hash_sha::<Sha384>(&mut ctx.sha, MULTI_BLOCK, &mut output);
+        assert_eq!(
+            output,
+            hex!(
+                "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712"
+                "fcc7c71a557e2db966c3e9fa91746039"
+            )
+        );
+
+        let mut output = [0u8; 64];
+        hash_sha::<Sha512>(&mut ctx.sha, ONE_BLOCK, &mut output);
+        assert_eq!(
+            output,
+            hex!(
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a"
+                "2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+            )
+        );
+        hash_sha::<Sha512>(&mut ctx.sha, MULTI_BLOCK, &mut output);
+        assert_eq!(
+            output,
+            hex!(
+                "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018"
+                "501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"
+            )
+        );
+
+        // Output buffers longer than the digest are only partially filled.
+        let mut output = [0u8; 64];
+        hash_sha::<Sha384>(&mut ctx.sha, ONE_BLOCK, &mut output);
+        assert_eq!(output[48..], [0; 16]);
     }
 
     #[test]
@@ -304,18 +1268,23 @@ mod tests {
                 }
             }
 
-            let mut digest = ShaDigest::restore(&mut ctx.sha, &mut sha1);
-            block!(digest.finish(sha1_p.1)).unwrap();
-            let mut digest = ShaDigest::restore(&mut ctx.sha, &mut sha224);
-            block!(digest.finish(sha224_p.1)).unwrap();
-            let mut digest = ShaDigest::restore(&mut ctx.sha, &mut sha256);
-            block!(digest.finish(sha256_p.1)).unwrap();
+            ShaDigest::restore(&mut ctx.sha, &mut sha1)
+                .finish(sha1_p.1)
+                .unwrap();
+            ShaDigest::restore(&mut ctx.sha, &mut sha224)
+                .finish(sha224_p.1)
+                .unwrap();
+            ShaDigest::restore(&mut ctx.sha, &mut sha256)
+                .finish(sha256_p.1)
+                .unwrap();
             #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
             {
-                let mut digest = ShaDigest::restore(&mut ctx.sha, &mut sha384);
-                block!(digest.finish(sha384_p.1)).unwrap();
-                let mut digest = ShaDigest::restore(&mut ctx.sha, &mut sha512);
-                block!(digest.finish(sha512_p.1)).unwrap();
+                ShaDigest::restore(&mut ctx.sha, &mut sha384)
+                    .finish(sha384_p.1)
+                    .unwrap();
+                ShaDigest::restore(&mut ctx.sha, &mut sha512)
+                    .finish(sha512_p.1)
+                    .unwrap();
             }
         });
     }
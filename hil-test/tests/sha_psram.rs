@@ -0,0 +1,72 @@
+//! DMA-SHA tests with the message in PSRAM
+
+//% CHIPS(quad): esp32s2
+// The S3 dev kit in the HIL-tester has octal PSRAM.
+//% CHIPS(octal): esp32s3
+//% ENV(octal): ESP_HAL_CONFIG_PSRAM_MODE=octal
+//% FEATURES: unstable psram
+
+#![no_std]
+#![no_main]
+
+use core::ops::Range;
+
+use digest::Digest;
+use esp_alloc as _;
+use esp_hal::sha::{Sha, Sha256, dma::ShaDma};
+use hil_test as _;
+
+extern crate alloc;
+
+struct Context {
+    sha: ShaDma<'static>,
+    psram: Range<usize>,
+}
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3)]
+mod tests {
+    use super::*;
+
+    #[init]
+    fn init() -> Context {
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+
+        let (start, size) = esp_hal::psram::psram_raw_parts(&peripherals.PSRAM);
+        esp_alloc::psram_allocator!(peripherals.PSRAM, esp_hal::psram);
+
+        cfg_if::cfg_if! {
+            if #[cfg(esp32s2)] {
+                let dma_channel = peripherals.DMA_CRYPTO;
+            } else {
+                let dma_channel = peripherals.DMA_CH0;
+            }
+        }
+
+        Context {
+            sha: Sha::new(peripherals.SHA).with_dma(dma_channel),
+            psram: start as usize..start as usize + size,
+        }
+    }
+
+    #[test]
+    fn test_sha_dma_hashes_message_in_psram(mut ctx: Context) {
+        // Large enough to need multiple DMA operations, and not a multiple of the block size.
+        let mut input = alloc::vec![0u8; 3 * 4096 + 13];
+        assert!(ctx.psram.contains(&(input.as_ptr() as usize)));
+
+        // The data is written through the cache, the driver has to write it back before the
+        // DMA can read it.
+        for (i, byte) in input.iter_mut().enumerate() {
+            *byte = (i ^ (i >> 8)) as u8;
+        }
+
+        let output: [u8; 32] = ctx.sha.hash_oneshot::<Sha256, _>(&input).unwrap();
+        assert_eq!(output, sha2::Sha256::digest(&input)[..]);
+
+        // Modifying the data must not leave stale bytes in the cache.
+        input[100] ^= 0xFF;
+        let output: [u8; 32] = ctx.sha.hash_oneshot::<Sha256, _>(&input).unwrap();
+        assert_eq!(output, sha2::Sha256::digest(&input)[..]);
+    }
+}
@@ -0,0 +1,124 @@
+//! Software PWM tests
+
+//% CHIPS: esp32 esp32c2 esp32c3 esp32c6 esp32h2 esp32s2 esp32s3
+//% FEATURES: unstable
+
+#![no_std]
+#![no_main]
+
+use esp_hal::{
+    gpio::{AnyPin, Input, InputConfig, Level, Pin},
+    soft_pwm::{Error, SoftPwm},
+    time::{Duration, Instant, Rate},
+    timer::timg::{Timer, TimerGroup},
+};
+use hil_test as _;
+
+struct Context {
+    pwm_pin: AnyPin<'static>,
+    input: Input<'static>,
+    timer: Timer<'static>,
+}
+
+/// Samples the input for 100 ms, and returns the number of rising edges and
+/// the percentage of samples that were high.
+fn measure(input: &Input<'_>) -> (u32, u32) {
+    let mut rising_edges = 0;
+    let mut samples = 0;
+    let mut high_samples = 0;
+
+    let mut last = input.level();
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(100) {
+        let level = input.level();
+        if level == Level::High {
+            high_samples += 1;
+            if last == Level::Low {
+                rising_edges += 1;
+            }
+        }
+        last = level;
+        samples += 1;
+    }
+
+    (rising_edges, high_samples * 100 / samples)
+}
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3)]
+mod tests {
+    use super::*;
+
+    #[init]
+    fn init() -> Context {
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+
+        let (input, pwm_pin) = hil_test::common_test_pins!(peripherals);
+        let input = Input::new(input, InputConfig::default());
+        let timg0 = TimerGroup::new(peripherals.TIMG0);
+
+        Context {
+            pwm_pin: pwm_pin.degrade(),
+            input,
+            timer: timg0.timer0,
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_settings(mut ctx: Context) {
+        assert_eq!(
+            SoftPwm::new(
+                ctx.pwm_pin.reborrow(),
+                ctx.timer.reborrow(),
+                Rate::from_hz(0)
+            )
+            .err(),
+            Some(Error::UnsupportedFrequency)
+        );
+        assert_eq!(
+            SoftPwm::new(
+                ctx.pwm_pin.reborrow(),
+                ctx.timer.reborrow(),
+                Rate::from_khz(2)
+            )
+            .err(),
+            Some(Error::UnsupportedFrequency)
+        );
+
+        let mut pwm = SoftPwm::new(ctx.pwm_pin, ctx.timer, Rate::from_hz(100)).unwrap();
+        assert_eq!(pwm.set_duty(101), Err(Error::InvalidDuty));
+        assert_eq!(pwm.duty(), 0);
+    }
+
+    #[test]
+    fn generates_requested_duty_cycle(ctx: Context) {
+        let mut pwm = SoftPwm::new(ctx.pwm_pin, ctx.timer, Rate::from_hz(500)).unwrap();
+
+        for duty in [25, 50, 90] {
+            pwm.set_duty(duty).unwrap();
+            let (rising_edges, high_percent) = measure(&ctx.input);
+
+            assert!((49..=51).contains(&rising_edges), "{} edges", rising_edges);
+            assert!(
+                high_percent.abs_diff(duty as u32) <= 3,
+                "{}% high at {}% duty",
+                high_percent,
+                duty
+            );
+        }
+    }
+
+    #[test]
+    fn extreme_duty_cycles_hold_the_level(ctx: Context) {
+        let mut pwm = SoftPwm::new(ctx.pwm_pin, ctx.timer, Rate::from_hz(500)).unwrap();
+
+        pwm.set_duty(100).unwrap();
+        assert_eq!(measure(&ctx.input), (0, 100));
+
+        pwm.set_duty(50).unwrap();
+        assert_ne!(measure(&ctx.input).0, 0);
+
+        pwm.set_duty(0).unwrap();
+        assert_eq!(measure(&ctx.input), (0, 0));
+    }
+}
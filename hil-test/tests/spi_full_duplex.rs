@@ -454,6 +454,50 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn test_dma_transfer_reports_progress(ctx: Context) {
+        let mut dma_rx_buf = DmaRxBuf::new(ctx.rx_descriptors, ctx.rx_buffer).unwrap();
+        let mut dma_tx_buf = DmaTxBuf::new(ctx.tx_descriptors, ctx.tx_buffer).unwrap();
+        let total = dma_tx_buf.len();
+
+        let mut spi = ctx.spi.with_dma(ctx.dma_channel);
+
+        // Write only: progress is tracked on the transmit descriptors.
+        let transfer = spi.write(total, dma_tx_buf).map_err(|e| e.0).unwrap();
+        let mut last = 0;
+        let mut saw_partial = false;
+        while !transfer.is_done() {
+            let transferred = transfer.transferred_bytes();
+            assert!(transferred >= last);
+            assert!(transferred <= total);
+            saw_partial |= transferred > 0 && transferred < total;
+            last = transferred;
+        }
+        assert_eq!(transfer.transferred_bytes(), total);
+        assert!(saw_partial);
+        (spi, dma_tx_buf) = transfer.wait();
+
+        // Full duplex: progress is tracked on the receive descriptors too.
+        let transfer = spi
+            .transfer(total, dma_rx_buf, total, dma_tx_buf)
+            .map_err(|e| e.0)
+            .unwrap();
+        let mut last = 0;
+        let mut saw_partial = false;
+        while !transfer.is_done() {
+            let transferred = transfer.transferred_bytes();
+            assert!(transferred >= last);
+            assert!(transferred <= total);
+            saw_partial |= transferred > 0 && transferred < total;
+            last = transferred;
+        }
+        assert_eq!(transfer.transferred_bytes(), total);
+        assert!(saw_partial);
+        (_, (dma_rx_buf, dma_tx_buf)) = transfer.wait();
+        assert_eq!(dma_rx_buf.as_slice(), dma_tx_buf.as_slice());
+    }
+
     #[test]
     #[cfg(feature = "unstable")]
     fn test_asymmetric_dma_transfer(ctx: Context) {
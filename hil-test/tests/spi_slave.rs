@@ -184,4 +184,40 @@ mod tests {
         assert_eq!(slave_receive.as_slice(), master_send);
         assert_eq!(master_receive, slave_send.as_slice());
     }
+
+    #[test]
+    #[cfg(gdma)]
+    fn test_cs_deassert_ends_transfer(mut ctx: Context) {
+        const DMA_SIZE: usize = 32;
+        const MASTER_SIZE: usize = 8;
+        let (rx_buffer, rx_descriptors, tx_buffer, tx_descriptors) = dma_buffers!(DMA_SIZE);
+        let mut slave_receive = DmaRxBuf::new(rx_descriptors, rx_buffer).unwrap();
+        let mut slave_send = DmaTxBuf::new(tx_descriptors, tx_buffer).unwrap();
+
+        let spi = ctx.spi.with_dma(ctx.dma_channel);
+
+        // The master clocks fewer bytes than the slave has buffered.
+        let master_send = &mut [0u8; MASTER_SIZE];
+        let master_receive = &mut [0xFFu8; MASTER_SIZE];
+
+        for (i, v) in master_send.iter_mut().enumerate() {
+            *v = (i + 1) as u8;
+        }
+        for (i, v) in slave_send.as_mut_slice().iter_mut().enumerate() {
+            *v = (254 - i) as u8;
+        }
+        slave_receive.as_mut_slice().fill(0xFF);
+
+        let transfer = spi
+            .transfer(DMA_SIZE, slave_receive, DMA_SIZE, slave_send)
+            .unwrap();
+
+        ctx.bitbang_spi.transfer_buf(master_receive, master_send);
+
+        let (spi, (slave_receive, slave_send)) = transfer.wait();
+
+        assert_eq!(spi.transferred_bytes(), MASTER_SIZE);
+        assert_eq!(&slave_receive.as_slice()[..MASTER_SIZE], master_send);
+        assert_eq!(master_receive, &slave_send.as_slice()[..MASTER_SIZE]);
+    }
 }
@@ -0,0 +1,36 @@
+//! Temperature sensor tests
+
+//% CHIPS: esp32c3 esp32c6
+//% FEATURES: unstable
+
+#![no_std]
+#![no_main]
+
+use hil_test as _;
+
+#[cfg(test)]
+#[embedded_test::tests(default_timeout = 3)]
+mod tests {
+    use esp_hal::{
+        delay::Delay,
+        tsens::{Config, MeasurementRange, TemperatureSensor},
+    };
+
+    #[test]
+    fn test_reads_room_temperature() {
+        let p = esp_hal::init(Default::default());
+
+        let config = Config::default().with_range(MeasurementRange::CelsiusMinus10To80);
+        let sensor = TemperatureSensor::new(p.TSENS, config).unwrap();
+
+        // Wait for the sensor to stabilize
+        Delay::new().delay_micros(500);
+
+        let celsius = sensor.read_celsius();
+        defmt::info!("Temperature: {} °C", celsius);
+
+        // The chip runs warmer than the ambient temperature, but anything outside
+        // of this band means the conversion or the calibration is off.
+        assert!((0.0..80.0).contains(&celsius), "{} °C", celsius);
+    }
+}
@@ -6,7 +6,7 @@
 #![no_std]
 #![no_main]
 
-use embedded_can::Frame;
+use embedded_can::{Frame, Id};
 use esp_hal::{
     Blocking,
     twai::{self, EspTwaiFrame, StandardId, TwaiMode, filter::SingleStandardFilter},
@@ -59,4 +59,21 @@ mod tests {
 
         assert_eq!(frame.data(), &[1, 2, 3])
     }
+
+    #[test]
+    fn test_filter_drops_frames(mut ctx: Context) {
+        // The filter only accepts ID 0.
+        let id = StandardId::new(1).unwrap();
+        let frame = EspTwaiFrame::new_self_reception(id, &[1, 2, 3]).unwrap();
+        block!(ctx.twai.transmit(&frame)).unwrap();
+
+        let frame = EspTwaiFrame::new_self_reception(StandardId::ZERO, &[4, 5, 6]).unwrap();
+        block!(ctx.twai.transmit(&frame)).unwrap();
+
+        let frame = block!(ctx.twai.receive()).unwrap();
+
+        assert_eq!(frame.id(), Id::Standard(embedded_can::StandardId::ZERO));
+        assert_eq!(frame.data(), &[4, 5, 6]);
+        assert_eq!(ctx.twai.num_available_messages(), 0);
+    }
 }
@@ -10,19 +10,25 @@ use esp_hal::{
     Blocking,
     delay::Delay,
     gpio::AnyPin,
-    uart::{self, ClockSource, Uart},
+    peripherals::UART1,
+    uart::{self, ClockSource, Uart, UartInterrupt},
 };
 use hil_test as _;
 
 struct Context {
     uart0: Uart<'static, Blocking>,
-    uart1: Uart<'static, Blocking>,
+    uart1: UART1<'static>,
     rx: AnyPin<'static>,
     tx: AnyPin<'static>,
     rts: AnyPin<'static>,
     delay: Delay,
 }
 
+/// Creates a UART1 driver with the default configuration.
+fn new_uart1(uart1: UART1<'static>) -> Uart<'static, Blocking> {
+    Uart::new(uart1, uart::Config::default()).unwrap()
+}
+
 #[cfg(test)]
 #[embedded_test::tests(default_timeout = 3)]
 mod tests {
@@ -42,11 +48,10 @@ mod tests {
         let delay = Delay::new();
 
         let uart0 = Uart::new(peripherals.UART0, uart::Config::default()).unwrap();
-        let uart1 = Uart::new(peripherals.UART1, uart::Config::default()).unwrap();
 
         Context {
             uart0,
-            uart1,
+            uart1: peripherals.UART1,
             rx: rx.degrade(),
             tx: tx.degrade(),
             rts: rts.degrade(),
@@ -56,7 +61,7 @@ mod tests {
 
     #[test]
     fn test_send_receive(ctx: Context) {
-        let mut uart = ctx.uart1.with_tx(ctx.tx).with_rx(ctx.rx);
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
 
         uart.write(&[0x42]).unwrap();
         let mut byte = [0u8; 1];
@@ -64,9 +69,42 @@ mod tests {
         assert_eq!(byte[0], 0x42);
     }
 
+    #[test]
+    fn test_send_receive_with_config_inversion(ctx: Context) {
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+
+        uart.apply_config(
+            &uart::Config::default()
+                .with_invert_tx(true)
+                .with_invert_rx(true),
+        )
+        .unwrap();
+
+        uart.write(&[0x42]).unwrap();
+        let mut byte = [0u8; 1];
+        uart.read(&mut byte).unwrap();
+        assert_eq!(byte[0], 0x42);
+    }
+
+    #[test]
+    fn tx_idle_reports_pending_data(ctx: Context) {
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+
+        uart.apply_config(&uart::Config::default().with_baudrate(9600))
+            .unwrap();
+        assert!(uart.is_tx_idle());
+
+        // A frame takes about a millisecond at this baud rate.
+        uart.write(&[0x42, 0x43]).unwrap();
+        assert!(!uart.is_tx_idle());
+
+        uart.flush().unwrap();
+        assert!(uart.is_tx_idle());
+    }
+
     #[test]
     fn flush_waits_for_data_to_be_transmitted(ctx: Context) {
-        let mut uart = ctx.uart1.with_tx(ctx.tx).with_rx(ctx.rx);
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
 
         assert!(uart.write_ready());
         assert!(!uart.read_ready());
@@ -94,7 +132,7 @@ mod tests {
 
     #[test]
     fn test_different_tolerance(ctx: Context) {
-        let mut uart = ctx.uart1.with_tx(ctx.tx).with_rx(ctx.rx);
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
 
         let configs = [
             uart::Config::default()
@@ -144,9 +182,131 @@ mod tests {
         assert_eq!(rts_input.is_input_high(), true);
     }
 
+    #[test]
+    fn test_rs485_drives_rts_while_transmitting(ctx: Context) {
+        let (rts_input, rts_output) = unsafe { ctx.rts.split() };
+        let mut uart = ctx
+            .uart1
+            .with_tx(ctx.tx)
+            .with_rx(ctx.rx)
+            .with_rts(rts_output);
+
+        for (polarity, active_high) in [
+            (uart::DriverEnablePolarity::ActiveHigh, true),
+            (uart::DriverEnablePolarity::ActiveLow, false),
+        ] {
+            uart.apply_config(
+                &uart::Config::default()
+                    .with_baudrate(9600)
+                    .with_rs485_mode(uart::Rs485Mode::HalfDuplex(polarity)),
+            )
+            .unwrap();
+            ctx.delay.delay_millis(1);
+            assert_eq!(rts_input.is_input_high(), !active_high);
+
+            // 16 bytes take more than 16 ms to transmit at 9600 baud.
+            uart.write(&[0x55; 16]).unwrap();
+            ctx.delay.delay_millis(5);
+            assert_eq!(rts_input.is_input_high(), active_high);
+
+            // `flush` returns after the last stop bit, by then the pin must be released.
+            uart.flush().unwrap();
+            assert_eq!(rts_input.is_input_high(), !active_high);
+        }
+
+        // RS-485 and RTS flow control would both drive the RTS pin.
+        assert_eq!(
+            uart.apply_config(
+                &uart::Config::default()
+                    .with_rs485_mode(uart::Rs485Mode::HalfDuplex(Default::default()))
+                    .with_hw_flow_ctrl(uart::HwFlowControl {
+                        cts: uart::CtsConfig::Disabled,
+                        rts: uart::RtsConfig::Enabled(4),
+                    }),
+            ),
+            Err(uart::ConfigError::Rs485WithRtsFlowControl)
+        );
+    }
+
+    #[test]
+    fn test_new_rs485_releases_de_after_transmission(ctx: Context) {
+        let (de_input, de_output) = unsafe { ctx.rts.split() };
+        let mut uart = Uart::new_rs485(
+            ctx.uart1,
+            uart::Config::default().with_baudrate(9600),
+            ctx.tx,
+            ctx.rx,
+            de_output,
+        )
+        .unwrap();
+
+        assert!(!de_input.is_input_high());
+
+        for _ in 0..3 {
+            uart.write(&[0x55; 16]).unwrap();
+            ctx.delay.delay_millis(5);
+            assert!(de_input.is_input_high());
+
+            uart.flush().unwrap();
+            assert!(!de_input.is_input_high());
+        }
+    }
+
+    #[test]
+    fn test_line_idle_after_burst(ctx: Context) {
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+
+        // Nothing has been received yet.
+        ctx.delay.delay_millis(1);
+        assert!(!uart.line_idle());
+
+        let data = [0x55; 16];
+        uart.write(&data).unwrap();
+        uart.flush().unwrap();
+
+        // The line has just become idle, the default timeout is 10 symbols.
+        assert!(!uart.line_idle());
+
+        ctx.delay.delay_millis(5);
+
+        let mut buffer = [0; 16];
+        embedded_io::Read::read_exact(&mut uart, &mut buffer).unwrap();
+        assert_eq!(data, buffer);
+
+        assert!(uart.line_idle());
+        // Each idle period is reported once.
+        assert!(!uart.line_idle());
+    }
+
+    #[test]
+    fn test_fifo_counts_and_tx_fifo_empty(ctx: Context) {
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+        let config = uart::Config::default()
+            .with_baudrate(9600)
+            .with_tx(uart::TxConfig::default().with_fifo_empty_threshold(10));
+        uart.apply_config(&config).unwrap();
+
+        // At 9600 baud, sending a byte takes over 1 ms.
+        let data = [0x55; 32];
+        uart.write(&data).unwrap();
+        assert!(uart.tx_fifo_count() > 10);
+
+        uart.clear_interrupts(UartInterrupt::TxFifoEmpty.into());
+        assert!(!uart.interrupts().contains(UartInterrupt::TxFifoEmpty));
+
+        uart.flush().unwrap();
+        assert_eq!(uart.tx_fifo_count(), 0);
+        assert!(uart.interrupts().contains(UartInterrupt::TxFifoEmpty));
+
+        // Wait for the last byte to be received.
+        ctx.delay.delay_millis(2);
+        assert_eq!(uart.rx_fifo_count(), data.len() as u16);
+        assert!(uart.rx_fifo_count() <= uart::FIFO_SIZE);
+    }
+
     #[test]
     fn test_send_receive_buffer(ctx: Context) {
-        let mut uart = ctx.uart1.with_tx(ctx.tx).with_rx(ctx.rx);
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
 
         const BUF_SIZE: usize = 128; // UART_FIFO_SIZE
 
@@ -161,9 +321,140 @@ mod tests {
         assert_eq!(data, buffer);
     }
 
+    #[test]
+    fn test_send_receive_with_checksum(ctx: Context) {
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+
+        // The two header bytes are not covered by the checksum.
+        let checksum = uart::ChecksumConfig::default()
+            .with_kind(uart::ChecksumKind::Crc8)
+            .with_skip(2);
+        let frame = *b"\xAA\x55123456789";
+
+        // The trailer is the CRC-8/SMBUS check value of "123456789".
+        uart.write_with_checksum(&frame, checksum).unwrap();
+        let mut raw = [0u8; 12];
+        embedded_io::Read::read_exact(&mut uart, &mut raw).unwrap();
+        assert_eq!(raw[..11], frame);
+        assert_eq!(raw[11], 0xF4);
+
+        // The RX side validates and strips the trailer.
+        uart.write_with_checksum(&frame, checksum).unwrap();
+        let mut received = [0u8; 11];
+        uart.read_exact_with_checksum(&mut received, checksum)
+            .unwrap();
+        assert_eq!(received, frame);
+
+        // A corrupted trailer is rejected.
+        uart.write(&frame).unwrap();
+        uart.write(&[!0xF4]).unwrap();
+        assert_eq!(
+            uart.read_exact_with_checksum(&mut received, checksum),
+            Err(uart::RxError::ChecksumMismatch)
+        );
+    }
+
+    #[test]
+    fn test_read_line(ctx: Context) {
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+
+        uart.write(b"hello\nworld\n").unwrap();
+        let mut buf = [0u8; 16];
+        let len = uart.read_line(&mut buf, b'\n').unwrap();
+        assert_eq!(&buf[..len], b"hello");
+
+        // The next line was left in the FIFO.
+        let len = uart.read_line(&mut buf, b'\n').unwrap();
+        assert_eq!(&buf[..len], b"world");
+
+        // The buffer has no room for the delimiter.
+        uart.write(b"hello\n").unwrap();
+        let mut buf = [0u8; 5];
+        assert_eq!(
+            uart.read_line(&mut buf, b'\n'),
+            Err(uart::RxError::LineTooLong)
+        );
+        assert_eq!(&buf, b"hello");
+
+        // The rest of the line is still there.
+        let mut byte = [0u8; 1];
+        uart.read(&mut byte).unwrap();
+        assert_eq!(byte, *b"\n");
+    }
+
+    #[test]
+    fn test_read_byte_status(ctx: Context) {
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+
+        uart.write(&[0x42]).unwrap();
+        let (byte, status) = nb::block!(uart.read_byte_status()).unwrap();
+        assert_eq!(byte, 0x42);
+        assert!(status.is_ok());
+
+        // Nothing else was received.
+        assert!(matches!(
+            uart.read_byte_status(),
+            Err(nb::Error::WouldBlock)
+        ));
+    }
+
+    #[test]
+    fn test_read_byte_status_reports_parity_error(ctx: Context) {
+        let mut uart0 = ctx.uart0;
+        uart0
+            .apply_config(&uart::Config::default().with_parity(uart::Parity::Even))
+            .unwrap();
+        let mut uart1 = new_uart1(ctx.uart1);
+        uart1
+            .apply_config(&uart::Config::default().with_parity(uart::Parity::Odd))
+            .unwrap();
+
+        let mut tx = uart0.split().1.with_tx(ctx.tx);
+        let mut rx = uart1.with_rx(ctx.rx);
+
+        // The even parity bit of the byte is the opposite of what the receiver expects.
+        tx.flush().unwrap();
+        tx.write(&[0x01]).unwrap();
+        tx.flush().unwrap();
+
+        assert!(rx.interrupts().contains(uart::UartInterrupt::RxParityError));
+
+        let (byte, status) = nb::block!(rx.read_byte_status()).unwrap();
+        assert_eq!(byte, 0x01);
+        assert!(status.parity_error);
+        assert!(!status.overrun);
+
+        // The condition is cleared once it has been reported.
+        assert!(!rx.interrupts().contains(uart::UartInterrupt::RxParityError));
+    }
+
+    #[test]
+    fn test_read_byte_status_reports_overrun(ctx: Context) {
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+
+        // Send more than the RX FIFO can hold without reading anything.
+        let data = [0x55; uart::FIFO_SIZE as usize + 16];
+        let mut remaining = &data[..];
+        while !remaining.is_empty() {
+            let written = uart.write(remaining).unwrap();
+            remaining = &remaining[written..];
+        }
+        uart.flush().unwrap();
+
+        let (byte, status) = nb::block!(uart.read_byte_status()).unwrap();
+        assert_eq!(byte, 0x55);
+        assert!(status.overrun);
+
+        // The FIFO was reset after the overrun was reported.
+        assert!(matches!(
+            uart.read_byte_status(),
+            Err(nb::Error::WouldBlock)
+        ));
+    }
+
     #[test]
     fn test_send_receive_different_baud_rates_and_clock_sources(ctx: Context) {
-        let mut uart = ctx.uart1.with_tx(ctx.tx).with_rx(ctx.rx);
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
 
         // The default baud rate for the UART is 115,200, so we will try to
         // send/receive with some other common baud rates to ensure this is
@@ -199,6 +490,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_baudrate_at_runtime(ctx: Context) {
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+
+        #[cfg(not(any(esp32, esp32s2)))]
+        uart.apply_config(&uart::Config::default().with_clock_source(ClockSource::Xtal))
+            .unwrap();
+
+        // Data queued before the change must still go out at the old rate.
+        uart.write(&[0x5A]).unwrap();
+        uart.set_baudrate(921_600).unwrap();
+        let mut byte = [0u8; 1];
+        uart.read(&mut byte).unwrap();
+        assert_eq!(byte[0], 0x5A);
+
+        for baudrate in [19_200, 460_800, 115_200] {
+            uart.set_baudrate(baudrate).unwrap();
+            uart.write(&[0xA5]).unwrap();
+            uart.read(&mut byte).unwrap();
+            assert_eq!(byte[0], 0xA5, "Baud rate {}", baudrate);
+        }
+
+        assert_eq!(
+            uart.set_baudrate(0),
+            Err(uart::ConfigError::BaudrateNotSupported)
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected_before_applying(ctx: Context) {
+        let mut uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+
+        let slow = uart::Config::default().with_baudrate(9600);
+        assert_eq!(
+            uart.apply_config(
+                &slow.with_rx(uart::RxConfig::default().with_fifo_full_threshold(uart::FIFO_SIZE))
+            ),
+            Err(uart::ConfigError::RxFifoThresholdNotSupported)
+        );
+        assert_eq!(
+            uart.apply_config(
+                &slow.with_tx(uart::TxConfig::default().with_fifo_empty_threshold(uart::FIFO_SIZE))
+            ),
+            Err(uart::ConfigError::TxFifoThresholdNotSupported)
+        );
+        assert_eq!(
+            uart.apply_config(&slow.with_rx(uart::RxConfig::default().with_timeout(u8::MAX))),
+            Err(uart::ConfigError::TimeoutTooLong)
+        );
+        assert_eq!(
+            uart.apply_config(
+                &slow.with_baudrate_tolerance(uart::BaudrateTolerance::ErrorPercent(0))
+            ),
+            Err(uart::ConfigError::BaudrateToleranceNotSupported)
+        );
+
+        // None of the above may have changed the baud rate: 16 bytes take about
+        // 1.4 ms at 115200 baud, but 17 ms at 9600 baud.
+        let start = esp_hal::time::Instant::now();
+        uart.write(&[0x55; 16]).unwrap();
+        uart.flush().unwrap();
+        assert!(start.elapsed() < esp_hal::time::Duration::from_millis(5));
+
+        let mut bytes = [0u8; 16];
+        let mut received = 0;
+        while received < bytes.len() {
+            received += uart.read(&mut bytes[received..]).unwrap();
+        }
+        assert_eq!(bytes, [0x55; 16]);
+    }
+
     #[test]
     fn test_send_receive_inverted(ctx: Context) {
         let mut uart = ctx
@@ -215,7 +577,7 @@ mod tests {
     #[test]
     fn test_split_send_receive(ctx: Context) {
         let mut tx = ctx.uart0.split().1.with_tx(ctx.tx);
-        let mut rx = ctx.uart1.split().0.with_rx(ctx.rx);
+        let mut rx = new_uart1(ctx.uart1).split().0.with_rx(ctx.rx);
 
         let byte = [0x42];
 
@@ -230,7 +592,7 @@ mod tests {
     #[test]
     fn test_split_send_receive_bytes(ctx: Context) {
         let mut tx = ctx.uart0.split().1.with_tx(ctx.tx);
-        let mut rx = ctx.uart1.split().0.with_rx(ctx.rx);
+        let mut rx = new_uart1(ctx.uart1).split().0.with_rx(ctx.rx);
 
         let bytes = [0x42, 0x43, 0x44];
         let mut buf = [0u8; 3];
@@ -242,4 +604,52 @@ mod tests {
 
         assert_eq!(buf, bytes);
     }
+
+    #[test]
+    fn test_split_halves_own_their_interrupts(ctx: Context) {
+        let uart = new_uart1(ctx.uart1).with_tx(ctx.tx).with_rx(ctx.rx);
+        let (mut rx, mut tx) = uart.split();
+
+        tx.write(&[0x42]).unwrap();
+        tx.flush().unwrap();
+        ctx.delay.delay_millis(1);
+
+        // Each half only sees its own interrupts.
+        assert!(tx.interrupts().contains(UartInterrupt::TxDone));
+        assert!(!rx.interrupts().contains(UartInterrupt::TxDone));
+
+        // The receiver can't clear the transmitter's interrupts.
+        rx.clear_interrupts(UartInterrupt::TxDone | UartInterrupt::RxFifoFull);
+        assert!(tx.interrupts().contains(UartInterrupt::TxDone));
+
+        tx.clear_interrupts(UartInterrupt::TxDone | UartInterrupt::RxFifoFull);
+        assert!(!tx.interrupts().contains(UartInterrupt::TxDone));
+
+        let mut byte = [0u8; 1];
+        rx.read(&mut byte).unwrap();
+        assert_eq!(byte[0], 0x42);
+    }
+
+    #[test]
+    fn test_send_break_is_detected(ctx: Context) {
+        let mut tx = ctx.uart0.split().1.with_tx(ctx.tx);
+        let mut rx = new_uart1(ctx.uart1).split().0.with_rx(ctx.rx);
+
+        tx.flush().unwrap();
+        rx.clear_interrupts(UartInterrupt::RxBreakDetected.into());
+
+        // The byte written before the break must not be mangled by it.
+        tx.write(&[0x42]).unwrap();
+        tx.send_break(20).unwrap();
+        tx.write(&[0x55]).unwrap();
+        tx.flush().unwrap();
+
+        assert!(rx.interrupts().contains(UartInterrupt::RxBreakDetected));
+        rx.clear_interrupts(UartInterrupt::RxBreakDetected.into());
+        assert!(!rx.interrupts().contains(UartInterrupt::RxBreakDetected));
+
+        let mut byte = [0u8; 1];
+        rx.read(&mut byte).unwrap();
+        assert_eq!(byte[0], 0x42);
+    }
 }
@@ -11,6 +11,7 @@
 mod tests {
     use esp_hal::{
         gpio::Flex,
+        system::{ClockGate, flush_uart0},
         uart::{self, UartRx, UartTx},
     };
     use hil_test as _;
@@ -43,4 +44,51 @@ mod tests {
 
         assert_eq!(byte[0], 0x42);
     }
+
+    #[test]
+    fn test_uart0_works_after_clock_gate() {
+        let mut peripherals = esp_hal::init(esp_hal::Config::default());
+
+        let (rx, tx) = hil_test::common_test_pins!(peripherals);
+
+        let mut rx = UartRx::new(peripherals.UART1, uart::Config::default())
+            .unwrap()
+            .with_rx(rx);
+
+        // The peripheral is reset while gated, the driver must configure it again.
+        let gate = ClockGate::new(peripherals.UART0.reborrow());
+        drop(gate);
+
+        let mut tx = UartTx::new(peripherals.UART0, uart::Config::default())
+            .unwrap()
+            .with_tx(tx);
+
+        tx.write(&[0x42]).unwrap();
+        let mut byte = [0u8; 1];
+        rx.read(&mut byte).unwrap();
+
+        assert_eq!(byte[0], 0x42);
+    }
+
+    #[test]
+    fn test_flush_uart0_waits_for_transmission() {
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+
+        let (_, tx) = hil_test::common_test_pins!(peripherals);
+
+        // A low baud rate makes sure that the data is still being sent when `flush_uart0` is
+        // called.
+        let mut tx = UartTx::new(
+            peripherals.UART0,
+            uart::Config::default().with_baudrate(9600),
+        )
+        .unwrap()
+        .with_tx(tx);
+
+        tx.write(&[0x55; 8]).unwrap();
+        assert!(!tx.is_tx_idle());
+
+        flush_uart0();
+        assert!(tx.is_tx_idle());
+    }
 }
@@ -9,7 +9,10 @@
 #[cfg(test)]
 #[embedded_test::tests(default_timeout = 3)]
 mod tests {
-    use esp_hal::usb_serial_jtag::UsbSerialJtag;
+    use esp_hal::{
+        time::{Duration, Instant},
+        usb_serial_jtag::{Error, UsbSerialJtag},
+    };
     use hil_test as _;
 
     #[test]
@@ -20,4 +23,21 @@ mod tests {
             .into_async()
             .split();
     }
+
+    #[test]
+    fn blocking_write_does_not_block_forever() {
+        let peripherals = esp_hal::init(esp_hal::Config::default());
+
+        let mut usb_serial = UsbSerialJtag::new(peripherals.USB_DEVICE);
+
+        // Whether or not the host reads the data, the writes must return.
+        let start = Instant::now();
+        for _ in 0..4 {
+            let result = usb_serial.write(&[b'.'; 1024]);
+            assert!(matches!(result, Ok(()) | Err(Error::HostNotConnected)));
+        }
+        let result = usb_serial.flush_tx();
+        assert!(matches!(result, Ok(()) | Err(Error::HostNotConnected)));
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
 }
@@ -0,0 +1,93 @@
+//! Read the temperature of all DS18B20 sensors on a 1-Wire bus
+//!
+//! This example searches the bus for devices, then repeatedly starts a
+//! conversion on all of them and prints the temperature of each DS18B20.
+//!
+//! The following wiring is assumed:
+//! - DQ => GPIO4, with a 4.7 kΩ pull-up resistor to 3.3V
+
+//% CHIPS: esp32 esp32c3 esp32c6 esp32h2 esp32s2 esp32s3
+//% TAG: ds18b20
+
+#![no_std]
+#![no_main]
+
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    main,
+    rmt::{
+        Rmt,
+        onewire::{self, Address, OneWire},
+    },
+    time::Rate,
+};
+use esp_println::println;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const DS18B20_FAMILY: u8 = 0x28;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+
+    // The 1-Wire driver needs the RMT channels to count in microseconds.
+    cfg_if::cfg_if! {
+        if #[cfg(esp32h2)] {
+            let (freq, divider) = (Rate::from_mhz(32), 32);
+        } else {
+            let (freq, divider) = (Rate::from_mhz(80), 80);
+        }
+    };
+    let rmt = Rmt::new(peripherals.RMT, freq).unwrap();
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(esp32, esp32s2))] {
+            let rx = rmt.channel1;
+        } else if #[cfg(esp32s3)] {
+            let rx = rmt.channel4;
+        } else {
+            let rx = rmt.channel2;
+        }
+    };
+    let mut bus = OneWire::new(rmt.channel0, rx, peripherals.GPIO4, divider).unwrap();
+
+    let mut sensors = [Address(0); 8];
+    let mut count = 0;
+    for address in bus.search() {
+        let address = address.unwrap();
+        println!("Found device {:016x}", address.0);
+        if address.family_code() == DS18B20_FAMILY && count < sensors.len() {
+            sensors[count] = address;
+            count += 1;
+        }
+    }
+
+    let delay = Delay::new();
+    loop {
+        // Start a conversion on all sensors at once.
+        bus.reset().unwrap();
+        bus.skip_rom().unwrap();
+        bus.write_bytes(&[0x44]).unwrap();
+        delay.delay_millis(750);
+
+        for sensor in &sensors[..count] {
+            bus.reset().unwrap();
+            bus.select(*sensor).unwrap();
+            bus.write_bytes(&[0xBE]).unwrap();
+
+            let mut scratchpad = [0u8; 9];
+            bus.read_bytes(&mut scratchpad).unwrap();
+            if onewire::crc8(&scratchpad) != 0 {
+                println!("{:016x}: CRC mismatch", sensor.0);
+                continue;
+            }
+
+            let raw = i16::from_le_bytes([scratchpad[0], scratchpad[1]]);
+            println!("{:016x}: {:.2}°C", sensor.0, raw as f32 / 16.0);
+        }
+
+        delay.delay_millis(1_000);
+    }
+}
@@ -0,0 +1,71 @@
+//! Demonstrates light sleep with timer and UART wakeup
+//!
+//! The chip wakes up every 5 seconds, or when data is received on UART1. Send
+//! a few characters to wake it up early; the character that triggers the wakeup
+//! is not received.
+//!
+//! The current drawn while asleep can be measured by connecting an ammeter
+//! in series with the 3V3 supply of the module. Disconnect the USB-to-UART
+//! bridge or power it separately, as its current would otherwise be included
+//! in the measurement.
+//!
+//! The following wiring is assumed:
+//! - UART1 RX => GPIO4
+
+//% CHIPS: esp32 esp32c3 esp32c6 esp32s2 esp32s3 esp32c2
+
+#![no_std]
+#![no_main]
+
+use core::time::Duration;
+
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    main,
+    rtc_cntl::{
+        Rtc,
+        sleep::{TimerWakeupSource, Uart1WakeupSource},
+        wakeup_cause,
+    },
+    uart::{Config, Uart},
+};
+use esp_println::println;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+
+    let delay = Delay::new();
+    let mut rtc = Rtc::new(peripherals.LPWR);
+
+    let mut uart = Uart::new(peripherals.UART1, Config::default())
+        .unwrap()
+        .with_rx(peripherals.GPIO4);
+
+    let timer = TimerWakeupSource::new(Duration::from_secs(5));
+    let uart_wakeup = Uart1WakeupSource::new(3);
+
+    let mut count = 0;
+    loop {
+        println!("sleeping! (woke up {} times)", count);
+        // Let the log output drain before the clocks are gated.
+        delay.delay_millis(10);
+
+        rtc.sleep_light(&[&timer, &uart_wakeup]);
+        count += 1;
+
+        println!("woke up, cause: {:?}", wakeup_cause());
+
+        // Drain whatever was received after waking up.
+        let mut buf = [0; 32];
+        while uart.read_ready() {
+            let Ok(len) = uart.read(&mut buf) else {
+                break;
+            };
+            println!("received: {:?}", &buf[..len]);
+        }
+    }
+}
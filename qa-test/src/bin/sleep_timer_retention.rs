@@ -0,0 +1,63 @@
+//! Demonstrates retaining a timer's configuration through deep sleep
+//!
+//! On the first boot, timer 0 of TIMG1 is started with a period of 1.5 seconds.
+//! After waking up from deep sleep, the configuration is restored instead, so
+//! the printed alarm value and configuration are the same after every wakeup.
+
+//% CHIPS: esp32 esp32c3 esp32c6 esp32s2 esp32s3
+
+#![no_std]
+#![no_main]
+
+use core::time::Duration;
+
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    main,
+    peripherals::TIMG1,
+    rtc_cntl::{RetainedPeripheral, Rtc, sleep::TimerWakeupSource},
+    timer::{PeriodicTimer, timg::TimerGroup},
+};
+use esp_println::println;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+
+    let delay = Delay::new();
+    let mut rtc = Rtc::new(peripherals.LPWR);
+
+    // Creating the driver resets the timer group, so restore afterwards.
+    let timg1 = TimerGroup::new(peripherals.TIMG1);
+    let mut timer = PeriodicTimer::new(timg1.timer0);
+
+    if rtc
+        .restore_peripheral_config(RetainedPeripheral::Timg1)
+        .unwrap()
+    {
+        println!("restored the timer configuration");
+    } else {
+        println!("configuring the timer");
+        timer
+            .start(esp_hal::time::Duration::from_millis(1500))
+            .unwrap();
+    }
+
+    let t0 = TIMG1::regs().t(0);
+    println!(
+        "config: {:#010x}, alarm: {}",
+        t0.config().read().bits(),
+        t0.alarmlo().read().bits()
+    );
+
+    rtc.retain_peripheral_config(RetainedPeripheral::Timg1)
+        .unwrap();
+
+    let wakeup = TimerWakeupSource::new(Duration::from_secs(5));
+    println!("sleeping!");
+    delay.delay_millis(100);
+    rtc.sleep_deep(&[&wakeup]);
+}
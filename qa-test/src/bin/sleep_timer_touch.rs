@@ -0,0 +1,69 @@
+//! Demonstrates deep sleep with timer and touch pad wakeup
+//!
+//! The chip wakes up either after 30 seconds or when the touch pad is touched,
+//! and prints which of the two woke it up.
+//!
+//! The following wiring is assumed:
+//! - touch pad => GPIO4
+
+//% CHIPS: esp32
+
+#![no_std]
+#![no_main]
+
+use core::time::Duration;
+
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    main,
+    rtc_cntl::{
+        Rtc,
+        SocResetReason,
+        reset_reason,
+        sleep::{TimerWakeupSource, TouchWakeupSource},
+        wakeup_cause,
+    },
+    system::{Cpu, SleepSource},
+    touch::{Touch, TouchConfig, TouchPad},
+};
+use esp_println::println;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+
+    let mut rtc = Rtc::new(peripherals.LPWR);
+
+    println!("up and runnning!");
+    let reason = reset_reason(Cpu::ProCpu).unwrap_or(SocResetReason::ChipPowerOn);
+    println!("reset reason: {:?}", reason);
+    match wakeup_cause() {
+        SleepSource::TouchPad => println!("woken up by the touch pad"),
+        SleepSource::Timer => println!("woken up by the timer"),
+        other => println!("wake reason: {:?}", other),
+    }
+
+    let delay = Delay::new();
+
+    let touch_cfg = Some(TouchConfig {
+        measurement_duration: Some(0x2000),
+        ..Default::default()
+    });
+    let touch = Touch::continuous_mode(peripherals.TOUCH, touch_cfg);
+    let mut touch_pad = TouchPad::new(peripherals.GPIO4, &touch);
+
+    // Give the touch FSM time to take a first measurement.
+    delay.delay_millis(100);
+    let baseline = touch_pad.read();
+    // A good threshold is 2/3 of the reading when the pad is not touched.
+    touch_pad.listen(baseline * 2 / 3);
+
+    let timer = TimerWakeupSource::new(Duration::from_secs(30));
+    let touch_wakeup = TouchWakeupSource::new(&touch);
+    println!("sleeping!");
+    delay.delay_millis(100);
+    rtc.sleep_deep(&[&timer, &touch_wakeup]);
+}
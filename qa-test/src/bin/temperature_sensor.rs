@@ -28,8 +28,8 @@ fn main() -> ! {
     delay.delay_micros(200);
 
     loop {
-        let temp = temperature_sensor.get_temperature();
-        println!("Temperature: {:.2}°C", temp.to_celsius());
+        let temp = temperature_sensor.read_celsius();
+        println!("Temperature: {:.2}°C", temp);
         delay.delay_millis(1_000);
     }
 }
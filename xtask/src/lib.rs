@@ -192,6 +192,7 @@ impl Package {
             Package::EspHal => {
                 features.push("unstable".to_owned());
                 features.push("rt".to_owned());
+                features.push("sha-kdf".to_owned());
                 if config.contains("psram") {
                     // TODO this doesn't test octal psram (since `ESP_HAL_CONFIG_PSRAM_MODE`
                     // defaults to `quad`) as it would require a separate build